@@ -11,6 +11,8 @@ pub fn load_module(src_file: &Path, threading: Threading) -> LoadedModule {
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading,
         exec_mode: ExecutionMode::Check,
+        cache_dir: None,
+        coverage: false,
     };
 
     let arena = Bump::new();
@@ -36,6 +36,8 @@ pub struct BuiltFile {
     pub total_time: Duration,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub interns: Interns,
+    /// The source files of the modules that went into the build, for `--watch`.
+    pub module_paths: Vec<PathBuf>,
 }
 
 pub enum BuildOrdering {
@@ -69,6 +71,7 @@ pub fn build_file<'a>(
     threading: Threading,
     wasm_dev_stack_bytes: Option<u32>,
     order: BuildOrdering,
+    render: RenderTarget,
 ) -> Result<BuiltFile, BuildFileError<'a>> {
     let compilation_start = Instant::now();
     let target_info = TargetInfo::from(target);
@@ -83,10 +86,11 @@ pub fn build_file<'a>(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         threading,
         exec_mode,
+        cache_dir: roc_load::default_cache_dir(),
+        coverage: false,
     };
     let load_result = roc_load::load_and_monomorphize(
         arena,
@@ -107,6 +111,8 @@ pub fn build_file<'a>(
         }
     };
 
+    let module_paths = crate::watch::module_paths(&loaded.sources);
+
     use target_lexicon::Architecture;
     let emit_wasm = matches!(target.architecture, Architecture::Wasm32);
 
@@ -240,7 +246,7 @@ pub fn build_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = program::report_problems_monomorphized(&mut loaded);
+    let problems = program::report_problems_monomorphized(&mut loaded, render);
     let expectations = std::mem::take(&mut loaded.expectations);
     let loaded = loaded;
 
@@ -390,6 +396,7 @@ pub fn build_file<'a>(
         total_time,
         interns,
         expectations,
+        module_paths,
     })
 }
 
@@ -464,7 +471,9 @@ pub fn check_file(
     roc_file_path: PathBuf,
     emit_timings: bool,
     threading: Threading,
-) -> Result<(program::Problems, Duration), LoadingProblem> {
+    render: RenderTarget,
+    fix: bool,
+) -> Result<(program::Problems, Duration, Vec<PathBuf>), LoadingProblem> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -476,13 +485,19 @@ pub fn check_file(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         threading,
         exec_mode: ExecutionMode::Check,
+        cache_dir: roc_load::default_cache_dir(),
+        coverage: false,
     };
     let mut loaded =
-        roc_load::load_and_typecheck(arena, roc_file_path, subs_by_module, load_config)?;
+        roc_load::load_and_typecheck(arena, roc_file_path.clone(), subs_by_module, load_config)?;
+
+    if fix && apply_suggested_fixes(&loaded, render)? > 0 {
+        // Check again, so that the problems we report are the ones that are left.
+        return check_file(arena, roc_file_path, emit_timings, threading, render, false);
+    }
 
     let buf = &mut String::with_capacity(1024);
 
@@ -528,7 +543,47 @@ pub fn check_file(
     }
 
     Ok((
-        program::report_problems_typechecked(&mut loaded),
+        program::report_problems_typechecked(&mut loaded, render),
         compilation_end,
+        crate::watch::module_paths(&loaded.sources),
     ))
 }
+
+/// Writes the fixes suggested for the problems in the loaded modules to their files,
+/// and returns how many of them were applied.
+fn apply_suggested_fixes<'a>(
+    loaded: &LoadedModule,
+    render: RenderTarget,
+) -> Result<usize, LoadingProblem<'a>> {
+    let mut total_applied = 0;
+    let mut files_changed = 0;
+
+    for (path, fixes) in program::suggested_fixes(loaded) {
+        let file_problem = |error: std::io::Error| LoadingProblem::FileProblem {
+            filename: path.clone(),
+            error: error.kind(),
+        };
+
+        let src = std::fs::read_to_string(&path).map_err(file_problem)?;
+        let (fixed, applied) = roc_reporting::fix::apply_fixes(&src, &fixes);
+
+        if applied > 0 {
+            std::fs::write(&path, fixed).map_err(file_problem)?;
+
+            total_applied += applied;
+            files_changed += 1;
+        }
+    }
+
+    if !matches!(render, RenderTarget::Json) {
+        println!(
+            "Applied {} {} to {} {}.",
+            total_applied,
+            if total_applied == 1 { "fix" } else { "fixes" },
+            files_changed,
+            if files_changed == 1 { "file" } else { "files" },
+        );
+    }
+
+    Ok(total_applied)
+}
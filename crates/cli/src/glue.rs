@@ -0,0 +1,258 @@
+//! `roc glue --spec`, which runs a glue spec written in Roc against a platform's types, so
+//! glue can be generated for languages `roc glue` doesn't know about.
+//!
+//! A glue spec is an app for the platform in `crates/glue/platform/RocType.roc`. It gets built
+//! into a shared library, loaded into the compiler, and given the `Types` of the platform
+//! module for every target architecture. It returns the files to write to the output directory.
+use crate::build::{build_file, BuildFileError, BuildOrdering, BuiltFile};
+use bumpalo::Bump;
+use libloading::Library;
+use roc_build::link::{LinkType, LinkingStrategy};
+use roc_glue::load::{load_types, IgnoreErrors};
+use roc_glue::roc_type;
+use roc_load::{LoadingProblem, Threading};
+use roc_mono::ir::OptLevel;
+use roc_reporting::report::RenderTarget;
+use roc_std::{RocList, RocResult, RocStr};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::path::{Component, Path, PathBuf};
+use target_lexicon::Triple;
+
+/// The function `RocType.roc` provides to its host
+const ENTRY_POINT: &[u8] = b"roc__makeGlueForHost_1_exposed_generic";
+
+type MakeGlue = unsafe extern "C" fn(
+    *mut RocResult<RocList<roc_type::File>, RocStr>,
+    *const RocList<roc_type::Types>,
+);
+
+pub fn generate_with_spec(
+    input_path: &Path,
+    spec_path: &Path,
+    output_dir: &Path,
+) -> io::Result<i32> {
+    let types_and_targets = match load_types(
+        input_path.to_path_buf(),
+        Threading::AllAvailable,
+        IgnoreErrors { can: false },
+    ) {
+        Ok(types_and_targets) => types_and_targets,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            eprintln!("Platform module file not found: {}", input_path.display());
+
+            return Ok(1);
+        }
+        Err(err) => {
+            eprintln!(
+                "Error loading platform module file {} - {:?}",
+                input_path.display(),
+                err.kind()
+            );
+
+            return Ok(1);
+        }
+    };
+
+    let lib_path = match build_spec(spec_path)? {
+        Some(lib_path) => lib_path,
+        None => return Ok(1),
+    };
+
+    let types: RocList<roc_type::Types> = types_and_targets
+        .iter()
+        .map(|(types, _)| roc_type::Types::from(types))
+        .collect();
+
+    // The files can point into the library's constants, so it needs to stay loaded
+    // until they've been written.
+    let (_lib, files) = match unsafe { run_spec(&lib_path, types) } {
+        Ok(lib_and_files) => lib_and_files,
+        Err(err) => {
+            eprintln!(
+                "The glue spec {} couldn't be loaded: {}",
+                spec_path.display(),
+                err
+            );
+
+            return Ok(1);
+        }
+    };
+
+    let files = match Result::from(files) {
+        Ok(files) => files,
+        Err(message) => {
+            eprintln!(
+                "The glue spec {} reported an error:\n\n    {}",
+                spec_path.display(),
+                message
+            );
+
+            return Ok(1);
+        }
+    };
+
+    // Check every name before writing anything, so a bad one doesn't leave half the glue behind.
+    if let Some(file) = files
+        .iter()
+        .find(|file| !is_inside_output_dir(Path::new(file.name.as_str())))
+    {
+        eprintln!(
+            "The glue spec {} returned a file named {:?}, but file names must be relative paths inside the output directory.",
+            spec_path.display(),
+            file.name.as_str()
+        );
+
+        return Ok(1);
+    }
+
+    let mut paths = Vec::with_capacity(files.len());
+
+    for file in files.iter() {
+        let path = output_dir.join(file.name.as_str());
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, file.content.as_str()).map_err(|err| {
+            eprintln!(
+                "Unable to write glue to output file {} - {:?}",
+                path.display(),
+                err
+            );
+
+            err
+        })?;
+
+        paths.push(format!("\t{}", path.display()));
+    }
+
+    println!("🎉 Generated type declarations in:\n\n{}", paths.join("\n"));
+
+    Ok(0)
+}
+
+/// Whether joining `name` to the output directory gives a file inside of it, and not e.g. the
+/// directory itself, an absolute path, or somewhere `..` leads to.
+fn is_inside_output_dir(name: &Path) -> bool {
+    name.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        && name
+            .components()
+            .any(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Builds the glue spec into a shared library, returning its path, or `None` if it had errors
+/// (which have already been reported).
+fn build_spec(spec_path: &Path) -> io::Result<Option<PathBuf>> {
+    let arena = Bump::new();
+    let triple = Triple::host();
+    let render = RenderTarget::ColorTerminal;
+
+    match build_file(
+        &arena,
+        &triple,
+        spec_path.to_path_buf(),
+        OptLevel::Normal,
+        false,
+        false,
+        LinkType::Dylib,
+        // The surgical linker can't produce shared libraries
+        LinkingStrategy::Legacy,
+        false,
+        Threading::AllAvailable,
+        None,
+        BuildOrdering::BuildIfChecks,
+        render,
+    ) {
+        Ok(BuiltFile {
+            binary_path,
+            problems,
+            total_time,
+            ..
+        }) => {
+            if problems.errors > 0 {
+                crate::print_problems(problems, total_time);
+                println!(" while building the glue spec.");
+
+                return Ok(None);
+            }
+
+            Ok(Some(dylib_path(binary_path, &triple)))
+        }
+        Err(BuildFileError::ErrorModule {
+            mut module,
+            total_time,
+        }) => {
+            let problems = roc_build::program::report_problems_typechecked(&mut module, render);
+
+            crate::print_problems(problems, total_time);
+            println!(" in the glue spec.");
+
+            Ok(None)
+        }
+        Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(report))) => {
+            print!("{}", report);
+
+            Ok(None)
+        }
+        Err(other) => {
+            panic!("build_file failed with error:\n{:?}", other);
+        }
+    }
+}
+
+/// `link` names the shared library after the binary it would have built otherwise.
+fn dylib_path(binary_path: PathBuf, triple: &Triple) -> PathBuf {
+    use target_lexicon::OperatingSystem;
+
+    match triple.operating_system {
+        OperatingSystem::Darwin | OperatingSystem::MacOSX { .. } => {
+            binary_path.with_extension("dylib")
+        }
+        OperatingSystem::Windows => binary_path,
+        _ => binary_path.with_extension("so.1.0"),
+    }
+}
+
+/// # Safety
+/// The library must have been built from a glue spec for `RocType.roc`.
+#[allow(clippy::type_complexity)]
+unsafe fn run_spec(
+    lib_path: &Path,
+    types: RocList<roc_type::Types>,
+) -> Result<(Library, RocResult<RocList<roc_type::File>, RocStr>), libloading::Error> {
+    let lib = Library::new(lib_path)?;
+    let files = {
+        let make_glue = lib.get::<MakeGlue>(ENTRY_POINT)?;
+
+        // Roc takes ownership of the arguments
+        let types = ManuallyDrop::new(types);
+        let mut files = MaybeUninit::uninit();
+
+        make_glue(files.as_mut_ptr(), &*types);
+
+        files.assume_init()
+    };
+
+    Ok((lib, files))
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_inside_output_dir;
+    use std::path::Path;
+
+    #[test]
+    fn file_names_stay_inside_the_output_dir() {
+        for name in ["glue.h", "./glue.h", "include/glue.h"] {
+            assert!(is_inside_output_dir(Path::new(name)), "{}", name);
+        }
+
+        for name in ["", ".", "/etc/glue.h", "../glue.h", "include/../../glue.h"] {
+            assert!(!is_inside_output_dir(Path::new(name)), "{}", name);
+        }
+    }
+}
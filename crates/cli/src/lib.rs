@@ -13,6 +13,7 @@ use roc_error_macros::{internal_error, user_error};
 use roc_load::{Expectations, LoadingProblem, Threading};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
+use roc_reporting::report::RenderTarget;
 use std::env;
 use std::ffi::{CString, OsStr};
 use std::io;
@@ -29,9 +30,17 @@ use tempfile::TempDir;
 
 pub mod build;
 mod format;
+#[cfg(feature = "glue-spec")]
+pub mod glue;
 pub use format::format;
+#[cfg(not(windows))]
+pub mod reporter;
+pub mod watch;
 
 use crate::build::{BuildFileError, BuildOrdering};
+#[cfg(not(windows))]
+use crate::reporter::Reporter;
+use crate::watch::WatchedRun;
 
 const DEFAULT_ROC_FILENAME: &str = "main.roc";
 
@@ -47,6 +56,7 @@ pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_GEN_STUB_LIB: &str = "gen-stub-lib";
+pub const CMD_LSP: &str = "lsp";
 
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_DEV: &str = "dev";
@@ -61,6 +71,23 @@ pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_FORMAT: &str = "format";
+pub const FLAG_FIX: &str = "fix";
+pub const FLAG_LIST: &str = "list";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_MODULE: &str = "module";
+pub const FLAG_NAME: &str = "name";
+pub const FLAG_REPORTER: &str = "reporter";
+pub const FLAG_TIMEOUT: &str = "timeout";
+pub const FLAG_SEED: &str = "seed";
+pub const FLAG_RUNS: &str = "runs";
+pub const FLAG_COVERAGE: &str = "coverage";
+pub const FLAG_DOC: &str = "doc";
+pub const FLAG_SPEC: &str = "spec";
+pub const FLAG_ROUND_TRIP: &str = "round-trip";
+pub const FLAG_PACKAGE_VERSION: &str = "package-version";
+pub const FLAG_OUTPUT_FORMAT: &str = "output-format";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_FILE: &str = "GLUE_FILE";
@@ -69,6 +96,10 @@ pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 
 const VERSION: &str = include_str!("../../../version.txt");
 
+/// Where `roc test --coverage` writes the coverage, in the current directory
+#[cfg(not(windows))]
+const LCOV_FILENAME: &str = "lcov.info";
+
 pub fn build_app<'a>() -> Command<'a> {
     let flag_optimize = Arg::new(FLAG_OPTIMIZE)
         .long(FLAG_OPTIMIZE)
@@ -121,6 +152,17 @@ pub fn build_app<'a>() -> Command<'a> {
         .validator(|s| s.parse::<u32>())
         .required(false);
 
+    let flag_watch = Arg::new(FLAG_WATCH)
+        .long(FLAG_WATCH)
+        .help("Run again whenever the .roc file or any module it imports changes")
+        .required(false);
+
+    let flag_format = Arg::new(FLAG_FORMAT)
+        .long(FLAG_FORMAT)
+        .help("Print problems as text for the terminal, or as JSON with one report per line")
+        .possible_values(["terminal", "json"])
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .allow_invalid_utf8(true)
@@ -149,6 +191,7 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_wasm_stack_size_kb.clone())
+            .arg(flag_format.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
@@ -187,6 +230,82 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_watch.clone())
+            .arg(flag_format.clone())
+            .arg(
+                Arg::new(FLAG_REPORTER)
+                    .long(FLAG_REPORTER)
+                    .help("Print the results as JUnit XML, TAP, or JSON with one line per `expect`, instead of as text for the terminal")
+                    .possible_values(["junit", "tap", "json"])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TIMEOUT)
+                    .long(FLAG_TIMEOUT)
+                    .help("Stop any `expect` that runs for longer than this many seconds, and report it as a failure")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<f64>())
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_SEED)
+                    .long(FLAG_SEED)
+                    .help("Generate property arguments from this seed, e.g. to get the same arguments as a previous run\n(Defaults to a different seed every run)")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<u64>())
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_RUNS)
+                    .long(FLAG_RUNS)
+                    .help("Call each property with this many sets of generated arguments")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<usize>())
+                    .default_value("100")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_COVERAGE)
+                    .long(FLAG_COVERAGE)
+                    .help("Measure which top-level defs and branches the tests reach, and write it to lcov.info")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_DOC)
+                    .long(FLAG_DOC)
+                    .help("Instead of the top-level `expect`s, run the ```roc code blocks in the docs of the main module's exposed defs, each as an `expect`")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_LIST)
+                    .long(FLAG_LIST)
+                    .help("List the `expect`s that would run, and where they are, without running them")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the `expect`s whose name or code contains this text (can be given more than once)")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_MODULE)
+                    .long(FLAG_MODULE)
+                    .help("Only run the `expect`s in the module with this name, e.g. `Dict` (can be given more than once)")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_NAME)
+                    .long(FLAG_NAME)
+                    .help("Only run the `expect` with this name: the comment right above it, or else its first line (can be given more than once)")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -198,6 +317,12 @@ pub fn build_app<'a>() -> Command<'a> {
         )
         .subcommand(Command::new(CMD_REPL)
             .about("Launch the interactive Read Eval Print Loop (REPL)")
+            .arg(
+                Arg::new(FLAG_DEV)
+                    .long(FLAG_DEV)
+                    .help("Evaluate entries with the dev backend, which starts evaluating sooner\n(Anything it doesn't support yet is still evaluated with LLVM.)")
+                    .required(false),
+            )
         )
         .subcommand(Command::new(CMD_RUN)
             .about("Run a .roc file even if it has build errors")
@@ -222,6 +347,7 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_watch.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -246,6 +372,14 @@ pub fn build_app<'a>() -> Command<'a> {
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_watch.clone())
+            .arg(flag_format.clone())
+            .arg(
+                Arg::new(FLAG_FIX)
+                    .long(FLAG_FIX)
+                    .help("Apply the suggested fixes for the problems found (e.g. remove unused imports), then check again")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -263,6 +397,22 @@ pub fn build_app<'a>() -> Command<'a> {
                     .help("The directory or files to build documentation for")
                     .allow_invalid_utf8(true)
                 )
+                .arg(
+                    Arg::new(FLAG_PACKAGE_VERSION)
+                        .long(FLAG_PACKAGE_VERSION)
+                        .help("Generate the docs for this version of the package, e.g. 0.1.0\n(Docs for each version go in their own directory, and every version's pages link to the others)")
+                        .takes_value(true)
+                        .validator(roc_docs::validate_version)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_OUTPUT_FORMAT)
+                        .long(FLAG_OUTPUT_FORMAT)
+                        .help("Generate the docs as an HTML site, a Markdown file per module (e.g. for a wiki), or one JSON file (e.g. for a custom renderer, or to diff the API between releases)")
+                        .possible_values(["html", "md", "json"])
+                        .default_value("html")
+                        .required(false),
+                )
         )
         .subcommand(Command::new(CMD_GLUE)
             .about("Generate glue code between a platform's Roc API and its host language")
@@ -274,10 +424,28 @@ pub fn build_app<'a>() -> Command<'a> {
             )
             .arg(
                 Arg::new(GLUE_FILE)
-                    .help("The filename for the generated glue code\n(Currently, this must be a .rs file because only Rust glue generation is supported so far.)")
+                    .help("The filename for the generated glue code\n(A .rs file generates Rust glue, a .h file generates a C header, a .zig file generates Zig glue, and a .swift file generates Swift glue along with the C header it uses. With --spec or --round-trip, this is the directory to write the generated files to.)")
                     .allow_invalid_utf8(true)
                     .required(true)
             )
+            .arg(
+                Arg::new(FLAG_SPEC)
+                    .long(FLAG_SPEC)
+                    .help("A glue spec written in Roc to generate the glue with, which is an app for the platform in crates/glue/platform/RocType.roc")
+                    .takes_value(true)
+                    .allow_invalid_utf8(true)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_ROUND_TRIP)
+                    .long(FLAG_ROUND_TRIP)
+                    .help("Generate Rust glue along with a Roc app and a Rust host that pass values of every type the platform exposes back and forth, to check that the glue agrees with the compiler about how they're laid out. Run the app with `roc` to check.")
+                    .conflicts_with(FLAG_SPEC)
+                    .required(false)
+            )
+        )
+        .subcommand(Command::new(CMD_LSP)
+            .about("Start a language server for editors, speaking the Language Server Protocol over stdio")
         )
         .subcommand(Command::new(CMD_GEN_STUB_LIB)
             .about("Generate a stubbed shared library that can be used for linking a platform binary.\nThe stubbed library has prototypes, but no function bodies.\n\nNote: This command will be removed in favor of just using `roc build` once all platforms support the surgical linker")
@@ -324,11 +492,22 @@ pub fn build_app<'a>() -> Command<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildConfig {
     BuildOnly,
     BuildAndRun,
     BuildAndRunIfNoErrors,
+    /// Like [BuildConfig::BuildAndRunIfNoErrors], but build and run again whenever a module changes.
+    WatchAndRunIfNoErrors,
+}
+
+/// How to print problems, based on `--format`. Only some subcommands accept that flag,
+/// so for the others this is always [RenderTarget::ColorTerminal].
+pub fn render_target(matches: &ArgMatches) -> RenderTarget {
+    match matches.try_get_one::<String>(FLAG_FORMAT) {
+        Ok(Some(format)) if format == "json" => RenderTarget::Json,
+        _ => RenderTarget::ColorTerminal,
+    }
 }
 
 pub enum FormatMode {
@@ -343,6 +522,17 @@ pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
 
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
+    if matches.is_present(FLAG_WATCH) {
+        let path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
+
+        watch::watch(path, || test_once(matches, &triple))
+    } else {
+        test_once(matches, &triple).map(|run| run.exit_code)
+    }
+}
+
+#[cfg(not(windows))]
+fn test_once(matches: &ArgMatches, triple: &Triple) -> io::Result<WatchedRun> {
     use roc_gen_llvm::llvm::build::LlvmBackendMode;
     use roc_load::{ExecutionMode, LoadConfig};
     use roc_target::TargetInfo;
@@ -394,68 +584,200 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     }
 
     let arena = &arena;
-    let target = &triple;
+    let target = triple;
     let opt_level = opt_level;
     let target_info = TargetInfo::from(target);
 
     // Step 1: compile the app and generate the .o file
     let subs_by_module = Default::default();
 
-    let load_config = LoadConfig {
+    let measure_coverage = matches.is_present(FLAG_COVERAGE);
+
+    let load_config = |exec_mode: ExecutionMode| LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        render: render_target(matches),
         threading,
-        exec_mode: ExecutionMode::Test,
+        exec_mode,
+        cache_dir: roc_load::default_cache_dir(),
+        coverage: measure_coverage,
+    };
+    let loaded = if matches.is_present(FLAG_DOC) {
+        load_with_doc_tests(arena, path, load_config)
+            .map(|(loaded, doc_tests)| (loaded, Some(doc_tests)))
+    } else {
+        roc_load::load_and_monomorphize(
+            arena,
+            path.to_path_buf(),
+            subs_by_module,
+            load_config(ExecutionMode::Test),
+        )
+        .map(|loaded| (loaded, None))
     };
-    let loaded =
-        roc_load::load_and_monomorphize(arena, path.to_path_buf(), subs_by_module, load_config)
-            .unwrap();
+
+    let (loaded, doc_tests) = match loaded {
+        Ok(loaded) => loaded,
+        Err(roc_load::LoadMonomorphizedError::LoadingProblem(LoadingProblem::FormattedReport(
+            report,
+        ))) => {
+            print!("{}", report);
+
+            return Ok(WatchedRun::finished(1, Vec::new()));
+        }
+        Err(other) => {
+            panic!("loading the tests failed with error:\n{:?}", other);
+        }
+    };
+
+    let module_paths = watch::module_paths(&loaded.sources);
+    let render = render_target(matches);
 
     let mut loaded = loaded;
+
+    if let Some(doc_tests) = &doc_tests {
+        if report_doc_test_problems(&mut loaded, doc_tests, render) > 0 {
+            return Ok(WatchedRun::finished(1, module_paths));
+        }
+    }
+
+    let filter = expect_filter(matches);
+    let selected = match doc_tests {
+        Some(doc_tests) => select_doc_tests(&mut loaded, doc_tests, &filter),
+        None => roc_repl_expect::filter::retain_expects(&mut loaded, &filter),
+    };
+
+    if matches.is_present(FLAG_LIST) {
+        print_expect_list(&selected, render);
+
+        return Ok(WatchedRun::finished(0, module_paths));
+    }
+
     let mut expectations = std::mem::take(&mut loaded.expectations);
     let loaded = loaded;
 
     let interns = loaded.interns.clone();
 
+    let sources = if measure_coverage {
+        loaded.sources.clone()
+    } else {
+        Default::default()
+    };
+
     let (lib, expects, layout_interner) = roc_repl_expect::run::expect_mono_module_to_dylib(
         arena,
         target.clone(),
         loaded,
         opt_level,
         LlvmBackendMode::CliTest,
+        measure_coverage,
     )
     .unwrap();
 
+    // the counters must be set up before the workers are forked, so they share them
+    let coverage_points = expects.coverage.clone();
+    let coverage_counters = if measure_coverage {
+        Some(roc_repl_expect::coverage::CoverageCounters::new(
+            &lib,
+            &coverage_points,
+        ))
+    } else {
+        None
+    };
+
     let arena = &bumpalo::Bump::new();
     let interns = arena.alloc(interns);
 
-    let mut writer = std::io::stdout();
+    let report_format: Option<Reporter> = matches.value_of_t(FLAG_REPORTER).ok();
+
+    // A reporter prints the failures as part of its results, so they need to be plain text,
+    // and must not be printed as they happen.
+    let (mut writer, expect_render): (Box<dyn io::Write>, _) = match report_format {
+        Some(_) => (Box::new(io::sink()), RenderTarget::Generic),
+        None => (Box::new(io::stdout()), render),
+    };
+
+    let run_options = roc_repl_expect::run::RunOptions {
+        jobs: match threading {
+            Threading::Single => 1,
+            Threading::AtMost(n) => n,
+            Threading::AllAvailable => std::thread::available_parallelism().map_or(1, usize::from),
+        },
+        timeout: matches.value_of_t::<f64>(FLAG_TIMEOUT).ok().map(|seconds| {
+            if seconds.is_finite() && seconds > 0.0 {
+                std::time::Duration::from_secs_f64(seconds)
+            } else {
+                user_error!("the timeout must be a positive number of seconds")
+            }
+        }),
+        property_runs: matches.value_of_t(FLAG_RUNS).unwrap_or(100),
+        seed: matches.value_of_t(FLAG_SEED).unwrap_or_else(|_| {
+            // a different seed every run finds more failures over time
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos() as u64)
+        }),
+    };
 
-    let (failed, passed) = roc_repl_expect::run::run_expects(
+    let results = roc_repl_expect::run::run_expects(
         &mut writer,
-        roc_reporting::report::RenderTarget::ColorTerminal,
+        expect_render,
         arena,
         interns,
         &layout_interner.into_global(),
         &lib,
         &mut expectations,
         expects,
+        run_options,
     )
     .unwrap();
 
     let total_time = start_time.elapsed();
+    let cases = reporter::test_cases(&selected, &results);
+
+    let coverage = match coverage_counters {
+        Some(counters) => {
+            let modules = roc_repl_expect::coverage::coverage_by_module(
+                &coverage_points,
+                &counters.counts(),
+                &expectations,
+                &sources,
+                interns,
+            );
+
+            let mut lcov_file = io::BufWriter::new(std::fs::File::create(LCOV_FILENAME)?);
+            roc_repl_expect::coverage::write_lcov(&mut lcov_file, &modules)?;
+
+            Some(modules)
+        }
+        None => None,
+    };
+
+    if let Some(report_format) = report_format {
+        reporter::write_report(&mut io::stdout(), report_format, &cases, total_time)?;
+    }
+
+    let print_summary = report_format.is_none() && !matches!(render, RenderTarget::Json);
+
+    let passed = results.iter().filter(|result| result.passed).count();
+    let failed = results.len() - passed;
 
     if failed == 0 && passed == 0 {
         // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        if print_summary {
+            if matches.is_present(FLAG_DOC) && filter.is_empty() {
+                println!("No doc tests were found.");
+            } else if filter.is_empty() {
+                println!("No expectations were found.");
+            } else {
+                println!("No expectations matched the filters.");
+            }
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
         // you actually have zero tests, but it can save you from
         // having a change to your CI script accidentally stop
         // running tests altogether!
-        Ok(2)
+        Ok(WatchedRun::finished(2, module_paths))
     } else {
         let failed_color = if failed == 0 {
             32 // green
@@ -463,12 +785,218 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
             31 // red
         };
 
+        if print_summary {
+            print_expect_results(&cases);
+
+            println!(
+                "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+                total_time.as_millis(),
+            );
+
+            if let Some(modules) = &coverage {
+                println!("Coverage (written to {}):\n", LCOV_FILENAME);
+                roc_repl_expect::coverage::write_summary(&mut io::stdout(), modules)?;
+                println!();
+            }
+        }
+
+        Ok(WatchedRun::finished((failed > 0) as i32, module_paths))
+    }
+}
+
+#[cfg(not(windows))]
+type DocTests = Vec<roc_docs::doc_tests::DocTest>;
+
+/// Loads the main module, and the other modules of its package, with the code blocks of their
+/// exposed defs' doc tests turned into top-level `expect`s in place, so they can use everything
+/// their module can, and any problems or failures in them are reported where they are in the docs.
+#[cfg(not(windows))]
+fn load_with_doc_tests<'a>(
+    arena: &'a Bump,
+    path: &Path,
+    load_config: impl Fn(roc_load::ExecutionMode) -> roc_load::LoadConfig,
+) -> Result<(roc_load::MonomorphizedModule<'a>, DocTests), roc_load::LoadMonomorphizedError<'a>> {
+    use roc_docs::doc_tests::{doc_tests, inline_doc_tests};
+
+    // Modules loaded from the cache have no docs, so this load doesn't use it.
+    let typechecked = roc_load::load_and_typecheck(
+        arena,
+        path.to_path_buf(),
+        Default::default(),
+        roc_load::LoadConfig {
+            cache_dir: None,
+            ..load_config(roc_load::ExecutionMode::Check)
+        },
+    )?;
+
+    let doc_tests = doc_tests(&typechecked);
+    let src = match typechecked.sources.get(&typechecked.module_id) {
+        Some((_, src)) => src,
+        None => internal_error!("the main module's source was not loaded"),
+    };
+    let src_with_doc_tests = arena.alloc_str(&inline_doc_tests(path, src, &doc_tests));
+
+    let mut module_sources = roc_collections::MutMap::default();
+
+    for (module_path, module_src) in typechecked.sources.values() {
+        if module_path != path && doc_tests.iter().any(|test| &test.path == module_path) {
+            let inlined = inline_doc_tests(module_path, module_src, &doc_tests);
+
+            module_sources.insert(module_path.clone(), &*arena.alloc_str(&inlined));
+        }
+    }
+
+    let loaded = roc_load::load_and_monomorphize_with_sources(
+        arena,
+        path.to_path_buf(),
+        src_with_doc_tests,
+        path.parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf(),
+        module_sources,
+        Default::default(),
+        load_config(roc_load::ExecutionMode::Test),
+    )?;
+
+    Ok((loaded, doc_tests))
+}
+
+/// The 1-based lines of the `expect` a doc test was turned into: the line of the opening ```,
+/// followed by the code.
+#[cfg(not(windows))]
+fn doc_test_lines(doc_test: &roc_docs::doc_tests::DocTest) -> std::ops::RangeInclusive<u32> {
+    let code_lines = doc_test.code.lines().count() as u32;
+
+    doc_test.line - 1..=doc_test.line + code_lines.saturating_sub(1)
+}
+
+/// Reports the problems in the doc tests, like `roc check` would, and leaves out the ones in the
+/// rest of the module, which `roc test` doesn't report either. Returns how many errors there were.
+#[cfg(not(windows))]
+fn report_doc_test_problems(
+    loaded: &mut roc_load::MonomorphizedModule,
+    doc_tests: &[roc_docs::doc_tests::DocTest],
+    render: RenderTarget,
+) -> usize {
+    use roc_region::all::{LineInfo, Region};
+
+    let mut can_problems = std::mem::take(&mut loaded.can_problems);
+    let mut type_problems = std::mem::take(&mut loaded.type_problems);
+
+    for (module_id, (path, src)) in loaded.sources.iter() {
+        let line_info = LineInfo::new(src);
+        let in_doc_test = |region: Option<Region>| {
+            region.map_or(false, |region| {
+                let line = line_info.convert_pos(region.start()).line + 1;
+
+                doc_tests.iter().any(|doc_test| {
+                    &doc_test.path == path && doc_test_lines(doc_test).contains(&line)
+                })
+            })
+        };
+
+        let mut module_can_problems = can_problems.remove(module_id).unwrap_or_default();
+        let mut module_type_problems = type_problems.remove(module_id).unwrap_or_default();
+
+        module_can_problems.retain(|problem| in_doc_test(problem.region()));
+        module_type_problems.retain(|problem| in_doc_test(problem.region()));
+
+        loaded.can_problems.insert(*module_id, module_can_problems);
+        loaded
+            .type_problems
+            .insert(*module_id, module_type_problems);
+    }
+
+    roc_build::program::report_problems_monomorphized(loaded, render).errors
+}
+
+/// Keeps only the `expect`s which came from doc tests, and describes them by the doc tests'
+/// code, so results and failures show what was written in the docs.
+#[cfg(not(windows))]
+fn select_doc_tests(
+    loaded: &mut roc_load::MonomorphizedModule,
+    doc_tests: DocTests,
+    filter: &roc_repl_expect::filter::ExpectFilter,
+) -> Vec<roc_repl_expect::filter::ExpectInfo> {
+    use roc_repl_expect::filter::{retain_expects_with, ExpectInfo};
+
+    retain_expects_with(loaded, |expect| {
+        // the `expect` is on the line of the code block's opening ```
+        let doc_test = doc_tests
+            .iter()
+            .find(|doc_test| doc_test.path == expect.path && doc_test.line == expect.line + 1)?;
+        let expect = ExpectInfo {
+            name: format!("doc test for `{}`", doc_test.def_name),
+            path: doc_test.path.clone(),
+            line: doc_test.line,
+            column: doc_test.column,
+            source: doc_test.code.clone(),
+            ..expect
+        };
+
+        if filter.matches(&expect) {
+            Some(expect)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(not(windows))]
+fn expect_filter(matches: &ArgMatches) -> roc_repl_expect::filter::ExpectFilter {
+    let values = |id| {
+        matches
+            .values_of(id)
+            .map(|values| values.map(String::from).collect())
+            .unwrap_or_default()
+    };
+
+    roc_repl_expect::filter::ExpectFilter {
+        modules: values(FLAG_MODULE),
+        names: values(FLAG_NAME),
+        substrings: values(FLAG_FILTER),
+    }
+}
+
+#[cfg(not(windows))]
+fn print_expect_list(expects: &[roc_repl_expect::filter::ExpectInfo], render: RenderTarget) {
+    for expect in expects {
+        if let RenderTarget::Json = render {
+            let json = serde_json::json!({
+                "name": expect.name,
+                "module": expect.module_name,
+                "filename": expect.path,
+                "line": expect.line,
+                "column": expect.column,
+            });
+
+            println!("{}", json);
+        } else {
+            println!("{}  {}", expect.location(), expect.name);
+        }
+    }
+}
+
+/// One line per expect that ran, in source order: whether it passed, how long it took, and
+/// where it is.
+#[cfg(not(windows))]
+fn print_expect_results(cases: &[reporter::TestCase]) {
+    println!();
+
+    for reporter::TestCase { expect, result } in cases {
+        let status = if result.passed {
+            "\x1B[32mPASS\x1B[39m"
+        } else {
+            "\x1B[31mFAIL\x1B[39m"
+        };
+
         println!(
-            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
-            total_time.as_millis(),
+            "{} {:9.3} ms   {}  {}",
+            status,
+            result.duration.as_secs_f64() * 1000.0,
+            expect.location(),
+            expect.name,
         );
-
-        Ok((failed > 0) as i32)
     }
 }
 
@@ -478,6 +1006,23 @@ pub fn build(
     triple: Triple,
     link_type: LinkType,
 ) -> io::Result<i32> {
+    if config == BuildConfig::WatchAndRunIfNoErrors {
+        let path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
+
+        watch::watch(path, || {
+            build_once(matches, config, triple.clone(), link_type)
+        })
+    } else {
+        build_once(matches, config, triple, link_type).map(|run| run.exit_code)
+    }
+}
+
+fn build_once(
+    matches: &ArgMatches,
+    config: BuildConfig,
+    triple: Triple,
+    link_type: LinkType,
+) -> io::Result<WatchedRun> {
     use build::build_file;
     use BuildConfig::*;
 
@@ -555,9 +1100,11 @@ pub fn build(
         .and_then(|s| s.parse::<u32>().ok())
         .map(|x| x * 1024);
 
+    let render = render_target(matches);
+
     let build_ordering = match config {
-        BuildAndRunIfNoErrors => BuildOrdering::BuildIfChecks,
-        _ => BuildOrdering::AlwaysBuild,
+        BuildAndRunIfNoErrors | WatchAndRunIfNoErrors => BuildOrdering::BuildIfChecks,
+        BuildOnly | BuildAndRun => BuildOrdering::AlwaysBuild,
     };
     let res_binary_path = build_file(
         &arena,
@@ -572,6 +1119,7 @@ pub fn build(
         threading,
         wasm_dev_stack_bytes,
         build_ordering,
+        render,
     );
 
     match res_binary_path {
@@ -581,6 +1129,7 @@ pub fn build(
             total_time,
             expectations,
             interns,
+            module_paths,
         }) => {
             match config {
                 BuildOnly => {
@@ -595,11 +1144,13 @@ pub fn build(
                     // since the process is about to exit anyway.
                     std::mem::forget(arena);
 
-                    print_problems(problems, total_time);
-                    println!(" while successfully building:\n\n    {generated_filename}");
+                    if !matches!(render, RenderTarget::Json) {
+                        print_problems(problems, total_time);
+                        println!(" while successfully building:\n\n    {generated_filename}");
+                    }
 
                     // Return a nonzero exit code if there were problems
-                    Ok(problems.exit_code())
+                    Ok(WatchedRun::finished(problems.exit_code(), module_paths))
                 }
                 BuildAndRun => {
                     if problems.errors > 0 || problems.warnings > 0 {
@@ -616,7 +1167,10 @@ pub fn build(
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(arena, opt_level, triple, args, bytes, expectations, interns)
+                    let exit_code =
+                        roc_run(arena, opt_level, triple, args, bytes, expectations, interns)?;
+
+                    Ok(WatchedRun::finished(exit_code, module_paths))
                 }
                 BuildAndRunIfNoErrors => {
                     debug_assert!(
@@ -637,7 +1191,31 @@ pub fn build(
                     // ManuallyDrop will leak the bytes because we don't drop manually
                     let bytes = &ManuallyDrop::new(std::fs::read(&binary_path).unwrap());
 
-                    roc_run(arena, opt_level, triple, args, bytes, expectations, interns)
+                    let exit_code =
+                        roc_run(arena, opt_level, triple, args, bytes, expectations, interns)?;
+
+                    Ok(WatchedRun::finished(exit_code, module_paths))
+                }
+                WatchAndRunIfNoErrors => {
+                    if problems.warnings > 0 {
+                        print_problems(problems, total_time);
+                        println!(
+                            ".\n\nRunning program…\n\n\x1B[36m{}\x1B[39m",
+                            "─".repeat(80)
+                        );
+                    }
+
+                    let args = matches.values_of_os(ARGS_FOR_APP).unwrap_or_default();
+
+                    // Unlike `roc_run`, this can't replace the current process with the program,
+                    // because we need to keep watching (and restart the program) afterwards.
+                    let child = process::Command::new(&binary_path).args(args).spawn()?;
+
+                    Ok(WatchedRun {
+                        exit_code: 0,
+                        module_paths,
+                        child: Some(child),
+                    })
                 }
             }
         }
@@ -647,26 +1225,31 @@ pub fn build(
         }) => {
             debug_assert!(module.total_problems() > 0);
 
-            let problems = roc_build::program::report_problems_typechecked(&mut module);
+            let problems = roc_build::program::report_problems_typechecked(&mut module, render);
 
-            print_problems(problems, total_time);
+            if !matches!(render, RenderTarget::Json) {
+                print_problems(problems, total_time);
 
-            print!(".\n\nYou can run the program anyway with \x1B[32mroc run");
+                print!(".\n\nYou can run the program anyway with \x1B[32mroc run");
 
-            // If you're running "main.roc" then you can just do `roc run`
-            // to re-run the program.
-            if filename != DEFAULT_ROC_FILENAME {
-                print!(" {}", &filename.to_string_lossy());
-            }
+                // If you're running "main.roc" then you can just do `roc run`
+                // to re-run the program.
+                if filename != DEFAULT_ROC_FILENAME {
+                    print!(" {}", &filename.to_string_lossy());
+                }
 
-            println!("\x1B[39m");
+                println!("\x1B[39m");
+            }
 
-            Ok(problems.exit_code())
+            Ok(WatchedRun::finished(
+                problems.exit_code(),
+                watch::module_paths(&module.sources),
+            ))
         }
         Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(report))) => {
             print!("{}", report);
 
-            Ok(1)
+            Ok(WatchedRun::finished(1, Vec::new()))
         }
         Err(other) => {
             panic!("build_file failed with error:\n{:?}", other);
@@ -1214,27 +1797,30 @@ impl std::str::FromStr for Target {
     }
 }
 
-// These functions don't end up in the final Roc binary but Windows linker needs a definition inside the crate.
-// On Windows, there seems to be less dead-code-elimination than on Linux or MacOS, or maybe it's done later.
-#[cfg(windows)]
+// These functions don't end up in the final Roc binary. `roc glue --spec` passes Roc values to
+// (and receives them from) glue specs, so roc_std needs them in builds that can run specs. The
+// spec's host allocates with malloc, so these must too.
+// On Windows, the linker needs a definition inside the crate even without glue specs, because
+// there seems to be less dead-code-elimination than on Linux or MacOS, or maybe it's done later.
+#[cfg(any(windows, feature = "glue-spec"))]
 #[allow(unused_imports)]
-use windows_roc_platform_functions::*;
+use roc_platform_functions::*;
 
-#[cfg(windows)]
-mod windows_roc_platform_functions {
+#[cfg(any(windows, feature = "glue-spec"))]
+mod roc_platform_functions {
     use core::ffi::c_void;
 
     /// # Safety
     /// The Roc application needs this.
     #[no_mangle]
-    pub unsafe fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
+    pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
         libc::malloc(size)
     }
 
     /// # Safety
     /// The Roc application needs this.
     #[no_mangle]
-    pub unsafe fn roc_realloc(
+    pub unsafe extern "C" fn roc_realloc(
         c_ptr: *mut c_void,
         new_size: usize,
         _old_size: usize,
@@ -1246,7 +1832,7 @@ mod windows_roc_platform_functions {
     /// # Safety
     /// The Roc application needs this.
     #[no_mangle]
-    pub unsafe fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
+    pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
         libc::free(c_ptr)
     }
 }
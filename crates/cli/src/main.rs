@@ -1,15 +1,19 @@
 //! the `roc` binary
 use roc_build::link::LinkType;
 use roc_cli::build::check_file;
+use roc_cli::watch::{self, WatchedRun};
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV,
-    CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST,
-    CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME,
-    GLUE_FILE, ROC_FILE,
+    build_app, format, render_target, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK,
+    CMD_DEV, CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_LSP, CMD_REPL,
+    CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_FIX, FLAG_LIB,
+    FLAG_NO_LINK, FLAG_OUTPUT_FORMAT, FLAG_PACKAGE_VERSION, FLAG_ROUND_TRIP, FLAG_SPEC,
+    FLAG_TARGET, FLAG_TIME, FLAG_WATCH, GLUE_FILE, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::generate_docs;
 use roc_error_macros::user_error;
 use roc_load::{LoadingProblem, Threading};
+use roc_repl_cli::ReplBackend;
+use roc_reporting::report::RenderTarget;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -70,12 +74,13 @@ fn main() -> io::Result<()> {
         }
         Some((CMD_DEV, matches)) => {
             if matches.is_present(ROC_FILE) {
-                build(
-                    matches,
-                    BuildConfig::BuildAndRunIfNoErrors,
-                    Triple::host(),
-                    LinkType::Executable,
-                )
+                let config = if matches.is_present(FLAG_WATCH) {
+                    BuildConfig::WatchAndRunIfNoErrors
+                } else {
+                    BuildConfig::BuildAndRunIfNoErrors
+                };
+
+                build(matches, config, Triple::host(), LinkType::Executable)
             } else {
                 eprintln!("What .roc file do you want to build? Specify it at the end of the `roc run` command.");
 
@@ -86,12 +91,14 @@ fn main() -> io::Result<()> {
             let input_path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
             let output_path = Path::new(matches.value_of_os(GLUE_FILE).unwrap());
 
-            if Some("rs") == output_path.extension().and_then(OsStr::to_str) {
-                roc_glue::generate(input_path, output_path)
-            } else {
-                eprintln!("Currently, `roc glue` only supports generating Rust glue files (with the .rs extension). In the future, the plan is to decouple `roc glue` from any particular output format, by having it accept a second .roc file which gets executed as a plugin to generate glue code for any desired language. However, this has not yet been implemented, and for now only .rs is supported.");
-
-                Ok(1)
+            match matches.value_of_os(FLAG_SPEC) {
+                Some(spec_path) => {
+                    generate_glue_with_spec(input_path, Path::new(spec_path), output_path)
+                }
+                None if matches.is_present(FLAG_ROUND_TRIP) => {
+                    roc_glue::generate_round_trip(input_path, output_path)
+                }
+                None => roc_glue::generate(input_path, output_path),
             }
         }
         Some((CMD_GEN_STUB_LIB, matches)) => {
@@ -121,8 +128,6 @@ fn main() -> io::Result<()> {
             )?)
         }
         Some((CMD_CHECK, matches)) => {
-            let arena = bumpalo::Bump::new();
-
             let emit_timings = matches.is_present(FLAG_TIME);
             let filename = matches.value_of_os(ROC_FILE).unwrap();
             let roc_file_path = PathBuf::from(filename);
@@ -135,57 +140,32 @@ fn main() -> io::Result<()> {
                 Some(1) => Threading::Single,
                 Some(n) => Threading::AtMost(n),
             };
+            let render = render_target(matches);
+            let fix = matches.is_present(FLAG_FIX);
 
-            match check_file(&arena, roc_file_path, emit_timings, threading) {
-                Ok((problems, total_time)) => {
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                    );
-
-                    Ok(problems.exit_code())
-                }
-
-                Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{}", report);
-
-                    Ok(1)
-                }
-                Err(other) => {
-                    panic!("build_file failed with error:\n{:?}", other);
-                }
+            if matches.is_present(FLAG_WATCH) {
+                watch::watch(&roc_file_path, || {
+                    check(&roc_file_path, emit_timings, threading, render, fix)
+                })
+            } else {
+                check(&roc_file_path, emit_timings, threading, render, fix).map(|run| run.exit_code)
             }
         }
-        Some((CMD_REPL, _)) => {
+        Some((CMD_REPL, matches)) => {
             {
-                roc_repl_cli::main()?;
+                let backend = if matches.is_present(FLAG_DEV) {
+                    ReplBackend::Dev
+                } else {
+                    ReplBackend::Llvm
+                };
+
+                roc_repl_cli::main(backend)?;
 
                 // Exit 0 if the repl exited normally
                 Ok(0)
             }
         }
+        Some((CMD_LSP, _)) => roc_lang_srv::run_stdio(),
         Some((CMD_EDIT, matches)) => {
             match matches
                 .values_of_os(DIRECTORY_OR_FILES)
@@ -233,7 +213,13 @@ fn main() -> io::Result<()> {
                 roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
             }
 
-            generate_docs_html(roc_files);
+            generate_docs(
+                roc_files,
+                matches.value_of(FLAG_PACKAGE_VERSION).map(str::to_string),
+                matches
+                    .value_of_t(FLAG_OUTPUT_FORMAT)
+                    .unwrap_or_else(|err| err.exit()),
+            );
 
             Ok(0)
         }
@@ -297,6 +283,67 @@ fn main() -> io::Result<()> {
     std::process::exit(exit_code);
 }
 
+fn check(
+    roc_file_path: &Path,
+    emit_timings: bool,
+    threading: Threading,
+    render: RenderTarget,
+    fix: bool,
+) -> io::Result<WatchedRun> {
+    let arena = bumpalo::Bump::new();
+
+    match check_file(
+        &arena,
+        roc_file_path.to_path_buf(),
+        emit_timings,
+        threading,
+        render,
+        fix,
+    ) {
+        Ok((problems, total_time, module_paths)) => {
+            if !matches!(render, RenderTarget::Json) {
+                println!(
+                    "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
+                    if problems.errors == 0 {
+                        32 // green
+                    } else {
+                        33 // yellow
+                    },
+                    problems.errors,
+                    if problems.errors == 1 {
+                        "error"
+                    } else {
+                        "errors"
+                    },
+                    if problems.warnings == 0 {
+                        32 // green
+                    } else {
+                        33 // yellow
+                    },
+                    problems.warnings,
+                    if problems.warnings == 1 {
+                        "warning"
+                    } else {
+                        "warnings"
+                    },
+                    total_time.as_millis(),
+                );
+            }
+
+            Ok(WatchedRun::finished(problems.exit_code(), module_paths))
+        }
+
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
+
+            Ok(WatchedRun::finished(1, Vec::new()))
+        }
+        Err(other) => {
+            panic!("build_file failed with error:\n{:?}", other);
+        }
+    }
+}
+
 fn read_all_roc_files(
     dir: &OsString,
     roc_file_paths: &mut Vec<OsString>,
@@ -335,6 +382,26 @@ fn roc_files_recursive<P: AsRef<Path>>(
     Ok(())
 }
 
+#[cfg(feature = "glue-spec")]
+fn generate_glue_with_spec(
+    input_path: &Path,
+    spec_path: &Path,
+    output_dir: &Path,
+) -> io::Result<i32> {
+    roc_cli::glue::generate_with_spec(input_path, spec_path, output_dir)
+}
+
+#[cfg(not(feature = "glue-spec"))]
+fn generate_glue_with_spec(
+    _input_path: &Path,
+    _spec_path: &Path,
+    _output_dir: &Path,
+) -> io::Result<i32> {
+    eprintln!("Cannot run glue specs because this build of roc did not include `feature = \"glue-spec\"`!");
+
+    Ok(1)
+}
+
 #[cfg(feature = "editor")]
 fn launch_editor(project_dir_path: Option<&Path>) -> io::Result<()> {
     roc_editor::launch(project_dir_path)
@@ -0,0 +1,381 @@
+//! Machine-readable results for `roc test --reporter`, for CI dashboards and other tools.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use roc_repl_expect::filter::ExpectInfo;
+use roc_repl_expect::run::ExpectResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reporter {
+    /// JUnit XML, with a `<testsuite>` per module
+    Junit,
+    /// The Test Anything Protocol, version 13
+    Tap,
+    /// One JSON object per expect, on a line of its own
+    Json,
+}
+
+impl std::str::FromStr for Reporter {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "junit" => Ok(Reporter::Junit),
+            "tap" => Ok(Reporter::Tap),
+            "json" => Ok(Reporter::Json),
+            _ => Err(format!(
+                "Roc does not know a test reporter named {}",
+                string
+            )),
+        }
+    }
+}
+
+/// An expect that ran, along with how it went.
+pub struct TestCase<'a> {
+    pub expect: &'a ExpectInfo,
+    pub result: &'a ExpectResult,
+}
+
+/// Pairs up the expects with their results, in source order. Expects that did not run are left out.
+pub fn test_cases<'a>(expects: &'a [ExpectInfo], results: &'a [ExpectResult]) -> Vec<TestCase<'a>> {
+    expects
+        .iter()
+        .filter_map(|expect| {
+            let result = results
+                .iter()
+                .find(|result| result.symbol == expect.symbol)?;
+
+            Some(TestCase { expect, result })
+        })
+        .collect()
+}
+
+pub fn write_report<W: Write>(
+    writer: &mut W,
+    reporter: Reporter,
+    cases: &[TestCase],
+    total_time: Duration,
+) -> io::Result<()> {
+    match reporter {
+        Reporter::Junit => write_junit(writer, cases, total_time),
+        Reporter::Tap => write_tap(writer, cases),
+        Reporter::Json => write_json(writer, cases),
+    }
+}
+
+fn write_junit<W: Write>(
+    writer: &mut W,
+    cases: &[TestCase],
+    total_time: Duration,
+) -> io::Result<()> {
+    // Group the test cases by module, keeping them in source order.
+    let mut modules: Vec<(&str, Vec<&TestCase>)> = Vec::new();
+
+    for case in cases {
+        let module_name = case.expect.module_name.as_str();
+
+        match modules.iter_mut().find(|(name, _)| *name == module_name) {
+            Some((_, module_cases)) => module_cases.push(case),
+            None => modules.push((module_name, vec![case])),
+        }
+    }
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites tests="{}" failures="{}" time="{:.6}">"#,
+        cases.len(),
+        count_failures(cases.iter()),
+        total_time.as_secs_f64(),
+    )?;
+
+    for (module_name, module_cases) in modules {
+        let time: Duration = module_cases.iter().map(|case| case.result.duration).sum();
+
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.6}">"#,
+            xml_escape(module_name),
+            module_cases.len(),
+            count_failures(module_cases.iter().copied()),
+            time.as_secs_f64(),
+        )?;
+
+        for case in module_cases {
+            let TestCase { expect, result } = case;
+
+            write!(
+                writer,
+                r#"    <testcase name="{}" classname="{}" file="{}" line="{}" time="{:.6}""#,
+                xml_escape(&expect.name),
+                xml_escape(&expect.module_name),
+                xml_escape(&expect.path.to_string_lossy()),
+                expect.line,
+                result.duration.as_secs_f64(),
+            )?;
+
+            if result.passed {
+                writeln!(writer, "/>")?;
+            } else {
+                writeln!(writer, ">")?;
+                writeln!(
+                    writer,
+                    r#"      <failure message="expect failed">{}</failure>"#,
+                    xml_escape(result.output.trim_end()),
+                )?;
+                writeln!(writer, "    </testcase>")?;
+            }
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")
+}
+
+fn count_failures<'a, I>(cases: I) -> usize
+where
+    I: Iterator<Item = &'a TestCase<'a>>,
+{
+    cases.filter(|case| !case.result.passed).count()
+}
+
+fn write_tap<W: Write>(writer: &mut W, cases: &[TestCase]) -> io::Result<()> {
+    writeln!(writer, "TAP version 13")?;
+    writeln!(writer, "1..{}", cases.len())?;
+
+    for (index, TestCase { expect, result }) in cases.iter().enumerate() {
+        let status = if result.passed { "ok" } else { "not ok" };
+
+        // `#` starts a directive in TAP, so it can't be part of the description.
+        let description = expect.name.replace('#', "\\#");
+
+        writeln!(writer, "{} {} - {}", status, index + 1, description)?;
+
+        // A YAML block with the details
+        writeln!(writer, "  ---")?;
+        writeln!(
+            writer,
+            "  file: {}",
+            yaml_quote(&expect.path.to_string_lossy())
+        )?;
+        writeln!(writer, "  line: {}", expect.line)?;
+        writeln!(
+            writer,
+            "  duration_ms: {:.3}",
+            result.duration.as_secs_f64() * 1000.0
+        )?;
+
+        if !result.passed {
+            writeln!(writer, "  message: |")?;
+
+            for line in result.output.trim_end().lines() {
+                writeln!(writer, "    {}", line)?;
+            }
+        }
+
+        writeln!(writer, "  ...")?;
+    }
+
+    Ok(())
+}
+
+fn write_json<W: Write>(writer: &mut W, cases: &[TestCase]) -> io::Result<()> {
+    for TestCase { expect, result } in cases {
+        let json = serde_json::json!({
+            "name": expect.name,
+            "module": expect.module_name,
+            "filename": expect.path,
+            "line": expect.line,
+            "column": expect.column,
+            "status": if result.passed { "passed" } else { "failed" },
+            "duration_ms": result.duration.as_secs_f64() * 1000.0,
+            "output": result.output.trim_end(),
+        });
+
+        writeln!(writer, "{}", json)?;
+    }
+
+    Ok(())
+}
+
+/// A YAML double-quoted string, which can hold any characters once they are escaped.
+fn yaml_quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+
+    quoted.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+
+    quoted
+}
+
+fn xml_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+
+    for c in string.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 does not allow most control characters, even escaped ones
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{test_cases, write_report, Reporter, TestCase};
+    use roc_module::symbol::Symbol;
+    use roc_region::all::Region;
+    use roc_repl_expect::filter::ExpectInfo;
+    use roc_repl_expect::run::ExpectResult;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn expect(symbol: Symbol, name: &str, module_name: &str, path: &str) -> ExpectInfo {
+        ExpectInfo {
+            symbol,
+            region: Region::zero(),
+            module_name: module_name.to_string(),
+            path: PathBuf::from(path),
+            line: 3,
+            column: 1,
+            name: name.to_string(),
+            source: String::new(),
+        }
+    }
+
+    fn result(symbol: Symbol, passed: bool, output: &str) -> ExpectResult {
+        ExpectResult {
+            symbol,
+            passed,
+            duration: Duration::from_millis(5),
+            output: output.to_string(),
+        }
+    }
+
+    fn report(reporter: Reporter, cases: &[TestCase]) -> String {
+        let mut buf = Vec::new();
+
+        write_report(&mut buf, reporter, cases, Duration::from_millis(20)).unwrap();
+
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn junit_has_a_testsuite_per_module() {
+        let expects = [
+            expect(Symbol::BOOL_TRUE, "a < b & \"c\"", "Main", "main.roc"),
+            expect(Symbol::BOOL_FALSE, "fails", "Main", "main.roc"),
+            expect(Symbol::LIST_LEN, "other", "Dep", "Dep.roc"),
+        ];
+        let results = [
+            result(Symbol::BOOL_TRUE, true, ""),
+            result(Symbol::BOOL_FALSE, false, "expected <1>\u{1b}[0m\n"),
+            result(Symbol::LIST_LEN, true, ""),
+        ];
+
+        assert_eq!(
+            report(Reporter::Junit, &test_cases(&expects, &results)),
+            indoc::indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites tests="3" failures="1" time="0.020000">
+                  <testsuite name="Main" tests="2" failures="1" time="0.010000">
+                    <testcase name="a &lt; b &amp; &quot;c&quot;" classname="Main" file="main.roc" line="3" time="0.005000"/>
+                    <testcase name="fails" classname="Main" file="main.roc" line="3" time="0.005000">
+                      <failure message="expect failed">expected &lt;1&gt;[0m</failure>
+                    </testcase>
+                  </testsuite>
+                  <testsuite name="Dep" tests="1" failures="0" time="0.005000">
+                    <testcase name="other" classname="Dep" file="Dep.roc" line="3" time="0.005000"/>
+                  </testsuite>
+                </testsuites>
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn json_has_a_line_per_expect() {
+        let expects = [
+            expect(Symbol::BOOL_TRUE, "passes", "Main", "main.roc"),
+            expect(Symbol::BOOL_FALSE, "fails", "Main", "main.roc"),
+        ];
+        let results = [
+            result(Symbol::BOOL_TRUE, true, ""),
+            result(Symbol::BOOL_FALSE, false, "it failed\n"),
+        ];
+
+        let json = report(Reporter::Json, &test_cases(&expects, &results));
+        let lines: Vec<serde_json::Value> = json
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                serde_json::json!({
+                    "name": "passes",
+                    "module": "Main",
+                    "filename": "main.roc",
+                    "line": 3,
+                    "column": 1,
+                    "status": "passed",
+                    "duration_ms": 5.0,
+                    "output": "",
+                }),
+                serde_json::json!({
+                    "name": "fails",
+                    "module": "Main",
+                    "filename": "main.roc",
+                    "line": 3,
+                    "column": 1,
+                    "status": "failed",
+                    "duration_ms": 5.0,
+                    "output": "it failed",
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn tap_file_is_a_yaml_string() {
+        let expects = [expect(
+            Symbol::BOOL_TRUE,
+            "passes",
+            "Main",
+            "dir \"a\\b\"\u{1b}/main.roc",
+        )];
+        let results = [result(Symbol::BOOL_TRUE, true, "")];
+
+        let tap = report(Reporter::Tap, &test_cases(&expects, &results));
+
+        assert!(
+            tap.contains(r#"  file: "dir \"a\\b\"\u001B/main.roc""#),
+            "{}",
+            tap
+        );
+    }
+}
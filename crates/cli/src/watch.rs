@@ -0,0 +1,218 @@
+//! Support for `--watch`: re-running a command whenever any of the modules it loaded change.
+//!
+//! Every run loads the program from scratch. Modules that did not change are not checked again,
+//! though: all of the watched commands use the on-disk module cache, where the solved types of
+//! those modules are found.
+
+use roc_collections::MutMap;
+use roc_module::symbol::ModuleId;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Editors often save a file in several steps (e.g. truncate, then write),
+/// so wait a little after the first change before re-running.
+const SETTLE_TIME: Duration = Duration::from_millis(50);
+
+/// The outcome of running a command once.
+pub struct WatchedRun {
+    pub exit_code: i32,
+    /// The source files of every module that was loaded. When loading failed before all of
+    /// them were discovered, this is empty, and the files from the previous run are watched.
+    pub module_paths: Vec<PathBuf>,
+    /// A program that was started by the command (e.g. by `roc dev`),
+    /// which gets stopped before the command runs again.
+    pub child: Option<Child>,
+}
+
+impl WatchedRun {
+    pub fn finished(exit_code: i32, module_paths: Vec<PathBuf>) -> Self {
+        Self {
+            exit_code,
+            module_paths,
+            child: None,
+        }
+    }
+
+    /// Stops the program the command started, if any.
+    fn stop_child(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // The program may well have exited on its own already, in which case this fails.
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// The files that are watched, which change as the program gains or loses modules.
+struct Watcher {
+    root_path: PathBuf,
+    watched_paths: Vec<PathBuf>,
+}
+
+impl Watcher {
+    fn new(root_path: &Path) -> Self {
+        Self {
+            root_path: root_path.to_path_buf(),
+            watched_paths: vec![root_path.to_path_buf()],
+        }
+    }
+
+    /// Watches the modules the last run loaded, along with the root module (which may not have
+    /// been loaded if it failed to parse).
+    fn update(&mut self, run: &WatchedRun) {
+        if !run.module_paths.is_empty() {
+            self.watched_paths = run.module_paths.clone();
+
+            if !self.watched_paths.contains(&self.root_path) {
+                self.watched_paths.push(self.root_path.clone());
+            }
+        }
+    }
+
+    /// Blocks until one of the watched files is modified, created or deleted.
+    fn wait_for_change(&self) {
+        let before = modification_times(&self.watched_paths);
+
+        while modification_times(&self.watched_paths) == before {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+
+        std::thread::sleep(SETTLE_TIME);
+    }
+}
+
+/// The paths of the (non-builtin) modules in a loaded program.
+pub fn module_paths(sources: &MutMap<ModuleId, (PathBuf, Box<str>)>) -> Vec<PathBuf> {
+    sources
+        .iter()
+        .filter(|(module_id, _)| !module_id.is_builtin())
+        .map(|(_, (path, _))| path.clone())
+        .collect()
+}
+
+/// Runs the command, then runs it again every time one of the files it loaded changes.
+///
+/// This only returns if running the command fails with an I/O error;
+/// otherwise it keeps watching until the process is interrupted.
+pub fn watch<F>(root_path: &Path, mut run: F) -> io::Result<i32>
+where
+    F: FnMut() -> io::Result<WatchedRun>,
+{
+    let mut watcher = Watcher::new(root_path);
+
+    loop {
+        clear_screen()?;
+
+        let mut watched_run = run()?;
+
+        watcher.update(&watched_run);
+
+        let count = watcher.watched_paths.len();
+
+        println!(
+            "\n\x1B[36mWatching {} {} for changes…\x1B[39m",
+            count,
+            if count == 1 { "file" } else { "files" }
+        );
+
+        watcher.wait_for_change();
+        watched_run.stop_child();
+    }
+}
+
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+fn clear_screen() -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    // Clear the screen and the scrollback, then move the cursor to the top left.
+    write!(stdout, "\x1B[2J\x1B[3J\x1B[H")?;
+
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{WatchedRun, Watcher};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    #[test]
+    fn watches_the_loaded_modules_and_the_root() {
+        let root = Path::new("/app/main.roc");
+        let mut watcher = Watcher::new(root);
+
+        assert_eq!(watcher.watched_paths, [root]);
+
+        let dep = PathBuf::from("/app/Dep.roc");
+        watcher.update(&WatchedRun::finished(0, vec![dep.clone()]));
+
+        assert_eq!(watcher.watched_paths, [dep.as_path(), root]);
+
+        // A run that failed before discovering the modules keeps the ones from before
+        watcher.update(&WatchedRun::finished(1, Vec::new()));
+
+        assert_eq!(watcher.watched_paths, [dep.as_path(), root]);
+    }
+
+    #[test]
+    fn notices_a_modified_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("main.roc");
+        std::fs::write(&root, "x = 1").unwrap();
+
+        let watcher = Watcher::new(&root);
+
+        let writer = std::thread::spawn(move || {
+            // Modification times can be as coarse as a second
+            std::thread::sleep(Duration::from_millis(1100));
+            std::fs::write(&root, "x = 2").unwrap();
+        });
+
+        watcher.wait_for_change();
+        writer.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stops_the_started_program() {
+        let child = std::process::Command::new("sleep")
+            .arg("60")
+            .spawn()
+            .unwrap();
+
+        let pid = child.id().to_string();
+
+        let mut run = WatchedRun {
+            exit_code: 0,
+            module_paths: Vec::new(),
+            child: Some(child),
+        };
+
+        run.stop_child();
+
+        // The process is gone, rather than killed but not yet reaped
+        let still_running = std::process::Command::new("kill")
+            .args(["-0", &pid])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap()
+            .success();
+
+        assert!(!still_running);
+    }
+}
@@ -11,12 +11,14 @@ extern crate roc_module;
 mod cli_run {
     use cli_utils::helpers::{
         extract_valgrind_errors, file_path_from_root, fixture_file, fixtures_dir, known_bad_file,
-        run_cmd, run_roc, run_with_valgrind, strip_colors, Out, ValgrindError, ValgrindErrorXWhat,
+        path_to_roc_binary, run_cmd, run_roc, run_with_valgrind, strip_colors, Out, ValgrindError,
+        ValgrindErrorXWhat,
     };
     use const_format::concatcp;
     use indoc::indoc;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_LSP, CMD_RUN, CMD_TEST};
     use roc_test_utils::assert_multiline_str_eq;
+    use serde_json::{json, Value};
     use serial_test::serial;
     use std::iter;
     use std::path::Path;
@@ -24,6 +26,14 @@ mod cli_run {
     const OPTIMIZE_FLAG: &str = concatcp!("--", roc_cli::FLAG_OPTIMIZE);
     const LINKER_FLAG: &str = concatcp!("--", roc_cli::FLAG_LINKER);
     const CHECK_FLAG: &str = concatcp!("--", roc_cli::FLAG_CHECK);
+    const FORMAT_FLAG: &str = concatcp!("--", roc_cli::FLAG_FORMAT);
+    const LIST_FLAG: &str = concatcp!("--", roc_cli::FLAG_LIST);
+    const FILTER_FLAG: &str = concatcp!("--", roc_cli::FLAG_FILTER);
+    const REPORTER_FLAG: &str = concatcp!("--", roc_cli::FLAG_REPORTER);
+    const TIMEOUT_FLAG: &str = concatcp!("--", roc_cli::FLAG_TIMEOUT);
+    const DOC_FLAG: &str = concatcp!("--", roc_cli::FLAG_DOC);
+    const WATCH_FLAG: &str = concatcp!("--", roc_cli::FLAG_WATCH);
+    const COVERAGE_FLAG: &str = concatcp!("--", roc_cli::FLAG_COVERAGE);
     const PREBUILT_PLATFORM: &str = concatcp!("--", roc_cli::FLAG_PREBUILT, "=true");
     #[allow(dead_code)]
     const TARGET_FLAG: &str = concatcp!("--", roc_cli::FLAG_TARGET);
@@ -1120,6 +1130,495 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn unused_import_json() {
+        let file = known_bad_file("UnusedImport.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), FORMAT_FLAG, "json"],
+            &[],
+            &[],
+        );
+
+        let lines: Vec<&str> = out.stdout.lines().collect();
+        assert_eq!(lines.len(), 1, "expected one report, got:\n{}", out.stdout);
+
+        let report: Value = serde_json::from_str(lines[0]).unwrap();
+
+        assert_eq!(report["title"], "UNUSED IMPORT");
+        assert_eq!(report["severity"], "warning");
+        assert_eq!(
+            report["region"],
+            json!({
+                "start": { "line": 3, "column": 14 },
+                "end": { "line": 3, "column": 30 },
+            })
+        );
+        assert!(report["filename"]
+            .as_str()
+            .unwrap()
+            .ends_with("UnusedImport.roc"));
+        assert!(report["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Nothing from Symbol is used in this module."));
+        assert_eq!(out.status.code(), Some(1));
+    }
+
+    #[test]
+    fn parse_error_json() {
+        let file = known_bad_file("ParseError.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), FORMAT_FLAG, "json"],
+            &[],
+            &[],
+        );
+
+        let report: Value = serde_json::from_str(out.stdout.trim()).unwrap();
+
+        assert_eq!(report["title"], "UNFINISHED LIST");
+        assert_eq!(report["severity"], "error");
+        assert_eq!(
+            report["region"],
+            json!({
+                "start": { "line": 6, "column": 1 },
+                "end": { "line": 6, "column": 2 },
+            })
+        );
+        assert_eq!(out.status.code(), Some(1));
+    }
+
+    /// Frames each message the way a language client would, with a Content-Length header.
+    fn lsp_input(messages: &[Value]) -> String {
+        messages
+            .iter()
+            .map(|message| {
+                let body = message.to_string();
+                format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+            })
+            .collect()
+    }
+
+    fn lsp_output(mut stdout: &str) -> Vec<Value> {
+        let mut messages = Vec::new();
+
+        while let Some(header_end) = stdout.find("\r\n\r\n") {
+            let length: usize = stdout[..header_end]
+                .trim_start_matches("Content-Length: ")
+                .parse()
+                .unwrap();
+            let body_start = header_end + 4;
+
+            messages.push(serde_json::from_str(&stdout[body_start..body_start + length]).unwrap());
+            stdout = &stdout[body_start + length..];
+        }
+
+        messages
+    }
+
+    #[test]
+    fn lsp_diagnostics_hover_and_definition() {
+        let file = known_bad_file("UnusedImport.roc").canonicalize().unwrap();
+        let uri = format!("file://{}", file.to_str().unwrap().replace('\\', "/"));
+        let text = std::fs::read_to_string(&file).unwrap();
+
+        let input = lsp_input(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": { "uri": uri, "languageId": "roc", "version": 1, "text": text }
+                }
+            }),
+            // `emText` in `emText = \str -> EmText str`
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": { "textDocument": { "uri": uri }, "position": { "line": 6, "character": 2 } }
+            }),
+            // the `str` argument in the body of `emText`
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "textDocument/definition",
+                "params": { "textDocument": { "uri": uri }, "position": { "line": 6, "character": 25 } }
+            }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+
+        let out = run_roc([CMD_LSP], &[&input], &[]);
+
+        assert!(out.status.success(), "roc lsp failed: {}", out.stderr);
+
+        let messages = lsp_output(&out.stdout);
+        let response = |id: u64| {
+            messages
+                .iter()
+                .find(|message| message["id"] == json!(id))
+                .unwrap_or_else(|| panic!("no response with id {} in {:?}", id, messages))
+        };
+
+        assert_eq!(
+            response(1)["result"]["capabilities"]["hoverProvider"],
+            json!(true)
+        );
+
+        let diagnostics = messages
+            .iter()
+            .find(|message| message["method"] == json!("textDocument/publishDiagnostics"))
+            .unwrap();
+        assert_eq!(diagnostics["params"]["uri"], json!(uri));
+
+        let diagnostic = &diagnostics["params"]["diagnostics"][0];
+        assert_eq!(
+            diagnostic["range"],
+            json!({
+                "start": { "line": 2, "character": 13 },
+                "end": { "line": 2, "character": 29 },
+            })
+        );
+        assert_eq!(diagnostic["severity"], json!(2));
+        assert_eq!(diagnostic["code"], json!("UNUSED IMPORT"));
+        assert!(diagnostic["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Nothing from Symbol is used in this module."));
+
+        let hover = response(2)["result"]["contents"]["value"].as_str().unwrap();
+        assert!(hover.contains("EmText"), "unexpected hover: {}", hover);
+
+        assert_eq!(
+            response(3)["result"]["range"],
+            json!({
+                "start": { "line": 6, "character": 10 },
+                "end": { "line": 6, "character": 13 },
+            })
+        );
+
+        assert_eq!(response(4)["result"], Value::Null);
+    }
+
+    #[test]
+    fn check_watch_reruns_when_a_dependency_changes() {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("Main.roc");
+        let dep = dir.path().join("Dep.roc");
+
+        std::fs::write(
+            &main,
+            "interface Main exposes [str] imports [Dep]\n\nstr = Dep.str\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &dep,
+            "interface Dep exposes [str] imports []\n\nstr = \"hi\"\n",
+        )
+        .unwrap();
+
+        // Unchanged modules come from the module cache on every run after the first one.
+        let mut roc = Command::new(path_to_roc_binary())
+            .args([CMD_CHECK, WATCH_FLAG, main.to_str().unwrap()])
+            .env("XDG_CACHE_HOME", dir.path().join("cache"))
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let (sender, lines) = mpsc::channel();
+        let stdout = BufReader::new(roc.stdout.take().unwrap());
+
+        std::thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(strip_colors(&line.unwrap())).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // The output of one run, up to the point where it starts watching
+        let next_run = || {
+            let mut output = String::new();
+
+            while let Ok(line) = lines.recv_timeout(Duration::from_secs(120)) {
+                output.push_str(&line);
+                output.push('\n');
+
+                if line.contains("for changes") {
+                    break;
+                }
+            }
+
+            output
+        };
+
+        let first_run = next_run();
+
+        // Modification times can be as coarse as a second
+        std::thread::sleep(Duration::from_millis(1100));
+        std::fs::write(
+            &dep,
+            "interface Dep exposes [str] imports []\n\nstr = 1 + \"hi\"\n",
+        )
+        .unwrap();
+
+        let second_run = next_run();
+
+        roc.kill().unwrap();
+        roc.wait().unwrap();
+
+        assert!(first_run.contains("0 errors"), "{}", first_run);
+        assert!(first_run.contains("Watching 2 files"), "{}", first_run);
+        assert!(second_run.contains("TYPE MISMATCH"), "{}", second_run);
+        assert!(second_run.contains("Watching 2 files"), "{}", second_run);
+    }
+
+    #[test]
+    fn list_expects() {
+        let file = fixture_file("expects", "Expects.roc");
+        let out = run_roc([CMD_TEST, LIST_FLAG, file.to_str().unwrap()], &[], &[]);
+
+        let listed: Vec<&str> = out
+            .stdout
+            .lines()
+            .map(|line| line.split_once("  ").unwrap().1)
+            .collect();
+
+        assert_eq!(
+            listed,
+            [
+                "addition works",
+                r#"expect Str.concat "a" "b" == "ab""#,
+                "subtraction works",
+            ]
+        );
+        assert!(out.stdout.contains("Expects.roc:6:1"));
+        assert!(out.status.success());
+    }
+
+    #[test]
+    fn filter_expects() {
+        let file = fixture_file("expects", "Expects.roc");
+        let out = run_roc(
+            [CMD_TEST, FILTER_FLAG, "subtraction", file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(stdout.contains("subtraction works"), "{}", stdout);
+        assert!(!stdout.contains("addition works"), "{}", stdout);
+        assert!(stdout.contains("0 failed and 1 passed"), "{}", stdout);
+        assert!(out.status.success());
+    }
+
+    #[test]
+    fn tap_reporter() {
+        let file = fixture_file("expects", "Expects.roc");
+        let out = run_roc(
+            [CMD_TEST, REPORTER_FLAG, "tap", file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        let results: Vec<&str> = out
+            .stdout
+            .lines()
+            .filter(|line| !line.starts_with(' '))
+            .collect();
+
+        assert_eq!(
+            results,
+            [
+                "TAP version 13",
+                "1..3",
+                "ok 1 - addition works",
+                r#"ok 2 - expect Str.concat "a" "b" == "ab""#,
+                "ok 3 - subtraction works",
+            ]
+        );
+        assert!(out.status.success());
+    }
+
+    #[test]
+    fn crashing_expect_is_isolated() {
+        let file = fixture_file("expects", "Crashes.roc");
+        let out = run_roc(
+            [CMD_TEST, TIMEOUT_FLAG, "60", file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(stdout.contains("EXPECT PANICKED"), "{}", stdout);
+        assert!(stdout.contains("1 failed and 1 passed"), "{}", stdout);
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    fn hanging_expect_is_stopped() {
+        let file = fixture_file("expects", "Hangs.roc");
+        let out = run_roc(
+            [
+                CMD_TEST,
+                TIMEOUT_FLAG,
+                "1",
+                FILTER_FLAG,
+                "never finishes",
+                file.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(
+            stdout.contains("did not finish within 1000 ms"),
+            "{}",
+            stdout
+        );
+        assert!(stdout.contains("1 failed and 0 passed"), "{}", stdout);
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    fn expect_killed_by_a_signal_is_isolated() {
+        let file = fixture_file("expects", "Hangs.roc");
+        let out = run_roc(
+            [
+                CMD_TEST,
+                TIMEOUT_FLAG,
+                "60",
+                FILTER_FLAG,
+                "overflows the stack",
+                file.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(stdout.contains("was killed by signal"), "{}", stdout);
+        assert!(stdout.contains("1 failed and 0 passed"), "{}", stdout);
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    fn coverage_includes_code_that_was_never_compiled() {
+        let file = fixture_file("expects", "Coverage.roc");
+        let dir = tempfile::tempdir().unwrap();
+
+        // the lcov file is written to the current directory
+        let output = std::process::Command::new(path_to_roc_binary())
+            .args([CMD_TEST, COVERAGE_FLAG, file.to_str().unwrap()])
+            .current_dir(dir.path())
+            .env("ROC_SKIP_MODULE_CACHE", "1")
+            .output()
+            .unwrap();
+
+        let stdout = strip_colors(&String::from_utf8(output.stdout).unwrap());
+
+        assert!(stdout.contains("0 failed and 1 passed"), "{}", stdout);
+        assert!(
+            stdout.contains("defs 1/2 (50.0%)  branches 1/4 (25.0%)"),
+            "{}",
+            stdout
+        );
+        assert!(output.status.success());
+
+        let lcov = std::fs::read_to_string(dir.path().join("lcov.info")).unwrap();
+        let lines: Vec<&str> = lcov
+            .lines()
+            .filter(|line| line.starts_with("FN") || line.starts_with("BR"))
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "FN:5,sign",
+                "FN:11,unused",
+                "FNDA:1,sign",
+                "FNDA:0,unused",
+                "FNF:2",
+                "FNH:1",
+                // the `if` in `sign`
+                "BRDA:7,0,0,0",
+                "BRDA:9,0,1,1",
+                // the `when` in `unused`, which no expect calls
+                "BRDA:13,1,0,0",
+                "BRDA:14,1,1,0",
+                "BRF:4",
+                "BRH:1",
+            ]
+        );
+    }
+
+    #[test]
+    fn list_doc_tests() {
+        let file = fixture_file("expects", "DocTests.roc");
+        let out = run_roc(
+            [CMD_TEST, DOC_FLAG, LIST_FLAG, file.to_str().unwrap()],
+            &[],
+            &[],
+        );
+
+        let listed: Vec<&str> = out
+            .stdout
+            .lines()
+            .map(|line| line.split_once("  ").unwrap().1)
+            .collect();
+
+        assert_eq!(
+            listed,
+            [
+                "doc test for `double`",
+                "doc test for `triple`",
+                "doc test for `Shape`",
+            ]
+        );
+        assert!(out.stdout.contains("DocTests.roc:8:4"), "{}", out.stdout);
+        assert!(out.status.success());
+    }
+
+    #[test]
+    fn failing_doc_test_points_at_the_docs() {
+        let file = fixture_file("expects", "DocTests.roc");
+        let out = run_roc([CMD_TEST, DOC_FLAG, file.to_str().unwrap()], &[], &[]);
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(stdout.contains("triple 2 == 5"), "{}", stdout);
+        assert!(stdout.contains("DocTests.roc:16:4"), "{}", stdout);
+        assert!(stdout.contains("1 failed and 2 passed"), "{}", stdout);
+        assert!(!out.status.success());
+    }
+
+    #[test]
+    fn doc_test_that_does_not_compile_points_at_the_docs() {
+        let file = fixture_file("expects", "BrokenDocTests.roc");
+        let out = run_roc([CMD_TEST, DOC_FLAG, file.to_str().unwrap()], &[], &[]);
+
+        let stdout = strip_colors(&out.stdout);
+
+        assert!(stdout.contains("TYPE MISMATCH"), "{}", stdout);
+        assert!(
+            stdout.contains(r#"8│     double "twenty-one" == 42"#),
+            "{}",
+            stdout
+        );
+        assert!(!out.status.success());
+    }
+
     #[test]
     fn format_check_good() {
         check_format_check_as_expected(&fixture_file("format", "Formatted.roc"), true);
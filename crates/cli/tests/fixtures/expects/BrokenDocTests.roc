@@ -0,0 +1,11 @@
+interface BrokenDocTests
+    exposes [double]
+    imports []
+
+## Doubles a number.
+##
+## ```roc
+## double "twenty-one" == 42
+## ```
+double : I64 -> I64
+double = \n -> n * 2
@@ -0,0 +1,16 @@
+interface Coverage
+    exposes [sign, unused]
+    imports []
+
+sign = \n ->
+    if n < 0 then
+        "negative"
+    else
+        "not negative"
+
+unused = \tag ->
+    when tag is
+        A -> 1
+        B -> 2
+
+expect sign 1 == "not negative"
@@ -0,0 +1,9 @@
+interface Crashes
+    exposes []
+    imports []
+
+# overflows
+expect Num.maxI64 + 1 > 0
+
+# still runs
+expect 1 + 1 == 2
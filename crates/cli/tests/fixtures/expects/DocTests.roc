@@ -0,0 +1,43 @@
+interface DocTests
+    exposes [double, triple, Shape, area]
+    imports []
+
+## Doubles a number.
+##
+## ```roc
+## double 21 == 42
+## ```
+double : I64 -> I64
+double = \n -> n * 2
+
+## Triples a number.
+##
+## ```roc
+## triple 2 == 5
+## ```
+##
+## Code blocks that aren't ```roc are not tests:
+##
+## ```roc-ignore
+## triple "not a number"
+## ```
+triple : I64 -> I64
+triple = \n -> n * 3
+
+## A shape with an area.
+##
+## ```roc
+## shape = Square 3
+##
+## area shape == 9
+## ```
+Shape : [Square I64, Rect I64 I64]
+
+area : Shape -> I64
+area = \shape ->
+    when shape is
+        Square side -> side * side
+        Rect width height -> width * height
+
+# Not a doc test, but not run by `roc test --doc` either
+expect double 1 == 2
@@ -0,0 +1,11 @@
+interface Expects
+    exposes []
+    imports []
+
+# addition works
+expect 1 + 1 == 2
+
+expect Str.concat "a" "b" == "ab"
+
+# subtraction works
+expect 3 - 1 == 2
@@ -0,0 +1,18 @@
+interface Hangs
+    exposes []
+    imports []
+
+spin : U64 -> U64
+spin = \n -> spin (Num.addWrap n 1)
+
+deep : U64 -> Str
+deep = \n -> Str.concat (deep (n + 1)) "!"
+
+# never finishes
+expect spin 0 == 0
+
+# overflows the stack
+expect deep 0 == ""
+
+# still runs
+expect 1 + 1 == 2
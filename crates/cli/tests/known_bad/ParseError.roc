@@ -0,0 +1,5 @@
+interface ParseError
+    exposes [x]
+    imports []
+
+x = [1, 2
@@ -142,6 +142,9 @@ where
         cmd.arg(arg);
     }
 
+    // Keep the modules of test programs out of the developer's own module cache.
+    cmd.env("ROC_SKIP_MODULE_CACHE", "1");
+
     for (k, v) in extra_env {
         cmd.env(k, v);
     }
@@ -516,6 +516,7 @@ pub fn rebuild_host(
     let cargo_host_src = host_input_path.with_file_name("Cargo.toml");
     let swift_host_src = host_input_path.with_file_name("host.swift");
     let swift_host_header_src = host_input_path.with_file_name("host.h");
+    let swift_glue_src = host_input_path.with_file_name("Glue.swift");
 
     let os = roc_target::OperatingSystem::from(target.operating_system);
     let executable_extension = match os {
@@ -772,12 +773,18 @@ pub fn rebuild_host(
 
         run_build_command(clang_cmd, "host.c");
     } else if swift_host_src.exists() {
-        // Compile host.swift, if it exists
+        // Compile host.swift, if it exists, along with any glue from `roc glue`
+        let mut sources = vec![swift_host_src.to_str().unwrap()];
+
+        if swift_glue_src.exists() {
+            sources.push(swift_glue_src.to_str().unwrap());
+        }
+
         let swiftc_cmd = build_swift_host_native(
             &env_path,
             &env_home,
             host_dest.to_str().unwrap(),
-            &[swift_host_src.to_str().unwrap()],
+            &sources,
             opt_level,
             shared_lib_path,
             swift_host_header_src
@@ -1297,8 +1304,35 @@ pub fn llvm_module_to_dylib(
 
     child.wait().unwrap();
 
-    // Load the dylib
-    let path = dylib_path.as_path().to_str().unwrap();
+    load_dylib(target, &dylib_path)
+}
+
+/// Like `llvm_module_to_dylib`, but for an object file from the dev backend. Unlike an LLVM
+/// module, that doesn't include the builtins, so they get linked in too.
+pub fn dev_object_to_dylib(object_bytes: &[u8], target: &Triple) -> Result<Library, Error> {
+    let dir = tempfile::tempdir().unwrap();
+    let app_o_file = dir.path().join("app.o");
+
+    std::fs::write(&app_o_file, object_bytes).expect("Writing .o file failed");
+
+    let (mut child, dylib_path) = link(
+        target,
+        app_o_file.clone(),
+        &[
+            app_o_file.to_str().unwrap(),
+            &bitcode::get_builtins_host_obj_path(),
+        ],
+        LinkType::Dylib,
+    )
+    .unwrap();
+
+    child.wait().unwrap();
+
+    load_dylib(target, &dylib_path)
+}
+
+fn load_dylib(target: &Triple, dylib_path: &Path) -> Result<Library, Error> {
+    let path = dylib_path.to_str().unwrap();
 
     if matches!(target.architecture, Architecture::Aarch64(_)) {
         // On AArch64 darwin machines, calling `ldopen` on Roc-generated libs from multiple threads
@@ -7,6 +7,7 @@ use roc_load::{EntryPoint, LoadedModule, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
 use roc_region::all::LineInfo;
+use roc_reporting::report::{Fix, RenderTarget};
 use roc_solve_problem::TypeError;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
@@ -21,26 +22,69 @@ pub struct CodeGenTiming {
     pub code_gen: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    render: RenderTarget,
+) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(loaded: &mut LoadedModule, render: RenderTarget) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
+/// The fixes to apply for each module's problems, by the module's path.
+/// Modules without any suggested fixes are left out.
+pub fn suggested_fixes(loaded: &LoadedModule) -> MutMap<PathBuf, Vec<Fix>> {
+    use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
+
+    let mut fixes = MutMap::default();
+
+    for (home, (module_path, src)) in loaded.sources.iter() {
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, *home, &loaded.interns);
+
+        let can_reports = loaded
+            .can_problems
+            .get(home)
+            .into_iter()
+            .flatten()
+            .map(|problem| can_problem(&alloc, &lines, module_path.clone(), problem.clone()));
+
+        let type_reports = loaded
+            .type_problems
+            .get(home)
+            .into_iter()
+            .flatten()
+            .filter_map(|problem| {
+                type_problem(&alloc, &lines, module_path.clone(), problem.clone())
+            });
+
+        let module_fixes = roc_reporting::fix::fixes_to_apply(can_reports.chain(type_reports));
+
+        if !module_fixes.is_empty() {
+            fixes.insert(module_path.clone(), module_fixes);
+        }
+    }
+
+    fixes
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
     pub errors: usize,
@@ -64,6 +108,7 @@ fn report_problems_help(
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
 ) -> Problems {
     use roc_reporting::report::{
         can_problem, type_problem, Report, RocDocAllocator, Severity::*, DEFAULT_PALETTE,
@@ -88,11 +133,12 @@ fn report_problems_help(
         let problems = can_problems.remove(home).unwrap_or_default();
 
         for problem in problems.into_iter() {
+            let region = problem.region().map(|region| lines.convert_region(region));
             let report = can_problem(&alloc, &lines, module_path.clone(), problem);
             let severity = report.severity;
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            report.render_with_region(render, region, &mut buf, &alloc, &palette);
 
             match severity {
                 Warning => {
@@ -107,11 +153,13 @@ fn report_problems_help(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
+            let region = problem.region().map(|region| lines.convert_region(region));
+
             if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
                 let severity = report.severity;
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                report.render_with_region(render, region, &mut buf, &alloc, &palette);
 
                 match severity {
                     Warning => {
@@ -125,6 +173,21 @@ fn report_problems_help(
         }
     }
 
+    let problems = Problems {
+        errors: errors.len(),
+        warnings: warnings.len(),
+    };
+
+    if let RenderTarget::Json = render {
+        // Each report is a line of its own, and tools reading them can filter by severity,
+        // so print all of them, without any decoration.
+        for report in errors.iter().chain(warnings.iter()) {
+            print!("{}", report);
+        }
+
+        return problems;
+    }
+
     let problems_reported;
 
     // Only print warnings if there are no errors
@@ -152,10 +215,7 @@ fn report_problems_help(
         println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
     }
 
-    Problems {
-        errors: errors.len(),
-        warnings: warnings.len(),
-    }
+    problems
 }
 
 pub enum CodeObject {
@@ -280,6 +340,7 @@ fn gen_from_mono_module_llvm(
         target_info,
         mode: LlvmBackendMode::Binary,
         exposed_to_host: loaded.exposed_to_host.values.keys().copied().collect(),
+        coverage: None,
     };
 
     // does not add any externs for this mode (we have a host) but cleans up some functions around
@@ -559,6 +620,7 @@ fn gen_from_mono_module_dev_assembly(
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators,
+        abort_on_panic: false,
     };
 
     let module_object = roc_gen_dev::build_module(&env, &mut interns, target, procedures);
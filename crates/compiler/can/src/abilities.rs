@@ -4,9 +4,13 @@ use roc_collections::{all::MutMap, VecMap, VecSet};
 use roc_error_macros::internal_error;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::Region;
+use roc_serialize::stream::{Reader, Writer};
 use roc_types::{
     subs::Variable,
-    types::{MemberImpl, Type},
+    types::{
+        read_region, read_symbol, read_variable, write_region, write_symbol, write_variable,
+        MemberImpl, RemapSymbol, Type,
+    },
 };
 
 /// During type solving and monomorphization, a module must know how its imported ability
@@ -683,6 +687,225 @@ impl IAbilitiesStore<Pending> {
     }
 }
 
+impl ImplKey {
+    fn remap_symbols(self, remap: RemapSymbol) -> Option<Self> {
+        Some(ImplKey {
+            opaque: remap(self.opaque)?,
+            ability_member: remap(self.ability_member)?,
+        })
+    }
+
+    fn write(&self, w: &mut Writer) {
+        write_symbol(w, self.opaque);
+        write_symbol(w, self.ability_member);
+    }
+
+    fn read(r: &mut Reader, remap: RemapSymbol) -> Option<Self> {
+        Some(ImplKey {
+            opaque: read_symbol(r, remap)?,
+            ability_member: read_symbol(r, remap)?,
+        })
+    }
+}
+
+impl<Phase: ResolvePhase> MemberSpecializationInfo<Phase> {
+    fn remap_symbols(self, remap: RemapSymbol) -> Option<Self> {
+        Some(Self {
+            symbol: remap(self.symbol)?,
+            ..self
+        })
+    }
+}
+
+impl AbilitiesStore {
+    /// Replaces the symbols in this store, when reading it back from the module cache with
+    /// different module ids. Returns `None` if `remap` does for any of them.
+    pub fn remap_symbols(self, remap: RemapSymbol) -> Option<Self> {
+        let Self {
+            members_of_ability,
+            specialization_to_root,
+            ability_members,
+            declared_implementations,
+            specializations,
+            next_specialization_id,
+            resolved_specializations,
+        } = self;
+
+        Some(Self {
+            members_of_ability: members_of_ability
+                .into_iter()
+                .map(|(ability, members)| {
+                    let members = members.into_iter().map(remap).collect::<Option<_>>()?;
+
+                    Some((remap(ability)?, members))
+                })
+                .collect::<Option<_>>()?,
+            specialization_to_root: specialization_to_root
+                .into_iter()
+                .map(|(symbol, key)| Some((remap(symbol)?, key.remap_symbols(remap)?)))
+                .collect::<Option<_>>()?,
+            ability_members: ability_members
+                .into_iter()
+                .map(|(member, data)| {
+                    let data = AbilityMemberData {
+                        parent_ability: remap(data.parent_ability)?,
+                        ..data
+                    };
+
+                    Some((remap(member)?, data))
+                })
+                .collect::<Option<_>>()?,
+            declared_implementations: declared_implementations
+                .into_iter()
+                .map(|(key, member_impl)| {
+                    let member_impl = match member_impl {
+                        MemberImpl::Impl(symbol) => MemberImpl::Impl(remap(symbol)?),
+                        MemberImpl::Error => MemberImpl::Error,
+                    };
+
+                    Some((key.remap_symbols(remap)?, member_impl))
+                })
+                .collect::<Option<_>>()?,
+            specializations: specializations
+                .into_iter()
+                .map(|(symbol, info)| Some((remap(symbol)?, info.remap_symbols(remap)?)))
+                .collect::<Option<_>>()?,
+            next_specialization_id,
+            resolved_specializations: resolved_specializations
+                .into_iter()
+                .map(|(id, symbol)| Some((id, remap(symbol)?)))
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+/// Like [AbilitiesStore::remap_symbols], for the implementations a module resolved.
+pub fn remap_solved_implementations(
+    solved_impls: ResolvedImplementations,
+    remap: RemapSymbol,
+) -> Option<ResolvedImplementations> {
+    solved_impls
+        .into_iter()
+        .map(|(key, resolved)| {
+            let resolved = match resolved {
+                ResolvedImpl::Impl(info) => ResolvedImpl::Impl(info.remap_symbols(remap)?),
+                ResolvedImpl::Error => ResolvedImpl::Error,
+            };
+
+            Some((key.remap_symbols(remap)?, resolved))
+        })
+        .collect()
+}
+
+impl PendingAbilitiesStore {
+    /// Writes a store made by [`Self::closure_from_imported`] to the module cache. Only such
+    /// stores can be written: the members of any other may have signatures, which are not.
+    pub fn write_imported(&self, w: &mut Writer) {
+        let Self {
+            members_of_ability,
+            ability_members,
+            declared_implementations,
+            specializations,
+            specialization_to_root: _, // restored from `declared_implementations`
+            next_specialization_id: _,
+            resolved_specializations,
+        } = self;
+
+        debug_assert!(resolved_specializations.is_empty());
+
+        w.len(members_of_ability.len());
+
+        for (ability, members) in members_of_ability {
+            write_symbol(w, *ability);
+            w.len(members.len());
+
+            for member in members {
+                let data = &ability_members[member];
+
+                if !matches!(data.typ, PendingMemberType::Imported) {
+                    internal_error!("{:?} is not imported, so it can't be cached", member);
+                }
+
+                write_symbol(w, *member);
+                write_symbol(w, data.parent_ability);
+                write_region(w, data.region);
+            }
+        }
+
+        w.len(declared_implementations.len());
+
+        for (key, member_impl) in declared_implementations {
+            key.write(w);
+
+            match member_impl {
+                MemberImpl::Impl(symbol) => {
+                    w.bool(true);
+                    write_symbol(w, *symbol);
+                }
+                MemberImpl::Error => w.bool(false),
+            }
+        }
+
+        w.len(specializations.len());
+
+        for info in specializations.values() {
+            write_symbol(w, info.symbol);
+            w.len(info.specialization_lambda_sets.len());
+
+            for (region, var) in info.specialization_lambda_sets.iter() {
+                w.u8(*region);
+                write_variable(w, *var);
+            }
+        }
+    }
+
+    pub fn read_imported(r: &mut Reader, remap: RemapSymbol) -> Option<Self> {
+        let mut store = Self::default();
+
+        for _ in 0..r.len()? {
+            let ability = read_symbol(r, remap)?;
+            let members = r.many(|r| {
+                let member = read_symbol(r, remap)?;
+                let data = AbilityMemberData {
+                    parent_ability: read_symbol(r, remap)?,
+                    region: read_region(r)?,
+                    typ: PendingMemberType::Imported,
+                };
+
+                Some((member, data))
+            })?;
+
+            store.register_ability(ability, members);
+        }
+
+        for _ in 0..r.len()? {
+            let key = ImplKey::read(r, remap)?;
+            let member_impl = match r.bool()? {
+                true => MemberImpl::Impl(read_symbol(r, remap)?),
+                false => MemberImpl::Error,
+            };
+
+            store.register_one_declared_impl(key, member_impl);
+        }
+
+        for _ in 0..r.len()? {
+            let symbol = read_symbol(r, remap)?;
+            let lambda_sets = r.many(|r| Some((r.u8()?, read_variable(r)?)))?;
+
+            store.specializations.insert(
+                symbol,
+                MemberSpecializationInfo {
+                    _phase: Default::default(),
+                    symbol,
+                    specialization_lambda_sets: lambda_sets.into_iter().collect(),
+                },
+            );
+        }
+
+        Some(store)
+    }
+}
+
 mod serialize {
     use roc_collections::{MutMap, VecMap};
     use roc_module::symbol::Symbol;
@@ -1561,7 +1561,11 @@ pub(crate) fn sort_can_defs_new(
 
     for ((condition, region), preceding_comment) in it {
         // an `expect` does not have a user-defined name, but we'll need a name to call the expectation
-        let name = scope.gen_unique_symbol();
+        let name = match &condition {
+            // a property (`expect \x -> ...`) is called like the function, so it's named after it
+            Closure(closure_data) => closure_data.name,
+            _ => scope.gen_unique_symbol(),
+        };
 
         declarations.push_expect(preceding_comment, name, Loc::at(region, condition));
     }
@@ -17,8 +17,9 @@ use roc_parse::header::HeaderFor;
 use roc_parse::pattern::PatternType;
 use roc_problem::can::{Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
+use roc_serialize::bytes::next_multiple_of;
 use roc_types::subs::{ExposedTypesStorageSubs, Subs, VarStore, Variable};
-use roc_types::types::{AbilitySet, Alias, AliasKind, AliasVar, Type};
+use roc_types::types::{AbilitySet, Alias, AliasKind, AliasVar, RemapSymbol, Type};
 
 /// The types of all exposed values/functions of a collection of modules
 #[derive(Clone, Debug, Default)]
@@ -1203,6 +1204,40 @@ pub struct TypeState {
 }
 
 impl TypeState {
+    /// Each part is serialized as if it started at offset 0, so every part after the first
+    /// is padded to this alignment; otherwise it would be read back from a misaligned offset.
+    ///
+    /// This is part of the format of the builtins' cached types (see `roc_load`'s build script)
+    /// as well as of the module cache. The build script is rerun whenever this crate changes, so
+    /// the builtins' types are regenerated; the module cache has a format version to bump.
+    const PART_ALIGNMENT: usize = 8;
+
+    /// Replaces the symbols in this state, when reading it back from the module cache with
+    /// different module ids. Returns `None` if `remap` does for any of them.
+    pub fn remap_symbols(self, remap: RemapSymbol) -> Option<Self> {
+        let Self {
+            mut subs,
+            exposed_vars_by_symbol,
+            abilities,
+            solved_implementations,
+        } = self;
+
+        subs.remap_symbols(remap)?;
+
+        Some(Self {
+            subs,
+            exposed_vars_by_symbol: exposed_vars_by_symbol
+                .into_iter()
+                .map(|(symbol, var)| Some((remap(symbol)?, var)))
+                .collect::<Option<_>>()?,
+            abilities: abilities.remap_symbols(remap)?,
+            solved_implementations: crate::abilities::remap_solved_implementations(
+                solved_implementations,
+                remap,
+            )?,
+        })
+    }
+
     pub fn serialize(&self, writer: &mut impl std::io::Write) -> std::io::Result<usize> {
         let Self {
             subs,
@@ -1211,19 +1246,31 @@ impl TypeState {
             solved_implementations,
         } = self;
 
-        let written_subs = subs.serialize(exposed_vars_by_symbol, writer)?;
-        let written_ab = abilities.serialize(writer)?;
-        let written_solved_impls =
+        let mut written = subs.serialize(exposed_vars_by_symbol, writer)?;
+        written = Self::write_padding(writer, written)?;
+        written += abilities.serialize(writer)?;
+        written = Self::write_padding(writer, written)?;
+        written +=
             crate::abilities::serialize_solved_implementations(solved_implementations, writer)?;
 
-        Ok(written_subs + written_ab + written_solved_impls)
+        Ok(written)
+    }
+
+    fn write_padding(writer: &mut impl std::io::Write, written: usize) -> std::io::Result<usize> {
+        let padded = next_multiple_of(written, Self::PART_ALIGNMENT);
+
+        writer.write_all(&[0; Self::PART_ALIGNMENT][..padded - written])?;
+
+        Ok(padded)
     }
 
     pub fn deserialize(bytes: &[u8]) -> (Self, usize) {
         let ((subs, exposed_vars_by_symbol), len_subs) = Subs::deserialize(bytes);
+        let len_subs = next_multiple_of(len_subs, Self::PART_ALIGNMENT);
         let bytes = &bytes[len_subs..];
 
         let (abilities, len_abilities) = AbilitiesStore::deserialize(bytes);
+        let len_abilities = next_multiple_of(len_abilities, Self::PART_ALIGNMENT);
         let bytes = &bytes[len_abilities..];
 
         let (solved_implementations, len_solved_impls) =
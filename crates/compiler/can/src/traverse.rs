@@ -1,7 +1,7 @@
 //! Traversals over the can ast.
 
 use roc_module::{ident::Lowercase, symbol::Symbol};
-use roc_region::all::{Loc, Region};
+use roc_region::all::{Loc, Position, Region};
 use roc_types::{subs::Variable, types::MemberImpl};
 
 use crate::{
@@ -50,7 +50,13 @@ pub fn walk_decls<V: Visitor>(visitor: &mut V, decls: &Declarations) {
             Expectation => {
                 let loc_condition = &decls.expressions[index];
 
-                visitor.visit_expr(&loc_condition.value, loc_condition.region, Variable::BOOL);
+                let var = match &loc_condition.value {
+                    // a property
+                    Expr::Closure(ClosureData { function_type, .. }) => *function_type,
+                    _ => Variable::BOOL,
+                };
+
+                visitor.visit_expr(&loc_condition.value, loc_condition.region, var);
             }
             ExpectationFx => {
                 let loc_condition = &decls.expressions[index];
@@ -539,6 +545,154 @@ pub fn find_type_at(region: Region, decls: &Declarations) -> Option<Variable> {
     visitor.typ
 }
 
+struct ClosestTypeAtVisitor {
+    position: Position,
+    found: Option<(Region, Variable)>,
+}
+
+impl Visitor for ClosestTypeAtVisitor {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.contains_pos(self.position)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if region.contains_pos(self.position) {
+            // Nodes are visited outside-in, so the last match is the innermost one.
+            if !region.is_empty() && var != Variable::NULL {
+                self.found = Some((region, var));
+            }
+
+            walk_expr(self, expr, var);
+        }
+    }
+
+    fn visit_pattern(&mut self, pat: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if region.contains_pos(self.position) {
+            if let Some(var) = opt_var {
+                self.found = Some((region, var));
+            }
+
+            walk_pattern(self, pat);
+        }
+    }
+}
+
+/// Finds the innermost expression or pattern enclosing `position`, along with its type.
+///
+/// Unlike [find_type_at], the region does not need to be known exactly, which makes this
+/// suitable for editor queries like hovering over a cursor position.
+pub fn find_closest_type_at(
+    position: Position,
+    decls: &Declarations,
+) -> Option<(Region, Variable)> {
+    let mut visitor = ClosestTypeAtVisitor {
+        position,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    visitor.found
+}
+
+/// Finds the symbol that is referenced or introduced at `position`, if any.
+pub fn find_symbol_at(position: Position, decls: &Declarations) -> Option<Loc<Symbol>> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<Loc<Symbol>>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.position)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if region.contains_pos(self.position) {
+                match expr {
+                    &Expr::Var(symbol, _) | &Expr::AbilityMember(symbol, _, _) => {
+                        self.found = Some(Loc::at(region, symbol));
+                    }
+                    _ => walk_expr(self, expr, var),
+                }
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if region.contains_pos(self.position) {
+                match pattern {
+                    Pattern::Identifier(symbol)
+                    | Pattern::Shadowed(_, _, symbol)
+                    | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_pattern(self, pattern),
+                }
+            }
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            if region.contains_pos(self.position) {
+                match &destruct.typ {
+                    DestructType::Guard(_, subpattern)
+                        if subpattern.region.contains_pos(self.position) =>
+                    {
+                        self.visit_pattern(&subpattern.value, subpattern.region, None)
+                    }
+                    _ => self.found = Some(Loc::at(region, destruct.symbol)),
+                }
+            }
+        }
+    }
+}
+
+/// Finds the region of the pattern that introduces `symbol`, if it is introduced in `decls`.
+pub fn find_declaration_region(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    let mut visitor = Finder {
+        symbol,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::Shadowed(_, _, symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found.get_or_insert(region);
+                }
+                _ => walk_pattern(self, pattern),
+            }
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            if destruct.symbol == self.symbol {
+                self.found.get_or_insert(region);
+            } else {
+                walk_record_destruct(self, destruct);
+            }
+        }
+    }
+}
+
 /// Given an ability Foo has foo : ..., returns (T, foo1) if the symbol at the given region is a
 /// symbol foo1 that specializes foo for T. Otherwise if the symbol is foo but the specialization
 /// is unknown, (Foo, foo) is returned. Otherwise [None] is returned.
@@ -99,6 +99,37 @@ impl SmallStringInterner {
         }
     }
 
+    /// The buffer, lengths and offsets of the interner, for serializing it.
+    /// Generated names have negative lengths.
+    pub fn raw_parts(&self) -> (&[u8], impl Iterator<Item = i16> + '_, &[u32]) {
+        (
+            &self.buffer,
+            self.lengths.iter().map(|length| length.0),
+            &self.offsets,
+        )
+    }
+
+    /// The inverse of [SmallStringInterner::raw_parts]. Returns `None` if the parts do not
+    /// describe valid strings.
+    pub fn from_raw_parts(buffer: Vec<u8>, lengths: Vec<i16>, offsets: Vec<u32>) -> Option<Self> {
+        if lengths.len() != offsets.len() {
+            return None;
+        }
+
+        for (length, offset) in lengths.iter().zip(offsets.iter()) {
+            let start = *offset as usize;
+            let end = start + length.unsigned_abs() as usize;
+
+            std::str::from_utf8(buffer.get(start..end)?).ok()?;
+        }
+
+        Some(Self {
+            buffer,
+            lengths: lengths.into_iter().map(Length).collect(),
+            offsets,
+        })
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &str> {
         (0..self.offsets.len()).map(move |index| self.get(index))
     }
@@ -2174,16 +2174,56 @@ pub fn constrain_decls(
                 let loc_expr = &declarations.expressions[index];
 
                 let bool_type = constraints.push_type(Variable(Variable::BOOL));
-                let expected =
-                    Expected::ForReason(Reason::ExpectCondition, bool_type, loc_expr.region);
 
-                let expect_constraint = constrain_expr(
-                    constraints,
-                    &mut env,
-                    loc_expr.region,
-                    &loc_expr.value,
-                    expected,
-                );
+                let expect_constraint = match &loc_expr.value {
+                    // A property, like `expect \x -> ...`: the body is the condition
+                    Closure(ClosureData {
+                        function_type,
+                        return_type,
+                        loc_body,
+                        ..
+                    }) => {
+                        let function_type_index = constraints.push_type(Variable(*function_type));
+
+                        let closure_constraint = constrain_expr(
+                            constraints,
+                            &mut env,
+                            loc_expr.region,
+                            &loc_expr.value,
+                            NoExpectation(function_type_index),
+                        );
+
+                        let expected = constraints.push_expected_type(Expected::ForReason(
+                            Reason::ExpectCondition,
+                            bool_type,
+                            loc_body.region,
+                        ));
+
+                        let body_constraint = constraints.equal_types_var(
+                            *return_type,
+                            expected,
+                            Category::Storage(std::file!(), std::line!()),
+                            loc_body.region,
+                        );
+
+                        constraints.and_constraint([closure_constraint, body_constraint])
+                    }
+                    _ => {
+                        let expected = Expected::ForReason(
+                            Reason::ExpectCondition,
+                            bool_type,
+                            loc_expr.region,
+                        );
+
+                        constrain_expr(
+                            constraints,
+                            &mut env,
+                            loc_expr.region,
+                            &loc_expr.value,
+                            expected,
+                        )
+                    }
+                };
 
                 constraint = constraints.let_constraint([], [], [], expect_constraint, constraint)
             }
@@ -30,6 +30,9 @@ pub struct Env<'a> {
     pub exposed_to_host: MutSet<Symbol>,
     pub lazy_literals: bool,
     pub generate_allocators: bool,
+    /// Whether the generated `roc_panic` aborts, instead of returning to the code that
+    /// panicked. Only used with `generate_allocators`.
+    pub abort_on_panic: bool,
 }
 
 // These relocations likely will need a length.
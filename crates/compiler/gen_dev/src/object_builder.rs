@@ -200,11 +200,16 @@ fn build_object<'a, B: Backend<'a>>(
             "roc_dealloc".into(),
             "free".into(),
         );
+        let panic_fn = if backend.env().abort_on_panic {
+            "abort"
+        } else {
+            "roc_builtins.utils.test_panic"
+        };
         generate_wrapper(
             &mut backend,
             &mut output,
             "roc_panic".into(),
-            "roc_builtins.utils.test_panic".into(),
+            panic_fn.into(),
         );
     }
 
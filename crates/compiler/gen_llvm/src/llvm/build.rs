@@ -12,6 +12,7 @@ use crate::llvm::compare::{generic_eq, generic_neq};
 use crate::llvm::convert::{
     self, argument_type_from_layout, basic_type_from_builtin, basic_type_from_layout, zig_str_type,
 };
+use crate::llvm::coverage::{build_coverage_hit, coverage_hit_point, Coverage, CoveragePoint};
 use crate::llvm::expect::clone_to_shared_memory;
 use crate::llvm::refcounting::{
     build_reset, decrement_refcount_layout, increment_refcount_layout, PointerToRefcount,
@@ -212,6 +213,8 @@ pub struct Env<'a, 'ctx, 'env> {
     pub target_info: TargetInfo,
     pub mode: LlvmBackendMode,
     pub exposed_to_host: MutSet<Symbol>,
+    /// Set when measuring coverage for `roc test --coverage`
+    pub coverage: Option<&'env Coverage>,
 }
 
 #[repr(u32)]
@@ -1135,6 +1138,23 @@ pub fn build_exp_call<'a, 'ctx, 'env>(
             run_higher_order_low_level(env, layout_ids, scope, layout, func_spec, higher_order)
         }
 
+        CallType::Foreign {
+            foreign_symbol,
+            ret_layout,
+        } if foreign_symbol.as_str() == roc_mono::ir::COVERAGE_HIT => {
+            debug_assert_eq!(**ret_layout, Layout::UNIT);
+
+            let mut values = Vec::with_capacity_in(arguments.len(), env.arena);
+
+            for symbol in arguments.iter() {
+                values.push(load_symbol(scope, symbol));
+            }
+
+            build_coverage_hit(env, coverage_hit_point(&values));
+
+            env.context.struct_type(&[], false).const_zero().into()
+        }
+
         CallType::Foreign {
             foreign_symbol,
             ret_layout,
@@ -4417,13 +4437,15 @@ pub fn build_procedures_return_main<'a, 'ctx, 'env>(
     promote_to_main_function(env, mod_solutions, entry_point.symbol, entry_point.layout)
 }
 
+/// Returns the names of the exposed expects, and of the exposed properties.
 pub fn build_procedures_expose_expects<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     opt_level: OptLevel,
     expects: &[Symbol],
+    properties: &[(Symbol, ProcLayout<'a>)],
     procedures: MutMap<(Symbol, ProcLayout<'a>), roc_mono::ir::Proc<'a>>,
     opt_entry_point: Option<EntryPoint<'a>>,
-) -> Vec<'a, &'a str> {
+) -> (Vec<'a, &'a str>, Vec<'a, &'a str>) {
     let mod_solutions = build_procedures_help(
         env,
         opt_level,
@@ -4483,7 +4505,143 @@ pub fn build_procedures_expose_expects<'a, 'ctx, 'env>(
         );
     }
 
-    expect_names
+    let mut property_names = Vec::with_capacity_in(properties.len(), env.arena);
+
+    for (symbol, proc_layout) in properties.iter().copied() {
+        let it = proc_layout.arguments.iter().copied();
+        let bytes = roc_alias_analysis::func_name_bytes_help(
+            symbol,
+            it,
+            proc_layout.captures_niche,
+            &proc_layout.result,
+        );
+        let func_name = FuncName(&bytes);
+        let func_solutions = mod_solutions.func_solutions(func_name).unwrap();
+
+        let mut it = func_solutions.specs();
+        let func_spec = it.next().unwrap();
+        debug_assert!(
+            it.next().is_none(),
+            "we expect only one specialization of this symbol"
+        );
+
+        let roc_function = function_value_by_func_spec(
+            env,
+            *func_spec,
+            symbol,
+            proc_layout.arguments,
+            proc_layout.captures_niche,
+            &proc_layout.result,
+        );
+
+        let name = roc_function.get_name().to_str().unwrap();
+
+        let property_name = env.arena.alloc_str(&format!("Property_{}", name));
+
+        expose_property_to_host(env, roc_function, proc_layout.arguments, property_name);
+
+        property_names.push(&*property_name);
+    }
+
+    (expect_names, property_names)
+}
+
+/// Builds `void c_function_name(u8 *arguments, RocCallResult<bool> *output)`, which `roc test`
+/// calls with generated arguments. The arguments are stored one after the other, each aligned
+/// to its own alignment.
+fn expose_property_to_host<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    roc_function: FunctionValue<'ctx>,
+    arguments: &[Layout<'a>],
+    c_function_name: &str,
+) -> FunctionValue<'ctx> {
+    let return_layout = Layout::bool();
+    let wrapper_return_type = roc_result_type(env, basic_type_from_layout(env, &return_layout));
+
+    let arguments_type = env.context.i8_type().ptr_type(AddressSpace::Generic);
+    let output_type = wrapper_return_type.ptr_type(AddressSpace::Generic);
+
+    let c_function_spec = FunctionSpec::cconv(
+        env,
+        CCReturn::Void,
+        None,
+        &[arguments_type.into(), output_type.into()],
+    );
+
+    let c_function = add_func(
+        env.context,
+        env.module,
+        c_function_name,
+        c_function_spec,
+        Linkage::External,
+    );
+
+    let subprogram = env.new_subprogram(c_function_name);
+    c_function.set_subprogram(subprogram);
+
+    let builder = env.builder;
+    let entry = env.context.append_basic_block(c_function, "entry");
+
+    builder.position_at_end(entry);
+
+    debug_info_init!(env, c_function);
+
+    let arguments_ptr = c_function.get_nth_param(0).unwrap().into_pointer_value();
+
+    let mut arguments_for_call = Vec::with_capacity_in(arguments.len(), env.arena);
+    let mut offset = 0;
+
+    for layout in arguments {
+        let alignment = layout.alignment_bytes(env.layout_interner, env.target_info);
+        offset = roc_mono::layout::round_up_to_alignment(offset, alignment);
+
+        let byte_ptr = unsafe {
+            builder.build_in_bounds_gep(
+                arguments_ptr,
+                &[env.ptr_int().const_int(offset as u64, false)],
+                "property_argument",
+            )
+        };
+
+        let argument_ptr = builder
+            .build_bitcast(
+                byte_ptr,
+                basic_type_from_layout(env, layout).ptr_type(AddressSpace::Generic),
+                "cast_property_argument",
+            )
+            .into_pointer_value();
+
+        arguments_for_call.push(load_roc_value(
+            env,
+            *layout,
+            argument_ptr,
+            "load_property_argument",
+        ));
+
+        offset += layout.stack_size(env.layout_interner, env.target_info);
+    }
+
+    let call_result = {
+        let last_block = builder.get_insert_block().unwrap();
+
+        let roc_wrapper_function = make_exception_catcher(env, roc_function, return_layout);
+
+        builder.position_at_end(last_block);
+
+        call_roc_function(
+            env,
+            roc_wrapper_function,
+            &Layout::struct_no_name_order(&[Layout::u64(), return_layout]),
+            &arguments_for_call,
+        )
+    };
+
+    let output_ptr = c_function.get_nth_param(1).unwrap().into_pointer_value();
+
+    builder.build_store(output_ptr, call_result);
+    builder.build_return(None);
+
+    c_function
 }
 
 fn build_procedures_help<'a, 'ctx, 'env>(
@@ -4992,6 +5150,8 @@ pub fn build_proc<'a, 'ctx, 'env>(
         scope.insert(*arg_symbol, (*layout, arg_val));
     }
 
+    build_coverage_hit(env, CoveragePoint::Def(proc.name.name()));
+
     let body = build_exp_stmt(
         env,
         layout_ids,
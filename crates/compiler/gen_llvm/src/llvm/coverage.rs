@@ -0,0 +1,124 @@
+//! Counters for `roc test --coverage`: how often each top-level def was called, and how often
+//! each `when` and `if` branch ran.
+//!
+//! Every point gets an index into an array of u64 counters, which the host provides by storing a
+//! pointer to it in the [COUNTERS_GLOBAL] global before running any Roc code.
+
+use std::cell::RefCell;
+
+use inkwell::values::{BasicValueEnum, IntValue};
+use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp};
+use roc_collections::all::MutMap;
+use roc_error_macros::internal_error;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_region::all::{Position, Region};
+
+use crate::llvm::build::Env;
+
+/// The global that holds the pointer to the counters.
+pub const COUNTERS_GLOBAL: &str = "roc__coverage_counters";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoveragePoint {
+    /// A call to a function. Only the points of top-level defs are interesting, but the backend
+    /// doesn't know which functions those are.
+    Def(Symbol),
+    /// The start of a `when` or `if` branch
+    Branch { module_id: ModuleId, region: Region },
+}
+
+/// The points that have counters, in the order of the counters.
+#[derive(Debug, Default)]
+pub struct Coverage {
+    points: RefCell<Vec<CoveragePoint>>,
+    indices: RefCell<MutMap<CoveragePoint, u32>>,
+}
+
+impl Coverage {
+    /// The points, indexed like the counters
+    pub fn points(&self) -> Vec<CoveragePoint> {
+        self.points.borrow().clone()
+    }
+
+    fn index_of(&self, point: CoveragePoint) -> u32 {
+        let mut indices = self.indices.borrow_mut();
+        let mut points = self.points.borrow_mut();
+
+        *indices.entry(point).or_insert_with(|| {
+            points.push(point);
+
+            points.len() as u32 - 1
+        })
+    }
+}
+
+/// Counts that the point was reached, if we are measuring coverage.
+pub(crate) fn build_coverage_hit<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>, point: CoveragePoint) {
+    let coverage = match env.coverage {
+        Some(coverage) => coverage,
+        None => return,
+    };
+
+    let index = coverage.index_of(point);
+
+    let counter_type = env.context.i64_type();
+    let counters_type = counter_type.ptr_type(AddressSpace::Generic);
+
+    let global = match env.module.get_global(COUNTERS_GLOBAL) {
+        Some(global) => global,
+        None => {
+            let global = env.module.add_global(counters_type, None, COUNTERS_GLOBAL);
+
+            global.set_initializer(&counters_type.const_null());
+
+            global
+        }
+    };
+
+    let counters = env
+        .builder
+        .build_load(global.as_pointer_value(), "coverage_counters")
+        .into_pointer_value();
+
+    let counter = unsafe {
+        env.builder.build_in_bounds_gep(
+            counters,
+            &[env.context.i32_type().const_int(index as u64, false)],
+            "coverage_counter",
+        )
+    };
+
+    // workers run tests at the same time, and share the counters
+    env.builder
+        .build_atomicrmw(
+            AtomicRMWBinOp::Add,
+            counter,
+            counter_type.const_int(1, false),
+            AtomicOrdering::Monotonic,
+        )
+        .unwrap();
+}
+
+/// The point of a call to [roc_mono::ir::COVERAGE_HIT]. Its arguments are constants.
+pub(crate) fn coverage_hit_point(arguments: &[BasicValueEnum<'_>]) -> CoveragePoint {
+    let constant = |value: &BasicValueEnum<'_>| {
+        let value: IntValue = value.into_int_value();
+
+        value
+            .get_zero_extended_constant()
+            .expect("coverage hit arguments are constants") as u32
+    };
+
+    match arguments {
+        [module_id, start, end] => CoveragePoint::Branch {
+            module_id: ModuleId::from_u32(constant(module_id))
+                .expect("coverage hits are passed a module id"),
+            region: Region::new(Position::new(constant(start)), Position::new(constant(end))),
+        },
+        _ => internal_error!(
+            "{} takes 3 arguments, but was given {}",
+            roc_mono::ir::COVERAGE_HIT,
+            arguments.len()
+        ),
+    }
+}
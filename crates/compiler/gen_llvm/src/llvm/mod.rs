@@ -4,6 +4,7 @@ pub mod build_list;
 pub mod build_str;
 pub mod compare;
 pub mod convert;
+pub mod coverage;
 mod expect;
 pub mod externs;
 pub mod refcounting;
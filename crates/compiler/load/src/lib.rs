@@ -13,6 +13,7 @@ const SKIP_SUBS_CACHE: bool = {
     }
 };
 
+pub use roc_load_internal::disk_cache::default_cache_dir;
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
     EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadResult, LoadStart, LoadedModule,
@@ -50,6 +51,8 @@ pub fn load_single_threaded<'a>(
         cached_subs,
         render,
         exec_mode,
+        None,
+        false,
     )
 }
 
@@ -84,10 +87,34 @@ pub fn load_and_monomorphize_from_str<'a>(
     src_dir: PathBuf,
     exposed_types: ExposedByModule,
     load_config: LoadConfig,
+) -> Result<MonomorphizedModule<'a>, LoadingProblem<'a>> {
+    load_and_monomorphize_with_sources(
+        arena,
+        filename,
+        src,
+        src_dir,
+        MutMap::default(),
+        exposed_types,
+        load_config,
+    )
+}
+
+/// Like [load_and_monomorphize_from_str], but the modules at the paths in `module_sources`
+/// are loaded from those sources, too, instead of from their files.
+#[allow(clippy::too_many_arguments)]
+pub fn load_and_monomorphize_with_sources<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    src: &'a str,
+    src_dir: PathBuf,
+    module_sources: MutMap<PathBuf, &'a str>,
+    exposed_types: ExposedByModule,
+    load_config: LoadConfig,
 ) -> Result<MonomorphizedModule<'a>, LoadingProblem<'a>> {
     use LoadResult::*;
 
-    let load_start = LoadStart::from_str(arena, filename, src, src_dir)?;
+    let load_start =
+        LoadStart::from_str(arena, filename, src, src_dir)?.with_module_sources(module_sources);
 
     match load(arena, load_start, exposed_types, load_config)? {
         Monomorphized(module) => Ok(module),
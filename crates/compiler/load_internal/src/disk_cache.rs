@@ -0,0 +1,541 @@
+//! An on-disk cache of canonicalized and solved interface modules, so that unchanged modules do
+//! not have to be processed again every time a project is checked or built.
+//!
+//! Entries are keyed by a hash of everything that canonicalizing and solving a module depends on:
+//! its name and source, and (recursively) the names and keys of the modules it imports. Changing
+//! a module therefore changes the key of every module that depends on it, and an entry is never
+//! read back for different inputs. Stale entries are simply never looked up again.
+//!
+//! Entries are kept in a directory per build of the compiler (see [compiler_stamp]), since every
+//! build has keys of its own. The first time a run writes an entry, the directories of all other
+//! builds are removed, so rebuilding the compiler does not leave their entries behind forever.
+//!
+//! [ModuleId]s, and the [IdentId]s of the identifiers modules expose, depend on the order modules
+//! happen to be loaded in, so they are not part of the key. Instead, an entry records the name
+//! and exposed identifiers of every module its symbols can come from, and the symbols are
+//! translated to the current ids when the entry is read back.
+//!
+//! Besides the solved types, an entry holds what canonicalizing the module produces that other
+//! modules depend on ([CachedCanonical]). `roc check` uses it to skip canonicalizing the module,
+//! too; everything else still canonicalizes it, because it needs the module's definitions.
+
+use roc_can::abilities::PendingAbilitiesStore;
+use roc_can::module::{Module, TypeState};
+use roc_collections::all::{MutMap, WyHash};
+use roc_collections::{SmallStringInterner, VecSet};
+use roc_module::symbol::{IdentId, IdentIds, ModuleId, Symbol};
+use roc_region::all::Region;
+use roc_serialize::stream::{Reader, Writer};
+use roc_solve_problem::TypeError;
+use roc_types::subs::Subs;
+use roc_types::types::{read_region, read_symbol, write_region, write_symbol, Alias, RemapSymbol};
+use std::hash::Hasher;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+const ROC_SKIP_MODULE_CACHE: &str = "ROC_SKIP_MODULE_CACHE";
+
+/// Bump this whenever the way an entry is serialized changes.
+const FORMAT_VERSION: u64 = 2;
+
+const MAGIC: [u8; 8] = *b"roctypes";
+
+/// magic, format version, payload length, payload checksum
+const HEADER_LEN: usize = 4 * 8;
+
+/// The default location of the module cache, `~/.cache/roc/modules` (respecting `XDG_CACHE_HOME`).
+///
+/// Returns `None` when the cache is disabled with `ROC_SKIP_MODULE_CACHE=1`, or on platforms where
+/// serialized types are not supported (the builtins' cached types are skipped there, too).
+pub fn default_cache_dir() -> Option<PathBuf> {
+    if cfg!(target_family = "wasm") || cfg!(windows) {
+        return None;
+    }
+
+    if matches!(std::env::var(ROC_SKIP_MODULE_CACHE).as_deref(), Ok("1")) {
+        return None;
+    }
+
+    let cache_home = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+
+    Some(cache_home.join("roc").join("modules"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CacheKey(u64, u64);
+
+/// Hashes the inputs of a module twice, with differently seeded hashers, so that reusing the
+/// wrong entry would take a collision in both at once.
+pub(crate) struct CacheKeyHasher {
+    first: WyHash,
+    second: WyHash,
+}
+
+impl CacheKeyHasher {
+    pub fn write_u64(&mut self, value: u64) {
+        self.first.write_u64(value);
+        self.second.write_u64(value);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        // Include the length, so that e.g. ["ab", "c"] and ["a", "bc"] hash differently.
+        self.write_u64(bytes.len() as u64);
+
+        self.first.write(bytes);
+        self.second.write(bytes);
+    }
+
+    /// Hashes the identifiers, but not their ids, which depend on the order modules are loaded in
+    pub fn write_idents(&mut self, ident_ids: &IdentIds) {
+        let mut idents: Vec<_> = ident_ids.ident_strs().map(|(_, ident)| ident).collect();
+        idents.sort_unstable();
+
+        self.write_u64(idents.len() as u64);
+
+        for ident in idents {
+            self.write_bytes(ident.as_bytes());
+        }
+    }
+
+    pub fn write_key(&mut self, CacheKey(first, second): CacheKey) {
+        self.write_u64(first);
+        self.write_u64(second);
+    }
+
+    pub fn finish(self) -> CacheKey {
+        CacheKey(self.first.finish(), self.second.finish())
+    }
+}
+
+/// Where the entry of a module is stored, and which modules its symbols can come from.
+#[derive(Debug, Clone)]
+pub(crate) struct EntryLocation {
+    pub key: CacheKey,
+    /// The module itself and every module it imports, directly or not, except for the builtins
+    /// (whose ids never change)
+    pub modules: Vec<EntryModule>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntryModule {
+    pub module_id: ModuleId,
+    pub name: String,
+    /// The identifiers the module has after its header is processed; their ids depend on the
+    /// order of the imports that expose them. Any identifiers after these are assigned while
+    /// canonicalizing the module, in the same order every time.
+    pub exposed_ident_ids: IdentIds,
+}
+
+/// What canonicalizing a module produces that other modules (and reporting its problems) need,
+/// so that `roc check` can skip canonicalizing it when its types come from the cache.
+#[derive(Debug)]
+pub(crate) struct CachedCanonical {
+    pub ident_ids: IdentIds,
+    pub aliases: MutMap<Symbol, (bool, Alias)>,
+    /// The abilities of the module, as [PendingAbilitiesStore::closure_from_imported] gives them
+    /// to the modules that import it
+    pub abilities: PendingAbilitiesStore,
+    pub exposed_imports: MutMap<Symbol, Region>,
+    pub referenced_values: VecSet<Symbol>,
+    pub referenced_types: VecSet<Symbol>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CacheEntry {
+    pub types: TypeState,
+    /// `None` when the module had canonicalization problems, which must be reported again
+    pub canonical: Option<CachedCanonical>,
+}
+
+/// What a worker needs to write the entry of a module once it is solved
+#[derive(Debug)]
+pub(crate) struct PendingWrite {
+    pub path: PathBuf,
+    pub modules: Vec<EntryModule>,
+    /// Whether the entry may include [CachedCanonical]
+    pub with_canonical: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct DiskCache {
+    dir: PathBuf,
+    compiler_stamp: u64,
+    removed_stale_entries: Once,
+}
+
+impl DiskCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            compiler_stamp: compiler_stamp(),
+            removed_stale_entries: Once::new(),
+        }
+    }
+
+    pub fn key_hasher(&self) -> CacheKeyHasher {
+        let mut hasher = CacheKeyHasher {
+            first: WyHash::with_seed(0),
+            second: WyHash::with_seed(1),
+        };
+
+        hasher.write_u64(FORMAT_VERSION);
+        hasher.write_u64(self.compiler_stamp);
+
+        hasher
+    }
+
+    fn entries_dir(&self) -> PathBuf {
+        self.dir.join(format!("{:016x}", self.compiler_stamp))
+    }
+
+    fn path(&self, CacheKey(first, second): CacheKey) -> PathBuf {
+        self.entries_dir()
+            .join(format!("{:016x}{:016x}.dat", first, second))
+    }
+
+    /// Where to write the entry for `key`. The first call removes the entries of other compilers.
+    pub fn write_path(&self, key: CacheKey) -> PathBuf {
+        self.removed_stale_entries
+            .call_once(|| self.remove_stale_entries());
+
+        self.path(key)
+    }
+
+    /// Removes the entry directories of other builds of the compiler, and the entries that
+    /// earlier versions of it kept directly in the cache directory.
+    fn remove_stale_entries(&self) {
+        let current = self.entries_dir();
+
+        let dir_entries = match std::fs::read_dir(&self.dir) {
+            Ok(dir_entries) => dir_entries,
+            Err(_) => return,
+        };
+
+        for dir_entry in dir_entries.flatten() {
+            let path = dir_entry.path();
+            let name = dir_entry.file_name();
+            let name = name.to_string_lossy();
+
+            // Another run may be removing the same entries right now, so failures are ignored.
+            if path.is_dir() {
+                if is_hex(&name, 16) && path != current {
+                    let _ = std::fs::remove_dir_all(&path);
+                }
+            } else if matches!(name.split_once('.'), Some((stem, _)) if is_hex(stem, 32)) {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+
+    /// Returns `None` if there is no entry for this module, or if it could not be read back intact.
+    pub fn read(&self, location: &EntryLocation) -> Option<CacheEntry> {
+        let bytes = std::fs::read(self.path(location.key)).ok()?;
+
+        read_entry(&bytes, &location.modules)
+    }
+}
+
+fn read_entry(bytes: &[u8], modules: &[EntryModule]) -> Option<CacheEntry> {
+    if bytes.len() < HEADER_LEN || bytes[..8] != MAGIC {
+        return None;
+    }
+
+    let header_u64 = |index: usize| {
+        let start = index * 8;
+
+        u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap())
+    };
+
+    let payload = &bytes[HEADER_LEN..];
+
+    if header_u64(1) != FORMAT_VERSION
+        || header_u64(2) != payload.len() as u64
+        || header_u64(3) != checksum(payload)
+    {
+        return None;
+    }
+
+    // Deserialization reinterprets the bytes in place, so they need to be 8-byte aligned.
+    let mut aligned = vec![0u64; (payload.len() + 7) / 8];
+    let aligned_bytes = unsafe {
+        std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, aligned.len() * 8)
+    };
+    aligned_bytes[..payload.len()].copy_from_slice(payload);
+
+    let mut reader = Reader::new(&aligned_bytes[..payload.len()]);
+    let types_len = reader.u64()? as usize;
+
+    // The types come right after their length, so they are still 8-byte aligned.
+    let types_bytes = aligned_bytes.get(8..8 + types_len)?;
+    let (types, offset) = TypeState::deserialize(types_bytes);
+
+    if offset != types_len {
+        return None;
+    }
+
+    let mut reader = Reader::new(&aligned_bytes[8 + types_len..payload.len()]);
+
+    // For each module id the entry was written with: the id the module has now, and the current
+    // ids of its exposed identifiers, by the ids they were written with.
+    let mut module_ids = MutMap::default();
+
+    for _ in 0..reader.len()? {
+        let written_id = reader.u32()?;
+        let name = reader.str()?;
+        let current = modules.iter().find(|module| module.name == name)?;
+
+        let exposed_ident_ids = reader.many(|reader| {
+            let ident_id = current.exposed_ident_ids.get_id(reader.str()?)?;
+
+            Some(ident_id.index() as u32)
+        })?;
+
+        if exposed_ident_ids.len() != current.exposed_ident_ids.len() {
+            return None;
+        }
+
+        module_ids.insert(written_id, (current.module_id, exposed_ident_ids));
+    }
+
+    let remap = |symbol: Symbol| {
+        let module_id = symbol.module_id();
+
+        if module_id.is_builtin() {
+            return Some(symbol);
+        }
+
+        let (current_id, exposed_ident_ids) = module_ids.get(&module_id.to_u32())?;
+        let ident_index = symbol.ident_id().index();
+        let ident_id = match exposed_ident_ids.get(ident_index) {
+            Some(current_index) => IdentId::from_index(*current_index),
+            None => symbol.ident_id(),
+        };
+
+        Some(Symbol::new(*current_id, ident_id))
+    };
+
+    let types = types.remap_symbols(&remap)?;
+
+    let canonical = match reader.bool()? {
+        true => {
+            let module = modules.first()?;
+
+            Some(read_canonical(
+                &mut reader,
+                &module.exposed_ident_ids,
+                &remap,
+            )?)
+        }
+        false => None,
+    };
+
+    if !reader.is_empty() {
+        return None;
+    }
+
+    Some(CacheEntry { types, canonical })
+}
+
+/// Whether the solved types of a module can be reused on a later run.
+///
+/// Type errors are only reported while solving, so modules that have any must be solved again.
+/// Likewise, solving populates the (shared) derived module with the implementations of derived
+/// abilities a module uses; skipping that would leave them missing when it is loaded from the cache.
+pub(crate) fn is_cacheable(subs: &Subs, problems: &[TypeError]) -> bool {
+    problems.is_empty()
+        && subs.problems.is_empty()
+        && !subs.symbol_names.iter().any(|symbol| {
+            matches!(
+                symbol.module_id(),
+                ModuleId::DERIVED_SYNTH | ModuleId::DERIVED_GEN
+            )
+        })
+}
+
+/// Serializes what canonicalizing a module produced, for [write]. Returns `None` if some of it
+/// can't be cached.
+pub(crate) fn write_canonical(module: &Module, ident_ids: &IdentIds) -> Option<Vec<u8>> {
+    let mut w = Writer::new();
+
+    let (buffer, lengths, offsets) = ident_ids.interner.raw_parts();
+    w.bytes(buffer);
+    w.len(offsets.len());
+    lengths.for_each(|length| w.u32(length as u16 as u32));
+    offsets.iter().for_each(|offset| w.u32(*offset));
+
+    w.len(module.aliases.len());
+    for (symbol, (exposed, alias)) in module.aliases.iter() {
+        write_symbol(&mut w, *symbol);
+        w.bool(*exposed);
+        alias.write(&mut w)?;
+    }
+
+    module
+        .abilities_store
+        .closure_from_imported(&module.exposed_symbols)
+        .write_imported(&mut w);
+
+    w.len(module.exposed_imports.len());
+    for (symbol, region) in module.exposed_imports.iter() {
+        write_symbol(&mut w, *symbol);
+        write_region(&mut w, *region);
+    }
+
+    for referenced in [&module.referenced_values, &module.referenced_types] {
+        w.len(referenced.len());
+        referenced
+            .iter()
+            .for_each(|symbol| write_symbol(&mut w, *symbol));
+    }
+
+    Some(w.finish())
+}
+
+/// `exposed_ident_ids` are the identifiers the module has now after its header is processed.
+/// Their ids may differ from the ones in the entry (see [EntryModule]), so the identifiers of the
+/// module are those, followed by the ones canonicalization added.
+fn read_canonical(
+    r: &mut Reader,
+    exposed_ident_ids: &IdentIds,
+    remap: RemapSymbol,
+) -> Option<CachedCanonical> {
+    let cached_buffer = r.bytes()?;
+    let len = r.len()?;
+    let cached_lengths: Vec<i16> = (0..len)
+        .map(|_| Some(r.u32()? as u16 as i16))
+        .collect::<Option<_>>()?;
+    let cached_offsets: Vec<u32> = (0..len).map(|_| r.u32()).collect::<Option<_>>()?;
+
+    let (buffer, lengths, offsets) = exposed_ident_ids.interner.raw_parts();
+    let mut buffer = buffer.to_vec();
+    let mut lengths: Vec<i16> = lengths.collect();
+    let mut offsets = offsets.to_vec();
+
+    for index in exposed_ident_ids.len()..len {
+        let length = cached_lengths[index];
+        let start = cached_offsets[index] as usize;
+        let end = start + length.unsigned_abs() as usize;
+
+        offsets.push(buffer.len() as u32);
+        lengths.push(length);
+        buffer.extend(cached_buffer.get(start..end)?);
+    }
+
+    let interner = SmallStringInterner::from_raw_parts(buffer, lengths, offsets)?;
+
+    let aliases = r.many(|r| {
+        let symbol = read_symbol(r, remap)?;
+        let exposed = r.bool()?;
+
+        Some((symbol, (exposed, Alias::read(r, remap)?)))
+    })?;
+
+    let abilities = PendingAbilitiesStore::read_imported(r, remap)?;
+
+    let exposed_imports = r.many(|r| Some((read_symbol(r, remap)?, read_region(r)?)))?;
+
+    let referenced_values = r.many(|r| read_symbol(r, remap))?;
+    let referenced_types = r.many(|r| read_symbol(r, remap))?;
+
+    Some(CachedCanonical {
+        ident_ids: IdentIds { interner },
+        aliases: aliases.into_iter().collect(),
+        abilities,
+        exposed_imports: exposed_imports.into_iter().collect(),
+        referenced_values: referenced_values.into_iter().collect(),
+        referenced_types: referenced_types.into_iter().collect(),
+    })
+}
+
+/// Writes an entry to a temporary file first and then moves it into place, so that concurrent
+/// runs never observe a partially written entry.
+///
+/// `canonical` comes from [write_canonical], if the module can skip canonicalization next time.
+pub(crate) fn write(
+    path: &Path,
+    modules: &[EntryModule],
+    type_state: &TypeState,
+    canonical: Option<&[u8]>,
+) -> io::Result<()> {
+    let mut types = Vec::new();
+    type_state.serialize(&mut types)?;
+
+    let mut rest = Writer::new();
+
+    rest.len(modules.len());
+    for module in modules {
+        rest.u32(module.module_id.to_u32());
+        rest.str(&module.name);
+        rest.len(module.exposed_ident_ids.len());
+
+        for (_, ident) in module.exposed_ident_ids.ident_strs() {
+            rest.str(ident);
+        }
+    }
+
+    rest.bool(canonical.is_some());
+    let rest = rest.finish();
+
+    let mut payload = Vec::with_capacity(8 + types.len() + rest.len());
+    payload.extend((types.len() as u64).to_le_bytes());
+    payload.extend(types);
+    payload.extend(rest);
+    payload.extend(canonical.unwrap_or_default());
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let tmp_path = path.with_extension(format!("tmp{}", std::process::id()));
+
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+
+        file.write_all(&MAGIC)?;
+        file.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        file.write_all(&(payload.len() as u64).to_le_bytes())?;
+        file.write_all(&checksum(&payload).to_le_bytes())?;
+        file.write_all(&payload)?;
+    }
+
+    std::fs::rename(&tmp_path, path)
+}
+
+fn is_hex(name: &str, len: usize) -> bool {
+    name.len() == len && name.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = WyHash::with_seed(0);
+    hasher.write(bytes);
+
+    hasher.finish()
+}
+
+/// Identifies the running compiler, so that entries written by a different build of it
+/// (which may canonicalize or solve differently) are not reused.
+fn compiler_stamp() -> u64 {
+    let mut hasher = WyHash::with_seed(0);
+
+    if let Ok(exe) = std::env::current_exe() {
+        hasher.write(exe.to_string_lossy().as_bytes());
+
+        if let Ok(metadata) = exe.metadata() {
+            hasher.write_u64(metadata.len());
+
+            if let Ok(modified) = metadata.modified() {
+                let since_epoch = modified
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default();
+
+                hasher.write_u128(since_epoch.as_nanos());
+            }
+        }
+    }
+
+    hasher.finish()
+}
@@ -35,7 +35,8 @@ use roc_mono::ir::{
     UpdateModeIds,
 };
 use roc_mono::layout::{
-    CapturesNiche, LambdaName, Layout, LayoutCache, LayoutProblem, STLayoutInterner,
+    Builtin, CapturesNiche, LambdaName, Layout, LayoutCache, LayoutProblem, RawFunctionLayout,
+    STLayoutInterner, UnionLayout,
 };
 use roc_parse::ast::{self, Defs, ExtractSpaces, Spaced, StrLiteral, TypeAnnotation};
 use roc_parse::header::{ExposedName, ImportsEntry, PackageEntry, PlatformHeader, To, TypedIdent};
@@ -43,7 +44,7 @@ use roc_parse::header::{HeaderFor, ModuleNameEnum, PackageName};
 use roc_parse::ident::UppercaseIdent;
 use roc_parse::module::module_defs;
 use roc_parse::parser::{FileError, Parser, SourceError, SyntaxError};
-use roc_region::all::{LineInfo, Loc, Region};
+use roc_region::all::{LineColumnRegion, LineInfo, Loc, Region};
 use roc_reporting::report::{Annotation, RenderTarget};
 use roc_solve::module::{extract_module_owned_implementations, Solved, SolvedModule};
 use roc_solve_problem::TypeError;
@@ -61,6 +62,7 @@ use std::str::from_utf8_unchecked;
 use std::sync::Arc;
 use std::{env, fs};
 
+use crate::disk_cache::{self, CachedCanonical, DiskCache, EntryLocation, EntryModule};
 pub use crate::work::Phase;
 use crate::work::{DepCycle, Dependencies};
 
@@ -90,6 +92,15 @@ pub struct LoadConfig {
     pub render: RenderTarget,
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
+    /// Where to keep canonicalized and solved modules between runs, so unchanged modules don't
+    /// need to be processed again. `None` disables this cache.
+    ///
+    /// With [ExecutionMode::Check], modules other than the root that come from the cache have no
+    /// declarations, expectations or documentation.
+    pub cache_dir: Option<PathBuf>,
+    /// Whether to count which top-level defs and branches run, for `roc test --coverage`. Only
+    /// has an effect with [ExecutionMode::Test].
+    pub coverage: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -135,6 +146,9 @@ struct ModuleCache<'a> {
     type_problems: MutMap<ModuleId, Vec<TypeError>>,
 
     sources: MutMap<ModuleId, (PathBuf, &'a str)>,
+
+    /// Where the modules that can be cached are stored in the on-disk cache
+    disk_cache_locations: MutMap<ModuleId, EntryLocation>,
 }
 
 impl<'a> ModuleCache<'a> {
@@ -199,6 +213,7 @@ impl Default for ModuleCache<'_> {
             type_problems: Default::default(),
             sources: Default::default(),
             expectations: Default::default(),
+            disk_cache_locations: Default::default(),
         }
     }
 }
@@ -248,6 +263,7 @@ fn start_phase<'a>(
                             // so other modules can populate them as they load.
                             module_ids: Arc::clone(&state.arc_modules),
                             shorthands: Arc::clone(&state.arc_shorthands),
+                            module_sources: Arc::clone(&state.module_sources),
                             ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
                         }
                     }
@@ -299,6 +315,15 @@ fn start_phase<'a>(
                 let qualified_module_ids = Arc::clone(&state.arc_modules);
                 let qualified_module_ids = { (*qualified_module_ids).lock().clone() };
 
+                let disk_cache_location = state.disk_cache.as_ref().and_then(|disk_cache| {
+                    disk_cache_location(
+                        disk_cache,
+                        &state.module_cache.disk_cache_locations,
+                        &qualified_module_ids,
+                        &parsed,
+                    )
+                });
+
                 let module_ids = qualified_module_ids.into_module_ids();
 
                 let exposed_symbols = state
@@ -353,6 +378,28 @@ fn start_phase<'a>(
                     }
                 }
 
+                let mut cached_canonical = None;
+
+                if let (Some(disk_cache), Some(location)) = (&state.disk_cache, disk_cache_location)
+                {
+                    if let Some(entry) = disk_cache.read(&location) {
+                        state.cached_types.lock().insert(module_id, entry.types);
+
+                        // Everything but checking needs the definitions of the module,
+                        // and so do the callers that inspect the root module after checking it.
+                        if matches!(state.exec_mode, ExecutionMode::Check)
+                            && module_id != state.root_id
+                        {
+                            cached_canonical = entry.canonical;
+                        }
+                    }
+
+                    state
+                        .module_cache
+                        .disk_cache_locations
+                        .insert(module_id, location);
+                }
+
                 let skip_constraint_gen = {
                     // Give this its own scope to make sure that the Guard from the lock() is dropped
                     // immediately after contains_key returns
@@ -367,6 +414,7 @@ fn start_phase<'a>(
                     aliases,
                     abilities_store,
                     skip_constraint_gen,
+                    cached_canonical,
                 }
             }
 
@@ -389,6 +437,23 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
+                let disk_cache_write = match (
+                    &state.disk_cache,
+                    state.module_cache.disk_cache_locations.get(&module_id),
+                ) {
+                    (Some(disk_cache), Some(location)) => Some(disk_cache::PendingWrite {
+                        path: disk_cache.write_path(location.key),
+                        modules: location.modules.clone(),
+                        // Problems found while canonicalizing have to be found again next time.
+                        with_canonical: state
+                            .module_cache
+                            .can_problems
+                            .get(&module_id)
+                            .map_or(true, Vec::is_empty),
+                    }),
+                    _ => None,
+                };
+
                 BuildTask::solve_module(
                     module,
                     ident_ids,
@@ -402,6 +467,7 @@ fn start_phase<'a>(
                     dep_idents,
                     declarations,
                     state.cached_types.clone(),
+                    disk_cache_write,
                     derived_module,
                 )
             }
@@ -435,6 +501,8 @@ fn start_phase<'a>(
                 let build_expects = matches!(state.exec_mode, ExecutionMode::Test)
                     && state.module_cache.expectations.contains_key(&module_id);
 
+                let coverage = build_expects && state.coverage;
+
                 BuildTask::BuildPendingSpecializations {
                     layout_cache,
                     module_id,
@@ -449,6 +517,7 @@ fn start_phase<'a>(
                     exposed_by_module: state.exposed_types.clone(),
                     derived_module,
                     build_expects,
+                    coverage,
                 }
             }
             Phase::MakeSpecializations => {
@@ -548,6 +617,10 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
+                let coverage = state.coverage
+                    && matches!(state.exec_mode, ExecutionMode::Test)
+                    && state.module_cache.expectations.contains_key(&module_id);
+
                 BuildTask::MakeSpecializations {
                     module_id,
                     ident_ids,
@@ -560,6 +633,7 @@ fn start_phase<'a>(
                     // TODO: awful, how can we get rid of the clone?
                     exposed_by_module: state.exposed_types.clone(),
                     derived_module,
+                    coverage,
                 }
             }
         }
@@ -580,6 +654,8 @@ pub struct LoadedModule {
     pub dep_idents: IdentIdsByModule,
     pub exposed_aliases: MutMap<Symbol, Alias>,
     pub exposed_values: Vec<Symbol>,
+    /// What the header of each module exposes
+    pub exposed_symbols_by_module: MutMap<ModuleId, VecSet<Symbol>>,
     pub exposed_types_storage: ExposedTypesStorageSubs,
     pub resolved_implementations: ResolvedImplementations,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
@@ -685,6 +761,156 @@ struct LateSpecializationsModule<'a> {
 pub struct ToplevelExpects {
     pub pure: VecMap<Symbol, Region>,
     pub fx: VecMap<Symbol, Region>,
+    pub properties: VecMap<Symbol, ToplevelProperty>,
+}
+
+/// A top-level `expect` of a function, like `expect \list -> List.len list >= 0`. `roc test` calls
+/// the function with generated arguments, and it should return `Bool.true` for all of them.
+#[derive(Debug, Clone)]
+pub struct ToplevelProperty {
+    pub region: Region,
+    /// The argument names, or e.g. `argument 2` when the argument is destructured
+    pub argument_names: Vec<String>,
+    /// The argument types, in the subs of the module's [Expectations]
+    pub argument_vars: Vec<Variable>,
+    /// An opaque type in the type of each argument, if there is one. Values of those are not
+    /// generated, because only the opaque type's module knows which of them are valid.
+    pub argument_opaques: Vec<Option<Symbol>>,
+}
+
+/// Whether a part of the layout is the layout of an unbound type variable, like the `a` of
+/// `List a`.
+fn contains_void(layout: &Layout) -> bool {
+    match layout {
+        Layout::Builtin(Builtin::List(element)) => contains_void(element),
+        Layout::Builtin(_) | Layout::LambdaSet(_) | Layout::RecursivePointer => false,
+        Layout::Struct { field_layouts, .. } => field_layouts.iter().any(contains_void),
+        Layout::Boxed(inner) => contains_void(inner),
+        Layout::Union(UnionLayout::NonRecursive([])) => true,
+        Layout::Union(union_layout) => match union_layout {
+            UnionLayout::NonRecursive(tags)
+            | UnionLayout::Recursive(tags)
+            | UnionLayout::NullableWrapped {
+                other_tags: tags, ..
+            } => tags.iter().any(|fields| fields.iter().any(contains_void)),
+            UnionLayout::NonNullableUnwrapped(fields)
+            | UnionLayout::NullableUnwrapped {
+                other_fields: fields,
+                ..
+            } => fields.iter().any(contains_void),
+        },
+    }
+}
+
+/// An opaque type in this type, other than the builtin ones (like `Num`) that `roc test` knows
+/// how to generate values of.
+fn find_opaque(subs: &Subs, var: Variable) -> Option<Symbol> {
+    use roc_types::subs::{Content, FlatType, GetSubsSlice};
+
+    let mut stack = vec![var];
+
+    while let Some(var) = stack.pop() {
+        match subs.get_content_without_compacting(var) {
+            Content::Alias(symbol, _, _, AliasKind::Opaque) if !symbol.module_id().is_builtin() => {
+                return Some(*symbol);
+            }
+            Content::Alias(_, _, real_var, _) => stack.push(*real_var),
+            Content::Structure(structure) => match structure {
+                FlatType::Apply(_, args) => stack.extend(subs.get_subs_slice(*args)),
+                FlatType::Record(fields, ext) => {
+                    if let Ok(iter) = fields.unsorted_iterator(subs, *ext) {
+                        stack.extend(iter.map(|(_, field)| *field.as_inner()));
+                    }
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    for (_, vars) in tags.unsorted_iterator(subs, *ext) {
+                        stack.extend(vars.iter().copied());
+                    }
+                }
+                // functions can't be generated either way, and the rest have no values in them
+                _ => {}
+            },
+            // a recursion var refers back to a tag union we're already looking at
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// The regions of the top-level defs, from the name to the end of the body.
+fn toplevel_def_regions(decls: &Declarations) -> VecMap<Symbol, Region> {
+    use roc_can::expr::DeclarationTag::*;
+
+    let mut regions = VecMap::default();
+
+    for (index, tag) in decls.declarations.iter().enumerate() {
+        if let Value | Function(_) | Recursive(_) | TailRecursive(_) = tag {
+            let loc_symbol = decls.symbols[index];
+            let body_region = decls.expressions[index].region;
+
+            regions.insert(
+                loc_symbol.value,
+                Region::span_across(&loc_symbol.region, &body_region),
+            );
+        }
+    }
+
+    regions
+}
+
+/// The regions of the branches of every `when` and `if`, like mono marks them when measuring
+/// coverage. These include the branches of code that never gets specialized.
+fn branch_regions(decls: &Declarations) -> Vec<Vec<Region>> {
+    use roc_can::expr::Expr;
+    use roc_can::traverse::{walk_expr, Visitor};
+
+    struct BranchCollector {
+        branches: Vec<Vec<Region>>,
+    }
+
+    impl Visitor for BranchCollector {
+        fn visit_expr(&mut self, expr: &Expr, _region: Region, var: Variable) {
+            let regions: Vec<Region> = match expr {
+                Expr::When { branches, .. } => {
+                    branches.iter().map(|branch| branch.value.region).collect()
+                }
+                Expr::If {
+                    branches,
+                    final_else,
+                    ..
+                } => branches
+                    .iter()
+                    .map(|(_, loc_then)| loc_then.region)
+                    .chain(std::iter::once(final_else.region))
+                    .collect(),
+                _ => Vec::new(),
+            };
+
+            // mono doesn't mark branches without a region
+            let regions: Vec<Region> = regions
+                .into_iter()
+                .filter(|region| !region.is_empty())
+                .collect();
+
+            if !regions.is_empty() {
+                self.branches.push(regions);
+            }
+
+            walk_expr(self, expr, var)
+        }
+    }
+
+    let mut collector = BranchCollector {
+        branches: Vec::new(),
+    };
+
+    collector.visit_decls(decls);
+
+    // declarations are sorted by their dependencies, not by where they are
+    collector.branches.sort_by_key(|regions| regions[0].start());
+
+    collector.branches
 }
 
 #[derive(Debug)]
@@ -721,6 +947,10 @@ pub struct Expectations {
     pub path: PathBuf,
     pub expectations: VecMap<Region, Vec<ExpectLookup>>,
     pub ident_ids: IdentIds,
+    /// Where each top-level def is, for `roc test --coverage`
+    pub toplevel_defs: VecMap<Symbol, Region>,
+    /// The branches of each `when` and `if`, in source order, for `roc test --coverage`
+    pub branches: Vec<Vec<Region>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -904,6 +1134,8 @@ struct State<'a> {
     /// From now on, these will be used by multiple threads; time to make an Arc<Mutex<_>>!
     pub arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
     pub arc_shorthands: Arc<Mutex<MutMap<&'a str, PackageName<'a>>>>,
+    /// See [LoadStart::with_module_sources]
+    pub module_sources: Arc<MutMap<PathBuf, &'a str>>,
     #[allow(unused)]
     pub derived_module: SharedDerivedModule,
 
@@ -924,6 +1156,8 @@ struct State<'a> {
 
     pub render: RenderTarget,
     pub exec_mode: ExecutionMode,
+    /// See [LoadConfig::coverage]
+    pub coverage: bool,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
@@ -933,6 +1167,9 @@ struct State<'a> {
     // cached types (used for builtin modules, could include packages in the future too)
     cached_types: CachedTypeState,
 
+    /// Solved types of user modules from previous runs
+    disk_cache: Option<DiskCache>,
+
     layout_interner: Arc<GlobalInterner<'a, Layout<'a>>>,
 }
 
@@ -949,11 +1186,14 @@ impl<'a> State<'a> {
         target_info: TargetInfo,
         exposed_types: ExposedByModule,
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
+        module_sources: MutMap<PathBuf, &'a str>,
         ident_ids_by_module: SharedIdentIdsByModule,
         cached_types: MutMap<ModuleId, TypeState>,
         render: RenderTarget,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        cache_dir: Option<PathBuf>,
+        coverage: bool,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
 
@@ -974,6 +1214,7 @@ impl<'a> State<'a> {
             exposed_types,
             arc_modules,
             arc_shorthands,
+            module_sources: Arc::new(module_sources),
             derived_module: Default::default(),
             constrained_ident_ids: IdentIds::exposed_builtins(0),
             ident_ids_by_module,
@@ -982,8 +1223,10 @@ impl<'a> State<'a> {
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            disk_cache: cache_dir.map(DiskCache::new),
             render,
             exec_mode,
+            coverage,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
             layout_interner: GlobalInterner::with_capacity(128),
@@ -1069,6 +1312,7 @@ enum BuildTask<'a> {
         module_name: PQModuleName<'a>,
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         shorthands: Arc<Mutex<MutMap<&'a str, PackageName<'a>>>>,
+        module_sources: Arc<MutMap<PathBuf, &'a str>>,
         ident_ids_by_module: SharedIdentIdsByModule,
     },
     Parse {
@@ -1082,6 +1326,7 @@ enum BuildTask<'a> {
         aliases: MutMap<Symbol, Alias>,
         abilities_store: PendingAbilitiesStore,
         skip_constraint_gen: bool,
+        cached_canonical: Option<CachedCanonical>,
     },
     Solve {
         module: Module,
@@ -1095,6 +1340,7 @@ enum BuildTask<'a> {
         declarations: Declarations,
         dep_idents: IdentIdsByModule,
         cached_subs: CachedTypeState,
+        disk_cache_write: Option<disk_cache::PendingWrite>,
         derived_module: SharedDerivedModule,
     },
     BuildPendingSpecializations {
@@ -1110,6 +1356,8 @@ enum BuildTask<'a> {
         abilities_store: AbilitiesStore,
         derived_module: SharedDerivedModule,
         build_expects: bool,
+        /// Whether to mark branches for `roc test --coverage`
+        coverage: bool,
     },
     MakeSpecializations {
         module_id: ModuleId,
@@ -1122,6 +1370,8 @@ enum BuildTask<'a> {
         exposed_by_module: ExposedByModule,
         world_abilities: WorldAbilities,
         derived_module: SharedDerivedModule,
+        /// Whether to mark branches for `roc test --coverage`
+        coverage: bool,
     },
 }
 
@@ -1207,6 +1457,8 @@ pub fn load_and_typecheck_str<'a>(
         render,
         threading,
         exec_mode: ExecutionMode::Check,
+        cache_dir: None,
+        coverage: false,
     };
 
     match load(arena, load_start, exposed_types, cached_subs, load_config)? {
@@ -1227,6 +1479,7 @@ pub struct LoadStart<'a> {
     root_id: ModuleId,
     root_msg: Msg<'a>,
     src_dir: PathBuf,
+    module_sources: MutMap<PathBuf, &'a str>,
 }
 
 impl<'a> LoadStart<'a> {
@@ -1302,7 +1555,7 @@ impl<'a> LoadStart<'a> {
                     return Err(LoadingProblem::FormattedReport(buf));
                 }
                 Err(LoadingProblem::FileProblem { filename, error }) => {
-                    let buf = to_file_problem_report(&filename, error);
+                    let buf = to_file_problem_report(&filename, error, render);
                     return Err(LoadingProblem::FormattedReport(buf));
                 }
                 Err(LoadingProblem::ImportCycle(filename, cycle)) => {
@@ -1355,6 +1608,7 @@ impl<'a> LoadStart<'a> {
             src_dir,
             root_id,
             root_msg,
+            module_sources: MutMap::default(),
         })
     }
 
@@ -1388,8 +1642,17 @@ impl<'a> LoadStart<'a> {
             ident_ids_by_module,
             root_id,
             root_msg,
+            module_sources: MutMap::default(),
         })
     }
+
+    /// Loads the modules at these paths from the given sources, instead of reading their files.
+    pub fn with_module_sources(self, module_sources: MutMap<PathBuf, &'a str>) -> Self {
+        Self {
+            module_sources,
+            ..self
+        }
+    }
 }
 
 pub enum LoadResult<'a> {
@@ -1495,6 +1758,8 @@ pub fn load<'a>(
             cached_types,
             load_config.render,
             load_config.exec_mode,
+            load_config.cache_dir,
+            load_config.coverage,
         ),
         Threads::Many(threads) => load_multi_threaded(
             arena,
@@ -1505,6 +1770,8 @@ pub fn load<'a>(
             load_config.render,
             threads,
             load_config.exec_mode,
+            load_config.cache_dir,
+            load_config.coverage,
         ),
     }
 }
@@ -1519,6 +1786,8 @@ pub fn load_single_threaded<'a>(
     cached_types: MutMap<ModuleId, TypeState>,
     render: RenderTarget,
     exec_mode: ExecutionMode,
+    cache_dir: Option<PathBuf>,
+    coverage: bool,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1526,7 +1795,7 @@ pub fn load_single_threaded<'a>(
         root_id,
         root_msg,
         src_dir,
-        ..
+        module_sources,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1541,11 +1810,14 @@ pub fn load_single_threaded<'a>(
         target_info,
         exposed_types,
         arc_modules,
+        module_sources,
         ident_ids_by_module,
         cached_types,
         render,
         number_of_workers,
         exec_mode,
+        cache_dir,
+        coverage,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1648,7 +1920,7 @@ fn state_thread_step<'a>(
                     Ok(ControlFlow::Break(LoadResult::Monomorphized(monomorphized)))
                 }
                 Msg::FailedToReadFile { filename, error } => {
-                    let buf = to_file_problem_report(&filename, error);
+                    let buf = to_file_problem_report(&filename, error, state.render);
                     Err(LoadingProblem::FormattedReport(buf))
                 }
 
@@ -1768,6 +2040,8 @@ fn load_multi_threaded<'a>(
     render: RenderTarget,
     available_threads: usize,
     exec_mode: ExecutionMode,
+    cache_dir: Option<PathBuf>,
+    coverage: bool,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1775,7 +2049,7 @@ fn load_multi_threaded<'a>(
         root_id,
         root_msg,
         src_dir,
-        ..
+        module_sources,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1805,11 +2079,14 @@ fn load_multi_threaded<'a>(
         target_info,
         exposed_types,
         arc_modules,
+        module_sources,
         ident_ids_by_module,
         cached_types,
         render,
         num_workers,
         exec_mode,
+        cache_dir,
+        coverage,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2177,6 +2454,17 @@ fn update<'a>(
                 {
                     work.extend(state.dependencies.notify_package(config_shorthand));
                 }
+
+                // When an app isn't built on one of its packages (like the repl's apps), no
+                // platform header comes along to resolve them, so the app header does that.
+                if let App {
+                    to_platform: To::NewPackage(_),
+                } = header.header_for
+                {
+                    for (shorthand, _) in header.packages.iter() {
+                        work.extend(state.dependencies.notify_package(*shorthand));
+                    }
+                }
             }
 
             match header.header_for {
@@ -2384,7 +2672,10 @@ fn update<'a>(
                 .type_problems
                 .insert(module_id, solved_module.problems);
 
-            let should_include_expects = !loc_expects.is_empty() && {
+            // When measuring coverage, every module of the package gets its expectations, so
+            // `roc test --coverage` can tell which of its code ran, even without expects in it.
+            let measure_coverage = state.coverage && matches!(state.exec_mode, ExecutionMode::Test);
+            let should_include_expects = (!loc_expects.is_empty() || measure_coverage) && {
                 let modules = state.arc_modules.lock();
                 modules
                     .package_eq(module_id, state.root_id)
@@ -2395,6 +2686,8 @@ fn update<'a>(
                 let (path, _) = state.module_cache.sources.get(&module_id).unwrap();
 
                 let expectations = Expectations {
+                    toplevel_defs: toplevel_def_regions(&decls),
+                    branches: branch_regions(&decls),
                     expectations: loc_expects,
                     subs: solved_subs.clone().into_inner(),
                     path: path.to_owned(),
@@ -2529,6 +2822,12 @@ fn update<'a>(
                 } else {
                     state.constrained_ident_ids.insert(module_id, ident_ids);
                     state.timings.insert(module_id, module_timing);
+
+                    // When only checking, hold on to the declarations of every module so that
+                    // tools like the language server can look up definitions across modules.
+                    if matches!(state.exec_mode, ExecutionMode::Check) {
+                        state.declarations_by_id.insert(module_id, decls);
+                    }
                 }
 
                 let work = if is_host_exposed
@@ -2571,6 +2870,10 @@ fn update<'a>(
 
             state.toplevel_expects.pure.extend(toplevel_expects.pure);
             state.toplevel_expects.fx.extend(toplevel_expects.fx);
+            state
+                .toplevel_expects
+                .properties
+                .extend(toplevel_expects.properties);
 
             state
                 .module_cache
@@ -2962,7 +3265,8 @@ fn finish_specialization<'a>(
                         }
                         Valid(To::NewPackage(p_or_p)) => p_or_p,
                         other => {
-                            let buf = to_missing_platform_report(state.root_id, other);
+                            let buf =
+                                to_missing_platform_report(state.root_id, other, state.render);
                             return Err(LoadingProblem::FormattedReport(buf));
                         }
                     };
@@ -3079,6 +3383,7 @@ fn finish(
         dep_idents,
         exposed_aliases: exposed_aliases_by_symbol,
         exposed_values,
+        exposed_symbols_by_module: state.exposed_symbols_by_module,
         exposed_to_host: exposed_vars_by_symbol.into_iter().collect(),
         exposed_types_storage,
         resolved_implementations,
@@ -3237,6 +3542,7 @@ fn load_module<'a>(
     module_name: PQModuleName<'a>,
     module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
     arc_shorthands: Arc<Mutex<MutMap<&'a str, PackageName<'a>>>>,
+    module_sources: &MutMap<PathBuf, &'a str>,
     ident_ids_by_module: SharedIdentIdsByModule,
 ) -> Result<(ModuleId, Msg<'a>), LoadingProblem<'a>> {
     let module_start_time = Instant::now();
@@ -3286,6 +3592,21 @@ fn load_module<'a>(
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, &module_name, arc_shorthands);
 
+    if let Some(src) = module_sources.get(&filename) {
+        return parse_header(
+            arena,
+            Duration::default(),
+            filename,
+            false,
+            opt_shorthand,
+            Some(module_name),
+            module_ids,
+            ident_ids_by_module,
+            src.as_bytes(),
+            module_start_time,
+        );
+    }
+
     load_filename(
         arena,
         filename,
@@ -4196,6 +4517,7 @@ impl<'a> BuildTask<'a> {
         dep_idents: IdentIdsByModule,
         declarations: Declarations,
         cached_subs: CachedTypeState,
+        disk_cache_write: Option<disk_cache::PendingWrite>,
         derived_module: SharedDerivedModule,
     ) -> Self {
         let exposed_by_module = exposed_types.retain_modules(imported_modules.keys());
@@ -4216,6 +4538,7 @@ impl<'a> BuildTask<'a> {
             dep_idents,
             module_timing,
             cached_subs,
+            disk_cache_write,
             derived_module,
         }
     }
@@ -4393,6 +4716,189 @@ pub fn add_imports(
     (import_variables, abilities_store)
 }
 
+/// Where a module is stored in the on-disk cache, or `None` if it can't be cached.
+///
+/// Only interface modules are cached, and only when every module they import can be too.
+fn disk_cache_location(
+    disk_cache: &DiskCache,
+    locations: &MutMap<ModuleId, EntryLocation>,
+    module_names: &PackageModuleIds,
+    parsed: &ParsedModule,
+) -> Option<EntryLocation> {
+    if !matches!(parsed.header_for, HeaderFor::Interface) {
+        return None;
+    }
+
+    let module_name = |module_id: ModuleId| match module_names.get_name(module_id) {
+        Some(PackageQualified::Unqualified(name)) => Some(name.as_str().to_string()),
+        Some(PackageQualified::Qualified(shorthand, name)) => {
+            Some(format!("{}.{}", shorthand, name.as_str()))
+        }
+        None => None,
+    };
+
+    let mut hasher = disk_cache.key_hasher();
+    let mut modules = vec![EntryModule {
+        module_id: parsed.module_id,
+        name: module_name(parsed.module_id)?,
+        exposed_ident_ids: parsed.exposed_ident_ids.clone(),
+    }];
+
+    hasher.write_bytes(modules[0].name.as_bytes());
+    hasher.write_bytes(parsed.src.as_bytes());
+    hasher.write_idents(&parsed.exposed_ident_ids);
+
+    // Hash every import on its own and sort the results,
+    // so the key does not depend on the iteration order of deps_by_name.
+    let mut dep_keys = Vec::with_capacity(parsed.deps_by_name.len());
+
+    for dep_id in parsed.deps_by_name.values() {
+        let mut dep_hasher = disk_cache.key_hasher();
+
+        dep_hasher.write_bytes(module_name(*dep_id)?.as_bytes());
+
+        // Builtins are part of the compiler, which is already accounted for in the key.
+        if !dep_id.is_builtin() {
+            let dep_location = locations.get(dep_id)?;
+
+            dep_hasher.write_key(dep_location.key);
+
+            for dep_module in dep_location.modules.iter() {
+                if !modules
+                    .iter()
+                    .any(|module| module.module_id == dep_module.module_id)
+                {
+                    modules.push(dep_module.clone());
+                }
+            }
+        }
+
+        dep_keys.push(dep_hasher.finish());
+    }
+
+    dep_keys.sort();
+
+    for dep_key in dep_keys {
+        hasher.write_key(dep_key);
+    }
+
+    Some(EntryLocation {
+        key: hasher.finish(),
+        modules,
+    })
+}
+
+/// Builds the result of canonicalizing a module from its entry in the on-disk cache, for checking
+/// a module whose types come from that entry, too. The module has no definitions or expectations.
+fn canonicalize_from_disk_cache(
+    dep_idents: IdentIdsByModule,
+    exposed_symbols: VecSet<Symbol>,
+    parsed: ParsedModule,
+    cached: CachedCanonical,
+) -> CanAndCon {
+    let CachedCanonical {
+        ident_ids,
+        aliases,
+        abilities,
+        exposed_imports,
+        referenced_values,
+        referenced_types,
+    } = cached;
+
+    let module = Module {
+        module_id: parsed.module_id,
+        exposed_imports,
+        exposed_symbols,
+        referenced_values,
+        referenced_types,
+        aliases,
+        rigid_variables: Default::default(),
+        abilities_store: abilities,
+        loc_expects: Default::default(),
+    };
+
+    let constrained_module = ConstrainedModule {
+        module,
+        declarations: Declarations::new(),
+        imported_modules: parsed.imported_modules,
+        var_store: VarStore::default(),
+        constraints: Constraints::new(),
+        constraint: roc_can::constraint::Constraint::True,
+        ident_ids,
+        dep_idents,
+        module_timing: parsed.module_timing,
+        pending_derives: Default::default(),
+    };
+
+    CanAndCon {
+        constrained_module,
+        canonicalization_problems: Vec::new(),
+        module_docs: None,
+    }
+}
+
+/// Saves the solved types of a module for later runs, if they can be reused.
+#[allow(clippy::complexity)]
+fn write_disk_cache(
+    pending: &disk_cache::PendingWrite,
+    canonical: Option<Vec<u8>>,
+    solved: (
+        Solved<Subs>,
+        ResolvedImplementations,
+        Vec<(Symbol, Variable)>,
+        Vec<TypeError>,
+        AbilitiesStore,
+    ),
+) -> (
+    Solved<Subs>,
+    ResolvedImplementations,
+    Vec<(Symbol, Variable)>,
+    Vec<TypeError>,
+    AbilitiesStore,
+) {
+    let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities) = solved;
+
+    if !disk_cache::is_cacheable(solved_subs.inner(), &problems) {
+        return (
+            solved_subs,
+            solved_implementations,
+            exposed_vars_by_symbol,
+            problems,
+            abilities,
+        );
+    }
+
+    let type_state = TypeState {
+        subs: solved_subs.into_inner(),
+        exposed_vars_by_symbol,
+        abilities,
+        solved_implementations,
+    };
+
+    // If this fails, the module just gets solved again next time.
+    let _ = disk_cache::write(
+        &pending.path,
+        &pending.modules,
+        &type_state,
+        canonical.as_deref(),
+    );
+
+    let TypeState {
+        subs,
+        exposed_vars_by_symbol,
+        abilities,
+        solved_implementations,
+    } = type_state;
+
+    (
+        Solved(subs),
+        solved_implementations,
+        exposed_vars_by_symbol,
+        problems,
+        abilities,
+    )
+}
+
 #[allow(clippy::complexity)]
 fn run_solve_solve(
     exposed_for_module: ExposedForModule,
@@ -4511,6 +5017,7 @@ fn run_solve<'a>(
     decls: Declarations,
     dep_idents: IdentIdsByModule,
     cached_types: CachedTypeState,
+    disk_cache_write: Option<disk_cache::PendingWrite>,
     derived_module: SharedDerivedModule,
 ) -> Msg<'a> {
     let solve_start = Instant::now();
@@ -4524,10 +5031,20 @@ fn run_solve<'a>(
     let loc_expects = std::mem::take(&mut module.loc_expects);
     let module = module;
 
-    let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities_store) = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
+    let cached = cached_types.lock().remove(&module_id);
+
+    let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities_store) =
+        match cached {
+            None => {
+                // Solving consumes the module, so serialize its canonical parts up front.
+                let canonical = match &disk_cache_write {
+                    Some(pending) if pending.with_canonical => {
+                        disk_cache::write_canonical(&module, &ident_ids)
+                    }
+                    _ => None,
+                };
+
+                let solved = run_solve_solve(
                     exposed_for_module,
                     constraints,
                     constraint,
@@ -4535,32 +5052,26 @@ fn run_solve<'a>(
                     var_store,
                     module,
                     derived_module,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => (
-                    Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    vec![],
-                    abilities,
-                ),
+                );
+
+                match disk_cache_write {
+                    Some(pending) => write_disk_cache(&pending, canonical, solved),
+                    None => solved,
+                }
             }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                constraints,
-                constraint,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-            )
-        }
-    };
+            Some(TypeState {
+                subs,
+                exposed_vars_by_symbol,
+                abilities,
+                solved_implementations,
+            }) => (
+                Solved(subs),
+                solved_implementations,
+                exposed_vars_by_symbol,
+                vec![],
+                abilities,
+            ),
+        };
 
     let mut solved_subs = solved_subs;
     let exposed_types = roc_solve::module::exposed_types_storage_subs(
@@ -4931,6 +5442,7 @@ fn make_specializations<'a>(
     world_abilities: WorldAbilities,
     exposed_by_module: &ExposedByModule,
     derived_module: SharedDerivedModule,
+    coverage: bool,
 ) -> Msg<'a> {
     let make_specializations_start = Instant::now();
     let mut update_mode_ids = UpdateModeIds::new();
@@ -4947,6 +5459,7 @@ fn make_specializations<'a>(
         abilities: AbilitiesView::World(&world_abilities),
         exposed_by_module,
         derived_module: &derived_module,
+        coverage,
     };
 
     let mut procs = Procs::new_in(arena);
@@ -5010,6 +5523,7 @@ fn build_pending_specializations<'a>(
     abilities_store: AbilitiesStore,
     derived_module: SharedDerivedModule,
     build_expects: bool,
+    coverage: bool,
 ) -> Msg<'a> {
     let find_specializations_start = Instant::now();
 
@@ -5041,6 +5555,8 @@ fn build_pending_specializations<'a>(
         abilities: AbilitiesView::Module(&abilities_store),
         exposed_by_module,
         derived_module: &derived_module,
+        // branches in top-level expects are covered too
+        coverage,
     };
 
     // Add modules' decls to Procs
@@ -5260,6 +5776,84 @@ fn build_pending_specializations<'a>(
                     continue;
                 }
 
+                if let roc_can::expr::Expr::Closure(closure_data) = &body.value {
+                    // A property: `roc test` calls it with generated arguments, so it's
+                    // specialized like a host-exposed function.
+                    let expr_var = closure_data.function_type;
+
+                    let argument_names = closure_data
+                        .arguments
+                        .iter()
+                        .enumerate()
+                        .map(|(position, (_, _, loc_pattern))| match loc_pattern.value {
+                            roc_can::pattern::Pattern::Identifier(argument) => mono_env
+                                .ident_ids
+                                .get_name(argument.ident_id())
+                                .unwrap_or_default()
+                                .to_string(),
+                            _ => format!("argument {}", position + 1),
+                        })
+                        .collect();
+
+                    let name_region = declarations.symbols[index].region;
+                    let region = Region::span_across(&name_region, &body.region);
+
+                    let argument_vars: Vec<_> = closure_data
+                        .arguments
+                        .iter()
+                        .map(|(var, _, _)| *var)
+                        .collect();
+                    let argument_opaques: Vec<_> = argument_vars
+                        .iter()
+                        .map(|var| find_opaque(mono_env.subs, *var))
+                        .collect();
+                    let has_opaque_argument = argument_opaques.iter().any(Option::is_some);
+
+                    // When the arguments have no layout, a type variable in their type (like
+                    // `List a`), or an opaque type, the property is not specialized, and
+                    // `roc test` reports that it can't generate arguments for it.
+                    toplevel_expects.properties.insert(
+                        symbol,
+                        ToplevelProperty {
+                            region,
+                            argument_names,
+                            argument_vars,
+                            argument_opaques,
+                        },
+                    );
+
+                    if has_opaque_argument {
+                        continue;
+                    }
+
+                    match layout_cache.raw_from_var(mono_env.arena, expr_var, mono_env.subs) {
+                        Ok(RawFunctionLayout::Function(arguments, _, _))
+                            if !arguments.iter().any(contains_void) => {}
+                        _ => continue,
+                    }
+
+                    procs_base.host_specializations.insert_host_exposed(
+                        mono_env.subs,
+                        LambdaName::no_niche(symbol),
+                        None,
+                        expr_var,
+                    );
+
+                    let partial_proc = PartialProc::from_named_function(
+                        &mut mono_env,
+                        expr_var,
+                        closure_data.arguments.clone(),
+                        (*closure_data.loc_body).clone(),
+                        CapturedSymbols::None,
+                        false,
+                        closure_data.return_type,
+                    );
+
+                    procs_base.partial_procs.insert(symbol, partial_proc);
+
+                    continue;
+                }
+
                 // mark this symbol as a top-level thunk before any other work on the procs
                 module_thunks.push(symbol);
 
@@ -5472,6 +6066,8 @@ fn load_derived_partial_procs<'a>(
             abilities: AbilitiesView::World(world_abilities),
             exposed_by_module,
             derived_module,
+            // derived code has no source to cover
+            coverage: false,
         };
 
         let partial_proc = match derived_expr {
@@ -5543,6 +6139,7 @@ fn run_task<'a>(
             module_name,
             module_ids,
             shorthands,
+            module_sources,
             ident_ids_by_module,
         } => load_module(
             arena,
@@ -5550,6 +6147,7 @@ fn run_task<'a>(
             module_name,
             module_ids,
             shorthands,
+            &module_sources,
             ident_ids_by_module,
         )
         .map(|(_, msg)| msg),
@@ -5562,17 +6160,23 @@ fn run_task<'a>(
             aliases,
             abilities_store,
             skip_constraint_gen,
+            cached_canonical,
         } => {
-            let can_and_con = canonicalize_and_constrain(
-                arena,
-                &module_ids,
-                dep_idents,
-                exposed_symbols,
-                aliases,
-                abilities_store,
-                parsed,
-                skip_constraint_gen,
-            );
+            let can_and_con = match cached_canonical {
+                Some(cached) => {
+                    canonicalize_from_disk_cache(dep_idents, exposed_symbols, parsed, cached)
+                }
+                None => canonicalize_and_constrain(
+                    arena,
+                    &module_ids,
+                    dep_idents,
+                    exposed_symbols,
+                    aliases,
+                    abilities_store,
+                    parsed,
+                    skip_constraint_gen,
+                ),
+            };
 
             Ok(Msg::CanonicalizedAndConstrained(can_and_con))
         }
@@ -5588,6 +6192,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            disk_cache_write,
             derived_module,
         } => Ok(run_solve(
             module,
@@ -5601,6 +6206,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            disk_cache_write,
             derived_module,
         )),
         BuildPendingSpecializations {
@@ -5616,6 +6222,7 @@ fn run_task<'a>(
             exposed_by_module,
             derived_module,
             build_expects,
+            coverage,
         } => Ok(build_pending_specializations(
             arena,
             solved_subs,
@@ -5631,6 +6238,7 @@ fn run_task<'a>(
             abilities_store,
             derived_module,
             build_expects,
+            coverage,
         )),
         MakeSpecializations {
             module_id,
@@ -5643,6 +6251,7 @@ fn run_task<'a>(
             world_abilities,
             exposed_by_module,
             derived_module,
+            coverage,
         } => Ok(make_specializations(
             arena,
             module_id,
@@ -5656,6 +6265,7 @@ fn run_task<'a>(
             world_abilities,
             &exposed_by_module,
             derived_module,
+            coverage,
         )),
     }?;
 
@@ -5666,7 +6276,7 @@ fn run_task<'a>(
     Ok(())
 }
 
-fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
+fn to_file_problem_report(filename: &Path, error: io::ErrorKind, render: RenderTarget) -> String {
     use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
 
@@ -5694,12 +6304,12 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                 ]),
             ]);
 
-            Report {
-                filename: "UNKNOWN.roc".into(),
+            Report::new(
+                "FILE NOT FOUND",
+                "UNKNOWN.roc".into(),
                 doc,
-                title: "FILE NOT FOUND".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         io::ErrorKind::PermissionDenied => {
             let doc = alloc.stack([
@@ -5711,12 +6321,12 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                     .concat([alloc.reflow(r"Is it the right file? Maybe change its permissions?")]),
             ]);
 
-            Report {
-                filename: "UNKNOWN.roc".into(),
+            Report::new(
+                "FILE PERMISSION DENIED",
+                "UNKNOWN.roc".into(),
                 doc,
-                title: "FILE PERMISSION DENIED".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         _ => {
             let error = std::io::Error::from(error);
@@ -5731,18 +6341,18 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                 alloc.text(formatted).annotate(Annotation::Error).indent(4),
             ]);
 
-            Report {
-                filename: "UNKNOWN.roc".into(),
+            Report::new(
+                "FILE PROBLEM",
+                "UNKNOWN.roc".into(),
                 doc,
-                title: "FILE PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
     };
 
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    report.render(render, &mut buf, &alloc, &palette);
 
     buf
 }
@@ -5793,12 +6403,7 @@ fn to_import_cycle_report(
         alloc.reflow("Cyclic dependencies are not allowed in Roc! Can you restructure a module in this import chain so that it doesn't have to depend on itself?")
     ]);
 
-    let report = Report {
-        filename,
-        doc,
-        title: "IMPORT CYCLE".to_string(),
-        severity: Severity::RuntimeError,
-    };
+    let report = Report::new("IMPORT CYCLE", filename, doc, Severity::RuntimeError);
 
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
@@ -5835,23 +6440,25 @@ fn to_incorrect_module_name_report<'a>(
     };
     let alloc = RocDocAllocator::new(&src_lines, module_id, &interns);
 
+    let region = lines.convert_region(found.region);
+
     let doc = alloc.stack([
         alloc.reflow("This module has a different name than I expected:"),
-        alloc.region(lines.convert_region(found.region)),
+        alloc.region(region),
         alloc.reflow("Based on the nesting and use of this module, I expect it to have name"),
         alloc.pq_module_name(expected).indent(4),
     ]);
 
-    let report = Report {
+    let report = Report::new(
+        "INCORRECT MODULE NAME",
         filename,
         doc,
-        title: "INCORRECT MODULE NAME".to_string(),
-        severity: Severity::RuntimeError,
-    };
+        Severity::RuntimeError,
+    );
 
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
-    report.render(render, &mut buf, &alloc, &palette);
+    report.render_with_region(render, Some(region), &mut buf, &alloc, &palette);
     buf
 }
 
@@ -5883,6 +6490,17 @@ fn to_parse_problem_report<'a>(
 
     let lines = LineInfo::new(src);
 
+    // Point at the character where the parser got stuck
+    let region = problem.problem.problem.region().map(|region| {
+        let region = lines.convert_region(region);
+
+        if region.start() == region.end() {
+            LineColumnRegion::new(region.start(), region.end().bump_column(1))
+        } else {
+            region
+        }
+    });
+
     let report = parse_problem(
         &alloc,
         &lines,
@@ -5894,12 +6512,16 @@ fn to_parse_problem_report<'a>(
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
 
-    report.render(render, &mut buf, &alloc, &palette);
+    report.render_with_region(render, region, &mut buf, &alloc, &palette);
 
     buf
 }
 
-fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> String {
+fn to_missing_platform_report(
+    module_id: ModuleId,
+    other: PlatformPath,
+    render: RenderTarget,
+) -> String {
     use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
     use PlatformPath::*;
@@ -5921,12 +6543,12 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     alloc.reflow("See also TODO."),
                 ]);
 
-                Report {
-                    filename: "UNKNOWN.roc".into(),
+                Report::new(
+                    "NO PLATFORM",
+                    "UNKNOWN.roc".into(),
                     doc,
-                    title: "NO PLATFORM".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             RootIsInterface => {
                 let doc = alloc.stack([
@@ -5936,12 +6558,12 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     alloc.reflow(r"Tip: You can use `roc check` or `roc test` to verify an interface module like this one."),
                 ]);
 
-                Report {
-                    filename: "UNKNOWN.roc".into(),
+                Report::new(
+                    "NO PLATFORM",
+                    "UNKNOWN.roc".into(),
                     doc,
-                    title: "NO PLATFORM".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             RootIsHosted => {
                 let doc = alloc.stack([
@@ -5951,12 +6573,12 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     alloc.reflow(r"Tip: You can use `roc check` or `roc test` to verify a hosted module like this one."),
                 ]);
 
-                Report {
-                    filename: "UNKNOWN.roc".into(),
+                Report::new(
+                    "NO PLATFORM",
+                    "UNKNOWN.roc".into(),
                     doc,
-                    title: "NO PLATFORM".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             RootIsPlatformModule => {
                 let doc = alloc.stack([
@@ -5966,19 +6588,19 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     alloc.reflow(r"Tip: You can use `roc check` or `roc test` to verify a platform module like this one."),
                 ]);
 
-                Report {
-                    filename: "UNKNOWN.roc".into(),
+                Report::new(
+                    "NO PLATFORM",
+                    "UNKNOWN.roc".into(),
                     doc,
-                    title: "NO PLATFORM".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         }
     };
 
     let palette = DEFAULT_PALETTE;
     let mut buf = String::new();
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    report.render(render, &mut buf, &alloc, &palette);
 
     buf
 }
@@ -3,6 +3,7 @@
 #![allow(clippy::large_enum_variant)]
 
 use roc_module::symbol::ModuleId;
+pub mod disk_cache;
 pub mod docs;
 pub mod file;
 mod work;
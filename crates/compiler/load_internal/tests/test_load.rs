@@ -30,13 +30,14 @@ use roc_target::TargetInfo;
 use roc_types::pretty_print::name_and_print_var;
 use roc_types::pretty_print::DebugPrint;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn load_and_typecheck(
     arena: &Bump,
     filename: PathBuf,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
+    cache_dir: Option<PathBuf>,
 ) -> Result<LoadedModule, LoadingProblem> {
     use LoadResult::*;
 
@@ -46,6 +47,8 @@ fn load_and_typecheck(
         render: RenderTarget::Generic,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Check,
+        cache_dir,
+        coverage: false,
     };
 
     match roc_load_internal::file::load(
@@ -165,7 +168,7 @@ fn multiple_modules_help<'a>(
         writeln!(file, "{}", source)?;
         file_handles.push(file);
 
-        load_and_typecheck(arena, full_file_path, Default::default(), TARGET_INFO)
+        load_and_typecheck(arena, full_file_path, Default::default(), TARGET_INFO, None)
     };
 
     Ok(result)
@@ -179,7 +182,7 @@ fn load_fixture(
     let src_dir = fixtures_dir().join(dir_name);
     let filename = src_dir.join(format!("{}.roc", module_name));
     let arena = Bump::new();
-    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO);
+    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO, None);
     let mut loaded_module = match loaded {
         Ok(x) => x,
         Err(roc_load_internal::file::LoadingProblem::FormattedReport(report)) => {
@@ -336,7 +339,7 @@ fn interface_with_deps() {
     let src_dir = fixtures_dir().join("interface_with_deps");
     let filename = src_dir.join("Primary.roc");
     let arena = Bump::new();
-    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO);
+    let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO, None);
 
     let mut loaded_module = loaded.expect("Test module failed to load");
     let home = loaded_module.module_id;
@@ -506,6 +509,168 @@ fn load_principal_types() {
     );
 }
 
+#[test]
+fn iface_dep_types_from_disk_cache() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/iface_dep_types_from_disk_cache");
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("Primary.roc");
+
+    // The first load solves every module and writes it to the cache;
+    // the second one reads them all back instead.
+    for _ in 0..2 {
+        let arena = Bump::new();
+        let loaded_module = load_and_typecheck(
+            &arena,
+            filename.clone(),
+            Default::default(),
+            TARGET_INFO,
+            Some(cache_dir.path().to_path_buf()),
+        )
+        .unwrap();
+
+        expect_types(
+            loaded_module,
+            hashmap! {
+                "blah2" => "Float *",
+                "blah3" => "Str",
+                "str" => "Str",
+                "alwaysThree" => "* -> Float *",
+                "identity" => "a -> a",
+                "z" => "Float *",
+                "w" => "Dep1.Identity {}",
+                "succeed" => "a -> Dep1.Identity a",
+                "yay" => "Res.Res {} err",
+                "withDefault" => "Res.Res a err, a -> a",
+            },
+        );
+    }
+
+    assert!(disk_cache_entries(cache_dir.path()) > 0);
+}
+
+/// The number of entries in a module cache, which are all in the directory of the current compiler.
+fn disk_cache_entries(cache_dir: &Path) -> usize {
+    let entry_dirs: Vec<_> = std::fs::read_dir(cache_dir)
+        .unwrap()
+        .map(|dir_entry| dir_entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    assert_eq!(entry_dirs.len(), 1, "{:?}", entry_dirs);
+
+    std::fs::read_dir(&entry_dirs[0]).unwrap().count()
+}
+
+#[test]
+fn disk_cache_removes_entries_of_other_compilers() {
+    let cache_dir =
+        roc_test_utils::TmpDir::new("tmp/disk_cache_removes_entries_of_other_compilers");
+    let other_compiler = cache_dir.path().join("0123456789abcdef");
+    let old_layout_entry = cache_dir
+        .path()
+        .join("0123456789abcdef0123456789abcdef.dat");
+    let unrelated = cache_dir.path().join("README");
+
+    std::fs::create_dir_all(&other_compiler).unwrap();
+    std::fs::write(
+        other_compiler.join("0123456789abcdef0123456789abcdef.dat"),
+        "",
+    )
+    .unwrap();
+    std::fs::write(&old_layout_entry, "").unwrap();
+    std::fs::write(&unrelated, "").unwrap();
+
+    let arena = Bump::new();
+    load_and_typecheck(
+        &arena,
+        fixtures_dir()
+            .join("interface_with_deps")
+            .join("OneDep.roc"),
+        Default::default(),
+        TARGET_INFO,
+        Some(cache_dir.path().to_path_buf()),
+    )
+    .unwrap();
+
+    assert!(!other_compiler.exists());
+    assert!(!old_layout_entry.exists());
+    assert!(unrelated.exists());
+    assert_eq!(disk_cache_entries(cache_dir.path()), 2);
+}
+
+#[test]
+fn disk_cache_is_independent_of_module_ids() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/disk_cache_is_independent_of_module_ids");
+    let fixture = fixtures_dir().join("interface_with_deps");
+    let cache_entries = || disk_cache_entries(cache_dir.path());
+
+    let load = |filename: &str| {
+        let arena = Bump::new();
+        let loaded_module = load_and_typecheck(
+            &arena,
+            fixture.join(filename),
+            Default::default(),
+            TARGET_INFO,
+            Some(cache_dir.path().to_path_buf()),
+        )
+        .unwrap();
+
+        let blah_id = loaded_module
+            .interns
+            .module_ids
+            .get_id(&"Dep3.Blah".into())
+            .unwrap();
+
+        (blah_id, loaded_module)
+    };
+
+    // Caches OneDep and Dep3.Blah
+    let (blah_id_alone, _) = load("OneDep.roc");
+    assert_eq!(cache_entries(), 2);
+
+    // Dep3.Blah gets a different module id when it's loaded along with more modules,
+    // but its entry is still reused rather than written again.
+    let (blah_id_with_others, loaded_module) = load("Primary.roc");
+    assert_ne!(blah_id_alone, blah_id_with_others);
+    assert_eq!(cache_entries(), 6);
+
+    let expected_types = hashmap! {
+        "blah2" => "Float *",
+        "blah3" => "Str",
+        "str" => "Str",
+        "alwaysThree" => "* -> Float *",
+        "identity" => "a -> a",
+        "z" => "Float *",
+        "w" => "Dep1.Identity {}",
+        "succeed" => "a -> Dep1.Identity a",
+        "yay" => "Res.Res {} err",
+        "withDefault" => "Res.Res a err, a -> a",
+    };
+
+    expect_types(loaded_module, expected_types.clone());
+
+    // Now every module comes from the cache, and the imports of Primary aren't canonicalized,
+    // except for Dep2, whose unused import must be reported again.
+    let (_, loaded_module) = load("Primary.roc");
+    assert_eq!(cache_entries(), 6);
+
+    for name in ["Dep1", "Dep3.Blah", "Res"] {
+        let module_id = loaded_module
+            .interns
+            .module_ids
+            .get_id(&name.into())
+            .unwrap();
+
+        assert_eq!(
+            loaded_module.timings[&module_id].canonicalize,
+            std::time::Duration::ZERO
+        );
+    }
+
+    expect_types(loaded_module, expected_types);
+}
+
 #[test]
 fn iface_dep_types() {
     let subs_by_module = Default::default();
@@ -373,6 +373,20 @@ impl ModuleId {
         (self.0.get() - 1) as usize
     }
 
+    /// The id as a number, for storing it outside of the compiler (e.g. in the module cache).
+    /// Module ids are only meaningful within the run of the compiler that assigned them.
+    pub const fn to_u32(self) -> u32 {
+        self.0.get()
+    }
+
+    /// Recovers an id from [ModuleId::to_u32]; returns `None` for numbers it can't have returned.
+    pub const fn from_u32(id: u32) -> Option<Self> {
+        match NonZeroU32::new(id) {
+            Some(id) => Some(ModuleId(id)),
+            None => None,
+        }
+    }
+
     #[cfg(any(debug_assertions, feature = "debug-symbols"))]
     pub fn register_debug_idents(self, ident_ids: &IdentIds) {
         let mut all = DEBUG_IDENT_IDS_BY_MODULE_ID.lock().expect("Failed to acquire lock for Debug interning into DEBUG_MODULE_ID_NAMES, presumably because a thread panicked.");
@@ -612,6 +626,11 @@ impl IdentId {
     pub const fn index(self) -> usize {
         self.0 as usize
     }
+
+    /// The inverse of [IdentId::index], for reading symbols back from the module cache
+    pub const fn from_index(index: u32) -> Self {
+        IdentId(index)
+    }
 }
 
 /// Stores a mapping between Ident and IdentId.
@@ -1485,6 +1485,9 @@ pub struct Env<'a, 'i> {
     pub abilities: AbilitiesView<'i>,
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
+    /// Mark where each `when` and `if` branch starts, for `roc test --coverage`. See
+    /// [COVERAGE_HIT].
+    pub coverage: bool,
 }
 
 impl<'a, 'i> Env<'a, 'i> {
@@ -1590,6 +1593,50 @@ impl<'a> Param<'a> {
     };
 }
 
+/// The foreign function that marks where a branch starts, when [Env::coverage] is set. It is called
+/// with the module id and the start and end offsets of the branch's region, as `U32`s. Backends
+/// that don't measure coverage can ignore these calls, because they return `{}`.
+pub const COVERAGE_HIT: &str = "roc_coverage_hit";
+
+/// Marks that the code in this region is about to run, for `roc test --coverage`.
+fn with_coverage_hit<'a>(env: &mut Env<'a, '_>, region: Region, stmt: Stmt<'a>) -> Stmt<'a> {
+    if !env.coverage || region.is_empty() {
+        return stmt;
+    }
+
+    let values = [
+        env.home.to_u32(),
+        region.start().offset,
+        region.end().offset,
+    ];
+
+    let symbols = values.map(|_| env.unique_symbol());
+    let result = env.unique_symbol();
+
+    let call = Call {
+        call_type: CallType::Foreign {
+            foreign_symbol: COVERAGE_HIT.into(),
+            ret_layout: &Layout::UNIT,
+        },
+        arguments: env.arena.alloc(symbols),
+    };
+
+    let mut stmt = Stmt::Let(
+        result,
+        Expr::Call(call),
+        Layout::UNIT,
+        env.arena.alloc(stmt),
+    );
+
+    for (symbol, value) in symbols.into_iter().zip(values).rev() {
+        let literal = Expr::Literal(Literal::Int((value as i128).to_ne_bytes()));
+
+        stmt = Stmt::Let(symbol, literal, Layout::u32(), env.arena.alloc(stmt));
+    }
+
+    stmt
+}
+
 pub fn cond<'a>(
     env: &mut Env<'a, '_>,
     cond_symbol: Symbol,
@@ -4427,6 +4474,7 @@ pub fn with_hole<'a>(
                     if is_terminated {
                         let terminator = hole;
 
+                        let else_region = final_else.region;
                         let mut stmt = with_hole(
                             env,
                             final_else.value,
@@ -4436,6 +4484,7 @@ pub fn with_hole<'a>(
                             assigned,
                             terminator,
                         );
+                        stmt = with_coverage_hit(env, else_region, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = env.unique_symbol();
@@ -4449,6 +4498,7 @@ pub fn with_hole<'a>(
                                 assigned,
                                 terminator,
                             );
+                            let then = with_coverage_hit(env, loc_then.region, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4472,6 +4522,7 @@ pub fn with_hole<'a>(
                             .arena
                             .alloc(Stmt::Jump(id, env.arena.alloc([assigned_in_jump])));
 
+                        let else_region = final_else.region;
                         let mut stmt = with_hole(
                             env,
                             final_else.value,
@@ -4481,6 +4532,7 @@ pub fn with_hole<'a>(
                             assigned_in_jump,
                             terminator,
                         );
+                        stmt = with_coverage_hit(env, else_region, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -4500,6 +4552,7 @@ pub fn with_hole<'a>(
                                 assigned_in_jump,
                                 terminator,
                             );
+                            let then = with_coverage_hit(env, loc_then.region, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -6411,7 +6464,9 @@ pub fn from_can<'a>(
                 .from_var(env.arena, cond_var, env.subs)
                 .expect("invalid cond_layout");
 
+            let else_region = final_else.region;
             let mut stmt = from_can(env, branch_var, final_else.value, procs, layout_cache);
+            stmt = with_coverage_hit(env, else_region, stmt);
 
             for (loc_cond, loc_then) in branches.into_iter().rev() {
                 let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -6422,6 +6477,7 @@ pub fn from_can<'a>(
                     cond_var,
                 );
                 let then = from_can(env, branch_var, loc_then.value, procs, layout_cache);
+                let then = with_coverage_hit(env, loc_then.region, then);
 
                 stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -6595,7 +6651,7 @@ fn to_opt_branches<'a>(
 ) -> std::vec::Vec<(
     Pattern<'a>,
     Option<Loc<roc_can::expr::Expr>>,
-    roc_can::expr::Expr,
+    Loc<roc_can::expr::Expr>,
 )> {
     debug_assert!(!branches.is_empty());
 
@@ -6639,15 +6695,18 @@ fn to_opt_branches<'a>(
                         ))
                     };
 
+                    // the region of the branch body, rather than of the pattern
+                    let loc_expr = Loc::at(when_branch.value.region, loc_expr.value);
+
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
                     opt_branches.push((
                         Pattern::Underscore,
                         when_branch.guard.clone(),
-                        roc_can::expr::Expr::RuntimeError(runtime_error),
+                        Loc::at_zero(roc_can::expr::Expr::RuntimeError(runtime_error)),
                     ));
                 }
             }
@@ -6660,7 +6719,9 @@ fn to_opt_branches<'a>(
         opt_branches.push((
             Pattern::Underscore,
             None,
-            roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
+            Loc::at_zero(roc_can::expr::Expr::RuntimeError(
+                roc_problem::can::RuntimeError::NonExhaustivePattern,
+            )),
         ));
     }
 
@@ -6701,7 +6762,7 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .filter_map(|(pattern, opt_guard, can_expr)| {
+        .filter_map(|(pattern, opt_guard, loc_expr)| {
             // If the pattern has a void layout we can drop it; however, we must still perform the
             // work of building the body, because that may contain specializations we must
             // discover for use elsewhere. See
@@ -6719,15 +6780,24 @@ fn from_can_when<'a>(
             };
 
             let branch_stmt = match join_point {
-                None => from_can(env, expr_var, can_expr, procs, layout_cache),
+                None => from_can(env, expr_var, loc_expr.value, procs, layout_cache),
                 Some(id) => {
                     let symbol = env.unique_symbol();
                     let arguments = bumpalo::vec![in env.arena; symbol].into_bump_slice();
                     let jump = env.arena.alloc(Stmt::Jump(id, arguments));
 
-                    with_hole(env, can_expr, expr_var, procs, layout_cache, symbol, jump)
+                    with_hole(
+                        env,
+                        loc_expr.value,
+                        expr_var,
+                        procs,
+                        layout_cache,
+                        symbol,
+                        jump,
+                    )
                 }
             };
+            let branch_stmt = with_coverage_hit(env, loc_expr.region, branch_stmt);
 
             use crate::decision_tree::Guard;
             let result = if let Some(loc_expr) = opt_guard {
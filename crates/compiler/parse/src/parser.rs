@@ -303,6 +303,28 @@ impl<'a, T> SourceError<'a, T> {
 }
 
 impl<'a> SyntaxError<'a> {
+    /// Where the parser got stuck, if the error says. Errors that only know a position have an
+    /// empty region there.
+    pub fn region(&self) -> Option<Region> {
+        use SyntaxError::*;
+
+        let at = |position: Position| Some(Region::new(position, position));
+
+        match self {
+            Unexpected(region)
+            | Eof(region)
+            | ReservedKeyword(region)
+            | ArgumentsBeforeEquals(region) => Some(*region),
+            Type(problem) => at(problem.position()),
+            Pattern(problem) => at(problem.position()),
+            Expr(problem, _) => at(problem.position()),
+            Header(problem) => at(problem.position()),
+            NotEndOfFile(position) => at(*position),
+            OutdentedTooFar | TooManyLines | InvalidPattern | BadUtf8 | NotYetImplemented(_)
+            | Todo | Space(_) => None,
+        }
+    }
+
     pub fn into_source_error(self, state: &State<'a>) -> SourceError<'a, SyntaxError<'a>> {
         SourceError {
             problem: self,
@@ -694,6 +716,562 @@ pub enum ETypeAbilityImpl<'a> {
     IndentAmpersand(Position),
 }
 
+impl<'a> EHeader<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EHeader::Provides(problem, _) => problem.position(),
+            EHeader::Exposes(problem, _) => problem.position(),
+            EHeader::Imports(problem, _) => problem.position(),
+            EHeader::Requires(problem, _) => problem.position(),
+            EHeader::Packages(problem, _) => problem.position(),
+            EHeader::Generates(problem, _) => problem.position(),
+            EHeader::GeneratesWith(problem, _) => problem.position(),
+            EHeader::AppName(problem, _) => problem.position(),
+            EHeader::PlatformName(problem, _) => problem.position(),
+            EHeader::InconsistentModuleName(region) => region.start(),
+            EHeader::Space(_, position)
+            | EHeader::Start(position)
+            | EHeader::ModuleName(position)
+            | EHeader::IndentStart(position) => *position,
+        }
+    }
+}
+
+impl<'a> EProvides<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EProvides::Package(problem, _) => problem.position(),
+            EProvides::Provides(position)
+            | EProvides::Open(position)
+            | EProvides::To(position)
+            | EProvides::IndentProvides(position)
+            | EProvides::IndentTo(position)
+            | EProvides::IndentListStart(position)
+            | EProvides::IndentListEnd(position)
+            | EProvides::IndentPackage(position)
+            | EProvides::ListStart(position)
+            | EProvides::ListEnd(position)
+            | EProvides::Identifier(position)
+            | EProvides::Space(_, position) => *position,
+        }
+    }
+}
+
+impl EExposes {
+    pub fn position(&self) -> Position {
+        match self {
+            EExposes::Exposes(position)
+            | EExposes::Open(position)
+            | EExposes::IndentExposes(position)
+            | EExposes::IndentListStart(position)
+            | EExposes::IndentListEnd(position)
+            | EExposes::ListStart(position)
+            | EExposes::ListEnd(position)
+            | EExposes::Identifier(position)
+            | EExposes::Space(_, position) => *position,
+        }
+    }
+}
+
+impl<'a> ERequires<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ERequires::TypedIdent(problem, _) => problem.position(),
+            ERequires::Requires(position)
+            | ERequires::Open(position)
+            | ERequires::IndentRequires(position)
+            | ERequires::IndentListStart(position)
+            | ERequires::IndentListEnd(position)
+            | ERequires::ListStart(position)
+            | ERequires::ListEnd(position)
+            | ERequires::Rigid(position)
+            | ERequires::Space(_, position) => *position,
+        }
+    }
+}
+
+impl<'a> ETypedIdent<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypedIdent::Type(problem, _) => problem.position(),
+            ETypedIdent::Space(_, position)
+            | ETypedIdent::HasType(position)
+            | ETypedIdent::IndentHasType(position)
+            | ETypedIdent::Name(position)
+            | ETypedIdent::IndentType(position)
+            | ETypedIdent::Identifier(position) => *position,
+        }
+    }
+}
+
+impl<'a> EPackages<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EPackages::PackageEntry(problem, _) => problem.position(),
+            EPackages::Open(position)
+            | EPackages::Space(_, position)
+            | EPackages::Packages(position)
+            | EPackages::IndentPackages(position)
+            | EPackages::ListStart(position)
+            | EPackages::ListEnd(position)
+            | EPackages::IndentListStart(position)
+            | EPackages::IndentListEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EPackageName<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EPackageName::BadPath(problem, _) => problem.position(),
+            EPackageName::Escapes(position) | EPackageName::Multiline(position) => *position,
+        }
+    }
+}
+
+impl<'a> EPackageEntry<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EPackageEntry::BadPackage(problem, _) => problem.position(),
+            EPackageEntry::Shorthand(position)
+            | EPackageEntry::Colon(position)
+            | EPackageEntry::IndentPackage(position)
+            | EPackageEntry::Space(_, position) => *position,
+        }
+    }
+}
+
+impl EImports {
+    pub fn position(&self) -> Position {
+        match self {
+            EImports::Open(position)
+            | EImports::Imports(position)
+            | EImports::IndentImports(position)
+            | EImports::IndentListStart(position)
+            | EImports::IndentListEnd(position)
+            | EImports::ListStart(position)
+            | EImports::ListEnd(position)
+            | EImports::Identifier(position)
+            | EImports::ExposingDot(position)
+            | EImports::ShorthandDot(position)
+            | EImports::Shorthand(position)
+            | EImports::ModuleName(position)
+            | EImports::Space(_, position)
+            | EImports::IndentSetStart(position)
+            | EImports::IndentSetEnd(position)
+            | EImports::SetStart(position)
+            | EImports::SetEnd(position) => *position,
+        }
+    }
+}
+
+impl EGenerates {
+    pub fn position(&self) -> Position {
+        match self {
+            EGenerates::Open(position)
+            | EGenerates::Generates(position)
+            | EGenerates::IndentGenerates(position)
+            | EGenerates::Identifier(position)
+            | EGenerates::Space(_, position)
+            | EGenerates::IndentTypeStart(position)
+            | EGenerates::IndentTypeEnd(position) => *position,
+        }
+    }
+}
+
+impl EGeneratesWith {
+    pub fn position(&self) -> Position {
+        match self {
+            EGeneratesWith::Open(position)
+            | EGeneratesWith::With(position)
+            | EGeneratesWith::IndentWith(position)
+            | EGeneratesWith::IndentListStart(position)
+            | EGeneratesWith::IndentListEnd(position)
+            | EGeneratesWith::ListStart(position)
+            | EGeneratesWith::ListEnd(position)
+            | EGeneratesWith::Identifier(position)
+            | EGeneratesWith::Space(_, position) => *position,
+        }
+    }
+}
+
+impl<'a> EExpr<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EExpr::DefMissingFinalExpr2(problem, _) => problem.position(),
+            EExpr::Type(problem, _) => problem.position(),
+            EExpr::Pattern(problem, _) => problem.position(),
+            EExpr::Ability(problem, _) => problem.position(),
+            EExpr::When(problem, _) => problem.position(),
+            EExpr::If(problem, _) => problem.position(),
+            EExpr::Expect(problem, _) => problem.position(),
+            EExpr::Closure(problem, _) => problem.position(),
+            EExpr::InParens(problem, _) => problem.position(),
+            EExpr::Record(problem, _) => problem.position(),
+            EExpr::Str(problem, _) => problem.position(),
+            EExpr::SingleQuote(problem, _) => problem.position(),
+            EExpr::List(problem, _) => problem.position(),
+            EExpr::Start(position)
+            | EExpr::End(position)
+            | EExpr::BadExprEnd(position)
+            | EExpr::Space(_, position)
+            | EExpr::Dot(position)
+            | EExpr::Access(position)
+            | EExpr::UnaryNot(position)
+            | EExpr::UnaryNegate(position)
+            | EExpr::BadOperator(_, position)
+            | EExpr::DefMissingFinalExpr(position)
+            | EExpr::IndentDefBody(position)
+            | EExpr::IndentEquals(position)
+            | EExpr::IndentAnnotation(position)
+            | EExpr::Equals(position)
+            | EExpr::Colon(position)
+            | EExpr::DoubleColon(position)
+            | EExpr::Ident(position)
+            | EExpr::ElmStyleFunction(_, position)
+            | EExpr::MalformedPattern(position)
+            | EExpr::QualifiedTag(position)
+            | EExpr::BackpassComma(position)
+            | EExpr::BackpassArrow(position)
+            | EExpr::Underscore(position)
+            | EExpr::Number(_, position)
+            | EExpr::IndentStart(position)
+            | EExpr::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EString<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EString::Format(problem, _) => problem.position(),
+            EString::Open(position)
+            | EString::CodePtOpen(position)
+            | EString::CodePtEnd(position)
+            | EString::Space(_, position)
+            | EString::EndlessSingle(position)
+            | EString::EndlessMulti(position)
+            | EString::UnknownEscape(position)
+            | EString::FormatEnd(position)
+            | EString::MultilineInsufficientIndent(position) => *position,
+        }
+    }
+}
+
+impl<'a> ERecord<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ERecord::Expr(problem, _) => problem.position(),
+            ERecord::End(position)
+            | ERecord::Open(position)
+            | ERecord::Updateable(position)
+            | ERecord::Field(position)
+            | ERecord::Colon(position)
+            | ERecord::QuestionMark(position)
+            | ERecord::Bar(position)
+            | ERecord::Ampersand(position)
+            | ERecord::Space(_, position)
+            | ERecord::IndentOpen(position)
+            | ERecord::IndentColon(position)
+            | ERecord::IndentBar(position)
+            | ERecord::IndentAmpersand(position)
+            | ERecord::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EInParens<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EInParens::Expr(problem, _) => problem.position(),
+            EInParens::End(position)
+            | EInParens::Open(position)
+            | EInParens::Space(_, position)
+            | EInParens::IndentOpen(position)
+            | EInParens::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EClosure<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EClosure::Pattern(problem, _) => problem.position(),
+            EClosure::Body(problem, _) => problem.position(),
+            EClosure::Space(_, position)
+            | EClosure::Start(position)
+            | EClosure::Arrow(position)
+            | EClosure::Comma(position)
+            | EClosure::Arg(position)
+            | EClosure::IndentArrow(position)
+            | EClosure::IndentBody(position)
+            | EClosure::IndentArg(position) => *position,
+        }
+    }
+}
+
+impl<'a> EList<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EList::Expr(problem, _) => problem.position(),
+            EList::Open(position)
+            | EList::End(position)
+            | EList::Space(_, position)
+            | EList::IndentOpen(position)
+            | EList::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EWhen<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EWhen::Pattern(problem, _) => problem.position(),
+            EWhen::IfGuard(problem, _) => problem.position(),
+            EWhen::Condition(problem, _) => problem.position(),
+            EWhen::Branch(problem, _) => problem.position(),
+            EWhen::Space(_, position)
+            | EWhen::When(position)
+            | EWhen::Is(position)
+            | EWhen::Arrow(position)
+            | EWhen::Bar(position)
+            | EWhen::IfToken(position)
+            | EWhen::IndentIs(position)
+            | EWhen::IndentCondition(position)
+            | EWhen::IndentPattern(position)
+            | EWhen::IndentArrow(position)
+            | EWhen::IndentBranch(position)
+            | EWhen::IndentIfGuard(position)
+            | EWhen::PatternAlignment(_, position) => *position,
+        }
+    }
+}
+
+impl<'a> EAbility<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EAbility::Type(problem, _) => problem.position(),
+            EAbility::Space(_, position)
+            | EAbility::DemandAlignment(_, position)
+            | EAbility::DemandName(position)
+            | EAbility::DemandColon(position) => *position,
+        }
+    }
+}
+
+impl<'a> EIf<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EIf::Condition(problem, _) => problem.position(),
+            EIf::ThenBranch(problem, _) => problem.position(),
+            EIf::ElseBranch(problem, _) => problem.position(),
+            EIf::Space(_, position)
+            | EIf::If(position)
+            | EIf::Then(position)
+            | EIf::Else(position)
+            | EIf::IndentCondition(position)
+            | EIf::IndentIf(position)
+            | EIf::IndentThenToken(position)
+            | EIf::IndentElseToken(position)
+            | EIf::IndentThenBranch(position)
+            | EIf::IndentElseBranch(position) => *position,
+        }
+    }
+}
+
+impl<'a> EExpect<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EExpect::Condition(problem, _) => problem.position(),
+            EExpect::Continuation(problem, _) => problem.position(),
+            EExpect::Space(_, position)
+            | EExpect::Expect(position)
+            | EExpect::IndentCondition(position) => *position,
+        }
+    }
+}
+
+impl<'a> EPattern<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EPattern::Record(problem, _) => problem.position(),
+            EPattern::List(problem, _) => problem.position(),
+            EPattern::PInParens(problem, _) => problem.position(),
+            EPattern::Underscore(position)
+            | EPattern::Start(position)
+            | EPattern::End(position)
+            | EPattern::Space(_, position)
+            | EPattern::NumLiteral(_, position)
+            | EPattern::IndentStart(position)
+            | EPattern::IndentEnd(position)
+            | EPattern::AsIndentStart(position) => *position,
+        }
+    }
+}
+
+impl<'a> PRecord<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            PRecord::Pattern(problem, _) => problem.position(),
+            PRecord::Expr(problem, _) => problem.position(),
+            PRecord::End(position)
+            | PRecord::Open(position)
+            | PRecord::Field(position)
+            | PRecord::Colon(position)
+            | PRecord::Optional(position)
+            | PRecord::Space(_, position)
+            | PRecord::IndentOpen(position)
+            | PRecord::IndentColon(position)
+            | PRecord::IndentOptional(position)
+            | PRecord::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> PList<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            PList::Pattern(problem, _) => problem.position(),
+            PList::End(position)
+            | PList::Open(position)
+            | PList::Rest(position)
+            | PList::Space(_, position)
+            | PList::IndentOpen(position)
+            | PList::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> PInParens<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            PInParens::Pattern(problem, _) => problem.position(),
+            PInParens::End(position)
+            | PInParens::Open(position)
+            | PInParens::Space(_, position)
+            | PInParens::IndentOpen(position)
+            | PInParens::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> EType<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            EType::TRecord(problem, _) => problem.position(),
+            EType::TTagUnion(problem, _) => problem.position(),
+            EType::TInParens(problem, _) => problem.position(),
+            EType::TApply(problem, _) => problem.position(),
+            EType::TInlineAlias(problem, _) => problem.position(),
+            EType::TAbilityImpl(problem, _) => problem.position(),
+            EType::Space(_, position)
+            | EType::TBadTypeVariable(position)
+            | EType::TWildcard(position)
+            | EType::TInferred(position)
+            | EType::TStart(position)
+            | EType::TEnd(position)
+            | EType::TFunctionArgument(position)
+            | EType::TWhereBar(position)
+            | EType::THasClause(position)
+            | EType::TIndentStart(position)
+            | EType::TIndentEnd(position)
+            | EType::TAsIndentStart(position) => *position,
+        }
+    }
+}
+
+impl<'a> ETypeRecord<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeRecord::Type(problem, _) => problem.position(),
+            ETypeRecord::End(position)
+            | ETypeRecord::Open(position)
+            | ETypeRecord::Field(position)
+            | ETypeRecord::Colon(position)
+            | ETypeRecord::Optional(position)
+            | ETypeRecord::Space(_, position)
+            | ETypeRecord::IndentOpen(position)
+            | ETypeRecord::IndentColon(position)
+            | ETypeRecord::IndentOptional(position)
+            | ETypeRecord::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> ETypeTagUnion<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeTagUnion::Type(problem, _) => problem.position(),
+            ETypeTagUnion::End(position)
+            | ETypeTagUnion::Open(position)
+            | ETypeTagUnion::Space(_, position)
+            | ETypeTagUnion::IndentOpen(position)
+            | ETypeTagUnion::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl<'a> ETypeInParens<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeInParens::Type(problem, _) => problem.position(),
+            ETypeInParens::End(position)
+            | ETypeInParens::Open(position)
+            | ETypeInParens::Space(_, position)
+            | ETypeInParens::IndentOpen(position)
+            | ETypeInParens::IndentEnd(position) => *position,
+        }
+    }
+}
+
+impl ETypeApply {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeApply::StartNotUppercase(position)
+            | ETypeApply::End(position)
+            | ETypeApply::Space(_, position)
+            | ETypeApply::DoubleDot(position)
+            | ETypeApply::TrailingDot(position)
+            | ETypeApply::StartIsNumber(position) => *position,
+        }
+    }
+}
+
+impl ETypeInlineAlias {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeInlineAlias::NotAnAlias(position)
+            | ETypeInlineAlias::Qualified(position)
+            | ETypeInlineAlias::ArgumentNotLowercase(position) => *position,
+        }
+    }
+}
+
+impl<'a> ETypeAbilityImpl<'a> {
+    pub fn position(&self) -> Position {
+        match self {
+            ETypeAbilityImpl::Type(problem, _) => problem.position(),
+            ETypeAbilityImpl::Expr(problem, _) => problem.position(),
+            ETypeAbilityImpl::End(position)
+            | ETypeAbilityImpl::Open(position)
+            | ETypeAbilityImpl::Field(position)
+            | ETypeAbilityImpl::Colon(position)
+            | ETypeAbilityImpl::Optional(position)
+            | ETypeAbilityImpl::Space(_, position)
+            | ETypeAbilityImpl::IndentOpen(position)
+            | ETypeAbilityImpl::IndentColon(position)
+            | ETypeAbilityImpl::IndentOptional(position)
+            | ETypeAbilityImpl::IndentEnd(position)
+            | ETypeAbilityImpl::Updateable(position)
+            | ETypeAbilityImpl::QuestionMark(position)
+            | ETypeAbilityImpl::Bar(position)
+            | ETypeAbilityImpl::Ampersand(position)
+            | ETypeAbilityImpl::IndentBar(position)
+            | ETypeAbilityImpl::IndentAmpersand(position) => *position,
+        }
+    }
+}
+
 impl<'a> From<ERecord<'a>> for ETypeAbilityImpl<'a> {
     fn from(e: ERecord<'a>) -> Self {
         match e {
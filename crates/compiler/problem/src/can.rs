@@ -190,6 +190,106 @@ pub enum Problem {
     },
 }
 
+impl Problem {
+    /// The region of source code this problem is primarily about, if there is one.
+    ///
+    /// Editors and other tools use this to attach the problem to a location; the rendered
+    /// report may mention further regions.
+    pub fn region(&self) -> Option<Region> {
+        use Problem::*;
+
+        match self {
+            UnusedDef(_, region)
+            | UnusedImport(_, region)
+            | UnusedModuleImport(_, region)
+            | UnusedArgument(_, _, _, region)
+            | UnusedBranchDef(_, region)
+            | UnsupportedPattern(_, region)
+            | CyclicAlias(_, region, _, _)
+            | InvalidInterpolation(region)
+            | InvalidHexadecimal(region)
+            | InvalidUnicodeCodePt(region)
+            | AbilityUsedAsType(_, _, region)
+            | NestedSpecialization(_, region)
+            | IllegalDerivedAbility(region)
+            | NotAnAbility(region)
+            | NoIdentifiersIntroduced(region)
+            | Problem::PrecedenceProblem(self::PrecedenceProblem::BothNonAssociative(
+                region,
+                _,
+                _,
+            ))
+            | PhantomTypeArgument {
+                variable_region: region,
+                ..
+            }
+            | UnboundTypeVariable {
+                one_occurrence: region,
+                ..
+            }
+            | DuplicateRecordFieldValue {
+                field_region: region,
+                ..
+            }
+            | DuplicateRecordFieldType {
+                field_region: region,
+                ..
+            }
+            | InvalidOptionalValue {
+                field_region: region,
+                ..
+            }
+            | DuplicateTag {
+                tag_region: region, ..
+            }
+            | SignatureDefMismatch {
+                def_pattern: region,
+                ..
+            }
+            | InvalidAliasRigid { region, .. }
+            | NestedDatatype {
+                differing_recursion_region: region,
+                ..
+            }
+            | InvalidExtensionType { region, .. }
+            | AbilityHasTypeVariables {
+                variables_region: region,
+                ..
+            }
+            | HasClauseIsNotAbility { region }
+            | IllegalHasClause { region }
+            | DuplicateHasAbility { region, .. }
+            | AbilityMemberMissingHasClause { region, .. }
+            | AbilityMemberMultipleBoundVars {
+                span_has_clauses: region,
+                ..
+            }
+            | AbilityNotOnToplevel { region }
+            | ImplementationNotFound { region, .. }
+            | NotAnAbilityMember { region, .. }
+            | OptionalAbilityImpl { region, .. }
+            | QualifiedAbilityImpl { region }
+            | AbilityImplNotIdent { region }
+            | DuplicateImpl {
+                duplicate: region, ..
+            }
+            | ImplementsNonRequired { region, .. }
+            | DoesNotImplementAbility { region, .. }
+            | NotBoundInAllPatterns { region, .. }
+            | OverloadedSpecialization {
+                overload: region, ..
+            }
+            | UnnecessaryOutputWildcard { region }
+            | MultipleListRestPattern { region } => Some(*region),
+            Shadowing { shadow, .. } => Some(shadow.region),
+            UnknownGeneratesWith(loc_ident) => Some(loc_ident.region),
+            BadRecursion(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            Problem::RuntimeError(runtime_error) => runtime_error.region(),
+            ExposedButNotDefined(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ExtensionTypeKind {
     Record,
@@ -353,6 +453,50 @@ pub enum RuntimeError {
 }
 
 impl RuntimeError {
+    /// The region of source code this error is primarily about, if there is one.
+    pub fn region(&self) -> Option<Region> {
+        use RuntimeError::*;
+
+        match self {
+            Shadowing { shadow, .. } => Some(shadow.region),
+            InvalidOptionalValue { field_region, .. } => Some(*field_region),
+            UnsupportedPattern(region)
+            | MalformedPattern(_, region)
+            | OpaqueAppliedToMultipleArgs(region)
+            | ValueNotExposed { region, .. }
+            | ModuleNotImported { region, .. }
+            | InvalidPrecedence(_, region)
+            | MalformedIdentifier(_, _, region)
+            | MalformedTypeName(_, region)
+            | MalformedClosure(region)
+            | InvalidRecordUpdate { region }
+            | InvalidFloat(_, region, _)
+            | InvalidInt(_, _, region, _)
+            | InvalidInterpolation(region)
+            | InvalidHexadecimal(region)
+            | InvalidUnicodeCodePt(region)
+            | EmptySingleQuote(region)
+            | MultipleCharsInSingleQuote(region)
+            | DegenerateBranch(region) => Some(*region),
+            OpaqueOutsideScope {
+                referenced_region, ..
+            } => Some(*referenced_region),
+            LookupNotInScope(loc_ident, _)
+            | OpaqueNotDefined {
+                usage: loc_ident, ..
+            }
+            | OpaqueNotApplied(loc_ident) => Some(loc_ident.region),
+            CircularDef(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            UnresolvedTypeVar
+            | ErroneousType
+            | NonExhaustivePattern
+            | NoImplementationNamed { .. }
+            | NoImplementation
+            | VoidValue
+            | ExposedButNotDefined(_) => None,
+        }
+    }
+
     pub fn runtime_message(self) -> String {
         use RuntimeError::*;
 
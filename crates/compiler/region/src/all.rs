@@ -22,6 +22,11 @@ impl Region {
         self.start <= other.start && self.end >= other.end
     }
 
+    /// Whether `pos` lies within this region, counting a position right at the end as inside.
+    pub fn contains_pos(&self, pos: Position) -> bool {
+        self.start <= pos && pos <= self.end
+    }
+
     pub fn is_empty(&self) -> bool {
         self.start == self.end
     }
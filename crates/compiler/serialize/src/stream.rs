@@ -0,0 +1,131 @@
+//! Serialization of tree-shaped data, like canonical types, that can't be written as flat slices.
+//!
+//! Values are written one after the other in little-endian order, and must be read back in the
+//! same order. Reading never panics: running out of bytes, or finding an invalid string, makes
+//! every read from then on return `None`.
+
+#[derive(Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.bytes.extend(value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes.extend(value.to_le_bytes());
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    /// The length of a collection, to be followed by its elements
+    pub fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.bytes.extend(bytes);
+    }
+
+    pub fn str(&mut self, string: &str) {
+        self.bytes(string.as_bytes());
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[derive(Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if n > self.bytes.len() {
+            self.bytes = &[];
+            return None;
+        }
+
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+
+        Some(taken)
+    }
+
+    pub fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    pub fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    pub fn len(&mut self) -> Option<usize> {
+        let len = self.u32()? as usize;
+
+        // Every element takes at least one byte, so this can't be a valid length;
+        // checking it here keeps a corrupt length from causing a huge allocation.
+        if len > self.bytes.len() {
+            return None;
+        }
+
+        Some(len)
+    }
+
+    pub fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.len()?;
+
+        self.take(len)
+    }
+
+    pub fn str(&mut self) -> Option<&'a str> {
+        std::str::from_utf8(self.bytes()?).ok()
+    }
+
+    /// Reads a collection written as its length followed by its elements
+    pub fn many<T>(&mut self, mut read: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.len()?;
+        let mut items = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            items.push(read(self)?);
+        }
+
+        Some(items)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
@@ -31,6 +31,53 @@ pub enum TypeError {
     },
 }
 
+impl TypeError {
+    /// The region of source code this error is primarily about, if there is one.
+    pub fn region(&self) -> Option<Region> {
+        use TypeError::*;
+
+        match self {
+            BadExpr(region, ..)
+            | BadPattern(region, ..)
+            | CircularType(region, ..)
+            | BadExprMissingAbility(region, ..)
+            | BadPatternMissingAbility(region, ..)
+            | StructuralSpecialization { region, .. }
+            | WrongSpecialization { region, .. } => Some(*region),
+            CircularDef(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            BadType(problem) => {
+                use roc_types::types::Problem::*;
+
+                match problem {
+                    CircularType(_, _, region)
+                    | CyclicAlias(_, region, _)
+                    | Shadowed(region, _)
+                    | BadTypeArguments { region, .. }
+                    | HasClauseIsNotAbility(region) => Some(*region),
+                    CanonicalizationProblem
+                    | UnrecognizedIdent(_)
+                    | InvalidModule
+                    | SolvedTypeError => None,
+                }
+            }
+            UnfulfilledAbility(Unfulfilled::OpaqueUnderivable { derive_region, .. }) => {
+                Some(*derive_region)
+            }
+            UnfulfilledAbility(_) | UnexposedLookup(_) => None,
+            Exhaustive(error) => {
+                use roc_exhaustive::Error::*;
+
+                match error {
+                    Incomplete(region, ..) => Some(*region),
+                    Redundant { branch_region, .. } | Unmatchable { branch_region, .. } => {
+                        Some(*branch_region)
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum Unfulfilled {
     /// No claimed implementation of an ability for an opaque type.
@@ -412,8 +412,8 @@ fn check_derived_typechecks_and_golden(
         let filename = PathBuf::from("Test.roc");
         let lines = LineInfo::new(" ");
         let src_lines = vec![" "];
-        let mut reports = Vec::new();
         let alloc = RocDocAllocator::new(&src_lines, test_module, interns);
+        let mut reports = Vec::new();
 
         for problem in problems.into_iter() {
             if let Some(report) = type_problem(&alloc, &lines, filename.clone(), problem.clone()) {
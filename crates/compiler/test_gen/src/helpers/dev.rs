@@ -55,6 +55,8 @@ pub fn helper(
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
+        coverage: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -182,6 +184,7 @@ pub fn helper(
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators: true, // Needed for testing, since we don't have a platform
+        abort_on_panic: false,
     };
 
     let target = target_lexicon::Triple::host();
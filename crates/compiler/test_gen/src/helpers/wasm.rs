@@ -89,6 +89,8 @@ fn compile_roc_to_wasm_bytes<'a, T: Wasm32Result>(
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
+        coverage: false,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -1,7 +1,7 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 use crate::types::{
     name_type_var, AbilitySet, AliasKind, ErrorType, Polarity, Problem, RecordField,
-    RecordFieldsError, TypeExt, Uls,
+    RecordFieldsError, RemapSymbol, TypeExt, Uls,
 };
 use roc_collections::all::{FnvMap, ImMap, ImSet, MutSet, SendMap};
 use roc_collections::{VecMap, VecSet};
@@ -119,6 +119,20 @@ struct SerializedTagName(SubsSlice<u8>);
 use roc_serialize::bytes;
 
 impl Subs {
+    /// Replaces the symbols in these subs, when reading them back from the module cache
+    /// with different module ids. Returns `None` if `remap` does for any of them.
+    pub fn remap_symbols(&mut self, remap: RemapSymbol) -> Option<()> {
+        for symbol in self.symbol_names.iter_mut() {
+            *symbol = remap(*symbol)?;
+        }
+
+        for Uls(_, member, _) in self.unspecialized_lambda_sets.iter_mut() {
+            *member = remap(*member)?;
+        }
+
+        self.utable.remap_symbols(remap)
+    }
+
     pub fn serialize(
         &self,
         exposed_vars_by_symbol: &[(Symbol, Variable)],
@@ -1,4 +1,4 @@
-use crate::num::NumericRange;
+use crate::num::{IntLitWidth, NumericRange};
 use crate::pretty_print::Parens;
 use crate::subs::{
     GetSubsSlice, RecordFields, Subs, UnionTags, VarStore, Variable, VariableSubsSlice,
@@ -8,8 +8,9 @@ use roc_error_macros::internal_error;
 use roc_module::called_via::CalledVia;
 use roc_module::ident::{ForeignSymbol, Ident, Lowercase, TagName};
 use roc_module::low_level::LowLevel;
-use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_region::all::{Loc, Region};
+use roc_module::symbol::{IdentId, Interns, ModuleId, Symbol};
+use roc_region::all::{Loc, Position, Region};
+use roc_serialize::stream::{Reader, Writer};
 use std::fmt;
 use std::fmt::Write;
 
@@ -3242,6 +3243,512 @@ fn instantiate_lambda_sets_as_unspecialized(
     }
 }
 
+/// Translates a symbol read back from the module cache to the module ids of the current run of
+/// the compiler, or returns `None` if it belongs to a module this run doesn't know.
+pub type RemapSymbol<'a> = &'a dyn Fn(Symbol) -> Option<Symbol>;
+
+pub fn write_symbol(w: &mut Writer, symbol: Symbol) {
+    w.u32(symbol.module_id().to_u32());
+    w.u32(symbol.ident_id().index() as u32);
+}
+
+pub fn read_symbol(r: &mut Reader, remap: RemapSymbol) -> Option<Symbol> {
+    let module_id = ModuleId::from_u32(r.u32()?)?;
+    let ident_id = IdentId::from_index(r.u32()?);
+
+    remap(Symbol::new(module_id, ident_id))
+}
+
+pub fn write_region(w: &mut Writer, region: Region) {
+    w.u32(region.start().offset);
+    w.u32(region.end().offset);
+}
+
+pub fn read_region(r: &mut Reader) -> Option<Region> {
+    let start = Position::new(r.u32()?);
+    let end = Position::new(r.u32()?);
+
+    Some(Region::new(start, end))
+}
+
+pub fn write_variable(w: &mut Writer, var: Variable) {
+    w.u32(var.index());
+}
+
+pub fn read_variable(r: &mut Reader) -> Option<Variable> {
+    // Safety: variables are only written for the subs they are read back with
+    Some(unsafe { Variable::from_index(r.u32()?) })
+}
+
+fn write_ability_set(w: &mut Writer, abilities: &Option<AbilitySet>) {
+    match abilities {
+        None => w.bool(false),
+        Some(AbilitySet(abilities)) => {
+            w.bool(true);
+            w.len(abilities.len());
+            abilities
+                .iter()
+                .for_each(|ability| write_symbol(w, *ability));
+        }
+    }
+}
+
+fn read_ability_set(r: &mut Reader, remap: RemapSymbol) -> Option<Option<AbilitySet>> {
+    if !r.bool()? {
+        return Some(None);
+    }
+
+    let abilities = r.many(|r| read_symbol(r, remap))?;
+
+    Some(Some(abilities.into_iter().collect()))
+}
+
+const INT_LIT_WIDTHS: [IntLitWidth; 14] = [
+    IntLitWidth::U8,
+    IntLitWidth::U16,
+    IntLitWidth::U32,
+    IntLitWidth::U64,
+    IntLitWidth::U128,
+    IntLitWidth::I8,
+    IntLitWidth::I16,
+    IntLitWidth::I32,
+    IntLitWidth::I64,
+    IntLitWidth::I128,
+    IntLitWidth::Nat,
+    IntLitWidth::F32,
+    IntLitWidth::F64,
+    IntLitWidth::Dec,
+];
+
+fn write_numeric_range(w: &mut Writer, range: NumericRange) {
+    let (tag, width) = match range {
+        NumericRange::IntAtLeastSigned(width) => (0, width),
+        NumericRange::IntAtLeastEitherSign(width) => (1, width),
+        NumericRange::NumAtLeastSigned(width) => (2, width),
+        NumericRange::NumAtLeastEitherSign(width) => (3, width),
+    };
+
+    w.u8(tag);
+    w.u8(INT_LIT_WIDTHS
+        .iter()
+        .position(|known| *known == width)
+        .unwrap() as u8);
+}
+
+fn read_numeric_range(r: &mut Reader) -> Option<NumericRange> {
+    let tag = r.u8()?;
+    let width = *INT_LIT_WIDTHS.get(r.u8()? as usize)?;
+
+    match tag {
+        0 => Some(NumericRange::IntAtLeastSigned(width)),
+        1 => Some(NumericRange::IntAtLeastEitherSign(width)),
+        2 => Some(NumericRange::NumAtLeastSigned(width)),
+        3 => Some(NumericRange::NumAtLeastEitherSign(width)),
+        _ => None,
+    }
+}
+
+fn write_alias_kind(w: &mut Writer, kind: AliasKind) {
+    w.bool(matches!(kind, AliasKind::Opaque));
+}
+
+fn read_alias_kind(r: &mut Reader) -> Option<AliasKind> {
+    match r.bool()? {
+        false => Some(AliasKind::Structural),
+        true => Some(AliasKind::Opaque),
+    }
+}
+
+fn write_types<'t>(w: &mut Writer, types: impl ExactSizeIterator<Item = &'t Type>) -> Option<()> {
+    w.len(types.len());
+    types.map(|typ| typ.write(w)).collect()
+}
+
+fn read_types(r: &mut Reader, remap: RemapSymbol) -> Option<Vec<Type>> {
+    r.many(|r| Type::read(r, remap))
+}
+
+fn write_lambda_sets(w: &mut Writer, lambda_sets: &[LambdaSet]) -> Option<()> {
+    write_types(w, lambda_sets.iter().map(LambdaSet::as_inner))
+}
+
+fn read_lambda_sets(r: &mut Reader, remap: RemapSymbol) -> Option<Vec<LambdaSet>> {
+    r.many(|r| Type::read(r, remap).map(LambdaSet))
+}
+
+fn write_union_tags(w: &mut Writer, tags: &[(TagName, Vec<Type>)]) -> Option<()> {
+    w.len(tags.len());
+
+    for (TagName(name), args) in tags {
+        w.str(name.as_str());
+        write_types(w, args.iter())?;
+    }
+
+    Some(())
+}
+
+fn read_union_tags(r: &mut Reader, remap: RemapSymbol) -> Option<Vec<(TagName, Vec<Type>)>> {
+    r.many(|r| {
+        let name = TagName(r.str()?.into());
+        let args = read_types(r, remap)?;
+
+        Some((name, args))
+    })
+}
+
+fn write_ext(w: &mut Writer, ext: &TypeExtension) -> Option<()> {
+    match ext {
+        TypeExtension::Closed => {
+            w.bool(false);
+
+            Some(())
+        }
+        TypeExtension::Open(ext) => {
+            w.bool(true);
+            ext.write(w)
+        }
+    }
+}
+
+fn read_ext(r: &mut Reader, remap: RemapSymbol) -> Option<TypeExtension> {
+    match r.bool()? {
+        false => Some(TypeExtension::Closed),
+        true => Some(TypeExtension::Open(Box::new(Type::read(r, remap)?))),
+    }
+}
+
+fn write_opt_able_type(w: &mut Writer, opt_able: &OptAbleType) -> Option<()> {
+    opt_able.typ.write(w)?;
+    write_ability_set(w, &opt_able.opt_abilities);
+
+    Some(())
+}
+
+fn read_opt_able_type(r: &mut Reader, remap: RemapSymbol) -> Option<OptAbleType> {
+    Some(OptAbleType {
+        typ: Type::read(r, remap)?,
+        opt_abilities: read_ability_set(r, remap)?,
+    })
+}
+
+impl Type {
+    /// Writes the type to the module cache. Returns `None` if it contains a type error,
+    /// which is never cached.
+    pub fn write(&self, w: &mut Writer) -> Option<()> {
+        match self {
+            Type::EmptyRec => w.u8(0),
+            Type::EmptyTagUnion => w.u8(1),
+            Type::Function(args, closure, ret) => {
+                w.u8(2);
+                write_types(w, args.iter())?;
+                closure.write(w)?;
+                ret.write(w)?;
+            }
+            Type::Record(fields, ext) => {
+                w.u8(3);
+                w.len(fields.len());
+
+                for (name, field) in fields {
+                    let (kind, typ) = match field {
+                        RecordField::Demanded(typ) => (0, typ),
+                        RecordField::Required(typ) => (1, typ),
+                        RecordField::Optional(typ) => (2, typ),
+                        RecordField::RigidRequired(typ) => (3, typ),
+                        RecordField::RigidOptional(typ) => (4, typ),
+                    };
+
+                    w.str(name.as_str());
+                    w.u8(kind);
+                    typ.write(w)?;
+                }
+
+                write_ext(w, ext)?;
+            }
+            Type::TagUnion(tags, ext) => {
+                w.u8(4);
+                write_union_tags(w, tags)?;
+                write_ext(w, ext)?;
+            }
+            Type::FunctionOrTagUnion(TagName(name), symbol, ext) => {
+                w.u8(5);
+                w.str(name.as_str());
+                write_symbol(w, *symbol);
+                write_ext(w, ext)?;
+            }
+            Type::ClosureTag {
+                name,
+                captures,
+                ambient_function,
+            } => {
+                w.u8(6);
+                write_symbol(w, *name);
+                write_types(w, captures.iter())?;
+                write_variable(w, *ambient_function);
+            }
+            Type::UnspecializedLambdaSet {
+                unspecialized: Uls(var, member, region),
+            } => {
+                w.u8(7);
+                write_variable(w, *var);
+                write_symbol(w, *member);
+                w.u8(*region);
+            }
+            Type::DelayedAlias(AliasCommon {
+                symbol,
+                type_arguments,
+                lambda_set_variables,
+                infer_ext_in_output_types,
+            }) => {
+                w.u8(8);
+                write_symbol(w, *symbol);
+                w.len(type_arguments.len());
+
+                for argument in type_arguments {
+                    write_region(w, argument.region);
+                    write_opt_able_type(w, &argument.value)?;
+                }
+
+                write_lambda_sets(w, lambda_set_variables)?;
+                write_types(w, infer_ext_in_output_types.iter())?;
+            }
+            Type::Alias {
+                symbol,
+                type_arguments,
+                lambda_set_variables,
+                infer_ext_in_output_types,
+                actual,
+                kind,
+            } => {
+                w.u8(9);
+                write_symbol(w, *symbol);
+                w.len(type_arguments.len());
+
+                for argument in type_arguments {
+                    write_opt_able_type(w, argument)?;
+                }
+
+                write_lambda_sets(w, lambda_set_variables)?;
+                write_types(w, infer_ext_in_output_types.iter())?;
+                actual.write(w)?;
+                write_alias_kind(w, *kind);
+            }
+            Type::HostExposedAlias {
+                name,
+                type_arguments,
+                lambda_set_variables,
+                actual_var,
+                actual,
+            } => {
+                w.u8(10);
+                write_symbol(w, *name);
+                write_types(w, type_arguments.iter())?;
+                write_lambda_sets(w, lambda_set_variables)?;
+                write_variable(w, *actual_var);
+                actual.write(w)?;
+            }
+            Type::RecursiveTagUnion(rec_var, tags, ext) => {
+                w.u8(11);
+                write_variable(w, *rec_var);
+                write_union_tags(w, tags)?;
+                write_ext(w, ext)?;
+            }
+            Type::Apply(symbol, args, region) => {
+                w.u8(12);
+                write_symbol(w, *symbol);
+                w.len(args.len());
+
+                for arg in args {
+                    write_region(w, arg.region);
+                    arg.value.write(w)?;
+                }
+
+                write_region(w, *region);
+            }
+            Type::Variable(var) => {
+                w.u8(13);
+                write_variable(w, *var);
+            }
+            Type::RangedNumber(range) => {
+                w.u8(14);
+                write_numeric_range(w, *range);
+            }
+            Type::Erroneous(_) => return None,
+        }
+
+        Some(())
+    }
+
+    pub fn read(r: &mut Reader, remap: RemapSymbol) -> Option<Type> {
+        let typ = match r.u8()? {
+            0 => Type::EmptyRec,
+            1 => Type::EmptyTagUnion,
+            2 => {
+                let args = read_types(r, remap)?;
+                let closure = Type::read(r, remap)?;
+                let ret = Type::read(r, remap)?;
+
+                Type::Function(args, Box::new(closure), Box::new(ret))
+            }
+            3 => {
+                let fields = r.many(|r| {
+                    let name = Lowercase::from(r.str()?);
+                    let kind = r.u8()?;
+                    let typ = Type::read(r, remap)?;
+
+                    let field = match kind {
+                        0 => RecordField::Demanded(typ),
+                        1 => RecordField::Required(typ),
+                        2 => RecordField::Optional(typ),
+                        3 => RecordField::RigidRequired(typ),
+                        4 => RecordField::RigidOptional(typ),
+                        _ => return None,
+                    };
+
+                    Some((name, field))
+                })?;
+
+                Type::Record(fields.into_iter().collect(), read_ext(r, remap)?)
+            }
+            4 => Type::TagUnion(read_union_tags(r, remap)?, read_ext(r, remap)?),
+            5 => {
+                let name = TagName(r.str()?.into());
+                let symbol = read_symbol(r, remap)?;
+
+                Type::FunctionOrTagUnion(name, symbol, read_ext(r, remap)?)
+            }
+            6 => Type::ClosureTag {
+                name: read_symbol(r, remap)?,
+                captures: read_types(r, remap)?,
+                ambient_function: read_variable(r)?,
+            },
+            7 => {
+                let var = read_variable(r)?;
+                let member = read_symbol(r, remap)?;
+                let region = r.u8()?;
+
+                Type::UnspecializedLambdaSet {
+                    unspecialized: Uls(var, member, region),
+                }
+            }
+            8 => Type::DelayedAlias(AliasCommon {
+                symbol: read_symbol(r, remap)?,
+                type_arguments: r.many(|r| {
+                    let region = read_region(r)?;
+
+                    Some(Loc::at(region, read_opt_able_type(r, remap)?))
+                })?,
+                lambda_set_variables: read_lambda_sets(r, remap)?,
+                infer_ext_in_output_types: read_types(r, remap)?,
+            }),
+            9 => Type::Alias {
+                symbol: read_symbol(r, remap)?,
+                type_arguments: r.many(|r| read_opt_able_type(r, remap))?,
+                lambda_set_variables: read_lambda_sets(r, remap)?,
+                infer_ext_in_output_types: read_types(r, remap)?,
+                actual: Box::new(Type::read(r, remap)?),
+                kind: read_alias_kind(r)?,
+            },
+            10 => Type::HostExposedAlias {
+                name: read_symbol(r, remap)?,
+                type_arguments: read_types(r, remap)?,
+                lambda_set_variables: read_lambda_sets(r, remap)?,
+                actual_var: read_variable(r)?,
+                actual: Box::new(Type::read(r, remap)?),
+            },
+            11 => {
+                let rec_var = read_variable(r)?;
+                let tags = read_union_tags(r, remap)?;
+
+                Type::RecursiveTagUnion(rec_var, tags, read_ext(r, remap)?)
+            }
+            12 => {
+                let symbol = read_symbol(r, remap)?;
+                let args = r.many(|r| {
+                    let region = read_region(r)?;
+
+                    Some(Loc::at(region, Type::read(r, remap)?))
+                })?;
+
+                Type::Apply(symbol, args, read_region(r)?)
+            }
+            13 => Type::Variable(read_variable(r)?),
+            14 => Type::RangedNumber(read_numeric_range(r)?),
+            _ => return None,
+        };
+
+        Some(typ)
+    }
+}
+
+impl Alias {
+    /// Writes the alias to the module cache. Returns `None` if it contains a type error.
+    pub fn write(&self, w: &mut Writer) -> Option<()> {
+        let Alias {
+            region,
+            type_variables,
+            lambda_set_variables,
+            infer_ext_in_output_variables,
+            recursion_variables,
+            typ,
+            kind,
+        } = self;
+
+        write_region(w, *region);
+        w.len(type_variables.len());
+
+        for type_variable in type_variables {
+            let AliasVar {
+                name,
+                var,
+                opt_bound_abilities,
+            } = &type_variable.value;
+
+            write_region(w, type_variable.region);
+            w.str(name.as_str());
+            write_variable(w, *var);
+            write_ability_set(w, opt_bound_abilities);
+        }
+
+        write_lambda_sets(w, lambda_set_variables)?;
+
+        w.len(infer_ext_in_output_variables.len());
+        for var in infer_ext_in_output_variables {
+            write_variable(w, *var);
+        }
+
+        w.len(recursion_variables.len());
+        for var in recursion_variables {
+            write_variable(w, *var);
+        }
+
+        typ.write(w)?;
+        write_alias_kind(w, *kind);
+
+        Some(())
+    }
+
+    pub fn read(r: &mut Reader, remap: RemapSymbol) -> Option<Alias> {
+        Some(Alias {
+            region: read_region(r)?,
+            type_variables: r.many(|r| {
+                let region = read_region(r)?;
+                let type_variable = AliasVar {
+                    name: Lowercase::from(r.str()?),
+                    var: read_variable(r)?,
+                    opt_bound_abilities: read_ability_set(r, remap)?,
+                };
+
+                Some(Loc::at(region, type_variable))
+            })?,
+            lambda_set_variables: read_lambda_sets(r, remap)?,
+            infer_ext_in_output_variables: r.many(read_variable)?,
+            recursion_variables: r.many(read_variable)?.into_iter().collect(),
+            typ: Type::read(r, remap)?,
+            kind: read_alias_kind(r)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,6 +1,9 @@
 use std::hint::unreachable_unchecked;
 
-use crate::subs::{Content, Descriptor, Mark, OptVariable, Rank, Variable, VariableSubsSlice};
+use crate::subs::{
+    Content, Descriptor, FlatType, Mark, OptVariable, Rank, Variable, VariableSubsSlice,
+};
+use crate::types::RemapSymbol;
 use roc_serialize::bytes;
 
 #[derive(Clone, Default)]
@@ -372,6 +375,20 @@ impl UnificationTable {
         self.set(key, desc.content, desc.rank, desc.mark, desc.copy);
     }
 
+    /// See [Subs::remap_symbols](crate::subs::Subs::remap_symbols)
+    pub(crate) fn remap_symbols(&mut self, remap: RemapSymbol) -> Option<()> {
+        for content in self.contents.iter_mut() {
+            match content {
+                Content::Alias(symbol, ..) | Content::Structure(FlatType::Apply(symbol, _)) => {
+                    *symbol = remap(*symbol)?;
+                }
+                _ => {}
+            }
+        }
+
+        Some(())
+    }
+
     pub(crate) fn serialize(
         &self,
         writer: &mut impl std::io::Write,
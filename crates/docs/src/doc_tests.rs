@@ -0,0 +1,226 @@
+//! Doc tests: the ```roc code blocks in the doc comments of a module's exposed defs,
+//! which `roc test --doc` runs as top-level `expect`s.
+
+use crate::exposed_names;
+use roc_load::docs::{DocEntry, ModuleDocumentation};
+use roc_load::LoadedModule;
+use roc_module::symbol::ModuleId;
+use std::path::{Component, Path, PathBuf};
+
+/// A ```roc code block from the docs of an exposed def.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocTest {
+    /// The def whose docs the code block is in, e.g. `map`
+    pub def_name: String,
+    pub module_name: String,
+    pub path: PathBuf,
+    /// Where the code block's first line of code is in the doc comment. 1-based, like in reports.
+    pub line: u32,
+    /// 1-based, like in reports.
+    pub column: u32,
+    /// The code, without the `##` in front of each line
+    pub code: String,
+}
+
+impl DocTest {
+    /// e.g. `src/Thing.roc:12:4`
+    pub fn location(&self) -> String {
+        format!("{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// All the doc tests in the docs of the root module's exposed defs, and of the exposed defs of
+/// the other modules in its directory, such as the rest of a package. The root module's come
+/// first, then the others' by module name, each in the order they appear.
+pub fn doc_tests(loaded_module: &LoadedModule) -> Vec<DocTest> {
+    let home = loaded_module.module_id;
+    let root_dir = match loaded_module.sources.get(&home) {
+        Some((path, _)) => path.parent().unwrap_or_else(|| Path::new("")),
+        None => return Vec::new(),
+    };
+
+    // Modules elsewhere, like the platform's, belong to packages with tests of their own.
+    let in_root_dir = |path: &Path| match path.strip_prefix(root_dir) {
+        Ok(rest) => rest
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir)),
+        Err(_) => false,
+    };
+
+    let mut modules: Vec<_> = loaded_module
+        .documentation
+        .iter()
+        .filter(|(module_id, _)| {
+            **module_id == home
+                || (!module_id.is_builtin()
+                    && loaded_module
+                        .sources
+                        .get(module_id)
+                        .map_or(false, |(path, _)| in_root_dir(path)))
+        })
+        .collect();
+
+    modules.sort_by_key(|(module_id, module_docs)| (**module_id != home, &module_docs.name));
+
+    modules
+        .into_iter()
+        .flat_map(|(module_id, module_docs)| {
+            module_doc_tests(loaded_module, *module_id, module_docs)
+        })
+        .collect()
+}
+
+fn module_doc_tests(
+    loaded_module: &LoadedModule,
+    module_id: ModuleId,
+    module_docs: &ModuleDocumentation,
+) -> Vec<DocTest> {
+    let (path, src) = match loaded_module.sources.get(&module_id) {
+        Some(source) => source,
+        None => return Vec::new(),
+    };
+
+    let exposed = if module_id == loaded_module.module_id {
+        exposed_names(loaded_module)
+    } else {
+        loaded_module
+            .exposed_symbols_by_module
+            .get(&module_id)
+            .into_iter()
+            .flat_map(|symbols| symbols.iter())
+            .map(|symbol| symbol.as_str(&loaded_module.interns))
+            .collect()
+    };
+    let src_lines: Vec<&str> = src.lines().collect();
+    let mut tests = Vec::new();
+
+    for entry in &module_docs.entries {
+        let (def_name, docs) = match entry {
+            DocEntry::DocDef(doc_def) if exposed.contains(&doc_def.name.as_str()) => {
+                match &doc_def.docs {
+                    Some(docs) => (&doc_def.name, docs),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        // Doc tests are compiled in place of their code block, so one we can't find in the
+        // source can't be run.
+        let first_doc_line = match doc_comment_start(&src_lines, def_name, docs.lines().count()) {
+            Some(first_doc_line) => first_doc_line,
+            None => continue,
+        };
+
+        for (code, docs_line) in roc_code_blocks(docs) {
+            // Point at the code itself, after the `##`
+            let line = first_doc_line + docs_line;
+            let first_code_line = code.lines().next().unwrap_or_default().trim();
+            let column = src_lines
+                .get(line)
+                .and_then(|line| line.find(first_code_line))
+                .unwrap_or(0);
+
+            tests.push(DocTest {
+                def_name: def_name.clone(),
+                module_name: module_docs.name.clone(),
+                path: path.clone(),
+                line: line as u32 + 1,
+                column: column as u32 + 1,
+                code,
+            });
+        }
+    }
+
+    tests
+}
+
+/// Turns the code block of each doc test from the module at `path` into a top-level `expect`, in
+/// place: the line with the opening ``` becomes `expect`, and the `##` in front of each line of
+/// code is blanked out. Every line keeps its length, so everything in the module (including the
+/// doc tests' problems and failures) is reported at the same line and column as in `src`.
+pub fn inline_doc_tests(path: &Path, src: &str, tests: &[DocTest]) -> String {
+    let mut lines: Vec<String> = src.split('\n').map(String::from).collect();
+
+    for test in tests.iter().filter(|test| test.path == path) {
+        // `line` is the 1-based line of the first line of code, right after the opening ```
+        let fence = test.line as usize - 2;
+
+        match lines.get_mut(fence) {
+            Some(line) if line.starts_with("##") => {
+                *line = format!("{:width$}", "expect", width = line.len());
+            }
+            _ => continue,
+        }
+
+        for line in lines
+            .iter_mut()
+            .skip(fence + 1)
+            .take(test.code.lines().count())
+        {
+            if let Some(code) = line.strip_prefix("##") {
+                *line = format!("  {}", code);
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// The code in each ```roc block, along with the (0-based) line of the docs it starts on.
+/// Blocks with any other info string, e.g. ```roc-ignore, are not tests.
+fn roc_code_blocks(docs: &str) -> Vec<(String, usize)> {
+    use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+
+    for (event, range) in Parser::new(docs).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) if &*info == "roc" => {
+                current = Some((String::new(), 0));
+            }
+            Event::Text(text) => {
+                if let Some((code, start_line)) = &mut current {
+                    if code.is_empty() {
+                        *start_line = docs[..range.start].matches('\n').count();
+                    }
+
+                    code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some((code, start_line)) = current.take() {
+                    if !code.trim().is_empty() {
+                        blocks.push((code, start_line));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// The (0-based) line where the def's doc comment starts: the `docs_lines` lines of `##`
+/// comments right above the line where the def is first declared.
+fn doc_comment_start(src_lines: &[&str], def_name: &str, docs_lines: usize) -> Option<usize> {
+    (docs_lines..src_lines.len()).find_map(|def_line| {
+        let rest = src_lines[def_line].strip_prefix(def_name)?;
+        let declares_def = rest
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_whitespace() || c == ':');
+        let first_doc_line = def_line - docs_lines;
+        let is_documented = src_lines[first_doc_line..def_line]
+            .iter()
+            .all(|line| line.trim_start().starts_with("##"));
+
+        if declares_def && is_documented {
+            Some(first_doc_line)
+        } else {
+            None
+        }
+    })
+}
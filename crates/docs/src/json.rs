@@ -0,0 +1,236 @@
+//! The docs as one JSON file, `docs.json`, for custom renderers and for diffing a package's API
+//! between releases. Modules are sorted by name and entries are in source order, so that the
+//! same API always gives the same JSON.
+
+use crate::search_index::single_line;
+use crate::{exposed_names, module_documentation, type_annotation_to_html};
+use roc_load::docs::{self, DocEntry, Documentation};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    pub docs: String,
+    pub modules: Vec<Module>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Module {
+    pub name: String,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Entry {
+    /// An exposed value, type or ability
+    Def {
+        name: String,
+        type_vars: Vec<String>,
+        /// The type annotation as it appears in the HTML docs
+        signature: String,
+        type_annotation: Type,
+        docs: Option<String>,
+    },
+    /// Docs which aren't attached to a def, e.g. section headings
+    DetachedDoc { docs: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Type {
+    TagUnion {
+        tags: Vec<Tag>,
+        extension: Box<Type>,
+    },
+    Function {
+        args: Vec<Type>,
+        output: Box<Type>,
+    },
+    ObscuredTagUnion,
+    ObscuredRecord,
+    BoundVariable {
+        name: String,
+    },
+    Apply {
+        name: String,
+        parts: Vec<Type>,
+    },
+    Record {
+        fields: Vec<RecordField>,
+        extension: Box<Type>,
+    },
+    Ability {
+        members: Vec<AbilityMember>,
+    },
+    Wildcard,
+    /// No annotation, e.g. for an opaque type or a def without a type annotation
+    None,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tag {
+    pub name: String,
+    pub values: Vec<Type>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum RecordField {
+    Required { name: String, type_annotation: Type },
+    Optional { name: String, type_annotation: Type },
+    LabelOnly { name: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbilityMember {
+    pub name: String,
+    pub type_annotation: Type,
+    /// e.g. `a has Hash` in `hash : a -> U64 | a has Hash`
+    pub able_variables: Vec<AbleVariable>,
+    pub docs: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbleVariable {
+    pub name: String,
+    pub abilities: Vec<Type>,
+}
+
+/// Writes the docs of every module in the package to `docs.json` in the given directory.
+pub fn write_json(out_dir: &Path, package: &Documentation) {
+    let json = serde_json::to_string_pretty(&package_model(package))
+        .expect("TODO gracefully handle failing to serialize the docs");
+
+    fs::write(out_dir.join("docs.json"), json)
+        .expect("TODO gracefully handle failing to write docs.json");
+}
+
+/// The exposed parts of the package's docs, in a form that can be serialized.
+pub fn package_model(package: &Documentation) -> Package {
+    let mut modules: Vec<Module> = package
+        .modules
+        .iter()
+        .filter_map(|loaded_module| {
+            let module_docs = module_documentation(loaded_module)?;
+            let exposed = exposed_names(loaded_module);
+
+            let entries = module_docs
+                .entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    DocEntry::DocDef(doc_def) if exposed.contains(&doc_def.name.as_str()) => {
+                        let mut signature = String::new();
+
+                        type_annotation_to_html(0, &mut signature, &doc_def.type_annotation, false);
+
+                        Some(Entry::Def {
+                            name: doc_def.name.clone(),
+                            type_vars: doc_def.type_vars.clone(),
+                            signature: single_line(&signature),
+                            type_annotation: type_model(&doc_def.type_annotation),
+                            docs: doc_def.docs.clone(),
+                        })
+                    }
+                    DocEntry::DocDef(_) => None,
+                    DocEntry::DetachedDoc(docs) => Some(Entry::DetachedDoc { docs: docs.clone() }),
+                })
+                .collect();
+
+            Some(Module {
+                name: module_docs.name.clone(),
+                entries,
+            })
+        })
+        .collect();
+
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Package {
+        name: package.name.clone(),
+        version: package.version.clone(),
+        docs: package.docs.clone(),
+        modules,
+    }
+}
+
+fn type_model(type_ann: &docs::TypeAnnotation) -> Type {
+    use docs::TypeAnnotation::*;
+
+    let boxed = |type_ann: &docs::TypeAnnotation| Box::new(type_model(type_ann));
+    let all = |type_anns: &[docs::TypeAnnotation]| -> Vec<Type> {
+        type_anns.iter().map(type_model).collect()
+    };
+
+    match type_ann {
+        TagUnion { tags, extension } => Type::TagUnion {
+            tags: tags
+                .iter()
+                .map(|tag| Tag {
+                    name: tag.name.clone(),
+                    values: all(&tag.values),
+                })
+                .collect(),
+            extension: boxed(extension),
+        },
+        Function { args, output } => Type::Function {
+            args: all(args),
+            output: boxed(output),
+        },
+        ObscuredTagUnion => Type::ObscuredTagUnion,
+        ObscuredRecord => Type::ObscuredRecord,
+        BoundVariable(name) => Type::BoundVariable { name: name.clone() },
+        Apply { name, parts } => Type::Apply {
+            name: name.clone(),
+            parts: all(parts),
+        },
+        Record { fields, extension } => Type::Record {
+            fields: fields
+                .iter()
+                .map(|field| match field {
+                    docs::RecordField::RecordField {
+                        name,
+                        type_annotation,
+                    } => RecordField::Required {
+                        name: name.clone(),
+                        type_annotation: type_model(type_annotation),
+                    },
+                    docs::RecordField::OptionalField {
+                        name,
+                        type_annotation,
+                    } => RecordField::Optional {
+                        name: name.clone(),
+                        type_annotation: type_model(type_annotation),
+                    },
+                    docs::RecordField::LabelOnly { name } => {
+                        RecordField::LabelOnly { name: name.clone() }
+                    }
+                })
+                .collect(),
+            extension: boxed(extension),
+        },
+        Ability { members } => Type::Ability {
+            members: members
+                .iter()
+                .map(|member| AbilityMember {
+                    name: member.name.clone(),
+                    type_annotation: type_model(&member.type_annotation),
+                    able_variables: member
+                        .able_variables
+                        .iter()
+                        .map(|(name, abilities)| AbleVariable {
+                            name: name.clone(),
+                            abilities: all(abilities),
+                        })
+                        .collect(),
+                    docs: member.docs.clone(),
+                })
+                .collect(),
+        },
+        Wildcard => Type::Wildcard,
+        NoTypeAnn => Type::None,
+    }
+}
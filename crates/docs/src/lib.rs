@@ -15,30 +15,90 @@ use roc_module::symbol::{IdentIdsByModule, Interns, ModuleId};
 use roc_parse::ident::{parse_ident, Ident};
 use roc_parse::state::State;
 use roc_region::all::Region;
+use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod doc_tests;
 mod docs_error;
 mod html;
+pub mod json;
+pub mod markdown;
+pub mod search_index;
 
 const BUILD_DIR: &str = "./generated-docs";
 
-pub fn generate_docs_html(filenames: Vec<PathBuf>) {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A website, with a page per module
+    Html,
+    /// A Markdown file per module, plus an index
+    Markdown,
+    /// One JSON file with the docs of every module, for custom renderers and API diffs
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "html" => Ok(OutputFormat::Html),
+            "md" => Ok(OutputFormat::Markdown),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "Roc does not know a docs output format named {}",
+                string
+            )),
+        }
+    }
+}
+
+/// Generates the docs site for the given modules.
+pub fn generate_docs_html(filenames: Vec<PathBuf>, version: Option<String>) {
+    generate_docs(filenames, version, OutputFormat::Html)
+}
+
+/// Generates docs for the given modules in the given format.
+///
+/// Without a version, the docs are written directly into the build dir. With one,
+/// they go into a subdirectory named after the version, so generating docs for
+/// several releases into the same build dir keeps each release's docs. For HTML,
+/// the build dir also gets a `versions.json` that the version selector on every
+/// page reads.
+pub fn generate_docs(filenames: Vec<PathBuf>, version: Option<String>, format: OutputFormat) {
     let build_dir = Path::new(BUILD_DIR);
+    let package_docs =
+        package_readme(&filenames).unwrap_or_else(|| "Package introduction or README.".to_string());
     let loaded_modules = load_modules_for_files(filenames);
 
     // TODO: get info from a package module; this is all hardcoded for now.
     let package = Documentation {
         name: "documentation".to_string(),
-        version: "".to_string(),
-        docs: "Package introduction or README.".to_string(),
+        version: version.unwrap_or_default(),
+        docs: package_docs,
         modules: loaded_modules,
     };
 
-    if !build_dir.exists() {
-        fs::create_dir_all(build_dir).expect("TODO gracefully handle unable to create build dir");
+    let out_dir = build_dir.join(package.version.as_str());
+
+    if !out_dir.exists() {
+        fs::create_dir_all(&out_dir).expect("TODO gracefully handle unable to create build dir");
+    }
+
+    match format {
+        OutputFormat::Html => write_html(build_dir, &package),
+        OutputFormat::Markdown => markdown::write_markdown(&out_dir, &package),
+        OutputFormat::Json => json::write_json(&out_dir, &package),
     }
 
+    println!("🎉 Docs generated in {}", out_dir.display());
+}
+
+fn write_html(build_dir: &Path, package: &Documentation) {
+    let site_dir = build_dir.join(package.version.as_str());
+    let site_url = site_url(package.version.as_str());
+
     // Copy over the assets
     fs::write(
         build_dir.join("search.js"),
@@ -69,10 +129,9 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
                 // being a hash map in the first place if only one of its entries
                 // actually has interesting information in it?
                 if *module_id == loaded_module.module_id {
-                    let exposed_values = loaded_module
-                        .exposed_values
-                        .iter()
-                        .map(|symbol| symbol.as_str(&loaded_module.interns).to_string())
+                    let exposed_values = exposed_names(loaded_module)
+                        .into_iter()
+                        .map(|name| name.to_string())
                         .collect::<Vec<String>>();
 
                     Some((module, exposed_values))
@@ -82,16 +141,28 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
             })
     });
 
+    let versions_url = if package.version.is_empty() {
+        String::new()
+    } else {
+        format!("{}versions.json", base_url())
+    };
+
     let template_html = include_str!("./static/index.html")
         .replace("<!-- search.js -->", "/search.js")
         .replace("<!-- styles.css -->", "/styles.css")
         .replace("<!-- favicon.svg -->", "/favicon.svg")
+        .replace(
+            "<!-- search-index.json -->",
+            &format!("{}search-index.json", site_url),
+        )
+        .replace("<!-- versions.json -->", &versions_url)
+        .replace("<!-- site root -->", &site_url)
         .replace(
             "<!-- Prefetch links -->",
             &module_pairs
                 .clone()
                 .map(|(module, _)| {
-                    let href = sidebar_link_url(module);
+                    let href = sidebar_link_url(&site_url, module);
 
                     format!(r#"<link rel="prefetch" href="{href}"/>"#)
                 })
@@ -100,14 +171,58 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
         )
         .replace(
             "<!-- Module links -->",
-            render_sidebar(module_pairs).as_str(),
+            render_sidebar(&site_url, module_pairs.clone()).as_str(),
+        )
+        .replace(
+            "<!-- Package Name and Version -->",
+            render_name_and_version(&site_url, package.name.as_str(), package.version.as_str())
+                .as_str(),
         );
 
+    // Write the package's landing page
+    fs::write(
+        site_dir.join("index.html"),
+        template_html
+            .replace(
+                "<!-- Page title -->",
+                format!("<title>{}</title>", package.name).as_str(),
+            )
+            .replace(
+                "<!-- Module Docs -->",
+                render_package_index(&site_url, package, module_pairs.clone()).as_str(),
+            ),
+    )
+    .expect("TODO gracefully handle failing to write the package's index.html");
+
+    // Write the search index
+    let search_entries = module_pairs
+        .flat_map(|(module, exposed_values)| {
+            let exposed_values = exposed_values
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<&str>>();
+
+            search_index::search_entries(
+                &module.name,
+                &sidebar_link_url(&site_url, module),
+                &module.entries,
+                &exposed_values,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    fs::write(
+        site_dir.join("search-index.json"),
+        serde_json::to_string(&search_entries)
+            .expect("TODO gracefully handle failing to serialize the search index"),
+    )
+    .expect("TODO gracefully handle failing to write the search index");
+
     // Write each package's module docs html file
     for loaded_module in package.modules.iter() {
         for (module_id, module_docs) in loaded_module.documentation.iter() {
             if *module_id == loaded_module.module_id {
-                let module_dir = build_dir.join(module_docs.name.replace('.', "/").as_str());
+                let module_dir = site_dir.join(module_docs.name.replace('.', "/").as_str());
 
                 fs::create_dir_all(&module_dir)
                     .expect("TODO gracefully handle not being able to create the module dir");
@@ -115,16 +230,11 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
                 let rendered_module = template_html
                     .replace(
                         "<!-- Page title -->",
-                        page_title(&package, module_docs).as_str(),
-                    )
-                    .replace(
-                        "<!-- Package Name and Version -->",
-                        render_name_and_version(package.name.as_str(), package.version.as_str())
-                            .as_str(),
+                        page_title(package, module_docs).as_str(),
                     )
                     .replace(
                         "<!-- Module Docs -->",
-                        render_module_documentation(module_docs, loaded_module).as_str(),
+                        render_module_documentation(&site_url, module_docs, loaded_module).as_str(),
                     );
 
                 fs::write(module_dir.join("index.html"), rendered_module).expect(
@@ -134,11 +244,179 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
         }
     }
 
-    println!("🎉 Docs generated in {}", build_dir.display());
+    if !package.version.is_empty() {
+        write_versions(build_dir);
+    }
+}
+
+/// Records every version that has been generated into the build dir, newest
+/// first, and points the build dir's index.html at the newest one.
+fn write_versions(build_dir: &Path) {
+    let mut versions = fs::read_dir(build_dir)
+        .expect("TODO gracefully handle failing to read the build dir")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+
+            if path.join("search-index.json").is_file() {
+                Some(path.file_name()?.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<String>>();
+
+    versions.sort_by(|a, b| compare_versions(b, a));
+
+    fs::write(
+        build_dir.join("versions.json"),
+        serde_json::to_string(&versions)
+            .expect("TODO gracefully handle failing to serialize the versions"),
+    )
+    .expect("TODO gracefully handle failing to write versions.json");
+
+    if let Some(newest) = versions.first() {
+        let newest_url = site_url(newest);
+
+        fs::write(
+            build_dir.join("index.html"),
+            format!(
+                r#"<!doctype html><meta charset="utf-8"><meta http-equiv="refresh" content="0; url={newest_url}"><a href="{newest_url}">{newest}</a>"#
+            ),
+        )
+        .expect("TODO gracefully handle failing to write the build dir's index.html");
+    }
+}
+
+/// Checks that a version can be used as the name of the directory its docs go
+/// in, without escaping the build dir.
+pub fn validate_version(version: &str) -> Result<(), String> {
+    if version.is_empty() {
+        Err("The package version can't be empty.".to_string())
+    } else if version.contains(|c| c == '/' || c == '\\') {
+        Err(format!(
+            "The package version {} can't contain a slash, because it's used as a directory name.",
+            version
+        ))
+    } else if version.contains("..") {
+        Err(format!(
+            "The package version {} can't contain `..`, because it's used as a directory name.",
+            version
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Compares versions like `0.10.1` component by component, so that `0.10.0`
+/// comes after `0.9.0`. As in semver, a pre-release like `1.0.0-beta` comes
+/// before `1.0.0`, and its components that aren't numbers are compared as text.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_release, a_pre) = split_pre_release(a);
+    let (b_release, b_pre) = split_pre_release(b);
+
+    compare_components(a_release.split('.'), b_release.split('.')).then_with(|| {
+        match (a_pre, b_pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a_pre), Some(b_pre)) => compare_components(a_pre.split('.'), b_pre.split('.')),
+        }
+    })
+}
+
+/// Splits `1.0.0-beta.2+build.5` into `1.0.0` and `beta.2`. Build metadata
+/// doesn't affect the order.
+fn split_pre_release(version: &str) -> (&str, Option<&str>) {
+    let version = version.split('+').next().unwrap_or(version);
+
+    match version.split_once('-') {
+        Some((release, pre)) => (release, Some(pre)),
+        None => (version, None),
+    }
+}
+
+fn compare_components<'a>(
+    mut a_parts: impl Iterator<Item = &'a str>,
+    mut b_parts: impl Iterator<Item = &'a str>,
+) -> Ordering {
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_part), Some(b_part)) => {
+                let ordering = match (a_part.parse::<u64>(), b_part.parse::<u64>()) {
+                    (Ok(a_num), Ok(b_num)) => a_num.cmp(&b_num),
+                    // numbers come before text, as in semver
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a_part.cmp(b_part),
+                };
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Looks for a README.md next to the given .roc files to use as the package's
+/// introduction on its landing page.
+fn package_readme(filenames: &[PathBuf]) -> Option<String> {
+    filenames
+        .iter()
+        .filter_map(|filename| filename.parent())
+        .map(|dir| dir.join("README.md"))
+        .find(|readme| readme.is_file())
+        .and_then(|readme| fs::read_to_string(readme).ok())
 }
 
-fn sidebar_link_url(module: &ModuleDocumentation) -> String {
-    format!("{}{}", base_url(), module.name.as_str())
+/// The docs of the module itself, as opposed to those of its dependencies.
+fn module_documentation(loaded_module: &LoadedModule) -> Option<&ModuleDocumentation> {
+    // TODO it seems this `documentation` dictionary has entries for every module,
+    // but only the current module has any info in it.
+    loaded_module.documentation.get(&loaded_module.module_id)
+}
+
+/// The names of the values, types and abilities a module exposes.
+fn exposed_names(loaded_module: &LoadedModule) -> Vec<&str> {
+    let home = loaded_module.module_id;
+    let mut names = loaded_module.exposed_values_str();
+
+    for symbol in loaded_module.exposed_aliases.keys() {
+        if symbol.module_id() == home {
+            names.push(symbol.as_str(&loaded_module.interns));
+        }
+    }
+
+    // Abilities aren't values or aliases, but exposing one exposes its members.
+    for (ability, members) in loaded_module.abilities_store.iter_abilities() {
+        if ability.module_id() == home
+            && members
+                .iter()
+                .any(|member| loaded_module.exposed_values.contains(member))
+        {
+            names.push(ability.as_str(&loaded_module.interns));
+        }
+    }
+
+    names
+}
+
+fn site_url(version: &str) -> String {
+    let mut url = base_url();
+
+    if !version.is_empty() {
+        url.push_str(version);
+        url.push('/');
+    }
+
+    url
+}
+
+fn sidebar_link_url(site_url: &str, module: &ModuleDocumentation) -> String {
+    format!("{}{}", site_url, module.name.as_str())
 }
 
 fn page_title(package: &Documentation, module: &ModuleDocumentation) -> String {
@@ -192,6 +470,7 @@ pub fn syntax_highlight_top_level_defs(code_str: &str) -> DocsResult<String> {
 }
 
 fn render_module_documentation(
+    site_url: &str,
     module: &ModuleDocumentation,
     loaded_module: &LoadedModule,
 ) -> String {
@@ -201,12 +480,12 @@ fn render_module_documentation(
         html_to_string(
             "h2",
             vec![("class", "module-name")],
-            html_to_string("a", vec![("href", "/#")], module.name.as_str()).as_str(),
+            html_to_string("a", vec![("href", site_url)], module.name.as_str()).as_str(),
         )
         .as_str(),
     );
 
-    let exposed_values = loaded_module.exposed_values_str();
+    let exposed_values = exposed_names(loaded_module);
 
     for entry in &module.entries {
         let mut should_render_entry = true;
@@ -233,21 +512,7 @@ fn render_module_documentation(
                         html_to_string("a", vec![("href", href.as_str())], name).as_str(),
                     );
 
-                    for type_var in &doc_def.type_vars {
-                        content.push(' ');
-                        content.push_str(type_var.as_str());
-                    }
-
-                    let type_ann = &doc_def.type_annotation;
-
-                    match type_ann {
-                        TypeAnnotation::NoTypeAnn => {}
-                        _ => {
-                            content.push_str(" : ");
-                        }
-                    }
-
-                    type_annotation_to_html(0, &mut content, type_ann, false);
+                    def_signature_to_html(&mut content, doc_def);
 
                     buf.push_str(
                         html_to_string(
@@ -261,6 +526,7 @@ fn render_module_documentation(
                     if let Some(docs) = &doc_def.docs {
                         buf.push_str(
                             markdown_to_html(
+                                site_url,
                                 &exposed_values,
                                 &module.scope,
                                 docs.to_string(),
@@ -274,6 +540,7 @@ fn render_module_documentation(
                 }
                 DocEntry::DetachedDoc(docs) => {
                     let markdown = markdown_to_html(
+                        site_url,
                         &exposed_values,
                         &module.scope,
                         docs.to_string(),
@@ -347,33 +614,85 @@ fn base_url() -> String {
     }
 }
 
-fn render_name_and_version(name: &str, version: &str) -> String {
+fn render_name_and_version(site_url: &str, name: &str, version: &str) -> String {
     let mut buf = String::new();
-    let mut url_str = base_url();
-
-    url_str.push_str(name);
 
     buf.push_str(
         html_to_string(
             "h1",
             vec![("class", "pkg-full-name")],
-            html_to_string("a", vec![("href", url_str.as_str())], name).as_str(),
+            html_to_string("a", vec![("href", site_url)], name).as_str(),
+        )
+        .as_str(),
+    );
+
+    if !version.is_empty() {
+        // search.js fills in the other versions from versions.json
+        buf.push_str(
+            html_to_string(
+                "select",
+                vec![
+                    ("class", "version"),
+                    ("id", "version-selector"),
+                    ("aria-label", "Version"),
+                ],
+                html_to_string(
+                    "option",
+                    vec![("value", version), ("selected", "")],
+                    version,
+                )
+                .as_str(),
+            )
+            .as_str(),
+        );
+    }
+
+    buf
+}
+
+fn render_package_index<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>>(
+    site_url: &str,
+    package: &Documentation,
+    modules: I,
+) -> String {
+    let mut buf = String::new();
+
+    buf.push_str(
+        html_to_string(
+            "h2",
+            vec![("class", "module-name")],
+            html_to_string("a", vec![("href", site_url)], package.name.as_str()).as_str(),
         )
         .as_str(),
     );
 
-    let mut versions_url_str = base_url();
+    pulldown_cmark::html::push_html(
+        &mut buf,
+        pulldown_cmark::Parser::new_ext(&package.docs, pulldown_cmark::Options::empty()),
+    );
+
+    let mut module_links = String::new();
+
+    for (module, _) in modules {
+        let href = sidebar_link_url(site_url, module);
+        let mut content = html_to_string("a", vec![("href", &href)], module.name.as_str());
 
-    versions_url_str.push('/');
-    versions_url_str.push_str(name);
-    versions_url_str.push('/');
-    versions_url_str.push_str(version);
+        // Docs which come before any of the module's defs describe the module itself
+        if let Some(DocEntry::DetachedDoc(docs)) = module.entries.first() {
+            content.push_str(
+                html_to_string("p", vec![], search_index::summary(docs).as_str()).as_str(),
+            );
+        }
 
+        module_links.push_str(html_to_string("li", vec![], content.as_str()).as_str());
+    }
+
+    buf.push_str(html_to_string("h3", vec![], "Modules").as_str());
     buf.push_str(
         html_to_string(
-            "a",
-            vec![("class", "version"), ("href", versions_url_str.as_str())],
-            version,
+            "ul",
+            vec![("class", "package-module-links")],
+            module_links.as_str(),
         )
         .as_str(),
     );
@@ -382,12 +701,13 @@ fn render_name_and_version(name: &str, version: &str) -> String {
 }
 
 fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>>(
+    site_url: &str,
     modules: I,
 ) -> String {
     let mut buf = String::new();
 
     for (module, exposed_values) in modules {
-        let href = sidebar_link_url(module);
+        let href = sidebar_link_url(site_url, module);
         let mut sidebar_entry_content = String::new();
 
         sidebar_entry_content.push_str(
@@ -458,6 +778,8 @@ pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> Vec<LoadedModule> {
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Check,
+            cache_dir: None,
+            coverage: false,
         };
         match roc_load::load_and_typecheck(&arena, filename, Default::default(), load_config) {
             Ok(loaded) => modules.push(loaded),
@@ -639,6 +961,10 @@ fn type_annotation_to_html(
             type_annotation_to_html(indent_level, buf, extension, true);
         }
         TypeAnnotation::Function { args, output } => {
+            if needs_parens {
+                buf.push('(');
+            }
+
             let mut peekable_args = args.iter().peekable();
             while let Some(arg) = peekable_args.next() {
                 if is_multiline {
@@ -648,7 +974,10 @@ fn type_annotation_to_html(
                     indent(buf, indent_level + 1);
                 }
 
-                type_annotation_to_html(indent_level, buf, arg, false);
+                // e.g. the `(a -> b)` in `List a, (a -> b) -> List b`
+                let arg_needs_parens = matches!(arg, TypeAnnotation::Function { .. });
+
+                type_annotation_to_html(indent_level, buf, arg, arg_needs_parens);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -669,9 +998,22 @@ fn type_annotation_to_html(
             }
 
             type_annotation_to_html(next_indent_level, buf, output, false);
+
+            if needs_parens {
+                buf.push(')');
+            }
         }
-        TypeAnnotation::Ability { members: _ } => {
-            // TODO(abilities): fill me in
+        TypeAnnotation::Ability { members } => {
+            for member in members {
+                new_line(buf);
+                indent(buf, indent_level + 1);
+
+                buf.push_str(member.name.as_str());
+                buf.push_str(" : ");
+
+                type_annotation_to_html(indent_level + 1, buf, &member.type_annotation, false);
+                able_variables_to_html(buf, &member.able_variables);
+            }
         }
         TypeAnnotation::ObscuredTagUnion => {
             buf.push_str("[@..]");
@@ -684,6 +1026,45 @@ fn type_annotation_to_html(
     }
 }
 
+// Everything after the def's name in its heading, e.g. the ` a : List a -> Nat` in `len a : List a -> Nat`
+fn def_signature_to_html(buf: &mut String, doc_def: &roc_load::docs::DocDef) {
+    for type_var in &doc_def.type_vars {
+        buf.push(' ');
+        buf.push_str(type_var.as_str());
+    }
+
+    let type_ann = &doc_def.type_annotation;
+
+    match type_ann {
+        TypeAnnotation::NoTypeAnn => {}
+        TypeAnnotation::Ability { .. } => {
+            buf.push_str(" has");
+        }
+        _ => {
+            buf.push_str(" : ");
+        }
+    }
+
+    type_annotation_to_html(0, buf, type_ann, false);
+}
+
+// e.g. the ` | a has Hash` in `hash : a -> U64 | a has Hash`
+fn able_variables_to_html(buf: &mut String, able_variables: &[(String, Vec<TypeAnnotation>)]) {
+    for (index, (var_name, abilities)) in able_variables.iter().enumerate() {
+        buf.push_str(if index == 0 { " | " } else { ", " });
+        buf.push_str(var_name.as_str());
+        buf.push_str(" has ");
+
+        for (index, ability) in abilities.iter().enumerate() {
+            if index > 0 {
+                buf.push_str(" & ");
+            }
+
+            type_annotation_to_html(0, buf, ability, true);
+        }
+    }
+}
+
 fn should_be_multiline(type_ann: &TypeAnnotation) -> bool {
     match type_ann {
         TypeAnnotation::TagUnion { tags, extension } => {
@@ -761,6 +1142,7 @@ struct DocUrl {
 }
 
 fn doc_url<'a>(
+    site_url: &str,
     home: ModuleId,
     exposed_values: &[&str],
     dep_idents: &IdentIdsByModule,
@@ -781,11 +1163,30 @@ fn doc_url<'a>(
                 module_name = symbol.module_string(interns);
             }
             Err(_) => {
-                // TODO return Err here
-                panic!(
-                "Tried to generate an automatic link in docs for symbol `{}`, but that symbol was not in scope in this module.",
-                ident
-            );
+                // Builtin types like [F32] can be linked to even from modules
+                // which don't import them.
+                let builtin_module = interns.all_ident_ids.keys().find(|module_id| {
+                    module_id.is_builtin()
+                        && ident.starts_with(|c: char| c.is_uppercase())
+                        && interns
+                            .all_ident_ids
+                            .get(module_id)
+                            .and_then(|ident_ids| ident_ids.get_id(ident))
+                            .is_some()
+                });
+
+                match builtin_module {
+                    Some(module_id) => {
+                        module_name = module_id.to_ident_str(interns).as_str();
+                    }
+                    None => {
+                        // TODO return Err here
+                        panic!(
+                        "Tried to generate an automatic link in docs for symbol `{}`, but that symbol was not in scope in this module.",
+                        ident
+                    );
+                    }
+                }
             }
         }
     } else {
@@ -831,7 +1232,7 @@ fn doc_url<'a>(
         }
     }
 
-    let mut url = base_url();
+    let mut url = site_url.to_string();
 
     // Example:
     //
@@ -847,6 +1248,7 @@ fn doc_url<'a>(
 }
 
 fn markdown_to_html(
+    site_url: &str,
     exposed_values: &[&str],
     scope: &Scope,
     markdown: String,
@@ -877,6 +1279,7 @@ fn markdown_to_html(
                         match iter.next() {
                             Some(symbol_name) if iter.next().is_none() => {
                                 let DocUrl { url, title } = doc_url(
+                                    site_url,
                                     loaded_module.module_id,
                                     exposed_values,
                                     &loaded_module.dep_idents,
@@ -900,6 +1303,7 @@ fn markdown_to_html(
                         // This looks like a tag name, but it could
                         // be a type alias that's in scope, e.g. [I64]
                         let DocUrl { url, title } = doc_url(
+                            site_url,
                             loaded_module.module_id,
                             exposed_values,
                             &loaded_module.dep_idents,
@@ -929,7 +1333,6 @@ fn markdown_to_html(
         Some(&mut broken_link_callback),
     )
     .fold((0, 0), |(start_quote_count, end_quote_count), event| {
-
         match &event {
             // Replace this sequence (`>>>` syntax):
             //     Start(BlockQuote)
@@ -1006,16 +1409,17 @@ fn markdown_to_html(
                 (0, 0)
             }
             Event::Text(CowStr::Borrowed(code_str)) if expecting_code_block => {
-
-                match syntax_highlight_expr(
-                    code_str
-                )
+                // Examples are usually expressions, but they can also be defs
+                // like `add : Num a, Num a -> Num a`.
+                match syntax_highlight_expr(code_str)
+                    .or_else(|_| syntax_highlight_top_level_defs(code_str))
                 {
                     Ok(highlighted_code_str) => {
                         docs_parser.push(Event::Html(CowStr::from(highlighted_code_str)));
                     }
-                    Err(syntax_error) => {
-                        panic!("Unexpected parse failure when parsing this for rendering in docs:\n\n{}\n\nParse error was:\n\n{:?}\n\n", code_str, syntax_error)
+                    Err(_) => {
+                        // Show the code without highlighting rather than failing the whole build
+                        docs_parser.push(event);
                     }
                 };
 
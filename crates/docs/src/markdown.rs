@@ -0,0 +1,114 @@
+//! The docs as Markdown: an `index.md` for the package, and a page per module with a heading
+//! for each exposed def. Doc comments are already Markdown, so they're written as they are.
+
+use crate::search_index::{single_line, summary};
+use crate::{def_signature_to_html, exposed_names, module_documentation};
+use roc_load::docs::{DocEntry, Documentation, ModuleDocumentation};
+use std::fs;
+use std::path::Path;
+
+/// Writes `index.md` and a `<Module>.md` per module into the given directory.
+pub fn write_markdown(out_dir: &Path, package: &Documentation) {
+    let mut modules: Vec<(&ModuleDocumentation, Vec<&str>)> = package
+        .modules
+        .iter()
+        .filter_map(|loaded_module| {
+            let module_docs = module_documentation(loaded_module)?;
+
+            Some((module_docs, exposed_names(loaded_module)))
+        })
+        .collect();
+
+    modules.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+    fs::write(
+        out_dir.join("index.md"),
+        render_package_index(package, &modules),
+    )
+    .expect("TODO gracefully handle failing to write index.md");
+
+    for (module_docs, exposed) in modules {
+        fs::write(
+            out_dir.join(module_file_name(module_docs)),
+            render_module(module_docs, &exposed),
+        )
+        .expect("TODO gracefully handle failing to write a module's Markdown file");
+    }
+}
+
+fn module_file_name(module_docs: &ModuleDocumentation) -> String {
+    format!("{}.md", module_docs.name)
+}
+
+pub fn render_package_index(
+    package: &Documentation,
+    modules: &[(&ModuleDocumentation, Vec<&str>)],
+) -> String {
+    let mut buf = String::new();
+
+    buf.push_str("# ");
+    buf.push_str(&package.name);
+
+    if !package.version.is_empty() {
+        buf.push(' ');
+        buf.push_str(&package.version);
+    }
+
+    buf.push_str("\n\n");
+    buf.push_str(package.docs.trim());
+    buf.push_str("\n\n## Modules\n\n");
+
+    for (module_docs, _) in modules {
+        buf.push_str(&format!(
+            "- [{}]({})",
+            module_docs.name,
+            module_file_name(module_docs)
+        ));
+
+        // Docs which come before any of the module's defs describe the module itself
+        if let Some(DocEntry::DetachedDoc(docs)) = module_docs.entries.first() {
+            buf.push_str(" - ");
+            buf.push_str(&summary(docs));
+        }
+
+        buf.push('\n');
+    }
+
+    buf
+}
+
+pub fn render_module(module_docs: &ModuleDocumentation, exposed: &[&str]) -> String {
+    let mut buf = String::new();
+
+    buf.push_str("# ");
+    buf.push_str(&module_docs.name);
+    buf.push('\n');
+
+    for entry in &module_docs.entries {
+        match entry {
+            DocEntry::DocDef(doc_def) if exposed.contains(&doc_def.name.as_str()) => {
+                let mut signature = doc_def.name.clone();
+
+                def_signature_to_html(&mut signature, doc_def);
+
+                buf.push_str("\n## ");
+                buf.push_str(&doc_def.name);
+                buf.push_str("\n\n```roc\n");
+                buf.push_str(&single_line(&signature));
+                buf.push_str("\n```\n");
+
+                if let Some(docs) = &doc_def.docs {
+                    buf.push('\n');
+                    buf.push_str(docs);
+                }
+            }
+            DocEntry::DocDef(_) => {}
+            DocEntry::DetachedDoc(docs) => {
+                buf.push('\n');
+                buf.push_str(docs);
+            }
+        }
+    }
+
+    buf
+}
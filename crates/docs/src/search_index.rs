@@ -0,0 +1,168 @@
+use crate::{able_variables_to_html, type_annotation_to_html};
+use roc_load::docs::{AbilityMember, DocDef, DocEntry, TypeAnnotation};
+use serde::Serialize;
+
+/// What kind of thing a search result points to. The site's search box uses
+/// this to label results, so a tag and a type with the same name can be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntryKind {
+    Value,
+    Type,
+    Ability,
+    AbilityMember,
+    Tag,
+}
+
+/// One entry of `search-index.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchEntry {
+    pub module: String,
+    pub name: String,
+    pub kind: EntryKind,
+    /// The type signature on a single line, e.g. `List a, (a -> b) -> List b`.
+    /// Tags are listed as constructors, e.g. `ok -> Result ok err`.
+    pub signature: String,
+    pub url: String,
+    /// The first paragraph of the entry's doc comment.
+    pub summary: String,
+}
+
+/// Collects every exposed def, type, ability member and tag of a module.
+///
+/// `module_url` is where the module's page lives; entries link to anchors on it.
+pub fn search_entries(
+    module_name: &str,
+    module_url: &str,
+    entries: &[DocEntry],
+    exposed_values: &[&str],
+) -> Vec<SearchEntry> {
+    let mut search_entries = Vec::new();
+
+    for entry in entries {
+        if let DocEntry::DocDef(doc_def) = entry {
+            if exposed_values.contains(&doc_def.name.as_str()) {
+                push_doc_def(module_name, module_url, doc_def, &mut search_entries);
+            }
+        }
+    }
+
+    search_entries
+}
+
+fn push_doc_def(
+    module_name: &str,
+    module_url: &str,
+    doc_def: &DocDef,
+    search_entries: &mut Vec<SearchEntry>,
+) {
+    let url = format!("{}#{}", module_url, doc_def.name);
+    let entry = |name: &str, kind, signature, docs: &Option<String>| SearchEntry {
+        module: module_name.to_string(),
+        name: name.to_string(),
+        kind,
+        signature,
+        url: url.clone(),
+        summary: docs.as_deref().map(summary).unwrap_or_default(),
+    };
+
+    let starts_uppercase = doc_def.name.starts_with(|c: char| c.is_uppercase());
+
+    match &doc_def.type_annotation {
+        TypeAnnotation::Ability { members } => {
+            search_entries.push(entry(
+                &doc_def.name,
+                EntryKind::Ability,
+                String::new(),
+                &doc_def.docs,
+            ));
+
+            for member in members {
+                search_entries.push(entry(
+                    &member.name,
+                    EntryKind::AbilityMember,
+                    ability_member_signature(member),
+                    &member.docs,
+                ));
+            }
+        }
+        type_ann if starts_uppercase => {
+            search_entries.push(entry(
+                &doc_def.name,
+                EntryKind::Type,
+                signature(type_ann),
+                &doc_def.docs,
+            ));
+
+            if let TypeAnnotation::TagUnion { tags, .. } = type_ann {
+                let mut applied_type = doc_def.name.clone();
+
+                for type_var in &doc_def.type_vars {
+                    applied_type.push(' ');
+                    applied_type.push_str(type_var);
+                }
+
+                for tag in tags {
+                    let mut constructor = tag
+                        .values
+                        .iter()
+                        .map(signature)
+                        .collect::<Vec<String>>()
+                        .join(", ");
+
+                    if !constructor.is_empty() {
+                        constructor.push_str(" -> ");
+                    }
+
+                    constructor.push_str(&applied_type);
+
+                    search_entries.push(entry(&tag.name, EntryKind::Tag, constructor, &None));
+                }
+            }
+        }
+        type_ann => {
+            search_entries.push(entry(
+                &doc_def.name,
+                EntryKind::Value,
+                signature(type_ann),
+                &doc_def.docs,
+            ));
+        }
+    }
+}
+
+fn ability_member_signature(member: &AbilityMember) -> String {
+    let mut buf = signature(&member.type_annotation);
+
+    able_variables_to_html(&mut buf, &member.able_variables);
+
+    buf
+}
+
+/// Renders a type annotation the way it appears in the docs, but on one line.
+fn signature(type_ann: &TypeAnnotation) -> String {
+    let mut buf = String::new();
+
+    type_annotation_to_html(0, &mut buf, type_ann, false);
+
+    single_line(&buf)
+}
+
+/// Puts a rendered type annotation on one line.
+pub(crate) fn single_line(annotation: &str) -> String {
+    // Multiline tag unions and function arguments put their brackets on their own lines.
+    collapse_whitespace(annotation)
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace("( ", "(")
+}
+
+pub(crate) fn summary(docs: &str) -> String {
+    let first_paragraph = docs.trim_start().split("\n\n").next().unwrap_or_default();
+
+    collapse_whitespace(first_paragraph)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
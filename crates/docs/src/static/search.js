@@ -1,11 +1,157 @@
 (() => {
   let sidebar = document.getElementById("sidebar-nav");
   let searchBox = document.getElementById("module-search");
+  let searchResults = document.getElementById("search-results");
+  let config = document.currentScript.dataset;
+
+  // The search index is only fetched once someone starts searching.
+  let searchIndex = null;
+
+  function loadSearchIndex() {
+    if (searchIndex === null) {
+      searchIndex = fetch(config.searchIndex)
+        .then((response) => response.json())
+        .catch(() => []);
+    }
+
+    return searchIndex;
+  }
+
+  // Splits a type signature into tokens, e.g. "List a, (a -> b)" becomes
+  // ["List", "a", ",", "(", "a", "->", "b", ")"]
+  function tokenize(signature) {
+    return signature.match(/->|[A-Za-z_][A-Za-z0-9_.]*|[^\sA-Za-z0-9_]/g) || [];
+  }
+
+  // Lowercase names are type variables, unless they're record field labels.
+  function isTypeVariable(tokens, index) {
+    let next = tokens[index + 1];
+
+    return /^[a-z]/.test(tokens[index]) && next !== ":" && next !== "?";
+  }
+
+  // Does the query appear in the signature starting at `start`? Type variables
+  // match as long as they're renamed consistently, so "a -> b" finds "x -> y".
+  function matchesAt(queryTokens, signatureTokens, start) {
+    let queryToSignature = new Map();
+    let signatureToQuery = new Map();
+
+    for (let index = 0; index < queryTokens.length; index++) {
+      let queryToken = queryTokens[index];
+      let signatureToken = signatureTokens[start + index];
+
+      if (signatureToken === undefined) {
+        return false;
+      }
+
+      let queryIsVar = isTypeVariable(queryTokens, index);
+      let signatureIsVar = isTypeVariable(signatureTokens, start + index);
+
+      if (queryIsVar && signatureIsVar) {
+        let mappedSignatureToken = queryToSignature.get(queryToken);
+        let mappedQueryToken = signatureToQuery.get(signatureToken);
+
+        if (mappedSignatureToken === undefined && mappedQueryToken === undefined) {
+          queryToSignature.set(queryToken, signatureToken);
+          signatureToQuery.set(signatureToken, queryToken);
+        } else if (mappedSignatureToken !== signatureToken || mappedQueryToken !== queryToken) {
+          return false;
+        }
+      } else if (queryIsVar !== signatureIsVar || queryToken !== signatureToken) {
+        return false;
+      }
+    }
+
+    return true;
+  }
+
+  function signatureMatches(queryTokens, signature) {
+    let signatureTokens = tokenize(signature);
+
+    for (let start = 0; start + queryTokens.length <= signatureTokens.length; start++) {
+      if (matchesAt(queryTokens, signatureTokens, start)) {
+        return true;
+      }
+    }
+
+    return false;
+  }
+
+  // A query which looks like a type (e.g. "List a -> a" or ": Str") searches
+  // signatures; anything else searches names.
+  function findEntries(entries, query) {
+    let trimmed = query.trim();
+
+    if (trimmed.startsWith(":") || trimmed.includes("->")) {
+      let queryTokens = tokenize(trimmed.replace(/^:/, ""));
+
+      if (queryTokens.length === 0) {
+        return [];
+      }
+
+      return entries.filter((entry) => signatureMatches(queryTokens, entry.signature));
+    }
+
+    let text = trimmed.toLowerCase();
+    let rank = (entry) => {
+      let name = entry.name.toLowerCase();
+      let qualified = `${entry.module}.${entry.name}`.toLowerCase();
+
+      if (name === text || qualified === text) {
+        return 0;
+      } else if (name.startsWith(text) || qualified.startsWith(text)) {
+        return 1;
+      } else if (qualified.includes(text)) {
+        return 2;
+      } else {
+        return null;
+      }
+    };
+
+    return entries
+      .map((entry) => ({ entry, rank: rank(entry) }))
+      .filter(({ rank }) => rank !== null)
+      .sort((a, b) => a.rank - b.rank)
+      .map(({ entry }) => entry);
+  }
+
+  function renderResults(entries) {
+    searchResults.replaceChildren(
+      ...entries.slice(0, 50).map((entry) => {
+        let item = document.createElement("li");
+        let link = document.createElement("a");
+        let name = document.createElement("span");
+        let kind = document.createElement("span");
+
+        link.href = entry.url;
+        link.title = entry.summary;
+        name.className = "search-result-name";
+        name.textContent = `${entry.module}.${entry.name}`;
+        kind.className = "search-result-kind";
+        kind.textContent = entry.kind.replace("-", " ");
+        link.append(name, kind);
+
+        if (entry.signature !== "") {
+          let signature = document.createElement("span");
+
+          signature.className = "search-result-signature";
+          signature.textContent = entry.signature;
+          link.append(signature);
+        }
+
+        item.append(link);
+
+        return item;
+      })
+    );
+  }
 
   function search() {
     let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
 
     if (text === "") {
+      searchResults.classList.add("hidden");
+
       // Un-hide everything
       sidebar.querySelectorAll(".sidebar-entry a").forEach((entry) => entry.classList.remove("hidden"));
 
@@ -21,6 +167,18 @@
         entry.querySelectorAll(".sidebar-sub-entries a").forEach((subEntry) => subEntry.classList.add("hidden"));
       })
     } else {
+      // Show the matching entries from the search index. Signature searches
+      // are case-sensitive, because case tells types and type variables apart.
+      let query = searchBox.value;
+
+      loadSearchIndex().then((entries) => {
+        // Ignore results for a query that has since been edited
+        if (searchBox.value === query) {
+          renderResults(findEntries(entries, query));
+          searchResults.classList.remove("hidden");
+        }
+      });
+
       // First, show/hide all the sub-entries within each module (top-level functions etc.)
       sidebar.querySelectorAll(".sidebar-sub-entries a").forEach((entry) => {
         if (entry.textContent.toLowerCase().includes(text)) {
@@ -65,4 +223,39 @@
     }
   });
 
+  // Fill in the version selector with every version in versions.json. Picking
+  // one opens the same page in that version's docs.
+  let versionSelector = document.getElementById("version-selector");
+
+  if (versionSelector !== null && config.versions !== "") {
+    let currentVersion = versionSelector.value;
+    let siteRoot = config.siteRoot;
+    let docsRoot = siteRoot.slice(0, siteRoot.length - currentVersion.length - 1);
+
+    fetch(config.versions)
+      .then((response) => response.json())
+      .then((versions) => {
+        versionSelector.replaceChildren(
+          ...versions.map((version) => {
+            let option = document.createElement("option");
+
+            option.value = version;
+            option.textContent = version;
+            option.selected = version === currentVersion;
+
+            return option;
+          })
+        );
+      })
+      .catch(() => {});
+
+    versionSelector.addEventListener("change", () => {
+      let pagePath = window.location.pathname.startsWith(siteRoot)
+        ? window.location.pathname.slice(siteRoot.length)
+        : "";
+
+      window.location.href = `${docsRoot}${versionSelector.value}/${pagePath}${window.location.hash}`;
+    });
+  }
+
 })();
@@ -0,0 +1,99 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod doc_tests {
+    use indoc::indoc;
+    use roc_docs::doc_tests::{doc_tests, inline_doc_tests, DocTest};
+    use roc_docs::load_modules_for_files;
+    use std::path::PathBuf;
+
+    fn fixture_named(file_name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(file_name)
+    }
+
+    fn fixture() -> PathBuf {
+        fixture_named("Examples.roc")
+    }
+
+    fn doc_test(def_name: &str, line: u32, code: &str) -> DocTest {
+        DocTest {
+            def_name: def_name.to_string(),
+            module_name: "Examples".to_string(),
+            path: fixture(),
+            line,
+            column: 4,
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn roc_code_blocks_of_exposed_defs() {
+        let loaded_module = load_modules_for_files(vec![fixture()]).remove(0);
+
+        assert_eq!(
+            doc_tests(&loaded_module),
+            vec![
+                doc_test("double", 10, "double 21 == 42\n"),
+                doc_test("Color", 20, "color = Red\n\ncolor != Green\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn roc_code_blocks_of_imported_modules() {
+        // Package.roc has no docs, but the module it imports from the same directory does
+        let loaded_module = load_modules_for_files(vec![fixture_named("Package.roc")]).remove(0);
+
+        assert_eq!(
+            doc_tests(&loaded_module),
+            vec![
+                doc_test("double", 10, "double 21 == 42\n"),
+                doc_test("Color", 20, "color = Red\n\ncolor != Green\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn inlined_as_expects() {
+        let src = indoc!(
+            r#"
+            interface Examples exposes [Color] imports []
+
+            ## ```roc
+            ## color = Red
+            ##
+            ## color != Green
+            ## ```
+            Color : [Red, Green]
+            "#
+        );
+
+        assert_eq!(
+            inline_doc_tests(
+                &fixture(),
+                src,
+                &[doc_test("Color", 4, "color = Red\n\ncolor != Green\n")],
+            ),
+            // every line keeps its length
+            "interface Examples exposes [Color] imports []\n\nexpect   \n   color = Red\n  \n   color != Green\n## ```\nColor : [Red, Green]\n"
+        );
+    }
+
+    #[test]
+    fn only_the_modules_own_doc_tests_are_inlined() {
+        let src = "interface Other exposes [] imports []\n\n## ```roc\n## 1 == 1\n## ```\n";
+
+        assert_eq!(
+            inline_doc_tests(
+                &fixture_named("Other.roc"),
+                src,
+                &[doc_test("one", 4, "1 == 1\n")],
+            ),
+            src
+        );
+    }
+}
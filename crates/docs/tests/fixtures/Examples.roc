@@ -0,0 +1,31 @@
+interface Examples
+    exposes [double, Color]
+    imports []
+
+## Doubles a number.
+##
+##     This indented block isn't fenced, so it isn't a test.
+##
+## ```roc
+## double 21 == 42
+## ```
+##
+## ```roc-ignore
+## double "not a number"
+## ```
+double : I64 -> I64
+double = \n -> n * 2
+
+## ```roc
+## color = Red
+##
+## color != Green
+## ```
+Color : [Red, Green]
+
+## Not exposed, so this isn't a test:
+##
+## ```roc
+## hidden == 1
+## ```
+hidden = 1
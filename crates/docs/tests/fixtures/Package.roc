@@ -0,0 +1,6 @@
+interface Package
+    exposes [quadruple]
+    imports [Examples]
+
+quadruple : I64 -> I64
+quadruple = \n -> Examples.double (Examples.double n)
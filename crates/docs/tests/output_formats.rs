@@ -0,0 +1,116 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod output_formats {
+    use indoc::indoc;
+    use roc_docs::{json, load_modules_for_files, markdown};
+    use roc_load::docs::Documentation;
+    use std::path::PathBuf;
+
+    fn package() -> Documentation {
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("Examples.roc");
+
+        Documentation {
+            name: "examples".to_string(),
+            version: "0.1.0".to_string(),
+            docs: "Some examples.".to_string(),
+            modules: load_modules_for_files(vec![fixture]),
+        }
+    }
+
+    #[test]
+    fn markdown_page_per_module() {
+        let package = package();
+        let loaded_module = &package.modules[0];
+        let module_docs = &loaded_module.documentation[&loaded_module.module_id];
+
+        assert_eq!(
+            markdown::render_module(module_docs, &["double", "Color"]),
+            indoc!(
+                r#"
+                # Examples
+
+                ## double
+
+                ```roc
+                double : I64 -> I64
+                ```
+
+                Doubles a number.
+
+                    This indented block isn't fenced, so it isn't a test.
+
+                ```roc
+                double 21 == 42
+                ```
+
+                ```roc-ignore
+                double "not a number"
+                ```
+
+                ## Color
+
+                ```roc
+                Color : [Red, Green]
+                ```
+
+                ```roc
+                color = Red
+
+                color != Green
+                ```
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn json_has_only_exposed_defs() {
+        let model = serde_json::to_value(json::package_model(&package())).unwrap();
+
+        assert_eq!(
+            model,
+            serde_json::json!({
+                "name": "examples",
+                "version": "0.1.0",
+                "docs": "Some examples.",
+                "modules": [{
+                    "name": "Examples",
+                    "entries": [
+                        {
+                            "kind": "def",
+                            "name": "double",
+                            "type_vars": [],
+                            "signature": "I64 -> I64",
+                            "type_annotation": {
+                                "kind": "function",
+                                "args": [{ "kind": "apply", "name": "I64", "parts": [] }],
+                                "output": { "kind": "apply", "name": "I64", "parts": [] },
+                            },
+                            "docs": model["modules"][0]["entries"][0]["docs"],
+                        },
+                        {
+                            "kind": "def",
+                            "name": "Color",
+                            "type_vars": [],
+                            "signature": "[Red, Green]",
+                            "type_annotation": {
+                                "kind": "tag-union",
+                                "tags": [
+                                    { "name": "Red", "values": [] },
+                                    { "name": "Green", "values": [] },
+                                ],
+                                "extension": { "kind": "none" },
+                            },
+                            "docs": model["modules"][0]["entries"][1]["docs"],
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+}
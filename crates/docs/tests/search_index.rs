@@ -0,0 +1,134 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod search_index {
+    use roc_docs::search_index::{search_entries, EntryKind, SearchEntry};
+    use roc_load::docs::{AbilityMember, DocDef, DocEntry, Tag, TypeAnnotation};
+
+    fn apply(name: &str, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: name.to_string(),
+            parts,
+        }
+    }
+
+    fn var(name: &str) -> TypeAnnotation {
+        TypeAnnotation::BoundVariable(name.to_string())
+    }
+
+    fn doc_def(name: &str, type_vars: &[&str], type_annotation: TypeAnnotation) -> DocEntry {
+        DocEntry::DocDef(DocDef {
+            name: name.to_string(),
+            type_vars: type_vars.iter().map(|var| var.to_string()).collect(),
+            type_annotation,
+            docs: Some(format!("Docs for {name}.\n\nMore about {name}.")),
+        })
+    }
+
+    fn entry(name: &str, kind: EntryKind, signature: &str, anchor: &str) -> SearchEntry {
+        let summary = match kind {
+            EntryKind::Tag => String::new(),
+            _ => format!("Docs for {name}."),
+        };
+
+        SearchEntry {
+            module: "Thing".to_string(),
+            name: name.to_string(),
+            kind,
+            signature: signature.to_string(),
+            url: format!("/Thing#{anchor}"),
+            summary,
+        }
+    }
+
+    #[test]
+    fn exposed_defs_types_tags_and_ability_members() {
+        let entries = vec![
+            DocEntry::DetachedDoc("A module about things.".to_string()),
+            doc_def(
+                "map",
+                &[],
+                TypeAnnotation::Function {
+                    args: vec![
+                        apply("List", vec![var("a")]),
+                        TypeAnnotation::Function {
+                            args: vec![var("a")],
+                            output: Box::new(var("b")),
+                        },
+                    ],
+                    output: Box::new(apply("List", vec![var("b")])),
+                },
+            ),
+            doc_def(
+                "Outcome",
+                &["ok", "err"],
+                TypeAnnotation::TagUnion {
+                    tags: vec![
+                        Tag {
+                            name: "Done".to_string(),
+                            values: vec![var("ok")],
+                        },
+                        Tag {
+                            name: "Failed".to_string(),
+                            values: vec![var("err"), apply("Str", vec![])],
+                        },
+                        Tag {
+                            name: "Pending".to_string(),
+                            values: vec![],
+                        },
+                    ],
+                    extension: Box::new(TypeAnnotation::NoTypeAnn),
+                },
+            ),
+            doc_def(
+                "Hash",
+                &[],
+                TypeAnnotation::Ability {
+                    members: vec![AbilityMember {
+                        name: "hash".to_string(),
+                        type_annotation: TypeAnnotation::Function {
+                            args: vec![var("a")],
+                            output: Box::new(apply("U64", vec![])),
+                        },
+                        able_variables: vec![("a".to_string(), vec![apply("Hash", vec![])])],
+                        docs: Some("Docs for hash.".to_string()),
+                    }],
+                },
+            ),
+            doc_def("Secret", &[], TypeAnnotation::NoTypeAnn),
+            doc_def("internal", &[], apply("Str", vec![])),
+        ];
+
+        let exposed = ["map", "Outcome", "Hash", "Secret"];
+
+        assert_eq!(
+            search_entries("Thing", "/Thing", &entries, &exposed),
+            vec![
+                entry("map", EntryKind::Value, "List a, (a -> b) -> List b", "map"),
+                entry(
+                    "Outcome",
+                    EntryKind::Type,
+                    "[Done ok, Failed err Str, Pending]",
+                    "Outcome"
+                ),
+                entry("Done", EntryKind::Tag, "ok -> Outcome ok err", "Outcome"),
+                entry(
+                    "Failed",
+                    EntryKind::Tag,
+                    "err, Str -> Outcome ok err",
+                    "Outcome"
+                ),
+                entry("Pending", EntryKind::Tag, "Outcome ok err", "Outcome"),
+                entry("Hash", EntryKind::Ability, "", "Hash"),
+                entry(
+                    "hash",
+                    EntryKind::AbilityMember,
+                    "a -> U64 | a has Hash",
+                    "Hash"
+                ),
+                entry("Secret", EntryKind::Type, "", "Secret"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod versions {
+    use roc_docs::{compare_versions, validate_version};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn numeric_components() {
+        assert_eq!(compare_versions("0.10.0", "0.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("0.1", "0.1.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.2.3", "1.2.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn pre_releases_come_before_the_release() {
+        let mut versions = vec![
+            "1.0.0",
+            "1.0.0-rc.1",
+            "1.0.0-beta.11",
+            "1.0.0-alpha",
+            "1.0.0-beta.2",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "0.9.0",
+        ];
+
+        versions.sort_by(|a, b| compare_versions(a, b));
+
+        assert_eq!(
+            versions,
+            vec![
+                "0.9.0",
+                "1.0.0-alpha",
+                "1.0.0-alpha.1",
+                "1.0.0-alpha.beta",
+                "1.0.0-beta",
+                "1.0.0-beta.2",
+                "1.0.0-beta.11",
+                "1.0.0-rc.1",
+                "1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn build_metadata_is_ignored() {
+        assert_eq!(compare_versions("1.0.0+abc", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0-beta+abc", "1.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn versions_that_would_escape_the_build_dir() {
+        assert!(validate_version("1.0.0-beta").is_ok());
+        assert!(validate_version("").is_err());
+        assert!(validate_version("../1.0.0").is_err());
+        assert!(validate_version("..").is_err());
+        assert!(validate_version("1.0/2.0").is_err());
+        assert!(validate_version("1.0\\2.0").is_err());
+    }
+}
@@ -26,7 +26,7 @@ fn main() -> io::Result<()> {
         roc_files_recursive(os_str, metadata.file_type(), &mut roc_files)?;
     }
 
-    generate_docs_html(roc_files);
+    generate_docs_html(roc_files, None);
 
     Ok(())
 }
@@ -0,0 +1,217 @@
+platform "roc-lang/glue"
+    requires {} { makeGlue : List Types -> Result (List File) Str }
+    exposes []
+    packages {}
+    imports []
+    provides [makeGlueForHost]
+
+## `roc glue --spec` calls this with the types of the platform it's generating glue for,
+## one `Types` per target architecture, and writes the returned files to the output directory.
+makeGlueForHost : List Types -> Result (List File) Str
+makeGlueForHost = makeGlue
+
+## A file to write, e.g. `{ name: "glue.ts", content: "..." }`. The name is relative to the
+## output directory.
+File : {
+    name : Str,
+    content : Str,
+}
+
+# TODO move into separate Target.roc interface once glue works across interfaces.
+Target : {
+    architecture : Architecture,
+    operatingSystem : OperatingSystem,
+}
+
+Architecture : [
+    Aarch32,
+    Aarch64,
+    Wasm32,
+    X86x32,
+    X86x64,
+]
+
+OperatingSystem : [
+    Windows,
+    Unix,
+    Wasi,
+]
+
+## An index into `types`, `sizes` and `aligns`
+TypeId : Nat
+
+Types : {
+    # These are all indexed by TypeId
+    types : List RocType,
+    sizes : List U32,
+    aligns : List U32,
+
+    ## Dependencies - that is, which type depends on which other type.
+    ## This is important for declaration order in C; we need to output a
+    ## type declaration earlier in the file than where it gets referenced by another type.
+    deps : List TypeDeps,
+
+    ## The values the platform exposes to its host, e.g. `mainForHost`, and their types.
+    entryPoints : List EntryPoint,
+    target : Target,
+}
+
+TypeDeps : {
+    id : TypeId,
+    dependsOn : List TypeId,
+}
+
+EntryPoint : {
+    name : Str,
+    id : TypeId,
+}
+
+RocType : [
+    RocStr,
+    Bool,
+    RocResult TypeId TypeId,
+    Num RocNum,
+    RocList TypeId,
+    RocDict TypeId TypeId,
+    RocSet TypeId,
+    RocBox TypeId,
+    TagUnion RocTagUnion,
+    EmptyTagUnion,
+    Struct RocStruct,
+    TagUnionPayload RocTagUnionPayload,
+    ## A recursive pointer, e.g. in StrConsList : [Nil, Cons Str StrConsList],
+    ## this would be the field of Cons containing the (recursive) StrConsList type,
+    ## and the TypeId is the TypeId of StrConsList itself.
+    RecursivePointer TypeId,
+    Function RocFn,
+    # A zero-sized type, such as an empty record or a single-tag union with no payload
+    Unit,
+]
+
+RocStruct : {
+    name : Str,
+    fields : List RocStructField,
+}
+
+RocStructField : {
+    name : Str,
+    type : TypeId,
+}
+
+RocTagUnionPayload : {
+    name : Str,
+    fields : List RocTagUnionPayloadField,
+}
+
+RocTagUnionPayloadField : {
+    discriminant : Nat,
+    type : TypeId,
+}
+
+RocFn : {
+    name : Str,
+    ## If the host can call this closure, the prefix of the symbols the compiler generates for
+    ## it, e.g. `roc__mainForHost_1__Fx` for `roc__mainForHost_1__Fx_caller`.
+    externName : RocExternName,
+    args : List TypeId,
+    ret : TypeId,
+}
+
+RocExternName : [Some Str, None]
+
+RocNum : [
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+    F32,
+    F64,
+    F128,
+    Dec,
+]
+
+RocTag : {
+    name : Str,
+    payload : RocTagPayload,
+}
+
+RocTagPayload : [Some TypeId, None]
+
+RocTagUnion : [
+    Enumeration RocEnumeration,
+    ## A non-recursive tag union
+    ## e.g. `Result a e : [Ok a, Err e]`
+    NonRecursive RocTagUnionInfo,
+    ## A recursive tag union (general case)
+    ## e.g. `Expr : [Sym Str, Add Expr Expr]`
+    Recursive RocTagUnionInfo,
+    ## A recursive tag union that has an empty variant
+    ## Optimization: Represent the empty variant as null pointer => no memory usage & fast comparison
+    ## It has more than one other variant, so they need tag IDs (payloads are "wrapped")
+    ## e.g. `FingerTree a : [Empty, Single a, More (Some a) (FingerTree (Tuple a)) (Some a)]`
+    ## see also: https://youtu.be/ip92VMpf_-A?t=164
+    NullableWrapped RocNullableWrapped,
+    ## Optimization: No need to store a tag ID (the payload is "unwrapped")
+    ## e.g. `RoseTree a : [Tree a (List (RoseTree a))]`
+    NonNullableUnwrapped RocNonNullableUnwrapped,
+    ## Optimization: No need to store a tag ID (the payload is "unwrapped")
+    ## e.g. `[Foo Str Bool]`
+    SingleTagStruct RocSingleTagStruct,
+    ## A recursive tag union with only two variants, where one is empty.
+    ## Optimizations: Use null for the empty variant AND don't store a tag ID for the other variant.
+    ## e.g. `ConsList a : [Nil, Cons a (ConsList a)]`
+    NullableUnwrapped RocNullableUnwrapped,
+]
+
+RocEnumeration : {
+    name : Str,
+    tags : List Str,
+    size : U32,
+}
+
+RocTagUnionInfo : {
+    name : Str,
+    tags : List RocTag,
+    discriminantSize : U32,
+    discriminantOffset : U32,
+}
+
+RocNullableWrapped : {
+    name : Str,
+    indexOfNullTag : U16,
+    tags : List RocTag,
+    discriminantSize : U32,
+    discriminantOffset : U32,
+}
+
+RocNonNullableUnwrapped : {
+    name : Str,
+    tagName : Str,
+    payload : TypeId, # These always have a payload.
+}
+
+RocSingleTagStruct : {
+    name : Str,
+    tagName : Str,
+    payloadFields : List TypeId,
+}
+
+RocNullableUnwrapped : {
+    name : Str,
+    ## e.g. Nil in `StrConsList : [Nil, Cons Str (ConsList Str)]`
+    nullTag : Str,
+    ## e.g. Cons in `StrConsList : [Nil, Cons Str (ConsList Str)]`
+    nonNullTag : Str,
+    ## There must be a payload associated with the non-null tag.
+    ## Otherwise, this would have been an Enumeration!
+    nonNullPayload : TypeId,
+    ## True iff the first tag (alphabetically) is represented by null.
+    ## If this is false, it means the second tag is represented by null instead.
+    nullRepresentsFirstTag : Bool,
+}
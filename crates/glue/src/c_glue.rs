@@ -0,0 +1,791 @@
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types, Unsupported};
+use indexmap::IndexMap;
+use roc_collections::MutSet;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::Write;
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.h");
+const INDENT: &str = "    ";
+
+/// Declares the types for a C host. Where the declarations differ between architectures,
+/// each one gets its own `#if` block. Fails if the host uses types C glue can't declare yet.
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> Result<String, Vec<Unsupported>> {
+    for (types, target_info) in types_and_targets {
+        let unsupported = types.find_unsupported(|typ| unsupported(typ, target_info.architecture));
+
+        if !unsupported.is_empty() {
+            return Err(unsupported);
+        }
+    }
+
+    let mut bodies: IndexMap<String, Vec<TargetInfo>> = IndexMap::default();
+
+    for (types, target_info) in types_and_targets {
+        bodies
+            .entry(emit_for_target(types))
+            .or_default()
+            .push(*target_info);
+    }
+
+    if bodies.len() == 1 {
+        return Ok(bodies.into_iter().next().unwrap().0);
+    }
+
+    let mut buf = String::new();
+
+    for (index, (body, targets)) in bodies.into_iter().enumerate() {
+        let directive = if index == 0 { "#if" } else { "#elif" };
+        let conditions: Vec<_> = targets
+            .iter()
+            .map(|target_info| arch_condition(target_info.architecture))
+            .collect();
+
+        write!(buf, "\n{directive} {}\n{body}", conditions.join(" || ")).unwrap();
+    }
+
+    buf.push_str(
+        "\n#else\n#error \"This glue wasn't generated for the architecture being compiled for\"\n#endif\n",
+    );
+
+    Ok(buf)
+}
+
+/// Why C glue can't declare this type yet, if it can't
+fn unsupported(typ: &RocType, architecture: Architecture) -> Option<&'static str> {
+    match typ {
+        RocType::Num(RocNum::F128) => Some("it contains an F128, which C glue doesn't support"),
+        RocType::Function { .. } => {
+            Some("it contains a function, and C glue can only declare the entry points' functions")
+        }
+        RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. })
+            if tags.len() > max_pointer_tagged_variants(architecture) =>
+        {
+            Some("it contains a recursive tag union with too many tags to store the tag in a pointer, which C glue doesn't support yet")
+        }
+        _ => None,
+    }
+}
+
+/// The preprocessor condition for compiling for this architecture, with GCC, Clang or MSVC
+fn arch_condition(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => "defined(__x86_64__) || defined(_M_X64)",
+        Architecture::X86_32 => "defined(__i386__) || defined(_M_IX86)",
+        Architecture::Aarch64 => "defined(__aarch64__) || defined(_M_ARM64)",
+        Architecture::Aarch32 => "defined(__arm__) || defined(_M_ARM)",
+        Architecture::Wasm32 => "defined(__wasm32__)",
+    }
+}
+
+fn emit_for_target(types: &Types) -> String {
+    let mut buf = String::new();
+    let ids = types.sorted_ids();
+
+    // Recursive tag unions are represented as pointers, so they can be declared before the
+    // payloads that refer back to them.
+    for id in ids.iter() {
+        declare_pointer(*id, types, &mut buf);
+    }
+
+    // C needs the types that are stored by value to be declared before they are used, and
+    // `sorted_ids` doesn't promise that once recursion is involved.
+    let mut added = MutSet::default();
+
+    for id in ids.iter() {
+        add_type_and_deps(*id, types, &mut added, &mut buf);
+    }
+
+    // Now that all the payloads are declared, the recursive tag unions can use them.
+    for id in ids {
+        add_recursive_type(id, types, &mut buf);
+    }
+
+    add_entry_points(types, &mut buf);
+
+    buf
+}
+
+fn declare_pointer(id: TypeId, types: &Types, buf: &mut String) {
+    let (name, pointee) = match types.get_type(id) {
+        RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. }) => {
+            let union_name = format!("union union_{name}");
+
+            write!(
+                buf,
+                "\n{union_name};\n\n// The pointer's unused low bits hold the discriminant.\n"
+            )
+            .unwrap();
+
+            (name, union_name)
+        }
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, payload, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            non_null_payload: payload,
+            ..
+        }) => {
+            buf.push('\n');
+
+            (name, type_name(*payload, types))
+        }
+        _ => return,
+    };
+
+    write!(
+        buf,
+        r#"struct {name} {{
+    {pointee} *pointer;
+}};
+"#
+    )
+    .unwrap();
+
+    add_layout_asserts(&format!("struct {name}"), id, types, buf);
+}
+
+/// Adds the types this one stores by value, followed by this type.
+fn add_type_and_deps(id: TypeId, types: &Types, added: &mut MutSet<TypeId>, buf: &mut String) {
+    if !added.insert(id) {
+        return;
+    }
+
+    let deps: Vec<TypeId> = match types.get_type(id) {
+        RocType::Struct { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnionPayload { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => {
+            payload_fields.clone()
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => {
+            tags.iter().filter_map(|(_, payload)| *payload).collect()
+        }
+        RocType::RocResult(ok_id, err_id) => vec![*ok_id, *err_id],
+        // Everything else refers to other types through pointers, if at all.
+        _ => Vec::new(),
+    };
+
+    for dep in deps {
+        add_type_and_deps(dep, types, added, buf);
+    }
+
+    add_type(id, types, buf);
+}
+
+fn add_type(id: TypeId, types: &Types, buf: &mut String) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => add_struct(
+            name,
+            fields
+                .iter()
+                .map(|(label, id)| (escape_kw(label.clone()), *id)),
+            id,
+            types,
+            buf,
+        ),
+        RocType::TagUnionPayload { name, fields } => add_struct(
+            name,
+            fields.iter().map(|(index, id)| (format!("f{index}"), *id)),
+            id,
+            types,
+            buf,
+        ),
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            add_enumeration(name, name, tags, *size, buf)
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive {
+            name,
+            tags,
+            discriminant_size,
+            discriminant_offset,
+        }) => {
+            // Empty tag unions can never come up at runtime,
+            // and so don't need declared types.
+            if !tags.is_empty() {
+                add_non_recursive_tag_union(
+                    name,
+                    id,
+                    tags,
+                    *discriminant_size,
+                    *discriminant_offset,
+                    types,
+                    buf,
+                )
+            }
+        }
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            payload_fields,
+            ..
+        }) => add_struct(
+            name,
+            payload_fields
+                .iter()
+                .enumerate()
+                .map(|(index, id)| (format!("f{index}"), *id)),
+            id,
+            types,
+            buf,
+        ),
+        RocType::RocResult(ok_id, err_id) => {
+            let tags = [
+                ("Err".to_string(), Some(*err_id)),
+                ("Ok".to_string(), Some(*ok_id)),
+            ];
+
+            // The discriminant comes right after the bigger payload.
+            let discriminant_offset = types
+                .size_rounded_to_alignment(*ok_id)
+                .max(types.size_rounded_to_alignment(*err_id));
+
+            add_non_recursive_tag_union(
+                &result_name(*ok_id, *err_id, types),
+                id,
+                &tags,
+                1,
+                discriminant_offset,
+                types,
+                buf,
+            )
+        }
+        // These are declared in the header, or don't need to be declared in C.
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_) => {}
+        RocType::RecursivePointer { .. } => {
+            // This is recursively pointing to a type that should already have been added,
+            // so no extra work needs to happen.
+        }
+        RocType::Function { .. } => {
+            // The host only calls these through the entry points.
+        }
+        RocType::TagUnion(RocTagUnion::Recursive { .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { .. }) => {
+            // These were declared as pointers up front, and get the rest in add_recursive_type.
+        }
+    }
+}
+
+/// The payloads and discriminant helpers of the tag unions that are declared as pointers
+fn add_recursive_type(id: TypeId, types: &Types, buf: &mut String) {
+    match types.get_type(id) {
+        RocType::TagUnion(RocTagUnion::Recursive {
+            name,
+            tags,
+            discriminant_size,
+            ..
+        }) => {
+            if !tags.is_empty() {
+                add_recursive_tag_union(name, tags, None, *discriminant_size, types, buf)
+            }
+        }
+        RocType::TagUnion(RocTagUnion::NullableWrapped {
+            name,
+            index_of_null_tag,
+            tags,
+            discriminant_size,
+            ..
+        }) => add_recursive_tag_union(
+            name,
+            tags,
+            Some(*index_of_null_tag as usize),
+            *discriminant_size,
+            types,
+            buf,
+        ),
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped {
+            name,
+            tag_name,
+            payload,
+        }) => add_unwrapped_constructor(name, tag_name, *payload, types, buf),
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            null_tag,
+            non_null_tag,
+            non_null_payload,
+            ..
+        }) => add_nullable_unwrapped(name, null_tag, non_null_tag, *non_null_payload, types, buf),
+        _ => {}
+    }
+}
+
+fn add_struct<I>(name: &str, fields: I, id: TypeId, types: &Types, buf: &mut String)
+where
+    I: Iterator<Item = (String, TypeId)>,
+{
+    // C has no zero-sized structs, but then no Roc value needs to be stored in one either.
+    if is_zero_sized(id, types) {
+        return;
+    }
+
+    write!(buf, "\nstruct {name} {{\n").unwrap();
+
+    for (label, field_id) in fields {
+        if !is_zero_sized(field_id, types) {
+            writeln!(buf, "{INDENT}{}", field_decl(&label, field_id, types)).unwrap();
+        }
+    }
+
+    buf.push_str("};\n");
+
+    // The payloads of recursive tag unions are recorded with the size of the pointer to them,
+    // and the payloads of other tag unions are checked by the union's own asserts.
+    if !matches!(types.get_type(id), RocType::TagUnionPayload { .. }) {
+        add_layout_asserts(&format!("struct {name}"), id, types, buf);
+    }
+}
+
+/// e.g. `typedef uint8_t MyEnum;` and a constant for each tag, like `MyEnum_Foo`
+fn add_enumeration(name: &str, prefix: &str, tags: &[String], size: u32, buf: &mut String) {
+    let int_type = unsigned_int_type(size);
+
+    write!(buf, "\ntypedef {int_type} {name};\n\nenum {{\n").unwrap();
+
+    for (index, tag) in tags.iter().enumerate() {
+        writeln!(buf, "{INDENT}{prefix}_{tag} = {index},").unwrap();
+    }
+
+    buf.push_str("};\n");
+}
+
+/// The discriminant of a tag union, e.g. `discriminant_MyTagUnion` with constants like
+/// `discriminant_MyTagUnion_Foo`
+fn add_discriminant(name: &str, tags: &[(String, Option<TypeId>)], size: u32, buf: &mut String) {
+    let discriminant_name = format!("discriminant_{name}");
+    let tag_names: Vec<String> = tags.iter().map(|(tag, _)| tag.clone()).collect();
+
+    add_enumeration(
+        &discriminant_name,
+        &discriminant_name,
+        &tag_names,
+        size,
+        buf,
+    );
+}
+
+/// The members of a tag union's `union`, one per tag with a payload
+fn add_union_members(tags: &[(String, Option<TypeId>)], types: &Types, buf: &mut String) {
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                let member = field_decl(&escape_kw(tag_name.clone()), *payload_id, types);
+
+                writeln!(buf, "{INDENT}{member}").unwrap();
+            }
+        }
+    }
+}
+
+/// Roc puts the discriminant right after the biggest payload, rather than after the whole
+/// `union`, so it lives inside the `union` at a fixed offset.
+fn add_non_recursive_tag_union(
+    name: &str,
+    id: TypeId,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    types: &Types,
+    buf: &mut String,
+) {
+    let size = types.size_rounded_to_alignment(id);
+    let discriminant_name = format!("discriminant_{name}");
+
+    add_discriminant(name, tags, discriminant_size, buf);
+
+    write!(buf, "\nunion {name} {{\n").unwrap();
+    add_union_members(tags, types, buf);
+    writeln!(buf, "{INDENT}uint8_t _sizer[{size}];").unwrap();
+    buf.push_str("};\n");
+
+    add_layout_asserts(&format!("union {name}"), id, types, buf);
+
+    write!(
+        buf,
+        r#"
+static inline {discriminant_name} {name}_discriminant(const union {name} *self) {{
+    return *(const {discriminant_name} *)((const char *)self + {discriminant_offset});
+}}
+
+static inline void {name}_set_discriminant(union {name} *self, {discriminant_name} discriminant) {{
+    *({discriminant_name} *)((char *)self + {discriminant_offset}) = discriminant;
+}}
+"#
+    )
+    .unwrap();
+
+    for (tag_name, opt_payload_id) in tags {
+        let (args, set_payload) = payload_arg(tag_name, *opt_payload_id, "self.", types);
+
+        write!(
+            buf,
+            r#"
+static inline union {name} {name}_make_{tag_name}({args}) {{
+    union {name} self = {{ 0 }};
+{set_payload}
+    {name}_set_discriminant(&self, {discriminant_name}_{tag_name});
+
+    return self;
+}}
+"#
+        )
+        .unwrap();
+    }
+}
+
+/// Tag unions that refer to themselves are heap-allocated, and their values are pointers
+/// to a `union` of the payloads. If there is more than one tag, the discriminant is stored in
+/// the unused low bits of the pointer.
+fn add_recursive_tag_union(
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    null_tag_index: Option<usize>,
+    discriminant_size: u32,
+    types: &Types,
+    buf: &mut String,
+) {
+    let architecture = types.target().architecture;
+
+    // emit rejects unions with more tags than this up front.
+    debug_assert!(tags.len() <= max_pointer_tagged_variants(architecture));
+
+    let union_name = format!("union union_{name}");
+    let discriminant_name = format!("discriminant_{name}");
+    let bitmask = format!("{:#x}", tagged_pointer_bitmask(architecture));
+    let has_discriminant = discriminant_size > 0 && tags.len() > 1;
+
+    if has_discriminant {
+        add_discriminant(name, tags, discriminant_size, buf);
+    }
+
+    write!(buf, "\n{union_name} {{\n").unwrap();
+    add_union_members(tags, types, buf);
+    buf.push_str("};\n");
+
+    write!(
+        buf,
+        r#"
+static inline {union_name} *{name}_union_pointer(struct {name} self) {{
+    return ({union_name} *)((uintptr_t)self.pointer & ~(uintptr_t){bitmask});
+}}
+"#
+    )
+    .unwrap();
+
+    if has_discriminant {
+        let null_check = match null_tag_index {
+            Some(index) => format!(
+                "\n{INDENT}if (self.pointer == NULL) {{\n{INDENT}{INDENT}return {discriminant_name}_{};\n{INDENT}}}\n",
+                tags[index].0
+            ),
+            None => String::new(),
+        };
+
+        write!(
+            buf,
+            r#"
+static inline {discriminant_name} {name}_discriminant(struct {name} self) {{{null_check}
+    return ({discriminant_name})((uintptr_t)self.pointer & {bitmask});
+}}
+"#
+        )
+        .unwrap();
+    }
+
+    for (tag_index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+        if Some(tag_index) == null_tag_index {
+            write!(
+                buf,
+                r#"
+static inline struct {name} {name}_make_{tag_name}(void) {{
+    struct {name} self = {{ NULL }};
+
+    return self;
+}}
+"#
+            )
+            .unwrap();
+
+            continue;
+        }
+
+        let (args, set_payload) = payload_arg(tag_name, *opt_payload_id, "pointer->", types);
+        let tagged = if has_discriminant {
+            format!("(uintptr_t)pointer | {discriminant_name}_{tag_name}")
+        } else {
+            "(uintptr_t)pointer".to_string()
+        };
+
+        write!(
+            buf,
+            r#"
+static inline struct {name} {name}_make_{tag_name}({args}) {{
+    {union_name} *pointer = roc_alloc_refcounted(sizeof({union_name}), _Alignof({union_name}));
+{set_payload}
+    struct {name} self = {{ ({union_name} *)({tagged}) }};
+
+    return self;
+}}
+"#
+        )
+        .unwrap();
+    }
+}
+
+/// A recursive tag union with only one tag, which points straight at the payload.
+fn add_unwrapped_constructor(
+    name: &str,
+    tag_name: &str,
+    payload_id: TypeId,
+    types: &Types,
+    buf: &mut String,
+) {
+    let payload_type = type_name(payload_id, types);
+
+    write!(
+        buf,
+        r#"
+static inline struct {name} {name}_make_{tag_name}({payload_type} payload) {{
+    struct {name} self = {{ roc_alloc_refcounted(sizeof({payload_type}), _Alignof({payload_type})) }};
+
+    *self.pointer = payload;
+
+    return self;
+}}
+"#
+    )
+    .unwrap();
+}
+
+/// A recursive tag union with two tags, where a null pointer means the tag without a payload,
+/// and any other pointer points straight at the payload of the other tag.
+fn add_nullable_unwrapped(
+    name: &str,
+    null_tag: &str,
+    non_null_tag: &str,
+    non_null_payload: TypeId,
+    types: &Types,
+    buf: &mut String,
+) {
+    let mut tags = vec![
+        (null_tag.to_string(), None),
+        (non_null_tag.to_string(), Some(non_null_payload)),
+    ];
+
+    tags.sort();
+
+    let discriminant_name = format!("discriminant_{name}");
+
+    add_discriminant(name, &tags, 1, buf);
+
+    write!(
+        buf,
+        r#"
+static inline {discriminant_name} {name}_discriminant(struct {name} self) {{
+    if (self.pointer == NULL) {{
+        return {discriminant_name}_{null_tag};
+    }} else {{
+        return {discriminant_name}_{non_null_tag};
+    }}
+}}
+
+static inline struct {name} {name}_make_{null_tag}(void) {{
+    struct {name} self = {{ NULL }};
+
+    return self;
+}}
+"#
+    )
+    .unwrap();
+
+    add_unwrapped_constructor(name, non_null_tag, non_null_payload, types, buf);
+}
+
+/// The `roc__*_exposed_generic` functions the host calls. They write their result to the
+/// pointer in their first argument.
+fn add_entry_points(types: &Types, buf: &mut String) {
+    if types.entry_points().is_empty() {
+        return;
+    }
+
+    buf.push('\n');
+
+    for (name, id) in types.entry_points() {
+        let mut params = Vec::new();
+
+        let ret_id = match types.get_type(*id) {
+            RocType::Function { args, ret, .. } => {
+                for (index, arg_id) in args.iter().enumerate() {
+                    let arg_type = type_name(*arg_id, types);
+
+                    // Strings and lists are passed by reference; everything else by value.
+                    match types.get_type(*arg_id) {
+                        RocType::RocStr | RocType::RocList(_) => {
+                            params.push(format!("{arg_type} *arg{index}"))
+                        }
+                        _ if is_zero_sized(*arg_id, types) => {}
+                        _ => params.push(format!("{arg_type} arg{index}")),
+                    }
+                }
+
+                *ret
+            }
+            _ => *id,
+        };
+
+        params.insert(0, format!("{} *ret", type_name(ret_id, types)));
+
+        writeln!(
+            buf,
+            "extern void roc__{name}_1_exposed_generic({});",
+            params.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+/// The parameters of a tag's constructor, and the statement that stores them in the payload.
+fn payload_arg(
+    tag_name: &str,
+    opt_payload_id: Option<TypeId>,
+    target: &str,
+    types: &Types,
+) -> (String, String) {
+    match opt_payload_id {
+        Some(payload_id) if !is_zero_sized(payload_id, types) => (
+            format!("{} payload", type_name(payload_id, types)),
+            format!(
+                "{INDENT}{target}{} = payload;\n",
+                escape_kw(tag_name.to_string())
+            ),
+        ),
+        _ => ("void".to_string(), String::new()),
+    }
+}
+
+fn add_layout_asserts(c_type: &str, id: TypeId, types: &Types, buf: &mut String) {
+    let size = types.size_rounded_to_alignment(id);
+    let align = types.align(id);
+
+    write!(
+        buf,
+        r#"
+_Static_assert(sizeof({c_type}) == {size}, "{c_type} should be {size} bytes, like in Roc");
+_Static_assert(_Alignof({c_type}) == {align}, "{c_type} should be aligned to {align} bytes, like in Roc");
+"#
+    )
+    .unwrap();
+}
+
+/// A struct field or union member, including the semicolon. C has no generics, so collections
+/// get a comment saying what their elements are.
+fn field_decl(label: &str, id: TypeId, types: &Types) -> String {
+    let c_type = type_name(id, types);
+
+    match types.get_type(id) {
+        RocType::RocList(elem_id) | RocType::RocSet(elem_id) => {
+            format!("{c_type} {label}; // of {}", type_name(*elem_id, types))
+        }
+        RocType::RocDict(key_id, val_id) => format!(
+            "{c_type} {label}; // of {} -> {}",
+            type_name(*key_id, types),
+            type_name(*val_id, types)
+        ),
+        _ => format!("{c_type} {label};"),
+    }
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    if is_zero_sized(id, types) {
+        return "void".to_string();
+    }
+
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::RocStr => "struct RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(RocNum::U8) => "uint8_t".to_string(),
+        RocType::Num(RocNum::U16) => "uint16_t".to_string(),
+        RocType::Num(RocNum::U32) => "uint32_t".to_string(),
+        RocType::Num(RocNum::U64) => "uint64_t".to_string(),
+        RocType::Num(RocNum::U128) => "roc_u128".to_string(),
+        RocType::Num(RocNum::I8) => "int8_t".to_string(),
+        RocType::Num(RocNum::I16) => "int16_t".to_string(),
+        RocType::Num(RocNum::I32) => "int32_t".to_string(),
+        RocType::Num(RocNum::I64) => "int64_t".to_string(),
+        RocType::Num(RocNum::I128) => "roc_i128".to_string(),
+        RocType::Num(RocNum::F32) => "float".to_string(),
+        RocType::Num(RocNum::F64) => "double".to_string(),
+        RocType::Num(RocNum::F128) => unreachable!("`emit` rejects F128"),
+        RocType::Num(RocNum::Dec) => "struct RocDec".to_string(),
+        RocType::RocDict(_, _) => "struct RocDict".to_string(),
+        RocType::RocSet(_) => "struct RocSet".to_string(),
+        RocType::RocList(_) => "struct RocList".to_string(),
+        RocType::RocBox(elem_id) => format!("{} *", type_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => {
+            format!("union {}", result_name(*ok_id, *err_id, types))
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, .. }) => name.clone(),
+        RocType::TagUnion(RocTagUnion::NonRecursive { name, .. }) => format!("union {name}"),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => format!("struct {name}"),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+        RocType::Function { .. } => unreachable!("`emit` rejects functions outside entry points"),
+    }
+}
+
+/// C has no generics, so each Result gets a name of its own, e.g. `RocResult_RocStr_uint8_t`
+pub(crate) fn result_name(ok_id: TypeId, err_id: TypeId, types: &Types) -> String {
+    let identifier = |id| {
+        type_name(id, types)
+            .trim_start_matches("struct ")
+            .trim_start_matches("union ")
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+    };
+
+    format!("RocResult_{}_{}", identifier(ok_id), identifier(err_id))
+}
+
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn unsigned_int_type(size: u32) -> &'static str {
+    match size {
+        1 => "uint8_t",
+        2 => "uint16_t",
+        4 => "uint32_t",
+        8 => "uint64_t",
+        _ => unreachable!("Invalid discriminant size: {}", size),
+    }
+}
+
+// Based on https://en.cppreference.com/w/c/keyword
+const RESERVED_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "bool", "true", "false",
+];
+
+/// Escape a C reserved keyword, if necessary.
+pub(crate) fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        format!("{input}_")
+    } else {
+        input
+    }
+}
@@ -0,0 +1,176 @@
+use crate::rust_glue::max_pointer_tagged_variants;
+use crate::types::{RocTagUnion, RocType, TypeId, Types};
+use roc_collections::VecMap;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt;
+
+/// A way the types of a platform differ between targets that glue covering all of them
+/// can't account for. Differences in size and alignment alone are fine, because each
+/// target gets its own declarations where they differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// The type isn't the same on every target, so the declarations for each target
+    /// don't line up with each other.
+    Shape {
+        name: String,
+        expected_on: Architecture,
+        differs_on: Vec<Architecture>,
+    },
+    /// The recursive tag union has too many tags to store its discriminant in the unused
+    /// bits of its pointer on these targets, which glue doesn't support yet.
+    TooManyTags {
+        name: String,
+        tags: usize,
+        architectures: Vec<Architecture>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Shape {
+                name,
+                expected_on,
+                differs_on,
+            } => write!(
+                f,
+                "{name} is a different type on {} than on {:?}, so its glue can't be shared between them.",
+                arch_list(differs_on),
+                expected_on
+            ),
+            Divergence::TooManyTags {
+                name,
+                tags,
+                architectures,
+            } => write!(
+                f,
+                "{name} has {tags} tags, which is too many to store in the unused bits of its pointer on {}. Glue doesn't support storing the discriminant anywhere else yet.",
+                arch_list(architectures)
+            ),
+        }
+    }
+}
+
+fn arch_list(architectures: &[Architecture]) -> String {
+    let names: Vec<_> = architectures
+        .iter()
+        .map(|architecture| format!("{:?}", architecture))
+        .collect();
+
+    names.join(", ")
+}
+
+/// Checks that one set of glue can cover all of these targets.
+pub fn check(types_and_targets: &[(Types, TargetInfo)]) -> Result<(), Vec<Divergence>> {
+    let mut divergences = Vec::new();
+
+    if let Some(((first_types, first_target), rest)) = types_and_targets.split_first() {
+        let mut differing: VecMap<TypeId, Vec<Architecture>> = VecMap::default();
+
+        for (types, target_info) in rest {
+            let architecture = target_info.architecture;
+
+            for (id, other_id) in first_types.ids().zip(types.ids()) {
+                if shape(first_types.get_type(id)) != shape(types.get_type(other_id)) {
+                    differing.get_or_insert(id, Vec::new).push(architecture);
+                }
+            }
+
+            // One of them has types the other doesn't
+            let (longer, shorter) = if types.ids().len() > first_types.ids().len() {
+                (types, first_types)
+            } else {
+                (first_types, types)
+            };
+
+            if let Some(id) = longer.ids().nth(shorter.ids().len()) {
+                divergences.push(Divergence::Shape {
+                    name: type_name(longer.get_type(id)),
+                    expected_on: first_target.architecture,
+                    differs_on: vec![architecture],
+                });
+            }
+        }
+
+        for (id, differs_on) in differing {
+            divergences.push(Divergence::Shape {
+                name: type_name(first_types.get_type(id)),
+                expected_on: first_target.architecture,
+                differs_on,
+            });
+        }
+
+        // Recursive tag unions are the same shape on every target, so checking the tags
+        // of the first target's unions covers all of them.
+        for id in first_types.ids() {
+            let (name, tags) = match first_types.get_type(id) {
+                RocType::TagUnion(RocTagUnion::Recursive { name, tags, .. })
+                | RocType::TagUnion(RocTagUnion::NullableWrapped { name, tags, .. }) => {
+                    (name, tags.len())
+                }
+                _ => continue,
+            };
+
+            let architectures: Vec<_> = types_and_targets
+                .iter()
+                .map(|(_, target_info)| target_info.architecture)
+                .filter(|architecture| tags > max_pointer_tagged_variants(*architecture))
+                .collect();
+
+            if !architectures.is_empty() {
+                divergences.push(Divergence::TooManyTags {
+                    name: name.clone(),
+                    tags,
+                    architectures,
+                });
+            }
+        }
+    }
+
+    if divergences.is_empty() {
+        Ok(())
+    } else {
+        Err(divergences)
+    }
+}
+
+/// The type without the parts of it that depend on the target's sizes and alignments
+fn shape(typ: &RocType) -> RocType {
+    let mut typ = typ.clone();
+
+    if let RocType::TagUnion(
+        RocTagUnion::NonRecursive {
+            discriminant_offset,
+            ..
+        }
+        | RocTagUnion::Recursive {
+            discriminant_offset,
+            ..
+        }
+        | RocTagUnion::NullableWrapped {
+            discriminant_offset,
+            ..
+        },
+    ) = &mut typ
+    {
+        *discriminant_offset = 0;
+    }
+
+    typ
+}
+
+fn type_name(typ: &RocType) -> String {
+    match typ {
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::Function { name, .. }
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. }) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
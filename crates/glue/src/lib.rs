@@ -1,10 +1,16 @@
+pub mod c_glue;
+pub mod cross_target;
 pub mod enums;
 pub mod load;
+pub mod round_trip;
 pub mod rust_glue;
 pub mod structs;
+pub mod swift_glue;
 pub mod types;
+pub mod zig_glue;
 
+/// The Rust side of `platform/RocType.roc`, generated by running `roc glue` on it
 #[rustfmt::skip]
-pub mod glue;
+pub mod roc_type;
 
-pub use load::generate;
+pub use load::{generate, generate_round_trip};
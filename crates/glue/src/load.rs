@@ -1,11 +1,14 @@
-use crate::rust_glue;
-use crate::types::{Env, Types};
+use crate::types::{Env, Types, Unsupported};
+use crate::{c_glue, cross_target, round_trip, rust_glue, swift_glue, zig_glue};
 use bumpalo::Bump;
 use roc_intern::GlobalInterner;
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_module::symbol::Symbol;
 use roc_reporting::report::RenderTarget;
 use roc_target::{Architecture, OperatingSystem, TargetInfo};
-use std::fs::File;
+use roc_types::subs::Variable;
+use std::ffi::OsStr;
+use std::fs::{self, File};
 use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::process;
@@ -20,62 +23,305 @@ impl IgnoreErrors {
     const NONE: Self = IgnoreErrors { can: false };
 }
 
+/// The languages `roc glue` can generate glue for, chosen by the glue file's extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+    Zig,
+    /// Swift glue goes through C, so this also generates a C header next to the Swift file.
+    Swift,
+}
+
+impl Language {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("rs") => Some(Language::Rust),
+            Some("h") => Some(Language::C),
+            Some("zig") => Some(Language::Zig),
+            Some("swift") => Some(Language::Swift),
+            _ => None,
+        }
+    }
+}
+
 pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
+    let language = match Language::from_path(output_path) {
+        Some(language) => language,
+        None => {
+            eprintln!(
+                "`roc glue` can generate Rust glue (a .rs file), a C header (a .h file), Zig glue (a .zig file) or Swift glue (a .swift file), so I don't know what to generate for {}",
+                output_path.display()
+            );
+
+            return Ok(1);
+        }
+    };
+
     match load_types(
         input_path.to_path_buf(),
         Threading::AllAvailable,
         IgnoreErrors::NONE,
     ) {
         Ok(types_and_targets) => {
-            let mut file = File::create(output_path).unwrap_or_else(|err| {
-                eprintln!(
-                    "Unable to create output file {} - {:?}",
-                    output_path.display(),
-                    err
-                );
+            if !covers_every_architecture(input_path, &types_and_targets) {
+                return Ok(1);
+            }
 
-                process::exit(1);
-            });
+            let files = match language {
+                Language::Rust => {
+                    let mut buf = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
 
-            let mut buf = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
-            let body = rust_glue::emit(&types_and_targets);
+                    buf.push_str(&rust_glue::emit(&types_and_targets));
 
-            buf.push_str(&body);
+                    vec![(output_path.to_path_buf(), buf)]
+                }
+                Language::C => {
+                    let mut buf = std::str::from_utf8(c_glue::HEADER).unwrap().to_string();
 
-            file.write_all(buf.as_bytes()).unwrap_or_else(|err| {
-                eprintln!(
-                    "Unable to write bindings to output file {} - {:?}",
-                    output_path.display(),
-                    err
-                );
+                    match c_glue::emit(&types_and_targets) {
+                        Ok(body) => buf.push_str(&body),
+                        Err(unsupported) => {
+                            report_unsupported("C", input_path, &unsupported);
+
+                            return Ok(1);
+                        }
+                    }
+
+                    vec![(output_path.to_path_buf(), buf)]
+                }
+                Language::Zig => {
+                    let mut buf = std::str::from_utf8(zig_glue::HEADER).unwrap().to_string();
+
+                    match zig_glue::emit(&types_and_targets) {
+                        Ok(body) => buf.push_str(&body),
+                        Err(unsupported) => {
+                            report_unsupported("Zig", input_path, &unsupported);
+
+                            return Ok(1);
+                        }
+                    }
+
+                    vec![(output_path.to_path_buf(), buf)]
+                }
+                Language::Swift => {
+                    let header_path = output_path.with_extension("h");
+                    let header_name = header_path.file_name().unwrap().to_string_lossy();
+
+                    // Swift glue supports fewer types than the C header it goes through, so
+                    // this reports everything Swift can't declare.
+                    let swift_and_c =
+                        swift_glue::emit(host_types(&types_and_targets), &header_name)
+                            .and_then(|swift| c_glue::emit(&types_and_targets).map(|c| (swift, c)));
+
+                    let (swift, c) = match swift_and_c {
+                        Ok(glue) => glue,
+                        Err(unsupported) => {
+                            report_unsupported("Swift", input_path, &unsupported);
+
+                            return Ok(1);
+                        }
+                    };
+
+                    let mut header = std::str::from_utf8(c_glue::HEADER).unwrap().to_string();
 
-                process::exit(1);
-            });
+                    header.push_str(&c);
+
+                    let mut buf = std::str::from_utf8(swift_glue::HEADER).unwrap().to_string();
+
+                    buf.push_str(&swift);
+
+                    vec![
+                        (output_path.to_path_buf(), buf),
+                        (header_path.clone(), header),
+                    ]
+                }
+            };
+
+            write_files(&files);
 
             println!(
-                "🎉 Generated type declarations in:\n\n\t{}",
-                output_path.display()
+                "🎉 Generated type declarations in:\n\n{}",
+                list_paths(&files)
             );
 
             Ok(0)
         }
-        Err(err) => match err.kind() {
-            ErrorKind::NotFound => {
-                eprintln!("Platform module file not found: {}", input_path.display());
-                process::exit(1);
+        Err(err) => report_load_error(input_path, err),
+    }
+}
+
+/// Generates Rust glue for the platform, along with a Roc app and a Rust host in the output
+/// directory that round-trip values of every type the platform exposes. Running the app
+/// with `roc` checks that the glue agrees with the compiler about how they're laid out.
+pub fn generate_round_trip(input_path: &Path, output_dir: &Path) -> io::Result<i32> {
+    match load_types(
+        input_path.to_path_buf(),
+        Threading::AllAvailable,
+        IgnoreErrors::NONE,
+    ) {
+        Ok(types_and_targets) => {
+            if !covers_every_architecture(input_path, &types_and_targets) {
+                return Ok(1);
             }
-            error => {
+
+            let harness = round_trip::emit(host_types(&types_and_targets));
+
+            if harness.round_tripped.is_empty() {
                 eprintln!(
-                    "Error loading platform module file {} - {:?}",
-                    input_path.display(),
-                    error
+                    "{} doesn't expose any types that `roc glue --round-trip` can round-trip.",
+                    input_path.display()
                 );
-                process::exit(1);
+
+                for (name, reason) in harness.skipped.iter() {
+                    eprintln!("    {name} isn't round-tripped, because {reason}.");
+                }
+
+                return Ok(1);
             }
-        },
+
+            let mut glue = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
+
+            glue.push_str(&rust_glue::emit(&types_and_targets));
+
+            // The harness is for checking glue while working on the compiler, so it uses the
+            // roc_std next to this crate rather than a published one.
+            let roc_std_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join("roc_std");
+            let cargo_toml = round_trip::CARGO_TOML.replace(
+                round_trip::ROC_STD_PATH,
+                &roc_std_path.display().to_string(),
+            );
+
+            let src_dir = output_dir.join("src");
+            let files = vec![
+                (output_dir.join("platform.roc"), harness.platform),
+                (output_dir.join("app.roc"), harness.app),
+                (output_dir.join("Cargo.toml"), cargo_toml),
+                (
+                    output_dir.join("build.rs"),
+                    round_trip::BUILD_RS.to_string(),
+                ),
+                (output_dir.join("host.c"), round_trip::HOST_C.to_string()),
+                (src_dir.join("main.rs"), round_trip::MAIN_RS.to_string()),
+                (
+                    src_dir.join("roc_externs.rs"),
+                    round_trip::ROC_EXTERNS_RS.to_string(),
+                ),
+                (src_dir.join("lib.rs"), harness.host),
+                (src_dir.join("glue.rs"), glue),
+            ];
+
+            fs::create_dir_all(&src_dir)?;
+
+            write_files(&files);
+
+            println!(
+                "🎉 Generated a round trip test for {} in:\n\n{}\n\nRun it with `roc {}`",
+                harness.round_tripped.join(", "),
+                list_paths(&files),
+                output_dir.join("app.roc").display()
+            );
+
+            Ok(0)
+        }
+        Err(err) => report_load_error(input_path, err),
+    }
+}
+
+fn covers_every_architecture(input_path: &Path, types_and_targets: &[(Types, TargetInfo)]) -> bool {
+    match cross_target::check(types_and_targets) {
+        Ok(()) => true,
+        Err(divergences) => {
+            eprintln!(
+                "`roc glue` can't generate glue that covers every architecture for {}:\n",
+                input_path.display()
+            );
+
+            for divergence in divergences {
+                eprintln!("    {}", divergence);
+            }
+
+            false
+        }
+    }
+}
+
+fn report_unsupported(language: &str, input_path: &Path, unsupported: &[Unsupported]) {
+    eprintln!(
+        "`roc glue` can't generate {language} glue for {} yet:\n",
+        input_path.display()
+    );
+
+    for unsupported in unsupported {
+        eprintln!("    {}", unsupported);
+    }
+}
+
+fn write_files(files: &[(PathBuf, String)]) {
+    for (path, buf) in files.iter() {
+        let mut file = File::create(path).unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to create output file {} - {:?}",
+                path.display(),
+                err
+            );
+
+            process::exit(1);
+        });
+
+        file.write_all(buf.as_bytes()).unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to write bindings to output file {} - {:?}",
+                path.display(),
+                err
+            );
+
+            process::exit(1);
+        });
+    }
+}
+
+fn list_paths(files: &[(PathBuf, String)]) -> String {
+    let paths: Vec<_> = files
+        .iter()
+        .map(|(path, _)| format!("\t{}", path.display()))
+        .collect();
+
+    paths.join("\n")
+}
+
+fn report_load_error(input_path: &Path, err: io::Error) -> ! {
+    match err.kind() {
+        ErrorKind::NotFound => {
+            eprintln!("Platform module file not found: {}", input_path.display());
+            process::exit(1);
+        }
+        error => {
+            eprintln!(
+                "Error loading platform module file {} - {:?}",
+                input_path.display(),
+                error
+            );
+            process::exit(1);
+        }
     }
 }
 
+/// The types for the architecture `roc glue` is running on. Swift glue only needs these,
+/// because it goes through the C header, and `cross_target::check` has made sure the other
+/// architectures have the same types.
+fn host_types(types_and_targets: &[(Types, TargetInfo)]) -> &Types {
+    let architecture = TargetInfo::from(&Triple::host()).architecture;
+
+    types_and_targets
+        .iter()
+        .find(|(_, target_info)| target_info.architecture == architecture)
+        .map(|(types, _)| types)
+        .unwrap_or_else(|| panic!("No types were generated for {:?}", architecture))
+}
+
 pub fn load_types(
     full_file_path: PathBuf,
     threading: Threading,
@@ -92,6 +338,7 @@ pub fn load_types(
         mut declarations_by_id,
         mut solved,
         interns,
+        exposed_to_host,
         ..
     } = roc_load::load_and_typecheck(
         arena,
@@ -102,6 +349,8 @@ pub fn load_types(
             render: RenderTarget::Generic,
             threading,
             exec_mode: ExecutionMode::Check,
+            cache_dir: None,
+            coverage: false,
         },
     )
     .unwrap_or_else(|problem| match problem {
@@ -150,6 +399,27 @@ pub fn load_types(
         }
     });
 
+    let mut entry_points: Vec<(String, Variable)> = exposed_to_host
+        .iter()
+        .map(|(symbol, var)| (symbol.as_str(&interns).to_string(), *var))
+        .collect();
+
+    entry_points.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    // The closures the entry points return, like the `Fx` in `mainForHost : Effect {} as Fx`,
+    // which the host calls through the `roc__mainForHost_1__Fx_caller` the compiler generates
+    let host_exposed_aliases: Vec<(Symbol, Variable, String)> = (0..decls.len())
+        .filter(|index| exposed_to_host.contains_key(&decls.symbols[*index].value))
+        .flat_map(|index| {
+            let entry_point = decls.symbols[index].value.as_str(&interns).to_string();
+
+            decls.annotations[index]
+                .iter()
+                .flat_map(|annotation| annotation.introduced_variables.host_exposed_aliases.iter())
+                .map(move |(alias, var)| (*alias, *var, entry_point.clone()))
+        })
+        .collect();
+
     let layout_interner = GlobalInterner::with_capacity(128);
 
     let architectures = Architecture::iter();
@@ -163,7 +433,7 @@ pub fn load_types(
         let types = {
             let mut env = Env::new(arena, subs, &interns, layout_interner.fork(), target_info);
 
-            env.vars_to_types(variables.clone())
+            env.vars_to_types(variables.clone(), &entry_points, &host_exposed_aliases)
         };
 
         types_and_targets.push((types, target_info));
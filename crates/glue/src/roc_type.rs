@@ -18,17 +18,33 @@
 
 #[cfg(any(
     target_arch = "arm",
+    target_arch = "aarch64",
     target_arch = "wasm32",
-    target_arch = "x86"
+    target_arch = "x86",
+    target_arch = "x86_64"
+))]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct File {
+    pub content: roc_std::RocStr,
+    pub name: roc_std::RocStr,
+}
+
+#[cfg(any(
+    target_arch = "arm",
+    target_arch = "aarch64",
+    target_arch = "wasm32",
+    target_arch = "x86",
+    target_arch = "x86_64"
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct Types {
     pub aligns: roc_std::RocList<u32>,
-    pub deps: roc_std::RocDict<u32, roc_std::RocList<u32>>,
+    pub deps: roc_std::RocList<TypeDeps>,
+    pub entryPoints: roc_std::RocList<EntryPoint>,
     pub sizes: roc_std::RocList<u32>,
     pub types: roc_std::RocList<RocType>,
-    pub typesByName: roc_std::RocDict<roc_std::RocStr, u32>,
     pub target: Target,
 }
 
@@ -88,17 +104,17 @@ impl core::fmt::Debug for discriminant_RocType {
 ))]
 #[repr(C)]
 pub union RocType {
-    Function: core::mem::ManuallyDrop<R1>,
+    Function: core::mem::ManuallyDrop<RocFn>,
     Num: RocNum,
     RecursivePointer: u32,
     RocBox: u32,
     RocDict: RocType_RocDict,
     RocList: u32,
-    RocResult: RocType_RocResult,
+    RocResult: RocType_RocDict,
     RocSet: u32,
-    Struct: core::mem::ManuallyDrop<R2>,
+    Struct: core::mem::ManuallyDrop<RocStruct>,
     TagUnion: core::mem::ManuallyDrop<RocTagUnion>,
-    TagUnionPayload: core::mem::ManuallyDrop<R14>,
+    TagUnionPayload: core::mem::ManuallyDrop<RocTagUnionPayload>,
     _sizer: [u8; 52],
 }
 
@@ -109,7 +125,7 @@ pub union RocType {
 ))]
 #[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R15 {
+pub struct RocTagUnionPayloadField {
     pub discriminant: u32,
     pub r#type: u32,
 }
@@ -123,37 +139,33 @@ pub struct R15 {
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R12 {
+pub struct RocTag {
     pub name: roc_std::RocStr,
-    pub payload: U4,
+    pub payload: RocTagPayload,
 }
 
 #[cfg(any(
     target_arch = "arm",
-    target_arch = "aarch64",
     target_arch = "wasm32",
-    target_arch = "x86",
-    target_arch = "x86_64"
+    target_arch = "x86"
 ))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R10 {
+pub struct RocStructField {
     pub name: roc_std::RocStr,
-    pub payload: U3,
+    pub r#type: u32,
 }
 
 #[cfg(any(
     target_arch = "arm",
-    target_arch = "aarch64",
     target_arch = "wasm32",
-    target_arch = "x86",
-    target_arch = "x86_64"
+    target_arch = "x86"
 ))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R7 {
+pub struct EntryPoint {
+    pub id: u32,
     pub name: roc_std::RocStr,
-    pub payload: U1,
 }
 
 #[cfg(any(
@@ -163,9 +175,9 @@ pub struct R7 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R3 {
-    pub name: roc_std::RocStr,
-    pub r#type: u32,
+pub struct TypeDeps {
+    pub dependsOn: roc_std::RocList<u32>,
+    pub id: u32,
 }
 
 #[cfg(any(
@@ -245,8 +257,8 @@ impl core::fmt::Debug for Architecture {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R14 {
-    pub fields: roc_std::RocList<R15>,
+pub struct RocTagUnionPayload {
+    pub fields: roc_std::RocList<RocTagUnionPayloadField>,
     pub name: roc_std::RocStr,
 }
 
@@ -290,13 +302,13 @@ impl core::fmt::Debug for discriminant_RocTagUnion {
 ))]
 #[repr(C)]
 pub union RocTagUnion {
-    Enumeration: core::mem::ManuallyDrop<R4>,
-    NonNullableUnwrapped: core::mem::ManuallyDrop<R5>,
-    NonRecursive: core::mem::ManuallyDrop<R6>,
-    NullableUnwrapped: core::mem::ManuallyDrop<R8>,
-    NullableWrapped: core::mem::ManuallyDrop<R9>,
-    Recursive: core::mem::ManuallyDrop<R11>,
-    SingleTagStruct: core::mem::ManuallyDrop<R13>,
+    Enumeration: core::mem::ManuallyDrop<RocEnumeration>,
+    NonNullableUnwrapped: core::mem::ManuallyDrop<RocNonNullableUnwrapped>,
+    NonRecursive: core::mem::ManuallyDrop<RocTagUnionInfo>,
+    NullableUnwrapped: core::mem::ManuallyDrop<RocNullableUnwrapped>,
+    NullableWrapped: core::mem::ManuallyDrop<RocNullableWrapped>,
+    Recursive: core::mem::ManuallyDrop<RocTagUnionInfo>,
+    SingleTagStruct: core::mem::ManuallyDrop<RocSingleTagStruct>,
     _sizer: [u8; 48],
 }
 
@@ -307,7 +319,7 @@ pub union RocTagUnion {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R13 {
+pub struct RocSingleTagStruct {
     pub name: roc_std::RocStr,
     pub payloadFields: roc_std::RocList<u32>,
     pub tagName: roc_std::RocStr,
@@ -320,132 +332,27 @@ pub struct R13 {
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R11 {
-    pub discriminantOffset: u32,
-    pub discriminantSize: u32,
-    pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R12>,
-}
-
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "aarch64",
-    target_arch = "wasm32",
-    target_arch = "x86",
-    target_arch = "x86_64"
-))]
-#[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(u8)]
-pub enum discriminant_U4 {
-    None = 0,
-    Some = 1,
-}
-
-impl core::fmt::Debug for discriminant_U4 {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::None => f.write_str("discriminant_U4::None"),
-            Self::Some => f.write_str("discriminant_U4::Some"),
-        }
-    }
-}
-
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "wasm32",
-    target_arch = "x86"
-))]
-#[repr(C)]
-pub union U4 {
-    Some: u32,
-    _sizer: [u8; 8],
-}
-
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "wasm32",
-    target_arch = "x86"
-))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(C)]
-pub struct R9 {
+pub struct RocNullableWrapped {
     pub discriminantOffset: u32,
     pub discriminantSize: u32,
     pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R10>,
+    pub tags: roc_std::RocList<RocTag>,
     pub indexOfNullTag: u16,
 }
 
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "aarch64",
-    target_arch = "wasm32",
-    target_arch = "x86",
-    target_arch = "x86_64"
-))]
-#[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(u8)]
-pub enum discriminant_U3 {
-    None = 0,
-    Some = 1,
-}
-
-impl core::fmt::Debug for discriminant_U3 {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::None => f.write_str("discriminant_U3::None"),
-            Self::Some => f.write_str("discriminant_U3::Some"),
-        }
-    }
-}
-
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "wasm32",
-    target_arch = "x86"
-))]
-#[repr(C)]
-pub union U3 {
-    Some: u32,
-    _sizer: [u8; 8],
-}
-
 #[cfg(any(
     target_arch = "arm",
     target_arch = "wasm32",
     target_arch = "x86"
 ))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R8 {
+pub struct RocNullableUnwrapped {
     pub name: roc_std::RocStr,
     pub nonNullPayload: u32,
     pub nonNullTag: roc_std::RocStr,
     pub nullTag: roc_std::RocStr,
-    pub whichTagIsNull: U2,
-}
-
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "aarch64",
-    target_arch = "wasm32",
-    target_arch = "x86",
-    target_arch = "x86_64"
-))]
-#[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(u8)]
-pub enum U2 {
-    FirstTagIsNull = 0,
-    SecondTagIsNull = 1,
-}
-
-impl core::fmt::Debug for U2 {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::FirstTagIsNull => f.write_str("U2::FirstTagIsNull"),
-            Self::SecondTagIsNull => f.write_str("U2::SecondTagIsNull"),
-        }
-    }
+    pub nullRepresentsFirstTag: bool,
 }
 
 #[cfg(any(
@@ -455,11 +362,11 @@ impl core::fmt::Debug for U2 {
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R6 {
+pub struct RocTagUnionInfo {
     pub discriminantOffset: u32,
     pub discriminantSize: u32,
     pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R7>,
+    pub tags: roc_std::RocList<RocTag>,
 }
 
 #[cfg(any(
@@ -471,16 +378,16 @@ pub struct R6 {
 ))]
 #[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(u8)]
-pub enum discriminant_U1 {
+pub enum discriminant_RocTagPayload {
     None = 0,
     Some = 1,
 }
 
-impl core::fmt::Debug for discriminant_U1 {
+impl core::fmt::Debug for discriminant_RocTagPayload {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::None => f.write_str("discriminant_U1::None"),
-            Self::Some => f.write_str("discriminant_U1::Some"),
+            Self::None => f.write_str("discriminant_RocTagPayload::None"),
+            Self::Some => f.write_str("discriminant_RocTagPayload::Some"),
         }
     }
 }
@@ -491,7 +398,7 @@ impl core::fmt::Debug for discriminant_U1 {
     target_arch = "x86"
 ))]
 #[repr(C)]
-pub union U1 {
+pub union RocTagPayload {
     Some: u32,
     _sizer: [u8; 8],
 }
@@ -503,7 +410,7 @@ pub union U1 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R5 {
+pub struct RocNonNullableUnwrapped {
     pub name: roc_std::RocStr,
     pub payload: u32,
     pub tagName: roc_std::RocStr,
@@ -516,7 +423,7 @@ pub struct R5 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R4 {
+pub struct RocEnumeration {
     pub name: roc_std::RocStr,
     pub size: u32,
     pub tags: roc_std::RocList<roc_std::RocStr>,
@@ -531,23 +438,11 @@ pub struct R4 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R2 {
-    pub fields: roc_std::RocList<R3>,
+pub struct RocStruct {
+    pub fields: roc_std::RocList<RocStructField>,
     pub name: roc_std::RocStr,
 }
 
-#[cfg(any(
-    target_arch = "arm",
-    target_arch = "wasm32",
-    target_arch = "x86"
-))]
-#[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(C)]
-struct RocType_RocResult {
-    pub f0: u32,
-    pub f1: u32,
-}
-
 #[cfg(any(
     target_arch = "arm",
     target_arch = "wasm32",
@@ -612,27 +507,47 @@ impl core::fmt::Debug for RocNum {
     target_arch = "wasm32",
     target_arch = "x86"
 ))]
-#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R1 {
+pub struct RocFn {
     pub args: roc_std::RocList<u32>,
+    pub externName: RocExternName,
     pub name: roc_std::RocStr,
     pub ret: u32,
 }
 
 #[cfg(any(
+    target_arch = "arm",
     target_arch = "aarch64",
+    target_arch = "wasm32",
+    target_arch = "x86",
     target_arch = "x86_64"
 ))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum discriminant_RocExternName {
+    None = 0,
+    Some = 1,
+}
+
+impl core::fmt::Debug for discriminant_RocExternName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::None => f.write_str("discriminant_RocExternName::None"),
+            Self::Some => f.write_str("discriminant_RocExternName::Some"),
+        }
+    }
+}
+
+#[cfg(any(
+    target_arch = "arm",
+    target_arch = "wasm32",
+    target_arch = "x86"
+))]
 #[repr(C)]
-pub struct Types {
-    pub aligns: roc_std::RocList<u32>,
-    pub deps: roc_std::RocDict<u64, roc_std::RocList<u64>>,
-    pub sizes: roc_std::RocList<u32>,
-    pub types: roc_std::RocList<RocType>,
-    pub typesByName: roc_std::RocDict<roc_std::RocStr, u64>,
-    pub target: Target,
+pub union RocExternName {
+    Some: core::mem::ManuallyDrop<roc_std::RocStr>,
+    _sizer: [u8; 16],
 }
 
 #[cfg(any(
@@ -641,17 +556,17 @@ pub struct Types {
 ))]
 #[repr(C)]
 pub union RocType {
-    Function: core::mem::ManuallyDrop<R1>,
+    Function: core::mem::ManuallyDrop<RocFn>,
     Num: RocNum,
     RecursivePointer: u64,
     RocBox: u64,
     RocDict: RocType_RocDict,
     RocList: u64,
-    RocResult: RocType_RocResult,
+    RocResult: RocType_RocDict,
     RocSet: u64,
-    Struct: core::mem::ManuallyDrop<R2>,
+    Struct: core::mem::ManuallyDrop<RocStruct>,
     TagUnion: core::mem::ManuallyDrop<RocTagUnion>,
-    TagUnionPayload: core::mem::ManuallyDrop<R14>,
+    TagUnionPayload: core::mem::ManuallyDrop<RocTagUnionPayload>,
     _sizer: [u8; 104],
 }
 
@@ -661,7 +576,7 @@ pub union RocType {
 ))]
 #[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R15 {
+pub struct RocTagUnionPayloadField {
     pub discriminant: u64,
     pub r#type: u64,
 }
@@ -672,7 +587,7 @@ pub struct R15 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R3 {
+pub struct RocStructField {
     pub name: roc_std::RocStr,
     pub r#type: u64,
 }
@@ -681,16 +596,11 @@ pub struct R3 {
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub union RocTagUnion {
-    Enumeration: core::mem::ManuallyDrop<R4>,
-    NonNullableUnwrapped: core::mem::ManuallyDrop<R5>,
-    NonRecursive: core::mem::ManuallyDrop<R6>,
-    NullableUnwrapped: core::mem::ManuallyDrop<R8>,
-    NullableWrapped: core::mem::ManuallyDrop<R9>,
-    Recursive: core::mem::ManuallyDrop<R11>,
-    SingleTagStruct: core::mem::ManuallyDrop<R13>,
-    _sizer: [u8; 96],
+pub struct EntryPoint {
+    pub id: u64,
+    pub name: roc_std::RocStr,
 }
 
 #[cfg(any(
@@ -699,33 +609,37 @@ pub union RocTagUnion {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R13 {
-    pub name: roc_std::RocStr,
-    pub payloadFields: roc_std::RocList<u64>,
-    pub tagName: roc_std::RocStr,
+pub struct TypeDeps {
+    pub dependsOn: roc_std::RocList<u64>,
+    pub id: u64,
 }
 
 #[cfg(any(
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R11 {
-    pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R12>,
-    pub discriminantOffset: u32,
-    pub discriminantSize: u32,
+pub union RocTagUnion {
+    Enumeration: core::mem::ManuallyDrop<RocEnumeration>,
+    NonNullableUnwrapped: core::mem::ManuallyDrop<RocNonNullableUnwrapped>,
+    NonRecursive: core::mem::ManuallyDrop<RocTagUnionInfo>,
+    NullableUnwrapped: core::mem::ManuallyDrop<RocNullableUnwrapped>,
+    NullableWrapped: core::mem::ManuallyDrop<RocNullableWrapped>,
+    Recursive: core::mem::ManuallyDrop<RocTagUnionInfo>,
+    SingleTagStruct: core::mem::ManuallyDrop<RocSingleTagStruct>,
+    _sizer: [u8; 96],
 }
 
 #[cfg(any(
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub union U4 {
-    Some: u64,
-    _sizer: [u8; 16],
+pub struct RocSingleTagStruct {
+    pub name: roc_std::RocStr,
+    pub payloadFields: roc_std::RocList<u64>,
+    pub tagName: roc_std::RocStr,
 }
 
 #[cfg(any(
@@ -734,9 +648,9 @@ pub union U4 {
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R9 {
+pub struct RocNullableWrapped {
     pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R10>,
+    pub tags: roc_std::RocList<RocTag>,
     pub discriminantOffset: u32,
     pub discriminantSize: u32,
     pub indexOfNullTag: u16,
@@ -746,24 +660,14 @@ pub struct R9 {
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
+#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub union U3 {
-    Some: u64,
-    _sizer: [u8; 16],
-}
-
-#[cfg(any(
-    target_arch = "aarch64",
-    target_arch = "x86_64"
-))]
-#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
-#[repr(C)]
-pub struct R8 {
+pub struct RocNullableUnwrapped {
     pub name: roc_std::RocStr,
     pub nonNullPayload: u64,
     pub nonNullTag: roc_std::RocStr,
     pub nullTag: roc_std::RocStr,
-    pub whichTagIsNull: U2,
+    pub nullRepresentsFirstTag: bool,
 }
 
 #[cfg(any(
@@ -772,9 +676,9 @@ pub struct R8 {
 ))]
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R6 {
+pub struct RocTagUnionInfo {
     pub name: roc_std::RocStr,
-    pub tags: roc_std::RocList<R7>,
+    pub tags: roc_std::RocList<RocTag>,
     pub discriminantOffset: u32,
     pub discriminantSize: u32,
 }
@@ -784,7 +688,7 @@ pub struct R6 {
     target_arch = "x86_64"
 ))]
 #[repr(C)]
-pub union U1 {
+pub union RocTagPayload {
     Some: u64,
     _sizer: [u8; 16],
 }
@@ -795,7 +699,7 @@ pub union U1 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R5 {
+pub struct RocNonNullableUnwrapped {
     pub name: roc_std::RocStr,
     pub payload: u64,
     pub tagName: roc_std::RocStr,
@@ -807,7 +711,7 @@ pub struct R5 {
 ))]
 #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R4 {
+pub struct RocEnumeration {
     pub name: roc_std::RocStr,
     pub tags: roc_std::RocList<roc_std::RocStr>,
     pub size: u32,
@@ -819,7 +723,7 @@ pub struct R4 {
 ))]
 #[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-struct RocType_RocResult {
+struct RocType_RocDict {
     pub f0: u64,
     pub f1: u64,
 }
@@ -828,23 +732,23 @@ struct RocType_RocResult {
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
-#[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-struct RocType_RocDict {
-    pub f0: u64,
-    pub f1: u64,
+pub struct RocFn {
+    pub args: roc_std::RocList<u64>,
+    pub externName: RocExternName,
+    pub name: roc_std::RocStr,
+    pub ret: u64,
 }
 
 #[cfg(any(
     target_arch = "aarch64",
     target_arch = "x86_64"
 ))]
-#[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
 #[repr(C)]
-pub struct R1 {
-    pub args: roc_std::RocList<u64>,
-    pub name: roc_std::RocStr,
-    pub ret: u64,
+pub union RocExternName {
+    Some: core::mem::ManuallyDrop<roc_std::RocStr>,
+    _sizer: [u8; 32],
 }
 
 impl RocType {
@@ -964,7 +868,7 @@ impl RocType {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `Function`, with the appropriate payload
-    pub fn Function(arg0: R1) -> Self {
+    pub fn Function(arg0: RocFn) -> Self {
             let mut answer = Self {
                 Function: core::mem::ManuallyDrop::new(arg0)
             };
@@ -984,7 +888,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `Function` and convert it to `Function`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Function`.
-            pub unsafe fn into_Function(mut self) -> R1 {
+            pub unsafe fn into_Function(mut self) -> RocFn {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::Function);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -1014,7 +918,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `Function` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Function`.
-            pub unsafe fn as_Function(&self) -> &R1 {
+            pub unsafe fn as_Function(&self) -> &RocFn {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::Function);
         let payload = &self.Function;
 
@@ -1275,7 +1179,7 @@ impl RocType {
     /// Construct a tag named `RocResult`, with the appropriate payload
     pub fn RocResult(arg0: u32, arg1: u32) -> Self {
             let mut answer = Self {
-                RocResult: RocType_RocResult {
+                RocResult: RocType_RocDict {
                     f0: arg0,
                     f1: arg1,
                 }
@@ -1416,7 +1320,7 @@ impl RocType {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `Struct`, with the appropriate payload
-    pub fn Struct(arg0: R2) -> Self {
+    pub fn Struct(arg0: RocStruct) -> Self {
             let mut answer = Self {
                 Struct: core::mem::ManuallyDrop::new(arg0)
             };
@@ -1436,7 +1340,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `Struct` and convert it to `Struct`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Struct`.
-            pub unsafe fn into_Struct(mut self) -> R2 {
+            pub unsafe fn into_Struct(mut self) -> RocStruct {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::Struct);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -1466,7 +1370,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `Struct` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Struct`.
-            pub unsafe fn as_Struct(&self) -> &R2 {
+            pub unsafe fn as_Struct(&self) -> &RocStruct {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::Struct);
         let payload = &self.Struct;
 
@@ -1546,7 +1450,7 @@ impl RocType {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `TagUnionPayload`, with the appropriate payload
-    pub fn TagUnionPayload(arg0: R14) -> Self {
+    pub fn TagUnionPayload(arg0: RocTagUnionPayload) -> Self {
             let mut answer = Self {
                 TagUnionPayload: core::mem::ManuallyDrop::new(arg0)
             };
@@ -1566,7 +1470,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `TagUnionPayload` and convert it to `TagUnionPayload`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `TagUnionPayload`.
-            pub unsafe fn into_TagUnionPayload(mut self) -> R14 {
+            pub unsafe fn into_TagUnionPayload(mut self) -> RocTagUnionPayload {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::TagUnionPayload);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -1596,7 +1500,7 @@ impl RocType {
     /// Unsafely assume the given `RocType` has a `.discriminant()` of `TagUnionPayload` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `TagUnionPayload`.
-            pub unsafe fn as_TagUnionPayload(&self) -> &R14 {
+            pub unsafe fn as_TagUnionPayload(&self) -> &RocTagUnionPayload {
                 debug_assert_eq!(self.discriminant(), discriminant_RocType::TagUnionPayload);
         let payload = &self.TagUnionPayload;
 
@@ -1884,7 +1788,7 @@ impl RocType {
     /// Construct a tag named `RocResult`, with the appropriate payload
     pub fn RocResult(arg0: u64, arg1: u64) -> Self {
             let mut answer = Self {
-                RocResult: RocType_RocResult {
+                RocResult: RocType_RocDict {
                     f0: arg0,
                     f1: arg1,
                 }
@@ -2364,7 +2268,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `Enumeration`, with the appropriate payload
-    pub fn Enumeration(arg0: R4) -> Self {
+    pub fn Enumeration(arg0: RocEnumeration) -> Self {
             let mut answer = Self {
                 Enumeration: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2384,7 +2288,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `Enumeration` and convert it to `Enumeration`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Enumeration`.
-            pub unsafe fn into_Enumeration(mut self) -> R4 {
+            pub unsafe fn into_Enumeration(mut self) -> RocEnumeration {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::Enumeration);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2414,7 +2318,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `Enumeration` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Enumeration`.
-            pub unsafe fn as_Enumeration(&self) -> &R4 {
+            pub unsafe fn as_Enumeration(&self) -> &RocEnumeration {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::Enumeration);
         let payload = &self.Enumeration;
 
@@ -2430,7 +2334,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `NonNullableUnwrapped`, with the appropriate payload
-    pub fn NonNullableUnwrapped(arg0: R5) -> Self {
+    pub fn NonNullableUnwrapped(arg0: RocNonNullableUnwrapped) -> Self {
             let mut answer = Self {
                 NonNullableUnwrapped: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2450,7 +2354,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NonNullableUnwrapped` and convert it to `NonNullableUnwrapped`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NonNullableUnwrapped`.
-            pub unsafe fn into_NonNullableUnwrapped(mut self) -> R5 {
+            pub unsafe fn into_NonNullableUnwrapped(mut self) -> RocNonNullableUnwrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NonNullableUnwrapped);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2480,7 +2384,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NonNullableUnwrapped` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NonNullableUnwrapped`.
-            pub unsafe fn as_NonNullableUnwrapped(&self) -> &R5 {
+            pub unsafe fn as_NonNullableUnwrapped(&self) -> &RocNonNullableUnwrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NonNullableUnwrapped);
         let payload = &self.NonNullableUnwrapped;
 
@@ -2496,7 +2400,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `NonRecursive`, with the appropriate payload
-    pub fn NonRecursive(arg0: R6) -> Self {
+    pub fn NonRecursive(arg0: RocTagUnionInfo) -> Self {
             let mut answer = Self {
                 NonRecursive: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2516,7 +2420,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NonRecursive` and convert it to `NonRecursive`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NonRecursive`.
-            pub unsafe fn into_NonRecursive(mut self) -> R6 {
+            pub unsafe fn into_NonRecursive(mut self) -> RocTagUnionInfo {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NonRecursive);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2546,7 +2450,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NonRecursive` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NonRecursive`.
-            pub unsafe fn as_NonRecursive(&self) -> &R6 {
+            pub unsafe fn as_NonRecursive(&self) -> &RocTagUnionInfo {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NonRecursive);
         let payload = &self.NonRecursive;
 
@@ -2562,7 +2466,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `NullableUnwrapped`, with the appropriate payload
-    pub fn NullableUnwrapped(arg0: R8) -> Self {
+    pub fn NullableUnwrapped(arg0: RocNullableUnwrapped) -> Self {
             let mut answer = Self {
                 NullableUnwrapped: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2582,7 +2486,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NullableUnwrapped` and convert it to `NullableUnwrapped`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NullableUnwrapped`.
-            pub unsafe fn into_NullableUnwrapped(mut self) -> R8 {
+            pub unsafe fn into_NullableUnwrapped(mut self) -> RocNullableUnwrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NullableUnwrapped);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2612,7 +2516,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NullableUnwrapped` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NullableUnwrapped`.
-            pub unsafe fn as_NullableUnwrapped(&self) -> &R8 {
+            pub unsafe fn as_NullableUnwrapped(&self) -> &RocNullableUnwrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NullableUnwrapped);
         let payload = &self.NullableUnwrapped;
 
@@ -2628,7 +2532,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `NullableWrapped`, with the appropriate payload
-    pub fn NullableWrapped(arg0: R9) -> Self {
+    pub fn NullableWrapped(arg0: RocNullableWrapped) -> Self {
             let mut answer = Self {
                 NullableWrapped: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2648,7 +2552,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NullableWrapped` and convert it to `NullableWrapped`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NullableWrapped`.
-            pub unsafe fn into_NullableWrapped(mut self) -> R9 {
+            pub unsafe fn into_NullableWrapped(mut self) -> RocNullableWrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NullableWrapped);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2678,7 +2582,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `NullableWrapped` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `NullableWrapped`.
-            pub unsafe fn as_NullableWrapped(&self) -> &R9 {
+            pub unsafe fn as_NullableWrapped(&self) -> &RocNullableWrapped {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::NullableWrapped);
         let payload = &self.NullableWrapped;
 
@@ -2694,7 +2598,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `Recursive`, with the appropriate payload
-    pub fn Recursive(arg0: R11) -> Self {
+    pub fn Recursive(arg0: RocTagUnionInfo) -> Self {
             let mut answer = Self {
                 Recursive: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2714,7 +2618,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `Recursive` and convert it to `Recursive`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Recursive`.
-            pub unsafe fn into_Recursive(mut self) -> R11 {
+            pub unsafe fn into_Recursive(mut self) -> RocTagUnionInfo {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::Recursive);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2744,7 +2648,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `Recursive` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Recursive`.
-            pub unsafe fn as_Recursive(&self) -> &R11 {
+            pub unsafe fn as_Recursive(&self) -> &RocTagUnionInfo {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::Recursive);
         let payload = &self.Recursive;
 
@@ -2760,7 +2664,7 @@ impl RocTagUnion {
         target_arch = "x86_64"
     ))]
     /// Construct a tag named `SingleTagStruct`, with the appropriate payload
-    pub fn SingleTagStruct(arg0: R13) -> Self {
+    pub fn SingleTagStruct(arg0: RocSingleTagStruct) -> Self {
             let mut answer = Self {
                 SingleTagStruct: core::mem::ManuallyDrop::new(arg0)
             };
@@ -2780,7 +2684,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `SingleTagStruct` and convert it to `SingleTagStruct`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `SingleTagStruct`.
-            pub unsafe fn into_SingleTagStruct(mut self) -> R13 {
+            pub unsafe fn into_SingleTagStruct(mut self) -> RocSingleTagStruct {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::SingleTagStruct);
         let payload = {
             let mut uninitialized = core::mem::MaybeUninit::uninit();
@@ -2810,7 +2714,7 @@ impl RocTagUnion {
     /// Unsafely assume the given `RocTagUnion` has a `.discriminant()` of `SingleTagStruct` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `SingleTagStruct`.
-            pub unsafe fn as_SingleTagStruct(&self) -> &R13 {
+            pub unsafe fn as_SingleTagStruct(&self) -> &RocSingleTagStruct {
                 debug_assert_eq!(self.discriminant(), discriminant_RocTagUnion::SingleTagStruct);
         let payload = &self.SingleTagStruct;
 
@@ -3075,18 +2979,18 @@ impl core::fmt::Debug for RocTagUnion {
     }
 }
 
-impl U4 {
+impl RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "wasm32",
         target_arch = "x86"
     ))]
     /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U4 {
+    pub fn discriminant(&self) -> discriminant_RocTagPayload {
         unsafe {
             let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
 
-            core::mem::transmute::<u8, discriminant_U4>(*bytes.as_ptr().add(4))
+            core::mem::transmute::<u8, discriminant_RocTagPayload>(*bytes.as_ptr().add(4))
         }
     }
 
@@ -3096,8 +3000,8 @@ impl U4 {
         target_arch = "x86"
     ))]
     /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U4) {
-        let discriminant_ptr: *mut discriminant_U4 = (self as *mut U4).cast();
+    fn set_discriminant(&mut self, discriminant: discriminant_RocTagPayload) {
+        let discriminant_ptr: *mut discriminant_RocTagPayload = (self as *mut RocTagPayload).cast();
 
         unsafe {
             *(discriminant_ptr.add(4)) = discriminant;
@@ -3111,11 +3015,11 @@ impl U4 {
     ))]
     /// A tag named None, which has no payload.
     pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U4>()];
+        let mut bytes = [0; core::mem::size_of::<RocTagPayload>()];
 
-        bytes[4] = discriminant_U4::None as u8;
+        bytes[4] = discriminant_RocTagPayload::None as u8;
 
-        core::mem::transmute::<[u8; core::mem::size_of::<U4>()], U4>(bytes)
+        core::mem::transmute::<[u8; core::mem::size_of::<RocTagPayload>()], RocTagPayload>(bytes)
     };
 
     #[cfg(any(
@@ -3155,7 +3059,7 @@ impl U4 {
                 Some: arg
             };
 
-            answer.set_discriminant(discriminant_U4::Some);
+            answer.set_discriminant(discriminant_RocTagPayload::Some);
 
             answer
     }
@@ -3165,11 +3069,11 @@ impl U4 {
         target_arch = "wasm32",
         target_arch = "x86"
     ))]
-    /// Unsafely assume the given `U4` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
+    /// Unsafely assume the given `RocTagPayload` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
             pub unsafe fn into_Some(self) -> u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U4::Some);
+                debug_assert_eq!(self.discriminant(), discriminant_RocTagPayload::Some);
         let payload = self.Some;
 
         payload
@@ -3180,11 +3084,11 @@ impl U4 {
         target_arch = "wasm32",
         target_arch = "x86"
     ))]
-    /// Unsafely assume the given `U4` has a `.discriminant()` of `Some` and return its payload.
+    /// Unsafely assume the given `RocTagPayload` has a `.discriminant()` of `Some` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
             pub unsafe fn as_Some(&self) -> &u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U4::Some);
+                debug_assert_eq!(self.discriminant(), discriminant_RocTagPayload::Some);
         let payload = &self.Some;
 
         &payload
@@ -3195,11 +3099,11 @@ impl U4 {
         target_arch = "x86_64"
     ))]
     /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U4 {
+    pub fn discriminant(&self) -> discriminant_RocTagPayload {
         unsafe {
             let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
 
-            core::mem::transmute::<u8, discriminant_U4>(*bytes.as_ptr().add(8))
+            core::mem::transmute::<u8, discriminant_RocTagPayload>(*bytes.as_ptr().add(8))
         }
     }
 
@@ -3208,8 +3112,8 @@ impl U4 {
         target_arch = "x86_64"
     ))]
     /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U4) {
-        let discriminant_ptr: *mut discriminant_U4 = (self as *mut U4).cast();
+    fn set_discriminant(&mut self, discriminant: discriminant_RocTagPayload) {
+        let discriminant_ptr: *mut discriminant_RocTagPayload = (self as *mut RocTagPayload).cast();
 
         unsafe {
             *(discriminant_ptr.add(8)) = discriminant;
@@ -3222,11 +3126,11 @@ impl U4 {
     ))]
     /// A tag named None, which has no payload.
     pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U4>()];
+        let mut bytes = [0; core::mem::size_of::<RocTagPayload>()];
 
-        bytes[8] = discriminant_U4::None as u8;
+        bytes[8] = discriminant_RocTagPayload::None as u8;
 
-        core::mem::transmute::<[u8; core::mem::size_of::<U4>()], U4>(bytes)
+        core::mem::transmute::<[u8; core::mem::size_of::<RocTagPayload>()], RocTagPayload>(bytes)
     };
 
     #[cfg(any(
@@ -3239,7 +3143,7 @@ impl U4 {
                 Some: arg
             };
 
-            answer.set_discriminant(discriminant_U4::Some);
+            answer.set_discriminant(discriminant_RocTagPayload::Some);
 
             answer
     }
@@ -3248,11 +3152,11 @@ impl U4 {
         target_arch = "aarch64",
         target_arch = "x86_64"
     ))]
-    /// Unsafely assume the given `U4` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
+    /// Unsafely assume the given `RocTagPayload` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
             pub unsafe fn into_Some(self) -> u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U4::Some);
+                debug_assert_eq!(self.discriminant(), discriminant_RocTagPayload::Some);
         let payload = self.Some;
 
         payload
@@ -3262,20 +3166,20 @@ impl U4 {
         target_arch = "aarch64",
         target_arch = "x86_64"
     ))]
-    /// Unsafely assume the given `U4` has a `.discriminant()` of `Some` and return its payload.
+    /// Unsafely assume the given `RocTagPayload` has a `.discriminant()` of `Some` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
             pub unsafe fn as_Some(&self) -> &u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U4::Some);
+                debug_assert_eq!(self.discriminant(), discriminant_RocTagPayload::Some);
         let payload = &self.Some;
 
         &payload
     }
 }
 
-impl Eq for U4 {}
+impl Eq for RocTagPayload {}
 
-impl PartialEq for U4 {
+impl PartialEq for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3290,14 +3194,14 @@ impl PartialEq for U4 {
 
             unsafe {
             match self.discriminant() {
-                discriminant_U4::None => true,
-                discriminant_U4::Some => self.Some == other.Some,
+                discriminant_RocTagPayload::None => true,
+                discriminant_RocTagPayload::Some => self.Some == other.Some,
             }
         }
     }
 }
 
-impl PartialOrd for U4 {
+impl PartialOrd for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3313,14 +3217,14 @@ impl PartialOrd for U4 {
 
         unsafe {
             match self.discriminant() {
-                discriminant_U4::None => Some(core::cmp::Ordering::Equal),
-                discriminant_U4::Some => self.Some.partial_cmp(&other.Some),
+                discriminant_RocTagPayload::None => Some(core::cmp::Ordering::Equal),
+                discriminant_RocTagPayload::Some => self.Some.partial_cmp(&other.Some),
             }
         }
     }
 }
 
-impl Ord for U4 {
+impl Ord for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3336,16 +3240,16 @@ impl Ord for U4 {
 
             unsafe {
             match self.discriminant() {
-                discriminant_U4::None => core::cmp::Ordering::Equal,
-                discriminant_U4::Some => self.Some.cmp(&other.Some),
+                discriminant_RocTagPayload::None => core::cmp::Ordering::Equal,
+                discriminant_RocTagPayload::Some => self.Some.cmp(&other.Some),
             }
         }
     }
 }
 
-impl Copy for U4 {}
+impl Copy for RocTagPayload {}
 
-impl Clone for U4 {
+impl Clone for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3356,11 +3260,11 @@ impl Clone for U4 {
     fn clone(&self) -> Self {
         let mut answer = unsafe {
             match self.discriminant() {
-                discriminant_U4::None => core::mem::transmute::<
-                    core::mem::MaybeUninit<U4>,
-                    U4,
+                discriminant_RocTagPayload::None => core::mem::transmute::<
+                    core::mem::MaybeUninit<RocTagPayload>,
+                    RocTagPayload,
                 >(core::mem::MaybeUninit::uninit()),
-                discriminant_U4::Some => Self {
+                discriminant_RocTagPayload::Some => Self {
                     Some: self.Some.clone(),
                 },
             }
@@ -3373,7 +3277,7 @@ impl Clone for U4 {
     }
 }
 
-impl core::hash::Hash for U4 {
+impl core::hash::Hash for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3382,16 +3286,16 @@ impl core::hash::Hash for U4 {
         target_arch = "x86_64"
     ))]
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {        match self.discriminant() {
-            discriminant_U4::None => discriminant_U4::None.hash(state),
-            discriminant_U4::Some => unsafe {
-                    discriminant_U4::Some.hash(state);
+            discriminant_RocTagPayload::None => discriminant_RocTagPayload::None.hash(state),
+            discriminant_RocTagPayload::Some => unsafe {
+                    discriminant_RocTagPayload::Some.hash(state);
                     self.Some.hash(state);
                 },
         }
     }
 }
 
-impl core::fmt::Debug for U4 {
+impl core::fmt::Debug for RocTagPayload {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3400,12 +3304,12 @@ impl core::fmt::Debug for U4 {
         target_arch = "x86_64"
     ))]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("U4::")?;
+        f.write_str("RocTagPayload::")?;
 
         unsafe {
             match self.discriminant() {
-                discriminant_U4::None => f.write_str("None"),
-                discriminant_U4::Some => f.debug_tuple("Some")
+                discriminant_RocTagPayload::None => f.write_str("None"),
+                discriminant_RocTagPayload::Some => f.debug_tuple("Some")
         .field(&self.Some)
         .finish(),
             }
@@ -3413,18 +3317,18 @@ impl core::fmt::Debug for U4 {
     }
 }
 
-impl U3 {
+impl RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "wasm32",
         target_arch = "x86"
     ))]
     /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U3 {
+    pub fn discriminant(&self) -> discriminant_RocExternName {
         unsafe {
             let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
 
-            core::mem::transmute::<u8, discriminant_U3>(*bytes.as_ptr().add(4))
+            core::mem::transmute::<u8, discriminant_RocExternName>(*bytes.as_ptr().add(12))
         }
     }
 
@@ -3434,11 +3338,11 @@ impl U3 {
         target_arch = "x86"
     ))]
     /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U3) {
-        let discriminant_ptr: *mut discriminant_U3 = (self as *mut U3).cast();
+    fn set_discriminant(&mut self, discriminant: discriminant_RocExternName) {
+        let discriminant_ptr: *mut discriminant_RocExternName = (self as *mut RocExternName).cast();
 
         unsafe {
-            *(discriminant_ptr.add(4)) = discriminant;
+            *(discriminant_ptr.add(12)) = discriminant;
         }
     }
 
@@ -3449,11 +3353,11 @@ impl U3 {
     ))]
     /// A tag named None, which has no payload.
     pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U3>()];
+        let mut bytes = [0; core::mem::size_of::<RocExternName>()];
 
-        bytes[4] = discriminant_U3::None as u8;
+        bytes[12] = discriminant_RocExternName::None as u8;
 
-        core::mem::transmute::<[u8; core::mem::size_of::<U3>()], U3>(bytes)
+        core::mem::transmute::<[u8; core::mem::size_of::<RocExternName>()], RocExternName>(bytes)
     };
 
     #[cfg(any(
@@ -3484,45 +3388,63 @@ impl U3 {
 
     #[cfg(any(
         target_arch = "arm",
+        target_arch = "aarch64",
         target_arch = "wasm32",
-        target_arch = "x86"
+        target_arch = "x86",
+        target_arch = "x86_64"
     ))]
     /// Construct a tag named `Some`, with the appropriate payload
-    pub fn Some(arg: u32) -> Self {
+    pub fn Some(arg: roc_std::RocStr) -> Self {
             let mut answer = Self {
-                Some: arg
+                Some: core::mem::ManuallyDrop::new(arg)
             };
 
-            answer.set_discriminant(discriminant_U3::Some);
+            answer.set_discriminant(discriminant_RocExternName::Some);
 
             answer
     }
 
     #[cfg(any(
         target_arch = "arm",
+        target_arch = "aarch64",
         target_arch = "wasm32",
-        target_arch = "x86"
+        target_arch = "x86",
+        target_arch = "x86_64"
     ))]
-    /// Unsafely assume the given `U3` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
+    /// Unsafely assume the given `RocExternName` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn into_Some(self) -> u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U3::Some);
-        let payload = self.Some;
+            pub unsafe fn into_Some(mut self) -> roc_std::RocStr {
+                debug_assert_eq!(self.discriminant(), discriminant_RocExternName::Some);
+        let payload = {
+            let mut uninitialized = core::mem::MaybeUninit::uninit();
+            let swapped = unsafe {
+                core::mem::replace(
+                    &mut self.Some,
+                    core::mem::ManuallyDrop::new(uninitialized.assume_init()),
+                )
+            };
+
+            core::mem::forget(self);
+
+            core::mem::ManuallyDrop::into_inner(swapped)
+        };
 
         payload
     }
 
     #[cfg(any(
         target_arch = "arm",
+        target_arch = "aarch64",
         target_arch = "wasm32",
-        target_arch = "x86"
+        target_arch = "x86",
+        target_arch = "x86_64"
     ))]
-    /// Unsafely assume the given `U3` has a `.discriminant()` of `Some` and return its payload.
+    /// Unsafely assume the given `RocExternName` has a `.discriminant()` of `Some` and return its payload.
             /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
             /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn as_Some(&self) -> &u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U3::Some);
+            pub unsafe fn as_Some(&self) -> &roc_std::RocStr {
+                debug_assert_eq!(self.discriminant(), discriminant_RocExternName::Some);
         let payload = &self.Some;
 
         &payload
@@ -3533,11 +3455,11 @@ impl U3 {
         target_arch = "x86_64"
     ))]
     /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U3 {
+    pub fn discriminant(&self) -> discriminant_RocExternName {
         unsafe {
             let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
 
-            core::mem::transmute::<u8, discriminant_U3>(*bytes.as_ptr().add(8))
+            core::mem::transmute::<u8, discriminant_RocExternName>(*bytes.as_ptr().add(24))
         }
     }
 
@@ -3546,11 +3468,11 @@ impl U3 {
         target_arch = "x86_64"
     ))]
     /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U3) {
-        let discriminant_ptr: *mut discriminant_U3 = (self as *mut U3).cast();
+    fn set_discriminant(&mut self, discriminant: discriminant_RocExternName) {
+        let discriminant_ptr: *mut discriminant_RocExternName = (self as *mut RocExternName).cast();
 
         unsafe {
-            *(discriminant_ptr.add(8)) = discriminant;
+            *(discriminant_ptr.add(24)) = discriminant;
         }
     }
 
@@ -3560,403 +3482,40 @@ impl U3 {
     ))]
     /// A tag named None, which has no payload.
     pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U3>()];
+        let mut bytes = [0; core::mem::size_of::<RocExternName>()];
 
-        bytes[8] = discriminant_U3::None as u8;
+        bytes[24] = discriminant_RocExternName::None as u8;
 
-        core::mem::transmute::<[u8; core::mem::size_of::<U3>()], U3>(bytes)
+        core::mem::transmute::<[u8; core::mem::size_of::<RocExternName>()], RocExternName>(bytes)
     };
+}
 
+impl Drop for RocExternName {
     #[cfg(any(
+        target_arch = "arm",
         target_arch = "aarch64",
+        target_arch = "wasm32",
+        target_arch = "x86",
         target_arch = "x86_64"
     ))]
-    /// Construct a tag named `Some`, with the appropriate payload
-    pub fn Some(arg: u64) -> Self {
-            let mut answer = Self {
-                Some: arg
-            };
-
-            answer.set_discriminant(discriminant_U3::Some);
+    fn drop(&mut self) {
+        // Drop the payloads
+                    match self.discriminant() {
+                discriminant_RocExternName::None => {}
+                discriminant_RocExternName::Some => unsafe { core::mem::ManuallyDrop::drop(&mut self.Some) },
+            }
 
-            answer
     }
+}
 
+impl Eq for RocExternName {}
+
+impl PartialEq for RocExternName {
     #[cfg(any(
+        target_arch = "arm",
         target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Unsafely assume the given `U3` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn into_Some(self) -> u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U3::Some);
-        let payload = self.Some;
-
-        payload
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Unsafely assume the given `U3` has a `.discriminant()` of `Some` and return its payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn as_Some(&self) -> &u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U3::Some);
-        let payload = &self.Some;
-
-        &payload
-    }
-}
-
-impl Eq for U3 {}
-
-impl PartialEq for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn eq(&self, other: &Self) -> bool {
-            if self.discriminant() != other.discriminant() {
-                return false;
-            }
-
-            unsafe {
-            match self.discriminant() {
-                discriminant_U3::None => true,
-                discriminant_U3::Some => self.Some == other.Some,
-            }
-        }
-    }
-}
-
-impl PartialOrd for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        match self.discriminant().partial_cmp(&other.discriminant()) {
-            Some(core::cmp::Ordering::Equal) => {}
-            not_eq => return not_eq,
-        }
-
-        unsafe {
-            match self.discriminant() {
-                discriminant_U3::None => Some(core::cmp::Ordering::Equal),
-                discriminant_U3::Some => self.Some.partial_cmp(&other.Some),
-            }
-        }
-    }
-}
-
-impl Ord for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-            match self.discriminant().cmp(&other.discriminant()) {
-                core::cmp::Ordering::Equal => {}
-                not_eq => return not_eq,
-            }
-
-            unsafe {
-            match self.discriminant() {
-                discriminant_U3::None => core::cmp::Ordering::Equal,
-                discriminant_U3::Some => self.Some.cmp(&other.Some),
-            }
-        }
-    }
-}
-
-impl Copy for U3 {}
-
-impl Clone for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn clone(&self) -> Self {
-        let mut answer = unsafe {
-            match self.discriminant() {
-                discriminant_U3::None => core::mem::transmute::<
-                    core::mem::MaybeUninit<U3>,
-                    U3,
-                >(core::mem::MaybeUninit::uninit()),
-                discriminant_U3::Some => Self {
-                    Some: self.Some.clone(),
-                },
-            }
-
-        };
-
-        answer.set_discriminant(self.discriminant());
-
-        answer
-    }
-}
-
-impl core::hash::Hash for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {        match self.discriminant() {
-            discriminant_U3::None => discriminant_U3::None.hash(state),
-            discriminant_U3::Some => unsafe {
-                    discriminant_U3::Some.hash(state);
-                    self.Some.hash(state);
-                },
-        }
-    }
-}
-
-impl core::fmt::Debug for U3 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("U3::")?;
-
-        unsafe {
-            match self.discriminant() {
-                discriminant_U3::None => f.write_str("None"),
-                discriminant_U3::Some => f.debug_tuple("Some")
-        .field(&self.Some)
-        .finish(),
-            }
-        }
-    }
-}
-
-impl U1 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U1 {
-        unsafe {
-            let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
-
-            core::mem::transmute::<u8, discriminant_U1>(*bytes.as_ptr().add(4))
-        }
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U1) {
-        let discriminant_ptr: *mut discriminant_U1 = (self as *mut U1).cast();
-
-        unsafe {
-            *(discriminant_ptr.add(4)) = discriminant;
-        }
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// A tag named None, which has no payload.
-    pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U1>()];
-
-        bytes[4] = discriminant_U1::None as u8;
-
-        core::mem::transmute::<[u8; core::mem::size_of::<U1>()], U1>(bytes)
-    };
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    /// Other `into_` methods return a payload, but since the None tag
-    /// has no payload, this does nothing and is only here for completeness.
-    pub fn into_None(self) {
-        ()
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
-        target_arch = "x86_64"
-    ))]
-    /// Other `as` methods return a payload, but since the None tag
-    /// has no payload, this does nothing and is only here for completeness.
-    pub fn as_None(&self) {
-        ()
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// Construct a tag named `Some`, with the appropriate payload
-    pub fn Some(arg: u32) -> Self {
-            let mut answer = Self {
-                Some: arg
-            };
-
-            answer.set_discriminant(discriminant_U1::Some);
-
-            answer
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// Unsafely assume the given `U1` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn into_Some(self) -> u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U1::Some);
-        let payload = self.Some;
-
-        payload
-    }
-
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "wasm32",
-        target_arch = "x86"
-    ))]
-    /// Unsafely assume the given `U1` has a `.discriminant()` of `Some` and return its payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn as_Some(&self) -> &u32 {
-                debug_assert_eq!(self.discriminant(), discriminant_U1::Some);
-        let payload = &self.Some;
-
-        &payload
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Returns which variant this tag union holds. Note that this never includes a payload!
-    pub fn discriminant(&self) -> discriminant_U1 {
-        unsafe {
-            let bytes = core::mem::transmute::<&Self, &[u8; core::mem::size_of::<Self>()]>(self);
-
-            core::mem::transmute::<u8, discriminant_U1>(*bytes.as_ptr().add(8))
-        }
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Internal helper
-    fn set_discriminant(&mut self, discriminant: discriminant_U1) {
-        let discriminant_ptr: *mut discriminant_U1 = (self as *mut U1).cast();
-
-        unsafe {
-            *(discriminant_ptr.add(8)) = discriminant;
-        }
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// A tag named None, which has no payload.
-    pub const None: Self = unsafe {
-        let mut bytes = [0; core::mem::size_of::<U1>()];
-
-        bytes[8] = discriminant_U1::None as u8;
-
-        core::mem::transmute::<[u8; core::mem::size_of::<U1>()], U1>(bytes)
-    };
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Construct a tag named `Some`, with the appropriate payload
-    pub fn Some(arg: u64) -> Self {
-            let mut answer = Self {
-                Some: arg
-            };
-
-            answer.set_discriminant(discriminant_U1::Some);
-
-            answer
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Unsafely assume the given `U1` has a `.discriminant()` of `Some` and convert it to `Some`'s payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn into_Some(self) -> u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U1::Some);
-        let payload = self.Some;
-
-        payload
-    }
-
-    #[cfg(any(
-        target_arch = "aarch64",
-        target_arch = "x86_64"
-    ))]
-    /// Unsafely assume the given `U1` has a `.discriminant()` of `Some` and return its payload.
-            /// (Always examine `.discriminant()` first to make sure this is the correct variant!)
-            /// Panics in debug builds if the `.discriminant()` doesn't return `Some`.
-            pub unsafe fn as_Some(&self) -> &u64 {
-                debug_assert_eq!(self.discriminant(), discriminant_U1::Some);
-        let payload = &self.Some;
-
-        &payload
-    }
-}
-
-impl Eq for U1 {}
-
-impl PartialEq for U1 {
-    #[cfg(any(
-        target_arch = "arm",
-        target_arch = "aarch64",
-        target_arch = "wasm32",
-        target_arch = "x86",
+        target_arch = "wasm32",
+        target_arch = "x86",
         target_arch = "x86_64"
     ))]
     fn eq(&self, other: &Self) -> bool {
@@ -3966,14 +3525,14 @@ impl PartialEq for U1 {
 
             unsafe {
             match self.discriminant() {
-                discriminant_U1::None => true,
-                discriminant_U1::Some => self.Some == other.Some,
+                discriminant_RocExternName::None => true,
+                discriminant_RocExternName::Some => self.Some == other.Some,
             }
         }
     }
 }
 
-impl PartialOrd for U1 {
+impl PartialOrd for RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -3989,14 +3548,14 @@ impl PartialOrd for U1 {
 
         unsafe {
             match self.discriminant() {
-                discriminant_U1::None => Some(core::cmp::Ordering::Equal),
-                discriminant_U1::Some => self.Some.partial_cmp(&other.Some),
+                discriminant_RocExternName::None => Some(core::cmp::Ordering::Equal),
+                discriminant_RocExternName::Some => self.Some.partial_cmp(&other.Some),
             }
         }
     }
 }
 
-impl Ord for U1 {
+impl Ord for RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -4012,16 +3571,14 @@ impl Ord for U1 {
 
             unsafe {
             match self.discriminant() {
-                discriminant_U1::None => core::cmp::Ordering::Equal,
-                discriminant_U1::Some => self.Some.cmp(&other.Some),
+                discriminant_RocExternName::None => core::cmp::Ordering::Equal,
+                discriminant_RocExternName::Some => self.Some.cmp(&other.Some),
             }
         }
     }
 }
 
-impl Copy for U1 {}
-
-impl Clone for U1 {
+impl Clone for RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -4032,11 +3589,11 @@ impl Clone for U1 {
     fn clone(&self) -> Self {
         let mut answer = unsafe {
             match self.discriminant() {
-                discriminant_U1::None => core::mem::transmute::<
-                    core::mem::MaybeUninit<U1>,
-                    U1,
+                discriminant_RocExternName::None => core::mem::transmute::<
+                    core::mem::MaybeUninit<RocExternName>,
+                    RocExternName,
                 >(core::mem::MaybeUninit::uninit()),
-                discriminant_U1::Some => Self {
+                discriminant_RocExternName::Some => Self {
                     Some: self.Some.clone(),
                 },
             }
@@ -4049,7 +3606,7 @@ impl Clone for U1 {
     }
 }
 
-impl core::hash::Hash for U1 {
+impl core::hash::Hash for RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -4058,16 +3615,16 @@ impl core::hash::Hash for U1 {
         target_arch = "x86_64"
     ))]
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {        match self.discriminant() {
-            discriminant_U1::None => discriminant_U1::None.hash(state),
-            discriminant_U1::Some => unsafe {
-                    discriminant_U1::Some.hash(state);
+            discriminant_RocExternName::None => discriminant_RocExternName::None.hash(state),
+            discriminant_RocExternName::Some => unsafe {
+                    discriminant_RocExternName::Some.hash(state);
                     self.Some.hash(state);
                 },
         }
     }
 }
 
-impl core::fmt::Debug for U1 {
+impl core::fmt::Debug for RocExternName {
     #[cfg(any(
         target_arch = "arm",
         target_arch = "aarch64",
@@ -4076,13 +3633,13 @@ impl core::fmt::Debug for U1 {
         target_arch = "x86_64"
     ))]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("U1::")?;
+        f.write_str("RocExternName::")?;
 
         unsafe {
             match self.discriminant() {
-                discriminant_U1::None => f.write_str("None"),
-                discriminant_U1::Some => f.debug_tuple("Some")
-        .field(&self.Some)
+                discriminant_RocExternName::None => f.write_str("None"),
+                discriminant_RocExternName::Some => f.debug_tuple("Some")
+        .field(&*self.Some)
         .finish(),
             }
         }
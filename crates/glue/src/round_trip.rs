@@ -0,0 +1,675 @@
+//! `roc glue --round-trip`, which generates a Roc app and a Rust host that pass values of
+//! every type a platform exposes across the FFI boundary in both directions.
+//!
+//! The text comparisons in `glue/tests` can't catch glue whose layout disagrees with what
+//! the compiler actually does at runtime. Here the same values get built on both sides from
+//! one walk over the types: the host checks that what Roc built is identical to what it built
+//! through the glue, and Roc checks that what the host built is identical to what it built.
+//!
+//! The app can't be for the original platform, since that platform decides what its app
+//! provides. Instead this generates a test platform that declares the same types and exposes
+//! a `sample{Name}ForHost` value and a `check{Name}ForHost` function for each of them.
+use crate::rust_glue::{escape_kw, type_name};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use roc_collections::MutMap;
+use std::fmt::Write;
+
+pub static CARGO_TOML: &str = include_str!("../templates/round-trip/Cargo.toml");
+pub static BUILD_RS: &str = include_str!("../templates/round-trip/build.rs");
+pub static HOST_C: &str = include_str!("../templates/round-trip/host.c");
+pub static MAIN_RS: &str = include_str!("../templates/round-trip/src/main.rs");
+pub static ROC_EXTERNS_RS: &str = include_str!("../templates/round-trip/src/roc_externs.rs");
+
+/// What `CARGO_TOML` calls the path to `roc_std`
+pub const ROC_STD_PATH: &str = "ROC_STD_PATH";
+
+const INDENT: &str = "    ";
+
+/// How many tag unions deep the sample values nest, unless a type needs more to be built at all
+const DEPTH: u32 = 3;
+
+/// The generated sources, apart from the glue itself and the templates above
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Harness {
+    /// `platform.roc`, the test platform
+    pub platform: String,
+    /// `app.roc`, which builds the sample values
+    pub app: String,
+    /// `src/lib.rs`, the host that builds the same values and checks them against Roc's
+    pub host: String,
+    /// The names of the types that get round-tripped
+    pub round_tripped: Vec<String>,
+    /// The names of the types that can't be round-tripped yet, and why
+    pub skipped: Vec<(String, &'static str)>,
+}
+
+/// Matches the name of any kind of tag union
+macro_rules! tag_union_name {
+    ($name:ident) => {
+        RocTagUnion::Enumeration { name: $name, .. }
+            | RocTagUnion::NonRecursive { name: $name, .. }
+            | RocTagUnion::Recursive { name: $name, .. }
+            | RocTagUnion::NullableWrapped { name: $name, .. }
+            | RocTagUnion::NullableUnwrapped { name: $name, .. }
+            | RocTagUnion::NonNullableUnwrapped { name: $name, .. }
+            | RocTagUnion::SingleTagStruct { name: $name, .. }
+    };
+}
+
+pub fn emit(types: &Types) -> Harness {
+    let heights = heights(types);
+    let mut sampler = Sampler {
+        types,
+        heights: &heights,
+        counter: 0,
+        long_string: false,
+    };
+    let mut aliases = String::new();
+    let mut provides = Vec::new();
+    let mut definitions = String::new();
+    let mut samples = String::new();
+    let mut externs = String::new();
+    let mut checks = String::new();
+    let mut calls = String::new();
+    let mut round_tripped = Vec::new();
+    let mut skipped = Vec::new();
+
+    for id in types.ids() {
+        let name = match types.get_type(id) {
+            RocType::Struct { name, .. } | RocType::TagUnion(tag_union_name!(name)) => name,
+            _ => continue,
+        };
+
+        if let Some(reason) = unsupported(id, types, &mut Vec::new()) {
+            skipped.push((name.clone(), reason));
+
+            continue;
+        }
+
+        let roc_type = roc_type_name(id, types);
+        let rust_type = type_name(id, types);
+        let field = lowercase_first(name);
+
+        if let RocType::TagUnion(tag_union) = types.get_type(id) {
+            let tags: Vec<String> = tags(tag_union, types)
+                .iter()
+                .map(|(tag_name, args)| {
+                    let mut buf = tag_name.clone();
+
+                    for arg in args {
+                        buf.push(' ');
+                        buf.push_str(&parens(roc_type_name(*arg, types)));
+                    }
+
+                    buf
+                })
+                .collect();
+
+            writeln!(aliases, "{name} : [{}]\n", tags.join(", ")).unwrap();
+        }
+
+        let values = sampler.top_level(id);
+
+        writeln!(
+            definitions,
+            "sample{name}ForHost : List {roc_type}\nsample{name}ForHost = main.{field}\n\ncheck{name}ForHost : List {roc_type} -> Bool\ncheck{name}ForHost = \\values -> values == main.{field}\n",
+            roc_type = parens(roc_type.clone()),
+        )
+        .unwrap();
+
+        provides.push(format!("sample{name}ForHost"));
+        provides.push(format!("check{name}ForHost"));
+
+        writeln!(samples, "{INDENT}{field}: [").unwrap();
+
+        for value in values.iter() {
+            writeln!(samples, "{INDENT}{INDENT}{},", value.roc).unwrap();
+        }
+
+        writeln!(samples, "{INDENT}],").unwrap();
+
+        write!(
+            externs,
+            r#"
+    #[link_name = "roc__sample{name}ForHost_1_exposed_generic"]
+    fn roc_sample_{name}(_: *mut roc_std::RocList<{rust_type}>);
+
+    #[link_name = "roc__check{name}ForHost_1_exposed_generic"]
+    fn roc_check_{name}(_: *mut bool, _: *const roc_std::RocList<{rust_type}>);
+"#
+        )
+        .unwrap();
+
+        let rust_values: Vec<String> = values
+            .iter()
+            .map(|value| format!("{INDENT}{INDENT}{},", value.rust))
+            .collect();
+        let rust_values = rust_values.join("\n");
+
+        write!(
+            checks,
+            r#"
+fn round_trip_{name}() {{
+    let from_roc = unsafe {{
+        let mut ret = core::mem::MaybeUninit::uninit();
+
+        roc_sample_{name}(ret.as_mut_ptr());
+
+        ret.assume_init()
+    }};
+
+    let from_host: roc_std::RocList<{rust_type}> = roc_std::RocList::from_slice(&[
+{rust_values}
+    ]);
+
+    assert_eq!(
+        from_roc, from_host,
+        "The {name} values Roc built are different from the ones the host built"
+    );
+
+    let roc_agrees = unsafe {{
+        let mut ret = core::mem::MaybeUninit::uninit();
+
+        // Roc takes ownership of the argument
+        let arg = core::mem::ManuallyDrop::new(from_host);
+
+        roc_check_{name}(ret.as_mut_ptr(), &*arg);
+
+        ret.assume_init()
+    }};
+
+    assert!(
+        roc_agrees,
+        "Roc read the {name} values the host built as different from the ones it built"
+    );
+
+    println!("{name} round-tripped");
+}}
+"#
+        )
+        .unwrap();
+
+        writeln!(calls, "{INDENT}round_trip_{name}();").unwrap();
+
+        round_tripped.push(name.clone());
+    }
+
+    let platform = format!(
+        r#"platform "round-trip"
+    requires {{}} {{ main : _ }}
+    exposes []
+    packages {{}}
+    imports []
+    provides [{}]
+
+{aliases}{}
+"#,
+        provides.join(", "),
+        definitions.trim_end(),
+    );
+
+    let app = format!(
+        r#"app "round-trip"
+    packages {{ pf: "platform.roc" }}
+    imports []
+    provides [main] to pf
+
+main = {{
+{samples}}}
+"#
+    );
+
+    let mut skipped_comment = String::new();
+
+    for (name, reason) in skipped.iter() {
+        writeln!(
+            skipped_comment,
+            "// {name} isn't round-tripped, because {reason}."
+        )
+        .unwrap();
+    }
+
+    if !skipped_comment.is_empty() {
+        skipped_comment.push('\n');
+    }
+
+    let host = format!(
+        r#"// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by `roc glue --round-trip`
+
+#![allow(non_snake_case)]
+
+mod glue;
+mod roc_externs;
+
+use glue::*;
+// The glue calls these through `crate::`
+use roc_externs::*;
+
+{skipped_comment}extern "C" {{{externs}}}
+{checks}
+#[no_mangle]
+pub extern "C" fn rust_main() -> i32 {{
+{calls}
+    0
+}}
+"#
+    );
+
+    Harness {
+        platform,
+        app,
+        host,
+        round_tripped,
+        skipped,
+    }
+}
+
+/// Why values of this type can't be round-tripped yet, if they can't
+fn unsupported(id: TypeId, types: &Types, visiting: &mut Vec<TypeId>) -> Option<&'static str> {
+    if visiting.contains(&id) {
+        return None;
+    }
+
+    visiting.push(id);
+
+    let answer = match types.get_type(id) {
+        RocType::RocStr | RocType::Bool | RocType::Unit => None,
+        RocType::Num(RocNum::F32 | RocNum::F64) => {
+            Some("it contains an F32 or F64, which Roc can't compare with ==")
+        }
+        RocType::Num(RocNum::F128) => Some("Roc doesn't support F128 yet"),
+        RocType::Num(_) => None,
+        RocType::RocDict(_, _) | RocType::RocSet(_) => {
+            Some("it contains a Dict or Set, which the host can't build in the same order as Roc")
+        }
+        RocType::RocBox(_) => Some("it contains a Box"),
+        RocType::Function { .. } => Some("it contains a function, which can't be compared"),
+        RocType::EmptyTagUnion => Some("it contains an empty tag union, which has no values"),
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) if tags.is_empty() => {
+            Some("it contains an empty tag union, which has no values")
+        }
+        RocType::RocList(elem) | RocType::RecursivePointer(elem) => {
+            unsupported(*elem, types, visiting)
+        }
+        RocType::RocResult(ok, err) => {
+            unsupported(*ok, types, visiting).or_else(|| unsupported(*err, types, visiting))
+        }
+        RocType::Struct { fields, .. } => fields
+            .iter()
+            .find_map(|(_, field)| unsupported(*field, types, visiting)),
+        RocType::TagUnionPayload { fields, .. } => fields
+            .iter()
+            .find_map(|(_, field)| unsupported(*field, types, visiting)),
+        RocType::TagUnion(tag_union) => tags(tag_union, types)
+            .iter()
+            .flat_map(|(_, args)| args.iter())
+            .find_map(|arg| unsupported(*arg, types, visiting)),
+    };
+
+    visiting.pop();
+
+    answer
+}
+
+/// Each tag of the union, along with the types of its payload's values in order
+fn tags(tag_union: &RocTagUnion, types: &Types) -> Vec<(String, Vec<TypeId>)> {
+    let args = |payload: &Option<TypeId>| match payload {
+        None => Vec::new(),
+        Some(id) => match types.get_type(*id) {
+            RocType::TagUnionPayload { fields, .. } => {
+                let mut fields = fields.clone();
+
+                fields.sort_by_key(|(index, _)| *index);
+
+                fields.into_iter().map(|(_, field)| field).collect()
+            }
+            _ => vec![*id],
+        },
+    };
+
+    match tag_union {
+        RocTagUnion::Enumeration { tags, .. } => tags
+            .iter()
+            .map(|tag_name| (tag_name.clone(), Vec::new()))
+            .collect(),
+        RocTagUnion::NonRecursive { tags, .. }
+        | RocTagUnion::Recursive { tags, .. }
+        | RocTagUnion::NullableWrapped { tags, .. } => tags
+            .iter()
+            .map(|(tag_name, payload)| (tag_name.clone(), args(payload)))
+            .collect(),
+        RocTagUnion::NonNullableUnwrapped {
+            tag_name, payload, ..
+        } => vec![(tag_name.clone(), args(&Some(*payload)))],
+        RocTagUnion::SingleTagStruct {
+            tag_name,
+            payload_fields,
+            ..
+        } => vec![(tag_name.clone(), payload_fields.clone())],
+        RocTagUnion::NullableUnwrapped {
+            null_tag,
+            non_null_tag,
+            non_null_payload,
+            ..
+        } => vec![
+            (null_tag.clone(), Vec::new()),
+            (non_null_tag.clone(), args(&Some(*non_null_payload))),
+        ],
+    }
+}
+
+/// The type as it's written in Roc. Records are written out in full, because Roc doesn't
+/// have names for them, but tag unions use the aliases the test platform declares, which
+/// recursive ones need.
+fn roc_type_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::RocStr => "Str".to_string(),
+        RocType::Bool => "Bool".to_string(),
+        RocType::Unit => "{}".to_string(),
+        RocType::Num(num) => format!("{:?}", num),
+        RocType::RocList(elem) => format!("List {}", parens(roc_type_name(*elem, types))),
+        RocType::RocResult(ok, err) => format!(
+            "Result {} {}",
+            parens(roc_type_name(*ok, types)),
+            parens(roc_type_name(*err, types))
+        ),
+        RocType::Struct { fields, .. } if fields.is_empty() => "{}".to_string(),
+        RocType::Struct { fields, .. } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(label, field)| format!("{label} : {}", roc_type_name(*field, types)))
+                .collect();
+
+            format!("{{ {} }}", fields.join(", "))
+        }
+        RocType::TagUnion(tag_union_name!(name)) => name.clone(),
+        RocType::RecursivePointer(content) => roc_type_name(*content, types),
+        other => unreachable!("{:?} can't be round-tripped", other),
+    }
+}
+
+/// Wraps a Roc type or value in parentheses if it needs them to be an argument
+fn parens(roc: String) -> String {
+    if roc.contains(' ') && !roc.starts_with(&['{', '[', '"'][..]) {
+        format!("({roc})")
+    } else {
+        roc
+    }
+}
+
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// How many tag unions deep the smallest value of each type has to nest. Only tag unions
+/// count, because every recursive type recurses through one, and lists can always be empty.
+/// Recursive types without a way out never get a height.
+fn heights(types: &Types) -> MutMap<TypeId, u32> {
+    let mut heights = MutMap::default();
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for id in types.ids() {
+            if let Some(height) = height(id, types, &heights) {
+                if heights.get(&id) != Some(&height) {
+                    heights.insert(id, height);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    heights
+}
+
+fn height(id: TypeId, types: &Types, heights: &MutMap<TypeId, u32>) -> Option<u32> {
+    let max = |ids: &[TypeId]| {
+        ids.iter().try_fold(0, |max: u32, id| {
+            heights.get(id).map(|height| max.max(*height))
+        })
+    };
+
+    match types.get_type(id) {
+        RocType::RocStr | RocType::Bool | RocType::Unit | RocType::Num(_) | RocType::RocList(_) => {
+            Some(0)
+        }
+        RocType::RocResult(ok, err) => [ok, err]
+            .iter()
+            .filter_map(|id| heights.get(id))
+            .min()
+            .copied(),
+        RocType::Struct { fields, .. } => {
+            let fields: Vec<TypeId> = fields.iter().map(|(_, field)| *field).collect();
+
+            max(&fields)
+        }
+        RocType::RecursivePointer(content) => heights.get(content).copied(),
+        RocType::TagUnion(tag_union) => tags(tag_union, types)
+            .iter()
+            .filter_map(|(_, args)| max(args))
+            .min()
+            .map(|height| height + 1),
+        RocType::TagUnionPayload { .. }
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::Function { .. }
+        | RocType::EmptyTagUnion => None,
+    }
+}
+
+/// The same value, written in Roc and in Rust using the glue
+struct Sample {
+    roc: String,
+    rust: String,
+}
+
+/// Builds sample values. Every number, string and choice between tags comes from the same
+/// counter, so neighbouring values differ from each other and a field read from the wrong
+/// offset shows up.
+struct Sampler<'a> {
+    types: &'a Types,
+    heights: &'a MutMap<TypeId, u32>,
+    counter: u32,
+    /// Strings alternate between ones short enough to be stored inline and ones that aren't
+    long_string: bool,
+}
+
+impl<'a> Sampler<'a> {
+    fn next(&mut self) -> u32 {
+        self.counter += 1;
+
+        self.counter
+    }
+
+    fn height(&self, id: TypeId) -> u32 {
+        self.heights[&id]
+    }
+
+    fn max_height(&self, ids: &[TypeId]) -> u32 {
+        ids.iter().map(|id| self.height(*id)).max().unwrap_or(0)
+    }
+
+    /// The values to round-trip for this type: one for each tag of a tag union, since those
+    /// are laid out differently, and one otherwise.
+    fn top_level(&mut self, id: TypeId) -> Vec<Sample> {
+        match self.types.get_type(id) {
+            RocType::TagUnion(tag_union) => tags(tag_union, self.types)
+                .into_iter()
+                .map(|(tag_name, args)| {
+                    let budget = DEPTH.max(self.max_height(&args) + 1);
+
+                    self.tag(id, &tag_name, &args, budget - 1)
+                })
+                .collect(),
+            _ => {
+                let budget = DEPTH.max(self.height(id));
+
+                vec![self.sample(id, budget)]
+            }
+        }
+    }
+
+    /// A value of the type that nests at most `budget` tag unions deep, which must be at
+    /// least the type's height.
+    fn sample(&mut self, id: TypeId, budget: u32) -> Sample {
+        match self.types.get_type(id) {
+            RocType::RocStr => {
+                let n = self.next();
+                let string = if self.long_string {
+                    format!("a string too long to be stored inline {n}")
+                } else {
+                    format!("s{n}")
+                };
+
+                self.long_string = !self.long_string;
+
+                Sample {
+                    roc: format!("\"{string}\""),
+                    rust: format!("roc_std::RocStr::from(\"{string}\")"),
+                }
+            }
+            RocType::Bool => {
+                let bool = self.next() % 2 == 0;
+
+                Sample {
+                    roc: if bool { "Bool.true" } else { "Bool.false" }.to_string(),
+                    rust: bool.to_string(),
+                }
+            }
+            RocType::Unit => Sample {
+                roc: "{}".to_string(),
+                rust: "()".to_string(),
+            },
+            RocType::Num(num) => self.num(*num),
+            RocType::RocList(elem) => {
+                let elem = *elem;
+                let len = if budget >= self.height(elem) { 2 } else { 0 };
+                let elems: Vec<Sample> = (0..len).map(|_| self.sample(elem, budget)).collect();
+                let roc: Vec<&str> = elems.iter().map(|elem| elem.roc.as_str()).collect();
+                let rust: Vec<&str> = elems.iter().map(|elem| elem.rust.as_str()).collect();
+
+                Sample {
+                    roc: format!("[{}]", roc.join(", ")),
+                    rust: format!("roc_std::RocList::from_slice(&[{}])", rust.join(", ")),
+                }
+            }
+            RocType::RocResult(ok, err) => {
+                let (ok, err) = (*ok, *err);
+                let mut options = Vec::new();
+
+                if self.height(ok) <= budget {
+                    options.push(("Ok", "ok", ok));
+                }
+
+                if self.height(err) <= budget {
+                    options.push(("Err", "err", err));
+                }
+
+                let (roc_tag, rust_fn, payload) = options[self.next() as usize % options.len()];
+                let payload = self.sample(payload, budget);
+
+                Sample {
+                    roc: format!("{roc_tag} {}", parens(payload.roc)),
+                    rust: format!("roc_std::RocResult::{rust_fn}({})", payload.rust),
+                }
+            }
+            RocType::Struct { fields, .. } if fields.is_empty() => Sample {
+                roc: "{}".to_string(),
+                rust: format!("{} {{}}", type_name(id, self.types)),
+            },
+            RocType::Struct { fields, .. } => {
+                let mut roc = Vec::with_capacity(fields.len());
+                let mut rust = Vec::with_capacity(fields.len());
+
+                for (label, field) in fields.iter() {
+                    let value = self.sample(*field, budget);
+
+                    roc.push(format!("{label}: {}", value.roc));
+                    rust.push(format!("{}: {}", escape_kw(label.clone()), value.rust));
+                }
+
+                Sample {
+                    roc: format!("{{ {} }}", roc.join(", ")),
+                    rust: format!("{} {{ {} }}", type_name(id, self.types), rust.join(", ")),
+                }
+            }
+            RocType::RecursivePointer(content) => self.sample(*content, budget),
+            RocType::TagUnion(tag_union) => {
+                // Nest as deeply as the budget allows, since layout bugs in recursive tag
+                // unions tend to show up below the first level.
+                let fitting: Vec<(String, Vec<TypeId>)> = tags(tag_union, self.types)
+                    .into_iter()
+                    .filter(|(_, args)| self.max_height(args) < budget)
+                    .collect();
+                let deepest = fitting
+                    .iter()
+                    .map(|(_, args)| self.max_height(args))
+                    .max()
+                    .unwrap_or(0);
+                let options: Vec<&(String, Vec<TypeId>)> = fitting
+                    .iter()
+                    .filter(|(_, args)| self.max_height(args) == deepest)
+                    .collect();
+                let (tag_name, args) = options[self.next() as usize % options.len()];
+
+                self.tag(id, tag_name, args, budget - 1)
+            }
+            other => unreachable!("{:?} can't be round-tripped", other),
+        }
+    }
+
+    fn tag(&mut self, union_id: TypeId, tag_name: &str, args: &[TypeId], budget: u32) -> Sample {
+        let union_name = type_name(union_id, self.types);
+
+        if args.is_empty() {
+            return Sample {
+                roc: tag_name.to_string(),
+                rust: format!("{union_name}::{tag_name}"),
+            };
+        }
+
+        let args: Vec<Sample> = args.iter().map(|arg| self.sample(*arg, budget)).collect();
+        let roc: Vec<String> = args.iter().map(|arg| parens(arg.roc.clone())).collect();
+        let rust: Vec<&str> = args.iter().map(|arg| arg.rust.as_str()).collect();
+
+        Sample {
+            roc: format!("{tag_name} {}", roc.join(" ")),
+            rust: format!("{union_name}::{tag_name}({})", rust.join(", ")),
+        }
+    }
+
+    fn num(&mut self, num: RocNum) -> Sample {
+        // Small enough to fit in every number type
+        let n = self.next() % 100;
+
+        match num {
+            RocNum::Dec => Sample {
+                roc: format!("{n}.5"),
+                rust: format!("roc_std::RocDec::from_str(\"{n}.5\").unwrap()"),
+            },
+            RocNum::I128 => Sample {
+                roc: n.to_string(),
+                rust: format!("roc_std::I128::from({n})"),
+            },
+            RocNum::U128 => Sample {
+                roc: n.to_string(),
+                rust: format!("roc_std::U128::from({n})"),
+            },
+            RocNum::F32 | RocNum::F64 | RocNum::F128 => {
+                unreachable!("{:?} can't be round-tripped", num)
+            }
+            _ => Sample {
+                roc: n.to_string(),
+                rust: n.to_string(),
+            },
+        }
+    }
+}
@@ -294,12 +294,106 @@ fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, impls: &mut Impl
             // This is recursively pointing to a type that should already have been added,
             // so no extra work needs to happen.
         }
-        RocType::Function { .. } => {
-            // TODO actually generate glue functions!
+        RocType::Function {
+            name,
+            extern_name: Some(extern_name),
+            args,
+            ret,
+        } => add_closure(name, extern_name, args, *ret, target_info, id, types, impls),
+        RocType::Function {
+            extern_name: None, ..
+        } => {
+            // The compiler only generates callers for closures exposed to the host with `as`,
+            // so the host has no way to call any other function.
         }
     }
 }
 
+/// A closure the host got from Roc, which it calls through the compiler-generated `_caller`.
+/// Its captures are opaque to the host, so only their size and alignment are declared.
+#[allow(clippy::too_many_arguments)]
+fn add_closure(
+    name: &str,
+    extern_name: &str,
+    args: &[TypeId],
+    ret_id: TypeId,
+    target_info: TargetInfo,
+    closure_id: TypeId,
+    types: &Types,
+    impls: &mut Impls,
+) {
+    let name = escape_kw(name.to_string());
+    let size = types.size_ignoring_alignment(closure_id);
+    let align = types.align(closure_id);
+
+    add_decl(
+        impls,
+        None,
+        target_info,
+        format!(
+            r#"#[repr(C, align({align}))]
+pub struct {name} {{
+    closure_data: [u8; {size}],
+}}"#
+        ),
+    );
+
+    let ret_type = type_name(ret_id, types);
+    let mut params = Vec::with_capacity(args.len());
+    let mut extern_params = Vec::with_capacity(args.len());
+    let mut extern_args = Vec::with_capacity(args.len());
+
+    for (index, arg_id) in args.iter().enumerate() {
+        let arg_type = type_name(*arg_id, types);
+
+        params.push(format!(", arg{index}: {arg_type}"));
+        extern_params.push(format!("arg{index}: *const {arg_type}"));
+        extern_args.push(format!("&*arg{index}"));
+    }
+
+    // Roc takes ownership of the arguments, so they must not be dropped here.
+    let owned_args: String = (0..args.len())
+        .map(|index| {
+            format!("{INDENT}{INDENT}let arg{index} = core::mem::ManuallyDrop::new(arg{index});\n")
+        })
+        .collect();
+
+    extern_params.push("closure_data: *const u8".to_string());
+    extern_params.push(format!("output: *mut {ret_type}"));
+    extern_args.push("self.closure_data.as_ptr()".to_string());
+    extern_args.push("output.as_mut_ptr()".to_string());
+
+    add_decl(
+        impls,
+        Some(format!("impl {name}")),
+        target_info,
+        format!(
+            r#"/// Calls the closure through `{extern_name}_caller`. This consumes the closure,
+    /// because Roc takes ownership of what it captured.
+    pub fn call(self{params}){ret} {{
+        extern "C" {{
+            fn {extern_name}_caller({extern_params});
+        }}
+
+{owned_args}        let mut output = core::mem::MaybeUninit::uninit();
+
+        unsafe {{
+            {extern_name}_caller({extern_args});
+
+            output.assume_init()
+        }}
+    }}"#,
+            params = params.concat(),
+            ret = match types.get_type(ret_id) {
+                RocType::Unit => String::new(),
+                _ => format!(" -> {ret_type}"),
+            },
+            extern_params = extern_params.join(", "),
+            extern_args = extern_args.join(", "),
+        ),
+    );
+}
+
 fn add_single_tag_struct(
     name: &str,
     tag_name: &str,
@@ -1711,7 +1805,7 @@ fn add_struct<S: Display>(
     add_decl(impls, None, target_info, buf);
 }
 
-fn type_name(id: TypeId, types: &Types) -> String {
+pub(crate) fn type_name(id: TypeId, types: &Types) -> String {
     match types.get_type(id) {
         RocType::Unit => "()".to_string(),
         RocType::EmptyTagUnion => "std::convert::Infallible".to_string(),
@@ -2186,7 +2280,7 @@ fn write_indents(indentations: usize, buf: &mut String) {
     }
 }
 
-fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
+pub(crate) fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused, so return 2^3 = 8
         Architecture::X86_64 | Architecture::Aarch64 => 8,
@@ -2196,7 +2290,7 @@ fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
 }
 
 #[inline(always)]
-fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
+pub(crate) fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused
         Architecture::X86_64 | Architecture::Aarch64 => 0b0000_0111,
@@ -2499,7 +2593,7 @@ const RESERVED_KEYWORDS: &[&str] = &[
 ];
 
 /// Escape a Rust reserved keyword, if necessary.
-fn escape_kw(input: String) -> String {
+pub(crate) fn escape_kw(input: String) -> String {
     let is_reserved_keyword = RESERVED_KEYWORDS.contains(&input.as_str());
 
     if is_reserved_keyword {
@@ -0,0 +1,795 @@
+use crate::c_glue;
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types, Unsupported};
+use std::fmt::Write;
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.swift");
+const INDENT: &str = "    ";
+
+/// The Swift types go in this namespace, so they don't clash with the C types of the same names,
+/// which Swift imports from the header generated alongside them.
+const NAMESPACE: &str = "Roc";
+
+/// Declares Swift types for the Roc types, and conversions to and from the C types in the header
+/// that goes with them, which the host's bridging header needs to include. Fails if the host uses
+/// types Swift glue can't declare yet.
+pub fn emit(types: &Types, c_header_name: &str) -> Result<String, Vec<Unsupported>> {
+    let unsupported = types.find_unsupported(unsupported);
+
+    if !unsupported.is_empty() {
+        return Err(unsupported);
+    }
+
+    let mut decls = String::new();
+    let mut conversions = String::new();
+
+    for id in types.sorted_ids() {
+        add_type(id, types, &mut decls, &mut conversions);
+    }
+
+    let mut buf = format!(
+        "\n// The C types used here are declared in {c_header_name}, which the host's bridging\n// header needs to include.\n"
+    );
+
+    write!(buf, "\nenum {NAMESPACE} {{{decls}}}\n{conversions}").unwrap();
+
+    add_entry_points(types, &mut buf);
+
+    Ok(buf)
+}
+
+/// Why Swift glue can't declare this type yet, if it can't
+fn unsupported(typ: &RocType) -> Option<&'static str> {
+    match typ {
+        RocType::Num(RocNum::U128 | RocNum::I128 | RocNum::F128 | RocNum::Dec) => {
+            Some("it contains a 128-bit number, which Swift glue doesn't support yet")
+        }
+        RocType::RocDict(_, _) | RocType::RocSet(_) => {
+            Some("it contains a Dict or Set, which Swift glue doesn't support yet")
+        }
+        RocType::RocBox(_) => Some("it contains a Box, which Swift glue doesn't support yet"),
+        RocType::Function { .. } => Some(
+            "it contains a function, and Swift glue can only declare the entry points' functions",
+        ),
+        _ => None,
+    }
+}
+
+/// What a tag carries, as a C type and as the associated values of a Swift enum case
+enum Payload {
+    None,
+    Single(TypeId),
+    /// The C struct of the payload, and its fields
+    Fields(String, Vec<(String, TypeId)>),
+}
+
+impl Payload {
+    fn new(opt_payload_id: Option<TypeId>, types: &Types) -> Self {
+        let payload_id = match opt_payload_id {
+            Some(payload_id) if !is_zero_sized(payload_id, types) => payload_id,
+            _ => return Payload::None,
+        };
+
+        match types.get_type(payload_id) {
+            RocType::TagUnionPayload { name, fields } => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .filter(|(_, id)| !is_zero_sized(*id, types))
+                    .map(|(index, id)| (format!("f{index}"), *id))
+                    .collect();
+
+                if fields.is_empty() {
+                    Payload::None
+                } else {
+                    Payload::Fields(name.clone(), fields)
+                }
+            }
+            _ => Payload::Single(payload_id),
+        }
+    }
+
+    fn swift_types(&self, types: &Types) -> Vec<String> {
+        match self {
+            Payload::None => Vec::new(),
+            Payload::Single(id) => vec![swift_type(*id, types)],
+            Payload::Fields(_, fields) => fields
+                .iter()
+                .map(|(_, id)| swift_type(*id, types))
+                .collect(),
+        }
+    }
+}
+
+fn add_type(id: TypeId, types: &Types, decls: &mut String, conversions: &mut String) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => add_struct(
+            name,
+            fields
+                .iter()
+                .map(|(label, id)| (label.clone(), c_glue::escape_kw(label.clone()), *id)),
+            types,
+            decls,
+            conversions,
+        ),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            payload_fields,
+            ..
+        }) => add_struct(
+            name,
+            payload_fields.iter().enumerate().map(|(index, id)| {
+                let label = format!("f{index}");
+
+                (label.clone(), label, *id)
+            }),
+            types,
+            decls,
+            conversions,
+        ),
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            write!(
+                decls,
+                "\n{INDENT}enum {name}: {} {{\n",
+                unsigned_int_type(*size)
+            )
+            .unwrap();
+
+            for (index, tag) in tags.iter().enumerate() {
+                writeln!(decls, "{INDENT}{INDENT}case {} = {index}", case_name(tag)).unwrap();
+            }
+
+            writeln!(decls, "{INDENT}}}").unwrap();
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive { name, tags, .. }) => {
+            // Empty tag unions can never come up at runtime,
+            // and so don't need declared types.
+            if !tags.is_empty() {
+                add_non_recursive_tag_union(name, tags, types, decls, conversions);
+            }
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            let tags = [
+                ("Err".to_string(), Some(*err_id)),
+                ("Ok".to_string(), Some(*ok_id)),
+            ];
+
+            add_non_recursive_tag_union(
+                &c_glue::result_name(*ok_id, *err_id, types),
+                &tags,
+                types,
+                decls,
+                conversions,
+            );
+        }
+        RocType::TagUnion(RocTagUnion::Recursive {
+            name,
+            tags,
+            discriminant_size,
+            ..
+        }) => {
+            if !tags.is_empty() {
+                add_recursive_tag_union(
+                    name,
+                    tags,
+                    None,
+                    *discriminant_size,
+                    types,
+                    decls,
+                    conversions,
+                );
+            }
+        }
+        RocType::TagUnion(RocTagUnion::NullableWrapped {
+            name,
+            index_of_null_tag,
+            tags,
+            discriminant_size,
+            ..
+        }) => add_recursive_tag_union(
+            name,
+            tags,
+            Some(*index_of_null_tag as usize),
+            *discriminant_size,
+            types,
+            decls,
+            conversions,
+        ),
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped {
+            name,
+            tag_name,
+            payload,
+        }) => {
+            let tags = [(tag_name.clone(), Some(*payload))];
+            let payload = Payload::new(Some(*payload), types);
+            let swift_value = format!(
+                "return {}",
+                case_value(tag_name, &payload, "pointer.pointee", types)
+            );
+
+            add_enum(name, true, &tags, types, decls);
+            add_conversions(name, &tags, &swift_value, types, conversions);
+        }
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            null_tag,
+            non_null_tag,
+            non_null_payload,
+            ..
+        }) => {
+            let mut tags = vec![
+                (null_tag.clone(), None),
+                (non_null_tag.clone(), Some(*non_null_payload)),
+            ];
+
+            tags.sort();
+
+            let payload = Payload::new(Some(*non_null_payload), types);
+            let swift_value = format!(
+                "if pointer == nil {{\n{INDENT}{INDENT}{INDENT}return .{}\n{INDENT}{INDENT}}} else {{\n{INDENT}{INDENT}{INDENT}return {}\n{INDENT}{INDENT}}}",
+                case_name(null_tag),
+                case_value(non_null_tag, &payload, "pointer.pointee", types)
+            );
+
+            add_enum(name, true, &tags, types, decls);
+            add_conversions(name, &tags, &swift_value, types, conversions);
+        }
+        // These are built into Swift, or are inlined into the cases of their enums.
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocList(_)
+        | RocType::TagUnionPayload { .. } => {}
+        RocType::RocDict(_, _) | RocType::RocSet(_) | RocType::RocBox(_) => {
+            // These only need declaring when something uses them, and swift_type
+            // reports that they aren't supported yet.
+        }
+        RocType::RecursivePointer { .. } => {
+            // This is recursively pointing to a type that should already have been added,
+            // so no extra work needs to happen.
+        }
+        RocType::Function { .. } => {
+            // The host only calls these through the entry points.
+        }
+    }
+}
+
+/// A Swift struct, and the conversions to and from the C struct of the same name.
+/// The fields are the Swift label, the C label and the type.
+fn add_struct<I>(name: &str, fields: I, types: &Types, decls: &mut String, conversions: &mut String)
+where
+    I: Iterator<Item = (String, String, TypeId)>,
+{
+    let fields: Vec<_> = fields
+        .filter(|(_, _, id)| !is_zero_sized(*id, types))
+        .collect();
+
+    // The C glue doesn't declare zero-sized structs, so there's nothing to convert to.
+    if fields.is_empty() {
+        return;
+    }
+
+    write!(decls, "\n{INDENT}struct {name} {{\n").unwrap();
+
+    for (label, _, id) in fields.iter() {
+        writeln!(
+            decls,
+            "{INDENT}{INDENT}var {}: {}",
+            escape_kw(label.clone()),
+            swift_type(*id, types)
+        )
+        .unwrap();
+    }
+
+    writeln!(decls, "{INDENT}}}").unwrap();
+
+    let c_args: Vec<_> = fields
+        .iter()
+        .map(|(label, c_label, id)| {
+            let swift_field = format!("value.{}", escape_kw(label.clone()));
+
+            format!(
+                "{}: {}",
+                escape_kw(c_label.clone()),
+                to_c(&swift_field, *id, types)
+            )
+        })
+        .collect();
+
+    let swift_args: Vec<_> = fields
+        .iter()
+        .map(|(label, c_label, id)| {
+            let c_field = format!("self.{}", escape_kw(c_label.clone()));
+
+            format!(
+                "{}: {}",
+                escape_kw(label.clone()),
+                to_swift(&c_field, *id, types)
+            )
+        })
+        .collect();
+
+    write!(
+        conversions,
+        r#"
+extension {name} {{
+    init(_ value: {NAMESPACE}.{name}) {{
+        self.init({})
+    }}
+
+    var swiftValue: {NAMESPACE}.{name} {{
+        {NAMESPACE}.{name}({})
+    }}
+}}
+"#,
+        c_args.join(", "),
+        swift_args.join(", ")
+    )
+    .unwrap();
+}
+
+fn add_non_recursive_tag_union(
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    types: &Types,
+    decls: &mut String,
+    conversions: &mut String,
+) {
+    let mut swift_value =
+        format!("var value = self\n\n{INDENT}{INDENT}switch {name}_discriminant(&value) {{\n");
+
+    for (index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+        let payload = Payload::new(*opt_payload_id, types);
+        let member = format!("value.{}", escape_kw(c_glue::escape_kw(tag_name.clone())));
+
+        write!(
+            swift_value,
+            "{INDENT}{INDENT}case {index}:\n{INDENT}{INDENT}{INDENT}return {}\n",
+            case_value(tag_name, &payload, &member, types)
+        )
+        .unwrap();
+    }
+
+    write!(
+        swift_value,
+        "{INDENT}{INDENT}default:\n{INDENT}{INDENT}{INDENT}fatalError(\"{name} has an invalid discriminant\")\n{INDENT}{INDENT}}}"
+    )
+    .unwrap();
+
+    add_enum(name, false, tags, types, decls);
+    add_conversions(name, tags, &swift_value, types, conversions);
+}
+
+/// Tag unions that refer to themselves are pointers in C. If there is more than one tag, the
+/// C glue gets the discriminant out of the pointer.
+fn add_recursive_tag_union(
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    null_tag_index: Option<usize>,
+    discriminant_size: u32,
+    types: &Types,
+    decls: &mut String,
+    conversions: &mut String,
+) {
+    let case_for = |tag_name: &str, opt_payload_id: Option<TypeId>, tag_index: usize| {
+        if Some(tag_index) == null_tag_index {
+            format!(".{}", case_name(tag_name))
+        } else {
+            let payload = Payload::new(opt_payload_id, types);
+            let member = format!(
+                "{name}_union_pointer(self).pointee.{}",
+                escape_kw(c_glue::escape_kw(tag_name.to_string()))
+            );
+
+            case_value(tag_name, &payload, &member, types)
+        }
+    };
+
+    let swift_value = if discriminant_size > 0 && tags.len() > 1 {
+        let mut swift_value = format!("switch {name}_discriminant(self) {{\n");
+
+        for (index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+            write!(
+                swift_value,
+                "{INDENT}{INDENT}case {index}:\n{INDENT}{INDENT}{INDENT}return {}\n",
+                case_for(tag_name, *opt_payload_id, index)
+            )
+            .unwrap();
+        }
+
+        write!(
+            swift_value,
+            "{INDENT}{INDENT}default:\n{INDENT}{INDENT}{INDENT}fatalError(\"{name} has an invalid discriminant\")\n{INDENT}{INDENT}}}"
+        )
+        .unwrap();
+
+        swift_value
+    } else {
+        let (tag_name, opt_payload_id) = &tags[0];
+
+        format!("return {}", case_for(tag_name, *opt_payload_id, 0))
+    };
+
+    add_enum(name, true, tags, types, decls);
+    add_conversions(name, tags, &swift_value, types, conversions);
+}
+
+/// e.g. `enum Name { case foo(String, UInt8) }`
+fn add_enum(
+    name: &str,
+    is_indirect: bool,
+    tags: &[(String, Option<TypeId>)],
+    types: &Types,
+    decls: &mut String,
+) {
+    let indirect = if is_indirect { "indirect " } else { "" };
+
+    write!(decls, "\n{INDENT}{indirect}enum {name} {{\n").unwrap();
+
+    for (tag_name, opt_payload_id) in tags {
+        let payload_types = Payload::new(*opt_payload_id, types).swift_types(types);
+
+        if payload_types.is_empty() {
+            writeln!(decls, "{INDENT}{INDENT}case {}", case_name(tag_name)).unwrap();
+        } else {
+            writeln!(
+                decls,
+                "{INDENT}{INDENT}case {}({})",
+                case_name(tag_name),
+                payload_types.join(", ")
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(decls, "{INDENT}}}").unwrap();
+}
+
+/// Conversions between a Swift enum and the C type of the same name. Converting to C uses the
+/// constructors in the C glue; `swift_value` is the body of the conversion from C.
+fn add_conversions(
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    swift_value: &str,
+    types: &Types,
+    conversions: &mut String,
+) {
+    let mut c_value = String::new();
+
+    for (tag_name, opt_payload_id) in tags {
+        let payload = Payload::new(*opt_payload_id, types);
+        let case = case_name(tag_name);
+
+        let (pattern, arg) = match &payload {
+            Payload::None => (format!(".{case}"), String::new()),
+            Payload::Single(id) => (format!(".{case}(let payload)"), to_c("payload", *id, types)),
+            Payload::Fields(c_name, fields) => {
+                let bindings: Vec<_> = fields
+                    .iter()
+                    .map(|(label, _)| format!("let {label}"))
+                    .collect();
+                let c_args: Vec<_> = fields
+                    .iter()
+                    .map(|(label, id)| format!("{label}: {}", to_c(label, *id, types)))
+                    .collect();
+
+                (
+                    format!(".{case}({})", bindings.join(", ")),
+                    format!("{c_name}({})", c_args.join(", ")),
+                )
+            }
+        };
+
+        write!(
+            c_value,
+            "{INDENT}{INDENT}case {pattern}:\n{INDENT}{INDENT}{INDENT}self = {name}_make_{tag_name}({arg})\n"
+        )
+        .unwrap();
+    }
+
+    write!(
+        conversions,
+        r#"
+extension {name} {{
+    init(_ value: {NAMESPACE}.{name}) {{
+        switch value {{
+{c_value}        }}
+    }}
+
+    var swiftValue: {NAMESPACE}.{name} {{
+        {swift_value}
+    }}
+}}
+"#
+    )
+    .unwrap();
+}
+
+/// The Swift enum case for a tag, given the C value of its payload
+fn case_value(tag_name: &str, payload: &Payload, c_payload: &str, types: &Types) -> String {
+    let case = case_name(tag_name);
+
+    match payload {
+        Payload::None => format!(".{case}"),
+        Payload::Single(id) => format!(".{case}({})", to_swift(c_payload, *id, types)),
+        Payload::Fields(_, fields) => {
+            let values: Vec<_> = fields
+                .iter()
+                .map(|(label, id)| to_swift(&format!("{c_payload}.{label}"), *id, types))
+                .collect();
+
+            format!(".{case}({})", values.join(", "))
+        }
+    }
+}
+
+/// Swift functions that call the `roc__*_exposed_generic` functions, converting their
+/// arguments and return values.
+fn add_entry_points(types: &Types, buf: &mut String) {
+    for (name, id) in types.entry_points() {
+        let (args, ret_id) = match types.get_type(*id) {
+            RocType::Function { args, ret, .. } => (args.as_slice(), *ret),
+            _ => (&[][..], *id),
+        };
+
+        let mut params = Vec::new();
+        let mut body = String::new();
+        let mut c_args = vec!["&ret".to_string()];
+
+        let returns_value = !is_zero_sized(ret_id, types);
+
+        if returns_value {
+            writeln!(body, "{INDENT}var ret = {}()", abi_type(ret_id, types)).unwrap();
+        } else {
+            writeln!(body, "{INDENT}var ret: UInt8 = 0").unwrap();
+        }
+
+        for (index, arg_id) in args.iter().enumerate() {
+            if is_zero_sized(*arg_id, types) {
+                continue;
+            }
+
+            let arg = format!("arg{index}");
+
+            params.push(format!("_ {arg}: {}", swift_type(*arg_id, types)));
+
+            // Strings and lists are passed by reference; everything else by value.
+            match types.get_type(*arg_id) {
+                RocType::RocStr | RocType::RocList(_) => {
+                    writeln!(body, "{INDENT}var {arg} = {}", to_c(&arg, *arg_id, types)).unwrap();
+
+                    c_args.push(format!("&{arg}"));
+                }
+                _ => c_args.push(to_c(&arg, *arg_id, types)),
+            }
+        }
+
+        writeln!(
+            body,
+            "\n{INDENT}roc__{name}_1_exposed_generic({})",
+            c_args.join(", ")
+        )
+        .unwrap();
+
+        let ret = if returns_value {
+            writeln!(body, "\n{INDENT}return {}", to_swift("ret", ret_id, types)).unwrap();
+
+            format!(" -> {}", swift_type(ret_id, types))
+        } else {
+            String::new()
+        };
+
+        let mut fn_name = name.clone();
+
+        if let Some(first) = fn_name.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+
+        write!(
+            buf,
+            "\n/// Calls `{name}` in the Roc app\nfunc roc{fn_name}({}){ret} {{\n{body}}}\n",
+            params.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+/// Converts the Swift value to a C value
+fn to_c(expr: &str, id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Num(_) | RocType::Bool => expr.to_string(),
+        RocType::RocStr => format!("RocStr({expr})"),
+        RocType::RocList(elem_id) => {
+            let elem = to_c("$0", *elem_id, types);
+
+            if elem == "$0" {
+                format!("RocList({expr})")
+            } else {
+                format!("RocList({expr}.map {{ {elem} }})")
+            }
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { .. }) => format!("{expr}.rawValue"),
+        RocType::RecursivePointer(content) => to_c(expr, *content, types),
+        _ => format!("{}({expr})", abi_type(id, types)),
+    }
+}
+
+/// Converts the C value to a Swift value
+fn to_swift(expr: &str, id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Num(_) | RocType::Bool => expr.to_string(),
+        RocType::RocStr => format!("String({expr})"),
+        RocType::RocList(elem_id) => {
+            let elem = to_swift("$0", *elem_id, types);
+            let elements = format!("{expr}.elements(of: {}.self)", abi_type(*elem_id, types));
+
+            if elem == "$0" {
+                format!("Array({elements})")
+            } else {
+                format!("{elements}.map {{ {elem} }}")
+            }
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, .. }) => {
+            format!("{NAMESPACE}.{name}(rawValue: {expr})!")
+        }
+        RocType::RecursivePointer(content) => to_swift(expr, *content, types),
+        _ => format!("{expr}.swiftValue"),
+    }
+}
+
+/// The name of the Swift type
+fn swift_type(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "Void".to_string(),
+        RocType::RocStr => "String".to_string(),
+        RocType::RocList(elem_id) => format!("[{}]", swift_type(*elem_id, types)),
+        RocType::RecursivePointer(content) => swift_type(*content, types),
+        RocType::RocResult(ok_id, err_id) => format!(
+            "{NAMESPACE}.{}",
+            c_glue::result_name(*ok_id, *err_id, types)
+        ),
+        RocType::Struct { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => {
+            format!("{NAMESPACE}.{name}")
+        }
+        RocType::TagUnionPayload { .. } => {
+            unreachable!("Tag union payloads are the associated values of their enum's cases")
+        }
+        _ => abi_type(id, types),
+    }
+}
+
+/// The name of the C type, as Swift imports it
+fn abi_type(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Bool => "Bool".to_string(),
+        RocType::Num(RocNum::U8) => "UInt8".to_string(),
+        RocType::Num(RocNum::U16) => "UInt16".to_string(),
+        RocType::Num(RocNum::U32) => "UInt32".to_string(),
+        RocType::Num(RocNum::U64) => "UInt64".to_string(),
+        RocType::Num(RocNum::I8) => "Int8".to_string(),
+        RocType::Num(RocNum::I16) => "Int16".to_string(),
+        RocType::Num(RocNum::I32) => "Int32".to_string(),
+        RocType::Num(RocNum::I64) => "Int64".to_string(),
+        RocType::Num(RocNum::F32) => "Float".to_string(),
+        RocType::Num(RocNum::F64) => "Double".to_string(),
+        RocType::Num(num @ (RocNum::U128 | RocNum::I128 | RocNum::F128 | RocNum::Dec)) => {
+            unreachable!("`emit` rejects {:?}", num)
+        }
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocDict(_, _) | RocType::RocSet(_) | RocType::RocBox(_) => {
+            unreachable!("`emit` rejects Dict, Set and Box")
+        }
+        RocType::RocResult(ok_id, err_id) => c_glue::result_name(*ok_id, *err_id, types),
+        RocType::RecursivePointer(content) => abi_type(*content, types),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => name.clone(),
+        RocType::Unit | RocType::EmptyTagUnion => "Void".to_string(),
+        RocType::Function { .. } => unreachable!("`emit` rejects functions outside entry points"),
+    }
+}
+
+/// Tags are capitalized in Roc, but enum cases are lowercase in Swift, e.g. `Foo` is `.foo`
+fn case_name(tag_name: &str) -> String {
+    let mut case = tag_name.to_string();
+
+    if let Some(first) = case.get_mut(0..1) {
+        first.make_ascii_lowercase();
+    }
+
+    escape_kw(case)
+}
+
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn unsigned_int_type(size: u32) -> &'static str {
+    match size {
+        1 => "UInt8",
+        2 => "UInt16",
+        4 => "UInt32",
+        8 => "UInt64",
+        _ => unreachable!("Invalid discriminant size: {}", size),
+    }
+}
+
+// Based on https://docs.swift.org/swift-book/ReferenceManual/LexicalStructure.html#ID413
+const RESERVED_KEYWORDS: &[&str] = &[
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "switch",
+    "where",
+    "while",
+    "as",
+    "Any",
+    "catch",
+    "false",
+    "is",
+    "nil",
+    "super",
+    "self",
+    "Self",
+    "throw",
+    "throws",
+    "true",
+    "try",
+];
+
+/// Escape a Swift reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        format!("`{input}`")
+    } else {
+        input
+    }
+}
@@ -1,4 +1,5 @@
 use crate::enums::Enums;
+use crate::roc_type;
 use crate::structs::Structs;
 use bumpalo::Bump;
 use fnv::FnvHashMap;
@@ -15,7 +16,7 @@ use roc_mono::layout::{
     cmp_fields, ext_var_is_empty_tag_union, round_up_to_alignment, Builtin, Discriminant, Layout,
     LayoutCache, LayoutInterner, UnionLayout,
 };
-use roc_target::TargetInfo;
+use roc_target::{Architecture, OperatingSystem, TargetInfo};
 use roc_types::{
     subs::{Content, FlatType, GetSubsSlice, Subs, UnionLabels, UnionTags, Variable},
     types::{AliasKind, RecordField},
@@ -49,6 +50,9 @@ pub struct Types {
     /// This is important for declaration order in C; we need to output a
     /// type declaration earlier in the file than where it gets referenced by another type.
     deps: VecMap<TypeId, Vec<TypeId>>,
+
+    /// The values the platform exposes to its host, e.g. `mainForHost`, and their types.
+    entry_points: Vec<(String, TypeId)>,
     target: TargetInfo,
 }
 
@@ -61,6 +65,7 @@ impl Types {
             sizes: Vec::new(),
             aligns: Vec::new(),
             deps: VecMap::with_capacity(cap),
+            entry_points: Vec::new(),
         }
     }
 
@@ -301,11 +306,13 @@ impl Types {
                     name: name_a,
                     args: args_a,
                     ret: ret_a,
+                    ..
                 },
                 Function {
                     name: name_b,
                     args: args_b,
                     ret: ret_b,
+                    ..
                 },
             ) => {
                 // for functions, the name is actually important because two functions
@@ -413,6 +420,14 @@ impl Types {
         id
     }
 
+    pub fn target(&self) -> TargetInfo {
+        self.target
+    }
+
+    pub fn entry_points(&self) -> &[(String, TypeId)] {
+        &self.entry_points
+    }
+
     pub fn depends(&mut self, id: TypeId, depends_on: TypeId) {
         self.deps.get_or_insert(id, Vec::new).push(depends_on);
     }
@@ -489,6 +504,346 @@ impl Types {
             } => unreachable!("Cyclic type definitions: {:?}", nodes_in_cycle),
         }
     }
+
+    /// The named types and entry points that use a type glue can't declare yet, i.e. one that
+    /// `reason` gives a reason for. An entry point's own function type isn't checked, because
+    /// glue declares entry points as functions.
+    pub fn find_unsupported<F>(&self, reason: F) -> Vec<Unsupported>
+    where
+        F: Fn(&RocType) -> Option<&'static str>,
+    {
+        let mut unsupported = Vec::new();
+
+        for id in self.ids() {
+            let name = match self.get_type(id) {
+                RocType::Struct { name, .. } => name.as_str(),
+                RocType::TagUnion(tag_union) => tag_union.name(),
+                _ => continue,
+            };
+
+            if let Some(reason) = self.unsupported_in(id, &reason, &mut Vec::new()) {
+                unsupported.push(Unsupported {
+                    name: name.to_string(),
+                    reason,
+                });
+            }
+        }
+
+        for (name, id) in self.entry_points() {
+            let ids = match self.get_type(*id) {
+                RocType::Function { args, ret, .. } => {
+                    args.iter().chain(std::iter::once(ret)).copied().collect()
+                }
+                _ => vec![*id],
+            };
+
+            if let Some(reason) = ids
+                .into_iter()
+                .find_map(|id| self.unsupported_in(id, &reason, &mut Vec::new()))
+            {
+                unsupported.push(Unsupported {
+                    name: name.clone(),
+                    reason,
+                });
+            }
+        }
+
+        unsupported
+    }
+
+    fn unsupported_in<F>(
+        &self,
+        id: TypeId,
+        reason: &F,
+        visiting: &mut Vec<TypeId>,
+    ) -> Option<&'static str>
+    where
+        F: Fn(&RocType) -> Option<&'static str>,
+    {
+        if visiting.contains(&id) {
+            return None;
+        }
+
+        let typ = self.get_type(id);
+
+        if let Some(reason) = reason(typ) {
+            return Some(reason);
+        }
+
+        let contents: Vec<TypeId> = match typ {
+            RocType::RocStr
+            | RocType::Bool
+            | RocType::Num(_)
+            | RocType::EmptyTagUnion
+            | RocType::Unit
+            | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => Vec::new(),
+            RocType::RocResult(a, b) | RocType::RocDict(a, b) => vec![*a, *b],
+            RocType::RocList(elem)
+            | RocType::RocSet(elem)
+            | RocType::RocBox(elem)
+            | RocType::RecursivePointer(elem) => vec![*elem],
+            RocType::Struct { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+            RocType::TagUnionPayload { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+            RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. })
+            | RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+            | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. }) => {
+                tags.iter().filter_map(|(_, payload)| *payload).collect()
+            }
+            RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { payload, .. })
+            | RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+                non_null_payload: payload,
+                ..
+            }) => vec![*payload],
+            RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => {
+                payload_fields.clone()
+            }
+            RocType::Function { args, ret, .. } => {
+                args.iter().chain(std::iter::once(ret)).copied().collect()
+            }
+        };
+
+        visiting.push(id);
+
+        let answer = contents
+            .into_iter()
+            .find_map(|id| self.unsupported_in(id, reason, visiting));
+
+        visiting.pop();
+
+        answer
+    }
+}
+
+/// A named type or entry point that uses a type glue can't declare yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub name: String,
+    pub reason: &'static str,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} isn't supported, because {}.", self.name, self.reason)
+    }
+}
+
+/// The `Types` a `.roc` glue spec receives; see `crates/glue/platform/RocType.roc`.
+impl From<&Types> for roc_type::Types {
+    fn from(types: &Types) -> Self {
+        let deps = types
+            .deps
+            .iter()
+            .map(|(id, deps)| roc_type::TypeDeps {
+                id: id.0 as _,
+                dependsOn: deps.iter().map(|dep| dep.0 as _).collect(),
+            })
+            .collect();
+
+        let entry_points = types
+            .entry_points
+            .iter()
+            .map(|(name, id)| roc_type::EntryPoint {
+                name: name.as_str().into(),
+                id: id.0 as _,
+            })
+            .collect();
+
+        roc_type::Types {
+            aligns: types.aligns.as_slice().into(),
+            deps,
+            entryPoints: entry_points,
+            sizes: types.sizes.as_slice().into(),
+            types: types.types.iter().map(roc_type::RocType::from).collect(),
+            target: types.target.into(),
+        }
+    }
+}
+
+impl From<&RocType> for roc_type::RocType {
+    fn from(typ: &RocType) -> Self {
+        match typ {
+            RocType::RocStr => roc_type::RocType::RocStr,
+            RocType::Bool => roc_type::RocType::Bool,
+            RocType::RocResult(ok, err) => roc_type::RocType::RocResult(ok.0 as _, err.0 as _),
+            RocType::Num(num) => roc_type::RocType::Num((*num).into()),
+            RocType::RocList(elem) => roc_type::RocType::RocList(elem.0 as _),
+            RocType::RocDict(key, value) => roc_type::RocType::RocDict(key.0 as _, value.0 as _),
+            RocType::RocSet(elem) => roc_type::RocType::RocSet(elem.0 as _),
+            RocType::RocBox(elem) => roc_type::RocType::RocBox(elem.0 as _),
+            RocType::TagUnion(union) => roc_type::RocType::TagUnion(union.into()),
+            RocType::EmptyTagUnion => roc_type::RocType::EmptyTagUnion,
+            RocType::Struct { name, fields } => roc_type::RocType::Struct(roc_type::RocStruct {
+                name: name.as_str().into(),
+                fields: fields
+                    .iter()
+                    .map(|(name, id)| roc_type::RocStructField {
+                        name: name.as_str().into(),
+                        r#type: id.0 as _,
+                    })
+                    .collect(),
+            }),
+            RocType::TagUnionPayload { name, fields } => {
+                roc_type::RocType::TagUnionPayload(roc_type::RocTagUnionPayload {
+                    name: name.as_str().into(),
+                    fields: fields
+                        .iter()
+                        .map(|(discriminant, id)| roc_type::RocTagUnionPayloadField {
+                            discriminant: *discriminant as _,
+                            r#type: id.0 as _,
+                        })
+                        .collect(),
+                })
+            }
+            RocType::RecursivePointer(id) => roc_type::RocType::RecursivePointer(id.0 as _),
+            RocType::Function {
+                name,
+                extern_name,
+                args,
+                ret,
+            } => roc_type::RocType::Function(roc_type::RocFn {
+                args: args.iter().map(|arg| arg.0 as _).collect(),
+                externName: match extern_name {
+                    Some(extern_name) => roc_type::RocExternName::Some(extern_name.as_str().into()),
+                    None => roc_type::RocExternName::None,
+                },
+                name: name.as_str().into(),
+                ret: ret.0 as _,
+            }),
+            RocType::Unit => roc_type::RocType::Unit,
+        }
+    }
+}
+
+impl From<&RocTagUnion> for roc_type::RocTagUnion {
+    fn from(union: &RocTagUnion) -> Self {
+        fn tags(tags: &[(String, Option<TypeId>)]) -> roc_std::RocList<roc_type::RocTag> {
+            tags.iter()
+                .map(|(name, payload)| roc_type::RocTag {
+                    name: name.as_str().into(),
+                    payload: match payload {
+                        Some(id) => roc_type::RocTagPayload::Some(id.0 as _),
+                        None => roc_type::RocTagPayload::None,
+                    },
+                })
+                .collect()
+        }
+
+        match union {
+            RocTagUnion::Enumeration { name, tags, size } => {
+                roc_type::RocTagUnion::Enumeration(roc_type::RocEnumeration {
+                    name: name.as_str().into(),
+                    tags: tags.iter().map(|tag| tag.as_str().into()).collect(),
+                    size: *size,
+                })
+            }
+            RocTagUnion::NonRecursive {
+                name,
+                tags: union_tags,
+                discriminant_size,
+                discriminant_offset,
+            } => roc_type::RocTagUnion::NonRecursive(roc_type::RocTagUnionInfo {
+                name: name.as_str().into(),
+                tags: tags(union_tags),
+                discriminantSize: *discriminant_size,
+                discriminantOffset: *discriminant_offset,
+            }),
+            RocTagUnion::Recursive {
+                name,
+                tags: union_tags,
+                discriminant_size,
+                discriminant_offset,
+            } => roc_type::RocTagUnion::Recursive(roc_type::RocTagUnionInfo {
+                name: name.as_str().into(),
+                tags: tags(union_tags),
+                discriminantSize: *discriminant_size,
+                discriminantOffset: *discriminant_offset,
+            }),
+            RocTagUnion::NonNullableUnwrapped {
+                name,
+                tag_name,
+                payload,
+            } => roc_type::RocTagUnion::NonNullableUnwrapped(roc_type::RocNonNullableUnwrapped {
+                name: name.as_str().into(),
+                tagName: tag_name.as_str().into(),
+                payload: payload.0 as _,
+            }),
+            RocTagUnion::SingleTagStruct {
+                name,
+                tag_name,
+                payload_fields,
+            } => roc_type::RocTagUnion::SingleTagStruct(roc_type::RocSingleTagStruct {
+                name: name.as_str().into(),
+                tagName: tag_name.as_str().into(),
+                payloadFields: payload_fields.iter().map(|id| id.0 as _).collect(),
+            }),
+            RocTagUnion::NullableWrapped {
+                name,
+                index_of_null_tag,
+                tags: union_tags,
+                discriminant_size,
+                discriminant_offset,
+            } => roc_type::RocTagUnion::NullableWrapped(roc_type::RocNullableWrapped {
+                name: name.as_str().into(),
+                indexOfNullTag: *index_of_null_tag,
+                tags: tags(union_tags),
+                discriminantSize: *discriminant_size,
+                discriminantOffset: *discriminant_offset,
+            }),
+            RocTagUnion::NullableUnwrapped {
+                name,
+                null_tag,
+                non_null_tag,
+                non_null_payload,
+                null_represents_first_tag,
+            } => roc_type::RocTagUnion::NullableUnwrapped(roc_type::RocNullableUnwrapped {
+                name: name.as_str().into(),
+                nullTag: null_tag.as_str().into(),
+                nonNullTag: non_null_tag.as_str().into(),
+                nonNullPayload: non_null_payload.0 as _,
+                nullRepresentsFirstTag: *null_represents_first_tag,
+            }),
+        }
+    }
+}
+
+impl From<RocNum> for roc_type::RocNum {
+    fn from(num: RocNum) -> Self {
+        match num {
+            RocNum::I8 => roc_type::RocNum::I8,
+            RocNum::U8 => roc_type::RocNum::U8,
+            RocNum::I16 => roc_type::RocNum::I16,
+            RocNum::U16 => roc_type::RocNum::U16,
+            RocNum::I32 => roc_type::RocNum::I32,
+            RocNum::U32 => roc_type::RocNum::U32,
+            RocNum::I64 => roc_type::RocNum::I64,
+            RocNum::U64 => roc_type::RocNum::U64,
+            RocNum::I128 => roc_type::RocNum::I128,
+            RocNum::U128 => roc_type::RocNum::U128,
+            RocNum::F32 => roc_type::RocNum::F32,
+            RocNum::F64 => roc_type::RocNum::F64,
+            RocNum::F128 => roc_type::RocNum::F128,
+            RocNum::Dec => roc_type::RocNum::Dec,
+        }
+    }
+}
+
+impl From<TargetInfo> for roc_type::Target {
+    fn from(target: TargetInfo) -> Self {
+        roc_type::Target {
+            architecture: match target.architecture {
+                Architecture::Aarch32 => roc_type::Architecture::Aarch32,
+                Architecture::Aarch64 => roc_type::Architecture::Aarch64,
+                Architecture::Wasm32 => roc_type::Architecture::Wasm32,
+                Architecture::X86_32 => roc_type::Architecture::X86x32,
+                Architecture::X86_64 => roc_type::Architecture::X86x64,
+            },
+            operatingSystem: match target.operating_system {
+                OperatingSystem::Windows => roc_type::OperatingSystem::Windows,
+                OperatingSystem::Unix => roc_type::OperatingSystem::Unix,
+                OperatingSystem::Wasi => roc_type::OperatingSystem::Wasi,
+            },
+        }
+    }
 }
 
 enum RocTypeOrPending<'a> {
@@ -523,6 +878,9 @@ pub enum RocType {
     RecursivePointer(TypeId),
     Function {
         name: String,
+        /// If the host can call this closure, the prefix of the symbols the compiler generates for
+        /// it, e.g. `roc__mainForHost_1__Fx` for `roc__mainForHost_1__Fx_caller`.
+        extern_name: Option<String>,
         args: Vec<TypeId>,
         ret: TypeId,
     },
@@ -659,6 +1017,20 @@ pub enum RocTagUnion {
     },
 }
 
+impl RocTagUnion {
+    pub fn name(&self) -> &str {
+        match self {
+            RocTagUnion::Enumeration { name, .. }
+            | RocTagUnion::NonRecursive { name, .. }
+            | RocTagUnion::Recursive { name, .. }
+            | RocTagUnion::NonNullableUnwrapped { name, .. }
+            | RocTagUnion::SingleTagStruct { name, .. }
+            | RocTagUnion::NullableWrapped { name, .. }
+            | RocTagUnion::NullableUnwrapped { name, .. } => name,
+        }
+    }
+}
+
 pub struct Env<'a> {
     arena: &'a Bump,
     subs: &'a Subs,
@@ -668,6 +1040,9 @@ pub struct Env<'a> {
     enum_names: Enums,
     pending_recursive_types: VecMap<TypeId, Variable>,
     known_recursive_types: VecMap<Variable, TypeId>,
+    /// The aliases of functions exposed to the host with `as`, by the root of their
+    /// lambda set variables, along with the entry point exposing them
+    host_exposed_aliases: VecMap<Variable, (Symbol, String)>,
     target: TargetInfo,
 }
 
@@ -687,26 +1062,62 @@ impl<'a> Env<'a> {
             enum_names: Default::default(),
             pending_recursive_types: Default::default(),
             known_recursive_types: Default::default(),
+            host_exposed_aliases: Default::default(),
             layout_cache: LayoutCache::new(layout_interner, target),
             target,
         }
     }
 
-    pub fn vars_to_types<I>(&mut self, variables: I) -> Types
+    /// `host_exposed_aliases` are the aliases the entry points expose to the host with `as`,
+    /// e.g. `Fx` in `mainForHost : Effect {} as Fx`, along with the names of those entry points.
+    pub fn vars_to_types<I>(
+        &mut self,
+        variables: I,
+        entry_points: &[(String, Variable)],
+        host_exposed_aliases: &[(Symbol, Variable, String)],
+    ) -> Types
     where
         I: Iterator<Item = Variable>,
     {
+        self.host_exposed_aliases = host_exposed_aliases
+            .iter()
+            .filter_map(|(alias, var, entry_point)| {
+                let closure_var = self.closure_var(*var)?;
+                let root = self.subs.get_root_key_without_compacting(closure_var);
+
+                Some((root, (*alias, entry_point.clone())))
+            })
+            .collect();
+
         let mut types = Types::with_capacity(variables.size_hint().0, self.target);
 
         for var in variables {
             self.add_type(var, &mut types);
         }
 
+        for (name, var) in entry_points {
+            let type_id = self.add_type(*var, &mut types);
+
+            types.entry_points.push((name.clone(), type_id));
+        }
+
         self.resolve_pending_recursive_types(&mut types);
 
         types
     }
 
+    /// The lambda set of the function this is an alias of, if it's a function at all.
+    /// (Aliases like `Effect {} as Fx` resolve to a function too.)
+    fn closure_var(&self, mut var: Variable) -> Option<Variable> {
+        loop {
+            match self.subs.get_content_without_compacting(var) {
+                Content::Alias(_, _, real_var, _) => var = *real_var,
+                Content::Structure(FlatType::Func(_, closure_var, _)) => return Some(*closure_var),
+                _ => return None,
+            }
+        }
+    }
+
     fn add_type(&mut self, var: Variable, types: &mut Types) -> TypeId {
         roc_tracing::debug!(content=?roc_types::subs::SubsFmtContent(self.subs.get_content_without_compacting(var), self.subs), "adding type");
 
@@ -843,12 +1254,31 @@ fn add_type_help<'a>(
                 add_type_help(env, ret_layout, *ret_var, None, types)
             };
 
-            let name = format!("TODO_roc_function_{:?}", closure_var);
+            let root_var = subs.get_root_key_without_compacting(*closure_var);
+            let (name, extern_name) = match env.host_exposed_aliases.get(&root_var) {
+                Some((alias, entry_point)) => {
+                    let name = alias.as_str(env.interns).to_string();
+
+                    // This is how the compiler names the closure caller,
+                    // e.g. `roc__mainForHost_1__Fx_caller`
+                    let extern_name = format!(
+                        "roc__{}_1_{}_{}",
+                        entry_point,
+                        alias.module_string(env.interns).as_str(),
+                        name
+                    );
+
+                    (name, Some(extern_name))
+                }
+                None => (format!("TODO_roc_function_{:?}", closure_var), None),
+            };
+
             let fn_type_id = types.add_named(
                 &env.layout_cache.interner,
                 name.clone(),
                 RocType::Function {
                     name,
+                    extern_name,
                     args: arg_type_ids.clone(),
                     ret: ret_type_id,
                 },
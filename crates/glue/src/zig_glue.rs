@@ -0,0 +1,844 @@
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types, Unsupported};
+use indexmap::IndexMap;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::Write;
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.zig");
+const INDENT: &str = "    ";
+
+/// Each declaration's name, and the architectures each version of its body is for.
+type Decls = IndexMap<String, IndexMap<String, Vec<TargetInfo>>>;
+
+fn add_decl(decls: &mut Decls, name: String, target_info: TargetInfo, body: String) {
+    let bodies = decls.entry(name).or_default();
+    let targets = bodies.entry(body).or_default();
+
+    targets.push(target_info);
+}
+
+/// Declares the types for a Zig host. Fails if the host uses types Zig glue can't declare yet.
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> Result<String, Vec<Unsupported>> {
+    for (types, target_info) in types_and_targets {
+        let unsupported = types.find_unsupported(|typ| unsupported(typ, target_info.architecture));
+
+        if !unsupported.is_empty() {
+            return Err(unsupported);
+        }
+    }
+
+    let mut buf = String::new();
+    let mut decls: Decls = IndexMap::default();
+    let mut layout_checks: IndexMap<String, Vec<TargetInfo>> = IndexMap::default();
+
+    // Zig declarations can refer to each other in any order, so these don't need sorting
+    // beyond what Rust glue does.
+    for (types, target_info) in types_and_targets {
+        for id in types.sorted_ids() {
+            add_type(*target_info, id, types, &mut decls);
+        }
+
+        layout_checks
+            .entry(layout_checks_for(types))
+            .or_default()
+            .push(*target_info);
+    }
+
+    for (name, bodies) in decls {
+        buf.push('\n');
+
+        match bodies.iter().next() {
+            Some((body, targets))
+                if bodies.len() == 1 && targets.len() == types_and_targets.len() =>
+            {
+                writeln!(buf, "pub const {name} = {body};").unwrap();
+            }
+            _ => {
+                // The declaration differs between architectures, so pick one at compile time.
+                writeln!(buf, "pub const {name} = switch (builtin.cpu.arch) {{").unwrap();
+
+                for (body, targets) in bodies {
+                    let archs: Vec<_> = targets
+                        .iter()
+                        .map(|target_info| format!(".{}", arch_to_str(target_info.architecture)))
+                        .collect();
+
+                    writeln!(
+                        buf,
+                        "{INDENT}{} => {},",
+                        archs.join(", "),
+                        indent_body(&body)
+                    )
+                    .unwrap();
+                }
+
+                writeln!(
+                    buf,
+                    "{INDENT}else => @compileError(\"{name} is not available on this architecture\"),"
+                )
+                .unwrap();
+                buf.push_str("};\n");
+            }
+        }
+    }
+
+    add_layout_checks(layout_checks, &mut buf);
+
+    // Entry points are passed the same way on every architecture.
+    if let Some((types, _)) = types_and_targets.first() {
+        add_entry_points(types, &mut buf);
+    }
+
+    Ok(buf)
+}
+
+/// Why Zig glue can't declare this type yet, if it can't
+fn unsupported(typ: &RocType, architecture: Architecture) -> Option<&'static str> {
+    match typ {
+        RocType::Function { .. } => Some(
+            "it contains a function, and Zig glue can only declare the entry points' functions",
+        ),
+        RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. })
+            if tags.len() > max_pointer_tagged_variants(architecture) =>
+        {
+            Some("it contains a recursive tag union with too many tags to store the tag in a pointer, which Zig glue doesn't support yet")
+        }
+        _ => None,
+    }
+}
+
+fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, decls: &mut Decls) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => add_struct(
+            name,
+            target_info,
+            fields
+                .iter()
+                .map(|(label, id)| (escape_kw(label.clone()), *id)),
+            types,
+            decls,
+        ),
+        RocType::TagUnionPayload { name, fields } => add_struct(
+            name,
+            target_info,
+            fields.iter().map(|(index, id)| (format!("f{index}"), *id)),
+            types,
+            decls,
+        ),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            payload_fields,
+            ..
+        }) => add_struct(
+            name,
+            target_info,
+            payload_fields
+                .iter()
+                .enumerate()
+                .map(|(index, id)| (format!("f{index}"), *id)),
+            types,
+            decls,
+        ),
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            let body = enum_body(tags.iter(), *size);
+
+            add_decl(decls, name.clone(), target_info, body);
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive {
+            name,
+            tags,
+            discriminant_size,
+            discriminant_offset,
+        }) => {
+            // Empty tag unions can never come up at runtime,
+            // and so don't need declared types.
+            if !tags.is_empty() {
+                add_non_recursive_tag_union(
+                    name,
+                    target_info,
+                    id,
+                    tags,
+                    *discriminant_size,
+                    *discriminant_offset,
+                    types,
+                    decls,
+                )
+            }
+        }
+        RocType::TagUnion(RocTagUnion::Recursive {
+            name,
+            tags,
+            discriminant_size,
+            ..
+        }) => {
+            if !tags.is_empty() {
+                add_recursive_tag_union(
+                    name,
+                    target_info,
+                    tags,
+                    None,
+                    *discriminant_size,
+                    types,
+                    decls,
+                )
+            }
+        }
+        RocType::TagUnion(RocTagUnion::NullableWrapped {
+            name,
+            index_of_null_tag,
+            tags,
+            discriminant_size,
+            ..
+        }) => add_recursive_tag_union(
+            name,
+            target_info,
+            tags,
+            Some(*index_of_null_tag as usize),
+            *discriminant_size,
+            types,
+            decls,
+        ),
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped {
+            name,
+            tag_name,
+            payload,
+        }) => {
+            let payload_type = type_name(*payload, types);
+            let constructor = unwrapped_constructor(tag_name, &payload_type);
+            let body = format!(
+                r#"extern struct {{
+    pointer: *{payload_type},
+
+    const Self = @This();
+{constructor}}}"#
+            );
+
+            add_decl(decls, name.clone(), target_info, body);
+        }
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            null_tag,
+            non_null_tag,
+            non_null_payload,
+            ..
+        }) => {
+            let mut tags = vec![null_tag, non_null_tag];
+
+            tags.sort();
+
+            let discriminant_name = format!("discriminant_{name}");
+            let payload_type = type_name(*non_null_payload, types);
+            let constructor = unwrapped_constructor(non_null_tag, &payload_type);
+            let body = format!(
+                r#"extern struct {{
+    pointer: ?*{payload_type},
+
+    const Self = @This();
+
+    pub fn discriminant(self: Self) {discriminant_name} {{
+        if (self.pointer == null) {{
+            return .{null_tag};
+        }} else {{
+            return .{non_null_tag};
+        }}
+    }}
+
+    pub fn init{null_tag}() Self {{
+        return Self{{ .pointer = null }};
+    }}
+{constructor}}}"#
+            );
+
+            add_decl(
+                decls,
+                discriminant_name,
+                target_info,
+                enum_body(tags.into_iter(), 1),
+            );
+            add_decl(decls, name.clone(), target_info, body);
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            let tags = [
+                ("Err".to_string(), Some(*err_id)),
+                ("Ok".to_string(), Some(*ok_id)),
+            ];
+
+            // The discriminant comes right after the bigger payload.
+            let discriminant_offset = types
+                .size_rounded_to_alignment(*ok_id)
+                .max(types.size_rounded_to_alignment(*err_id));
+
+            add_non_recursive_tag_union(
+                &result_name(*ok_id, *err_id, types),
+                target_info,
+                id,
+                &tags,
+                1,
+                discriminant_offset,
+                types,
+                decls,
+            )
+        }
+        // These are declared in the header, or don't need to be declared in Zig.
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_) => {}
+        RocType::RecursivePointer { .. } => {
+            // This is recursively pointing to a type that should already have been added,
+            // so no extra work needs to happen.
+        }
+        RocType::Function { .. } => {
+            // The host only calls these through the entry points.
+        }
+    }
+}
+
+fn add_struct<I>(name: &str, target_info: TargetInfo, fields: I, types: &Types, decls: &mut Decls)
+where
+    I: Iterator<Item = (String, TypeId)>,
+{
+    let mut body = "extern struct {\n".to_string();
+
+    for (label, field_id) in fields {
+        if !is_zero_sized(field_id, types) {
+            writeln!(body, "{INDENT}{label}: {},", type_name(field_id, types)).unwrap();
+        }
+    }
+
+    body.push('}');
+
+    add_decl(decls, name.to_string(), target_info, body);
+}
+
+/// e.g. `enum(u8) { Bar = 0, Foo = 1 }`, one tag per line
+fn enum_body<'a, I>(tags: I, size: u32) -> String
+where
+    I: Iterator<Item = &'a String>,
+{
+    let mut body = format!("enum({}) {{\n", unsigned_int_type(size));
+
+    for (index, tag) in tags.enumerate() {
+        writeln!(body, "{INDENT}{} = {index},", escape_kw(tag.clone())).unwrap();
+    }
+
+    body.push('}');
+
+    body
+}
+
+/// The fields of a tag union's `extern union`, one per tag with a payload
+fn union_fields(tags: &[(String, Option<TypeId>)], types: &Types) -> String {
+    let mut fields = String::new();
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                writeln!(
+                    fields,
+                    "{INDENT}{}: {},",
+                    escape_kw(tag_name.clone()),
+                    type_name(*payload_id, types)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    fields
+}
+
+/// Roc puts the discriminant right after the biggest payload, rather than after the whole
+/// union, so it lives inside the `extern union` at a fixed offset.
+#[allow(clippy::too_many_arguments)]
+fn add_non_recursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    id: TypeId,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let size = types.size_rounded_to_alignment(id);
+    let discriminant_name = format!("discriminant_{name}");
+    let fields = union_fields(tags, types);
+
+    let mut body = format!(
+        r#"extern union {{
+{fields}    _sizer: [{size}]u8,
+
+    const Self = @This();
+    const discriminant_offset = {discriminant_offset};
+
+    pub fn discriminant(self: *const Self) {discriminant_name} {{
+        const bytes = @ptrCast([*]const u8, self) + discriminant_offset;
+
+        return @ptrCast(*const {discriminant_name}, @alignCast(@alignOf({discriminant_name}), bytes)).*;
+    }}
+
+    pub fn setDiscriminant(self: *Self, discriminant: {discriminant_name}) void {{
+        const bytes = @ptrCast([*]u8, self) + discriminant_offset;
+
+        @ptrCast(*{discriminant_name}, @alignCast(@alignOf({discriminant_name}), bytes)).* = discriminant;
+    }}
+"#
+    );
+
+    for (tag_name, opt_payload_id) in tags {
+        let tag = escape_kw(tag_name.clone());
+
+        match opt_payload_id {
+            Some(payload_id) if !is_zero_sized(*payload_id, types) => write!(
+                body,
+                r#"
+    pub fn init{tag_name}(payload: {}) Self {{
+        var self = Self{{ ._sizer = [_]u8{{0}} ** {size} }};
+
+        self.{tag} = payload;
+        self.setDiscriminant(.{tag});
+
+        return self;
+    }}
+"#,
+                type_name(*payload_id, types)
+            ),
+            _ => write!(
+                body,
+                r#"
+    pub fn init{tag_name}() Self {{
+        var self = Self{{ ._sizer = [_]u8{{0}} ** {size} }};
+
+        self.setDiscriminant(.{tag});
+
+        return self;
+    }}
+"#
+            ),
+        }
+        .unwrap();
+    }
+
+    body.push('}');
+
+    let tag_names = tags.iter().map(|(tag_name, _)| tag_name);
+
+    add_decl(
+        decls,
+        discriminant_name,
+        target_info,
+        enum_body(tag_names, discriminant_size),
+    );
+    add_decl(decls, name.to_string(), target_info, body);
+}
+
+/// Tag unions that refer to themselves are heap-allocated, and their values are pointers
+/// to an `extern union` of the payloads. If there is more than one tag, the discriminant is
+/// stored in the unused low bits of the pointer, which depend on the architecture.
+fn add_recursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    null_tag_index: Option<usize>,
+    discriminant_size: u32,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let architecture = target_info.architecture;
+
+    // emit rejects unions with more tags than this up front.
+    debug_assert!(tags.len() <= max_pointer_tagged_variants(architecture));
+
+    let union_name = format!("union_{name}");
+    let discriminant_name = format!("discriminant_{name}");
+    let discriminant_type = unsigned_int_type(discriminant_size.max(1));
+    let bitmask = format!("{:#b}", tagged_pointer_bitmask(architecture));
+    let has_discriminant = discriminant_size > 0 && tags.len() > 1;
+
+    let mut body = format!(
+        r#"extern struct {{
+    pointer: ?*anyopaque,
+
+    const Self = @This();
+    const tag_mask = {bitmask};
+
+    pub fn unionPointer(self: Self) *{union_name} {{
+        return @intToPtr(*{union_name}, @ptrToInt(self.pointer) & ~@as(usize, tag_mask));
+    }}
+"#
+    );
+
+    if has_discriminant {
+        let null_check = match null_tag_index {
+            Some(index) => format!(
+                "\n{INDENT}{INDENT}if (self.pointer == null) {{\n{INDENT}{INDENT}{INDENT}return .{};\n{INDENT}{INDENT}}}\n",
+                escape_kw(tags[index].0.clone())
+            ),
+            None => String::new(),
+        };
+
+        write!(
+            body,
+            r#"
+    pub fn discriminant(self: Self) {discriminant_name} {{{null_check}
+        return @intToEnum({discriminant_name}, @intCast({discriminant_type}, @ptrToInt(self.pointer) & tag_mask));
+    }}
+"#
+        )
+        .unwrap();
+    }
+
+    for (tag_index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+        if Some(tag_index) == null_tag_index {
+            write!(
+                body,
+                r#"
+    pub fn init{tag_name}() Self {{
+        return Self{{ .pointer = null }};
+    }}
+"#
+            )
+            .unwrap();
+
+            continue;
+        }
+
+        let tag = escape_kw(tag_name.clone());
+        let (params, store_payload) = match opt_payload_id {
+            Some(payload_id) if !is_zero_sized(*payload_id, types) => (
+                format!("payload: {}", type_name(*payload_id, types)),
+                format!("\n{INDENT}{INDENT}pointer[0] = {union_name}{{ .{tag} = payload }};\n"),
+            ),
+            _ => (String::new(), String::new()),
+        };
+        let address = if has_discriminant {
+            format!("@ptrToInt(pointer) | @enumToInt({discriminant_name}.{tag})")
+        } else {
+            "@ptrToInt(pointer)".to_string()
+        };
+
+        write!(
+            body,
+            r#"
+    pub fn init{tag_name}({params}) Self {{
+        const pointer = allocateRefcounted({union_name}, 1);
+{store_payload}
+        return Self{{ .pointer = @intToPtr(?*anyopaque, {address}) }};
+    }}
+"#
+        )
+        .unwrap();
+    }
+
+    body.push('}');
+
+    if has_discriminant {
+        let tag_names = tags.iter().map(|(tag_name, _)| tag_name);
+
+        add_decl(
+            decls,
+            discriminant_name,
+            target_info,
+            enum_body(tag_names, discriminant_size),
+        );
+    }
+
+    add_decl(
+        decls,
+        union_name,
+        target_info,
+        format!("extern union {{\n{}}}", union_fields(tags, types)),
+    );
+    add_decl(decls, name.to_string(), target_info, body);
+}
+
+/// The constructor of a recursive tag union that points straight at its payload
+fn unwrapped_constructor(tag_name: &str, payload_type: &str) -> String {
+    format!(
+        r#"
+    pub fn init{tag_name}(payload: {payload_type}) Self {{
+        const pointer = allocateRefcounted({payload_type}, 1);
+
+        pointer[0] = payload;
+
+        return Self{{ .pointer = &pointer[0] }};
+    }}
+"#
+    )
+}
+
+/// Checks that the types are laid out like Roc lays them out on this architecture.
+fn layout_checks_for(types: &Types) -> String {
+    let mut checks = String::new();
+
+    for id in types.sorted_ids() {
+        let name = match types.get_type(id) {
+            RocType::Struct { name, .. }
+            | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. })
+            | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+            | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+            | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+            | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. }) => name.clone(),
+            RocType::TagUnion(RocTagUnion::NonRecursive { name, tags, .. }) if !tags.is_empty() => {
+                name.clone()
+            }
+            RocType::RocResult(ok_id, err_id) => result_name(*ok_id, *err_id, types),
+            // The payloads of recursive tag unions are recorded with the size of the pointer
+            // to them, and the payloads of other tag unions are checked by the union.
+            _ => continue,
+        };
+
+        if !is_zero_sized(id, types) {
+            writeln!(
+                checks,
+                "{INDENT}{INDENT}{INDENT}assertLayout({name}, {}, {});",
+                types.size_rounded_to_alignment(id),
+                types.align(id)
+            )
+            .unwrap();
+        }
+    }
+
+    checks
+}
+
+fn add_layout_checks(layout_checks: IndexMap<String, Vec<TargetInfo>>, buf: &mut String) {
+    if layout_checks.keys().all(|checks| checks.is_empty()) {
+        return;
+    }
+
+    write!(buf, "\ncomptime {{\n{INDENT}switch (builtin.cpu.arch) {{\n").unwrap();
+
+    for (checks, targets) in layout_checks {
+        if checks.is_empty() {
+            continue;
+        }
+
+        let archs: Vec<_> = targets
+            .iter()
+            .map(|target_info| format!(".{}", arch_to_str(target_info.architecture)))
+            .collect();
+
+        write!(
+            buf,
+            "{INDENT}{INDENT}{} => {{\n{checks}{INDENT}{INDENT}}},\n",
+            archs.join(", ")
+        )
+        .unwrap();
+    }
+
+    write!(buf, "{INDENT}{INDENT}else => {{}},\n{INDENT}}}\n}}\n").unwrap();
+}
+
+/// The `roc__*_exposed_generic` functions the host calls. They write their result to the
+/// pointer in their first argument.
+fn add_entry_points(types: &Types, buf: &mut String) {
+    if types.entry_points().is_empty() {
+        return;
+    }
+
+    buf.push('\n');
+
+    for (name, id) in types.entry_points() {
+        let mut params = Vec::new();
+
+        let ret_id = match types.get_type(*id) {
+            RocType::Function { args, ret, .. } => {
+                for (index, arg_id) in args.iter().enumerate() {
+                    let arg_type = type_name(*arg_id, types);
+
+                    // Strings and lists are passed by reference; everything else by value.
+                    match types.get_type(*arg_id) {
+                        RocType::RocStr | RocType::RocList(_) => {
+                            params.push(format!("arg{index}: *{arg_type}"))
+                        }
+                        _ if is_zero_sized(*arg_id, types) => {}
+                        _ => params.push(format!("arg{index}: {arg_type}")),
+                    }
+                }
+
+                *ret
+            }
+            _ => *id,
+        };
+
+        params.insert(0, format!("ret: *{}", type_name(ret_id, types)));
+
+        writeln!(
+            buf,
+            "pub extern fn roc__{name}_1_exposed_generic({}) void;",
+            params.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    if is_zero_sized(id, types) {
+        return "void".to_string();
+    }
+
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(RocNum::U8) => "u8".to_string(),
+        RocType::Num(RocNum::U16) => "u16".to_string(),
+        RocType::Num(RocNum::U32) => "u32".to_string(),
+        RocType::Num(RocNum::U64) => "u64".to_string(),
+        RocType::Num(RocNum::U128) => "u128".to_string(),
+        RocType::Num(RocNum::I8) => "i8".to_string(),
+        RocType::Num(RocNum::I16) => "i16".to_string(),
+        RocType::Num(RocNum::I32) => "i32".to_string(),
+        RocType::Num(RocNum::I64) => "i64".to_string(),
+        RocType::Num(RocNum::I128) => "i128".to_string(),
+        RocType::Num(RocNum::F32) => "f32".to_string(),
+        RocType::Num(RocNum::F64) => "f64".to_string(),
+        RocType::Num(RocNum::F128) => "f128".to_string(),
+        RocType::Num(RocNum::Dec) => "RocDec".to_string(),
+        RocType::RocDict(key_id, val_id) => format!(
+            "RocDict({}, {})",
+            type_name(*key_id, types),
+            type_name(*val_id, types)
+        ),
+        RocType::RocSet(elem_id) => format!("RocSet({})", type_name(*elem_id, types)),
+        RocType::RocList(elem_id) => format!("RocList({})", type_name(*elem_id, types)),
+        RocType::RocBox(elem_id) => format!("RocBox({})", type_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => result_name(*ok_id, *err_id, types),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => name.clone(),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+        RocType::Function { .. } => unreachable!("`emit` rejects functions outside entry points"),
+    }
+}
+
+/// Each Result gets a name of its own, like in C glue, e.g. `RocResult_RocStr_u8`
+fn result_name(ok_id: TypeId, err_id: TypeId, types: &Types) -> String {
+    let identifier = |id| type_name(id, types).replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+    format!("RocResult_{}_{}", identifier(ok_id), identifier(err_id))
+}
+
+/// Indents every line but the first, for declarations nested in a `switch`
+fn indent_body(body: &str) -> String {
+    body.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{INDENT}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn unsigned_int_type(size: u32) -> &'static str {
+    match size {
+        1 => "u8",
+        2 => "u16",
+        4 => "u32",
+        8 => "u64",
+        _ => unreachable!("Invalid discriminant size: {}", size),
+    }
+}
+
+fn arch_to_str(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => "x86_64",
+        Architecture::X86_32 => "i386",
+        Architecture::Aarch64 => "aarch64",
+        Architecture::Aarch32 => "arm",
+        Architecture::Wasm32 => "wasm32",
+    }
+}
+
+// Based on https://ziglang.org/documentation/0.9.1/#Keyword-Reference, plus the primitive
+// types and values that identifiers can't shadow
+const RESERVED_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "noalias",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "linksection",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+    "anyopaque",
+    "bool",
+    "false",
+    "noreturn",
+    "null",
+    "true",
+    "type",
+    "undefined",
+    "void",
+];
+
+/// Escape a Zig reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        format!("@\"{input}\"")
+    } else {
+        input
+    }
+}
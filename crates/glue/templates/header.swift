@@ -0,0 +1,53 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+import Foundation
+
+extension String {
+    /// Copies a Roc string
+    init(_ str: RocStr) {
+        var str = str
+
+        self = withUnsafePointer(to: &str) { pointer in
+            let bytes = UnsafeRawBufferPointer(
+                start: UnsafeRawPointer(roc_str_bytes(pointer)),
+                count: roc_str_len(pointer)
+            )
+
+            return String(decoding: bytes, as: UTF8.self)
+        }
+    }
+}
+
+extension RocStr {
+    /// Copies a Swift string into a new Roc string
+    init(_ string: String) {
+        var string = string
+
+        self = string.withUTF8 { bytes in
+            roc_str_from_bytes(bytes.baseAddress, bytes.count)
+        }
+    }
+}
+
+extension RocList {
+    /// The elements of the list, which must be of type T
+    func elements<T>(of type: T.Type) -> UnsafeBufferPointer<T> {
+        UnsafeBufferPointer(start: elements?.assumingMemoryBound(to: T.self), count: length)
+    }
+
+    /// Copies the elements into a new Roc list
+    init<T>(_ array: [T]) {
+        if array.isEmpty {
+            self.init(elements: nil, length: 0, capacity: 0)
+        } else {
+            let elements = roc_alloc_refcounted(
+                MemoryLayout<T>.stride * array.count,
+                MemoryLayout<T>.alignment
+            )!
+
+            elements.initializeMemory(as: T.self, from: array, count: array.count)
+
+            self.init(elements: elements, length: array.count, capacity: array.count)
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+const std = @import("std");
+const builtin = @import("builtin");
+
+extern fn roc_alloc(size: usize, alignment: u32) callconv(.C) ?*anyopaque;
+
+// Roc stores the reference count right before a heap-allocated value.
+// A count of 1 is represented by the smallest isize.
+fn allocateRefcounted(comptime T: type, count: usize) [*]T {
+    const prefix = std.math.max(@sizeOf(usize), @alignOf(T));
+
+    const allocation = roc_alloc(prefix + count * @sizeOf(T), prefix) orelse @panic("roc_alloc failed");
+    const data = @ptrCast([*]u8, allocation) + prefix;
+    const refcount = @ptrCast([*]isize, @alignCast(@alignOf(isize), data - @sizeOf(isize)));
+
+    refcount[0] = std.math.minInt(isize);
+
+    return @ptrCast([*]T, @alignCast(@alignOf(T), data));
+}
+
+// Fails to compile if T isn't laid out the way Roc lays it out.
+fn assertLayout(comptime T: type, comptime size: usize, comptime alignment: usize) void {
+    if (@sizeOf(T) != size) {
+        @compileError(std.fmt.comptimePrint("{s} should be {d} bytes, like in Roc, but it is {d}", .{ @typeName(T), size, @sizeOf(T) }));
+    }
+
+    if (@alignOf(T) != alignment) {
+        @compileError(std.fmt.comptimePrint("{s} should be aligned to {d} bytes, like in Roc, but it is aligned to {d}", .{ @typeName(T), alignment, @alignOf(T) }));
+    }
+}
+
+pub const RocStr = extern struct {
+    bytes: ?[*]u8,
+    length: usize,
+    capacity: usize,
+
+    const small_string_size = @sizeOf(RocStr);
+
+    pub fn empty() RocStr {
+        return fromSlice("");
+    }
+
+    // Strings shorter than a RocStr are stored inside the RocStr itself, with their length in
+    // the last byte. That is signalled by the highest bit of the capacity being set.
+    pub fn fromSlice(slice: []const u8) RocStr {
+        if (slice.len < small_string_size) {
+            var str = RocStr{ .bytes = null, .length = 0, .capacity = 0 };
+            const str_bytes = @ptrCast([*]u8, &str);
+
+            std.mem.copy(u8, str_bytes[0..slice.len], slice);
+            str_bytes[small_string_size - 1] = @intCast(u8, slice.len) | 0b1000_0000;
+
+            return str;
+        } else {
+            const bytes = allocateRefcounted(u8, slice.len);
+
+            std.mem.copy(u8, bytes[0..slice.len], slice);
+
+            return RocStr{ .bytes = bytes, .length = slice.len, .capacity = slice.len };
+        }
+    }
+
+    pub fn isSmall(self: *const RocStr) bool {
+        return @bitCast(isize, self.capacity) < 0;
+    }
+
+    pub fn len(self: *const RocStr) usize {
+        if (self.isSmall()) {
+            return @ptrCast([*]const u8, self)[small_string_size - 1] ^ 0b1000_0000;
+        } else {
+            return self.length;
+        }
+    }
+
+    pub fn asSlice(self: *const RocStr) []const u8 {
+        if (self.isSmall()) {
+            return @ptrCast([*]const u8, self)[0..self.len()];
+        } else if (self.bytes) |bytes| {
+            return bytes[0..self.length];
+        } else {
+            return "";
+        }
+    }
+};
+
+pub fn RocList(comptime T: type) type {
+    return extern struct {
+        elements: ?[*]T,
+        length: usize,
+        capacity: usize,
+
+        const Self = @This();
+
+        pub fn empty() Self {
+            return Self{ .elements = null, .length = 0, .capacity = 0 };
+        }
+
+        pub fn fromSlice(slice: []const T) Self {
+            if (slice.len == 0) {
+                return empty();
+            }
+
+            const elements = allocateRefcounted(T, slice.len);
+
+            std.mem.copy(T, elements[0..slice.len], slice);
+
+            return Self{ .elements = elements, .length = slice.len, .capacity = slice.len };
+        }
+
+        pub fn len(self: Self) usize {
+            return self.length;
+        }
+
+        pub fn asSlice(self: Self) []const T {
+            if (self.elements) |elements| {
+                return elements[0..self.length];
+            } else {
+                return &[_]T{};
+            }
+        }
+    };
+}
+
+// A list of the key-value pairs. Whichever of the key and the value has the higher alignment
+// comes first, with the key first when they are aligned the same.
+pub fn RocDict(comptime K: type, comptime V: type) type {
+    return extern struct {
+        items: RocList(Entry),
+
+        pub const Entry = if (@alignOf(K) >= @alignOf(V))
+            extern struct { key: K, value: V }
+        else
+            extern struct { value: V, key: K };
+
+        const Self = @This();
+
+        pub fn len(self: Self) usize {
+            return self.items.len();
+        }
+
+        pub fn entries(self: Self) []const Entry {
+            return self.items.asSlice();
+        }
+    };
+}
+
+// A dictionary with empty values, which take up no space
+pub fn RocSet(comptime T: type) type {
+    return extern struct {
+        items: RocList(T),
+
+        const Self = @This();
+
+        pub fn len(self: Self) usize {
+            return self.items.len();
+        }
+
+        pub fn asSlice(self: Self) []const T {
+            return self.items.asSlice();
+        }
+    };
+}
+
+pub fn RocBox(comptime T: type) type {
+    return extern struct {
+        contents: *T,
+
+        const Self = @This();
+
+        pub fn init(value: T) Self {
+            const contents = allocateRefcounted(T, 1);
+
+            contents[0] = value;
+
+            return Self{ .contents = &contents[0] };
+        }
+
+        pub fn get(self: Self) T {
+            return self.contents.*;
+        }
+    };
+}
+
+// A fixed-point decimal number with 18 digits after the decimal point
+pub const RocDec = extern struct {
+    num: i128,
+};
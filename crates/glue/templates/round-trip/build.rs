@@ -0,0 +1,6 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by `roc glue --round-trip`
+
+fn main() {
+    println!("cargo:rustc-link-lib=dylib=app");
+    println!("cargo:rustc-link-search=.");
+}
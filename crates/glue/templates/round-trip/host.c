@@ -0,0 +1,5 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by `roc glue --round-trip`
+
+extern int rust_main();
+
+int main() { return rust_main(); }
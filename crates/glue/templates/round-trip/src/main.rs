@@ -0,0 +1,5 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by `roc glue --round-trip`
+
+fn main() {
+    std::process::exit(host::rust_main() as _);
+}
@@ -0,0 +1,295 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod test_gen_c {
+    use crate::helpers::{
+        check_cross_target, generate_c_bindings, generate_cross_target_c_bindings,
+        try_generate_c_bindings,
+    };
+    use roc_glue::cross_target::Divergence;
+    use roc_glue::types::Unsupported;
+    use roc_target::Architecture;
+
+    #[test]
+    fn basic_record_aliased() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : I128 }
+
+            main : MyRcd
+            main = { a: 1u64, b: 2i128 }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                struct MyRcd {
+                    roc_i128 b;
+                    uint64_t a;
+                };
+
+                _Static_assert(sizeof(struct MyRcd) == 32, "struct MyRcd should be 32 bytes, like in Roc");
+                _Static_assert(_Alignof(struct MyRcd) == 16, "struct MyRcd should be aligned to 16 bytes, like in Roc");
+
+                extern void roc__main_1_exposed_generic(struct MyRcd *ret);
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn list_field() {
+        let module = indoc!(
+            r#"
+            Outer : { y : Str, z : List U8 }
+
+            main : Outer
+            main = { y: "foo", z: [1, 2] }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                struct Outer {
+                    struct RocStr y;
+                    struct RocList z; // of uint8_t
+                };
+
+                _Static_assert(sizeof(struct Outer) == 48, "struct Outer should be 48 bytes, like in Roc");
+                _Static_assert(_Alignof(struct Outer) == 8, "struct Outer should be aligned to 8 bytes, like in Roc");
+
+                extern void roc__main_1_exposed_generic(struct Outer *ret);
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        let module = indoc!(
+            r#"
+            Enumeration : [Blah, Foo, Bar,]
+
+            main : Enumeration
+            main = Foo
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef uint8_t Enumeration;
+
+                enum {
+                    Enumeration_Bar = 0,
+                    Enumeration_Blah = 1,
+                    Enumeration_Foo = 2,
+                };
+
+                extern void roc__main_1_exposed_generic(Enumeration *ret);
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn cons_list_of_strings() {
+        let module = indoc!(
+            r#"
+            StrConsList : [Nil, Cons Str StrConsList]
+
+            main : StrConsList
+            main = Nil
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                struct StrConsList {
+                    struct StrConsList_Cons *pointer;
+                };
+
+                _Static_assert(sizeof(struct StrConsList) == 8, "struct StrConsList should be 8 bytes, like in Roc");
+                _Static_assert(_Alignof(struct StrConsList) == 8, "struct StrConsList should be aligned to 8 bytes, like in Roc");
+
+                struct StrConsList_Cons {
+                    struct RocStr f0;
+                    struct StrConsList f1;
+                };
+
+                typedef uint8_t discriminant_StrConsList;
+
+                enum {
+                    discriminant_StrConsList_Cons = 0,
+                    discriminant_StrConsList_Nil = 1,
+                };
+
+                static inline discriminant_StrConsList StrConsList_discriminant(struct StrConsList self) {
+                    if (self.pointer == NULL) {
+                        return discriminant_StrConsList_Nil;
+                    } else {
+                        return discriminant_StrConsList_Cons;
+                    }
+                }
+
+                static inline struct StrConsList StrConsList_make_Nil(void) {
+                    struct StrConsList self = { NULL };
+
+                    return self;
+                }
+
+                static inline struct StrConsList StrConsList_make_Cons(struct StrConsList_Cons payload) {
+                    struct StrConsList self = { roc_alloc_refcounted(sizeof(struct StrConsList_Cons), _Alignof(struct StrConsList_Cons)) };
+
+                    *self.pointer = payload;
+
+                    return self;
+                }
+
+                extern void roc__main_1_exposed_generic(struct StrConsList *ret);
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn cross_target_record() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U8, b : Nat }
+
+            main : MyRcd
+            main = { a: 1, b: 2 }
+        "#
+        );
+
+        assert_eq!(
+            generate_cross_target_c_bindings(module),
+            indoc!(
+                r#"
+
+                #if defined(__arm__) || defined(_M_ARM) || defined(__wasm32__) || defined(__i386__) || defined(_M_IX86)
+
+                struct MyRcd {
+                    uint32_t b;
+                    uint8_t a;
+                };
+
+                _Static_assert(sizeof(struct MyRcd) == 8, "struct MyRcd should be 8 bytes, like in Roc");
+                _Static_assert(_Alignof(struct MyRcd) == 4, "struct MyRcd should be aligned to 4 bytes, like in Roc");
+
+                extern void roc__main_1_exposed_generic(struct MyRcd *ret);
+
+                #elif defined(__aarch64__) || defined(_M_ARM64) || defined(__x86_64__) || defined(_M_X64)
+
+                struct MyRcd {
+                    uint64_t b;
+                    uint8_t a;
+                };
+
+                _Static_assert(sizeof(struct MyRcd) == 16, "struct MyRcd should be 16 bytes, like in Roc");
+                _Static_assert(_Alignof(struct MyRcd) == 8, "struct MyRcd should be aligned to 8 bytes, like in Roc");
+
+                extern void roc__main_1_exposed_generic(struct MyRcd *ret);
+
+                #else
+                #error "This glue wasn't generated for the architecture being compiled for"
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn too_many_tags_for_32_bit_pointers() {
+        let module = indoc!(
+            r#"
+            Expr : [A Str, B Expr, C Expr Expr, D I64, E Expr]
+
+            main : Expr
+            main = A ""
+        "#
+        );
+
+        assert_eq!(
+            check_cross_target(module),
+            Err(vec![Divergence::TooManyTags {
+                name: "Expr".to_string(),
+                tags: 5,
+                architectures: vec![
+                    Architecture::Aarch32,
+                    Architecture::Wasm32,
+                    Architecture::X86_32
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn closures_are_unsupported() {
+        let module = indoc!(
+            r#"
+            Thunk : {} -> Str
+
+            main : U64 -> Thunk as Fx
+            main = \n -> \{} -> Num.toStr n
+        "#
+        );
+
+        assert_eq!(
+            try_generate_c_bindings(module),
+            Err(vec![Unsupported {
+                name: "main".to_string(),
+                reason: "it contains a function, and C glue can only declare the entry points' functions",
+            }])
+        );
+    }
+
+    #[test]
+    fn too_many_tags_for_pointer_tagging_are_unsupported() {
+        let module = indoc!(
+            r#"
+            Expr : [A Str, B Expr, C Expr Expr, D I64, E Expr, F Expr, G Expr, H Expr, I Expr]
+
+            main : Expr
+            main = A ""
+        "#
+        );
+        let reason = "it contains a recursive tag union with too many tags to store the tag in a pointer, which C glue doesn't support yet";
+
+        assert_eq!(
+            try_generate_c_bindings(module),
+            Err(vec![
+                Unsupported {
+                    name: "Expr".to_string(),
+                    reason,
+                },
+                Unsupported {
+                    name: "main".to_string(),
+                    reason,
+                },
+            ])
+        );
+    }
+}
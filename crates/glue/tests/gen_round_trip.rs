@@ -0,0 +1,125 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod test_gen_round_trip {
+    use crate::helpers::generate_round_trip;
+
+    #[test]
+    fn record() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : Str, c : List Str }
+
+            main : MyRcd
+            main = { a: 1u64, b: "", c: [] }
+        "#
+        );
+
+        let harness = generate_round_trip(module);
+
+        assert_eq!(
+            harness.platform,
+            indoc!(
+                r#"
+                platform "round-trip"
+                    requires {} { main : _ }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [sampleMyRcdForHost, checkMyRcdForHost]
+
+                sampleMyRcdForHost : List { a : U64, b : Str, c : List Str }
+                sampleMyRcdForHost = main.myRcd
+
+                checkMyRcdForHost : List { a : U64, b : Str, c : List Str } -> Bool
+                checkMyRcdForHost = \values -> values == main.myRcd
+            "#
+            )
+        );
+
+        assert_eq!(
+            harness.app,
+            indoc!(
+                r#"
+                app "round-trip"
+                    packages { pf: "platform.roc" }
+                    imports []
+                    provides [main] to pf
+
+                main = {
+                    myRcd: [
+                        { a: 1, b: "s2", c: ["a string too long to be stored inline 3", "s4"] },
+                    ],
+                }
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn cons_list_of_strings() {
+        let module = indoc!(
+            r#"
+            StrConsList : [Nil, Cons Str StrConsList]
+
+            main : StrConsList
+            main = Nil
+        "#
+        );
+
+        let harness = generate_round_trip(module);
+
+        assert_eq!(
+            harness.app,
+            indoc!(
+                r#"
+                app "round-trip"
+                    packages { pf: "platform.roc" }
+                    imports []
+                    provides [main] to pf
+
+                main = {
+                    strConsList: [
+                        Nil,
+                        Cons "s1" (Cons "a string too long to be stored inline 3" Nil),
+                    ],
+                }
+            "#
+            )
+        );
+
+        // The host builds the same values through the glue
+        assert!(harness.host.contains(
+            r#"StrConsList::Cons(roc_std::RocStr::from("s1"), StrConsList::Cons(roc_std::RocStr::from("a string too long to be stored inline 3"), StrConsList::Nil)),"#
+        ));
+    }
+
+    #[test]
+    fn floats_are_skipped() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : F64 }
+
+            main : MyRcd
+            main = { a: 1.5 }
+        "#
+        );
+
+        let harness = generate_round_trip(module);
+
+        assert_eq!(harness.round_tripped, Vec::<String>::new());
+        assert_eq!(
+            harness.skipped,
+            vec![(
+                "MyRcd".to_string(),
+                "it contains an F32 or F64, which Roc can't compare with =="
+            )]
+        );
+    }
+}
@@ -233,4 +233,68 @@ mod test_gen_rs {
             )
         );
     }
+
+    #[test]
+    fn host_exposed_closure() {
+        let module = indoc!(
+            r#"
+            Thunk : {} -> Str
+
+            main : U64 -> Thunk as Fx
+            main = \n -> \{} -> Num.toStr n
+        "#
+        );
+
+        assert_eq!(
+            generate_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86_64"
+                ))]
+                #[repr(C, align(8))]
+                pub struct Fx {
+                    closure_data: [u8; 8],
+                }
+
+                #[cfg(target_arch = "x86")]
+                #[repr(C, align(4))]
+                pub struct Fx {
+                    closure_data: [u8; 8],
+                }
+
+                impl Fx {
+                    #[cfg(any(
+                        target_arch = "arm",
+                        target_arch = "aarch64",
+                        target_arch = "wasm32",
+                        target_arch = "x86",
+                        target_arch = "x86_64"
+                    ))]
+                    /// Calls the closure through `roc__main_1__Fx_caller`. This consumes the closure,
+                    /// because Roc takes ownership of what it captured.
+                    pub fn call(self, arg0: ()) -> roc_std::RocStr {
+                        extern "C" {
+                            fn roc__main_1__Fx_caller(arg0: *const (), closure_data: *const u8, output: *mut roc_std::RocStr);
+                        }
+
+                        let arg0 = core::mem::ManuallyDrop::new(arg0);
+                        let mut output = core::mem::MaybeUninit::uninit();
+
+                        unsafe {
+                            roc__main_1__Fx_caller(&*arg0, self.closure_data.as_ptr(), output.as_mut_ptr());
+
+                            output.assume_init()
+                        }
+                    }
+                }
+            "#
+            )
+        );
+    }
 }
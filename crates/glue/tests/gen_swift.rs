@@ -0,0 +1,157 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod test_gen_swift {
+    use crate::helpers::{generate_swift_bindings, try_generate_swift_bindings};
+    use roc_glue::types::Unsupported;
+
+    #[test]
+    fn record_with_list_and_enumeration() {
+        let module = indoc!(
+            r#"
+            Color : [Red, Green, Blue]
+            Point : { x : F64, color : Color, tags : List Str }
+
+            main : Point
+            main = { x: 1, color: Red, tags: [] }
+        "#
+        );
+
+        assert_eq!(
+            generate_swift_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                // The C types used here are declared in Glue.h, which the host's bridging
+                // header needs to include.
+
+                enum Roc {
+                    struct Point {
+                        var tags: [String]
+                        var x: Double
+                        var color: Roc.Color
+                    }
+
+                    enum Color: UInt8 {
+                        case blue = 0
+                        case green = 1
+                        case red = 2
+                    }
+                }
+
+                extension Point {
+                    init(_ value: Roc.Point) {
+                        self.init(tags: RocList(value.tags.map { RocStr($0) }), x: value.x, color: value.color.rawValue)
+                    }
+
+                    var swiftValue: Roc.Point {
+                        Roc.Point(tags: self.tags.elements(of: RocStr.self).map { String($0) }, x: self.x, color: Roc.Color(rawValue: self.color)!)
+                    }
+                }
+
+                /// Calls `main` in the Roc app
+                func rocMain() -> Roc.Point {
+                    var ret = Point()
+
+                    roc__main_1_exposed_generic(&ret)
+
+                    return ret.swiftValue
+                }
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn cons_list_of_strings() {
+        let module = indoc!(
+            r#"
+            StrConsList : [Nil, Cons Str StrConsList]
+
+            main : Str -> StrConsList
+            main = \_ -> Nil
+        "#
+        );
+
+        assert_eq!(
+            generate_swift_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                // The C types used here are declared in Glue.h, which the host's bridging
+                // header needs to include.
+
+                enum Roc {
+                    indirect enum StrConsList {
+                        case cons(String, Roc.StrConsList)
+                        case `nil`
+                    }
+                }
+
+                extension StrConsList {
+                    init(_ value: Roc.StrConsList) {
+                        switch value {
+                        case .cons(let f0, let f1):
+                            self = StrConsList_make_Cons(StrConsList_Cons(f0: RocStr(f0), f1: StrConsList(f1)))
+                        case .`nil`:
+                            self = StrConsList_make_Nil()
+                        }
+                    }
+
+                    var swiftValue: Roc.StrConsList {
+                        if pointer == nil {
+                            return .`nil`
+                        } else {
+                            return .cons(String(pointer.pointee.f0), pointer.pointee.f1.swiftValue)
+                        }
+                    }
+                }
+
+                /// Calls `main` in the Roc app
+                func rocMain(_ arg0: String) -> Roc.StrConsList {
+                    var ret = StrConsList()
+                    var arg0 = RocStr(arg0)
+
+                    roc__main_1_exposed_generic(&ret, &arg0)
+
+                    return ret.swiftValue
+                }
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn dict_is_unsupported() {
+        let module = indoc!(
+            r#"
+            Config : { name : Str, settings : Dict Str Str }
+
+            main : Config
+            main = { name: "", settings: Dict.empty }
+        "#
+        );
+
+        assert_eq!(
+            try_generate_swift_bindings(module),
+            Err(vec![
+                Unsupported {
+                    name: "Config".to_string(),
+                    reason: "it contains a Dict or Set, which Swift glue doesn't support yet",
+                },
+                Unsupported {
+                    name: "main".to_string(),
+                    reason: "it contains a Dict or Set, which Swift glue doesn't support yet",
+                },
+            ])
+        );
+    }
+}
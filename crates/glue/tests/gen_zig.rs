@@ -0,0 +1,215 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_zig {
+    use crate::helpers::{generate_zig_bindings, try_generate_zig_bindings};
+    use roc_glue::types::Unsupported;
+
+    #[test]
+    fn basic_record_aliased() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : I128 }
+
+            main : MyRcd
+            main = { a: 1u64, b: 2i128 }
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const MyRcd = extern struct {
+                    b: i128,
+                    a: u64,
+                };
+
+                comptime {
+                    switch (builtin.cpu.arch) {
+                        .arm, .aarch64, .wasm32, .i386, .x86_64 => {
+                            assertLayout(MyRcd, 32, 16);
+                        },
+                        else => {},
+                    }
+                }
+
+                pub extern fn roc__main_1_exposed_generic(ret: *MyRcd) void;
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn recursive_tag_union_per_architecture() {
+        let module = indoc!(
+            r#"
+            Expr : [String Str, Concat Expr Expr]
+
+            main : Expr
+            main = String "x"
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const discriminant_Expr = enum(u8) {
+                    Concat = 0,
+                    String = 1,
+                };
+
+                pub const union_Expr = extern union {
+                    Concat: Expr_Concat,
+                    String: Expr_String,
+                };
+
+                pub const Expr = switch (builtin.cpu.arch) {
+                    .arm, .wasm32, .i386 => extern struct {
+                        pointer: ?*anyopaque,
+
+                        const Self = @This();
+                        const tag_mask = 0b11;
+
+                        pub fn unionPointer(self: Self) *union_Expr {
+                            return @intToPtr(*union_Expr, @ptrToInt(self.pointer) & ~@as(usize, tag_mask));
+                        }
+
+                        pub fn discriminant(self: Self) discriminant_Expr {
+                            return @intToEnum(discriminant_Expr, @intCast(u8, @ptrToInt(self.pointer) & tag_mask));
+                        }
+
+                        pub fn initConcat(payload: Expr_Concat) Self {
+                            const pointer = allocateRefcounted(union_Expr, 1);
+
+                            pointer[0] = union_Expr{ .Concat = payload };
+
+                            return Self{ .pointer = @intToPtr(?*anyopaque, @ptrToInt(pointer) | @enumToInt(discriminant_Expr.Concat)) };
+                        }
+
+                        pub fn initString(payload: Expr_String) Self {
+                            const pointer = allocateRefcounted(union_Expr, 1);
+
+                            pointer[0] = union_Expr{ .String = payload };
+
+                            return Self{ .pointer = @intToPtr(?*anyopaque, @ptrToInt(pointer) | @enumToInt(discriminant_Expr.String)) };
+                        }
+                    },
+                    .aarch64, .x86_64 => extern struct {
+                        pointer: ?*anyopaque,
+
+                        const Self = @This();
+                        const tag_mask = 0b111;
+
+                        pub fn unionPointer(self: Self) *union_Expr {
+                            return @intToPtr(*union_Expr, @ptrToInt(self.pointer) & ~@as(usize, tag_mask));
+                        }
+
+                        pub fn discriminant(self: Self) discriminant_Expr {
+                            return @intToEnum(discriminant_Expr, @intCast(u8, @ptrToInt(self.pointer) & tag_mask));
+                        }
+
+                        pub fn initConcat(payload: Expr_Concat) Self {
+                            const pointer = allocateRefcounted(union_Expr, 1);
+
+                            pointer[0] = union_Expr{ .Concat = payload };
+
+                            return Self{ .pointer = @intToPtr(?*anyopaque, @ptrToInt(pointer) | @enumToInt(discriminant_Expr.Concat)) };
+                        }
+
+                        pub fn initString(payload: Expr_String) Self {
+                            const pointer = allocateRefcounted(union_Expr, 1);
+
+                            pointer[0] = union_Expr{ .String = payload };
+
+                            return Self{ .pointer = @intToPtr(?*anyopaque, @ptrToInt(pointer) | @enumToInt(discriminant_Expr.String)) };
+                        }
+                    },
+                    else => @compileError("Expr is not available on this architecture"),
+                };
+
+                pub const Expr_String = extern struct {
+                    f0: RocStr,
+                };
+
+                pub const Expr_Concat = extern struct {
+                    f0: Expr,
+                    f1: Expr,
+                };
+
+                comptime {
+                    switch (builtin.cpu.arch) {
+                        .arm, .wasm32, .i386 => {
+                            assertLayout(Expr, 4, 4);
+                        },
+                        .aarch64, .x86_64 => {
+                            assertLayout(Expr, 8, 8);
+                        },
+                        else => {},
+                    }
+                }
+
+                pub extern fn roc__main_1_exposed_generic(ret: *Expr) void;
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn closures_are_unsupported() {
+        let module = indoc!(
+            r#"
+            Thunk : {} -> Str
+
+            main : U64 -> Thunk as Fx
+            main = \n -> \{} -> Num.toStr n
+        "#
+        );
+
+        assert_eq!(
+            try_generate_zig_bindings(module),
+            Err(vec![Unsupported {
+                name: "main".to_string(),
+                reason: "it contains a function, and Zig glue can only declare the entry points' functions",
+            }])
+        );
+    }
+
+    #[test]
+    fn too_many_tags_for_32_bit_pointers_are_unsupported() {
+        let module = indoc!(
+            r#"
+            Expr : [A Str, B Expr, C Expr Expr, D I64, E Expr]
+
+            main : Expr
+            main = A ""
+        "#
+        );
+        let reason = "it contains a recursive tag union with too many tags to store the tag in a pointer, which Zig glue doesn't support yet";
+
+        assert_eq!(
+            try_generate_zig_bindings(module),
+            Err(vec![
+                Unsupported {
+                    name: "Expr".to_string(),
+                    reason,
+                },
+                Unsupported {
+                    name: "main".to_string(),
+                    reason,
+                },
+            ])
+        );
+    }
+}
@@ -1,6 +1,10 @@
+use roc_glue::cross_target::{self, Divergence};
 use roc_glue::load::{load_types, IgnoreErrors};
-use roc_glue::rust_glue;
+use roc_glue::round_trip::{self, Harness};
+use roc_glue::types::{Types, Unsupported};
+use roc_glue::{c_glue, rust_glue, swift_glue, zig_glue};
 use roc_load::Threading;
+use roc_target::TargetInfo;
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -8,6 +12,79 @@ use std::path::PathBuf;
 
 #[allow(dead_code)]
 pub fn generate_bindings(decl_src: &str) -> String {
+    rust_glue::emit(&load_decls(decl_src))
+}
+
+#[allow(dead_code)]
+pub fn generate_zig_bindings(decl_src: &str) -> String {
+    try_generate_zig_bindings(decl_src).expect("the types are supported in Zig glue")
+}
+
+/// Like [generate_zig_bindings], but for types Zig glue might not support
+#[allow(dead_code)]
+pub fn try_generate_zig_bindings(decl_src: &str) -> Result<String, Vec<Unsupported>> {
+    zig_glue::emit(&load_decls(decl_src))
+}
+
+/// The C header body for the architecture the tests are running on
+#[allow(dead_code)]
+pub fn generate_c_bindings(decl_src: &str) -> String {
+    try_generate_c_bindings(decl_src).expect("the types are supported in C glue")
+}
+
+/// Like [generate_c_bindings], but for types C glue might not support
+#[allow(dead_code)]
+pub fn try_generate_c_bindings(decl_src: &str) -> Result<String, Vec<Unsupported>> {
+    let types_and_targets = load_decls(decl_src);
+    let architecture = TargetInfo::from(&target_lexicon::Triple::host()).architecture;
+    let host: Vec<_> = types_and_targets
+        .into_iter()
+        .filter(|(_, target_info)| target_info.architecture == architecture)
+        .collect();
+
+    c_glue::emit(&host)
+}
+
+/// The C header body for every architecture
+#[allow(dead_code)]
+pub fn generate_cross_target_c_bindings(decl_src: &str) -> String {
+    c_glue::emit(&load_decls(decl_src)).expect("the types are supported in C glue")
+}
+
+#[allow(dead_code)]
+pub fn check_cross_target(decl_src: &str) -> Result<(), Vec<Divergence>> {
+    cross_target::check(&load_decls(decl_src))
+}
+
+/// The Swift body for the architecture the tests are running on, which uses `Glue.h`
+#[allow(dead_code)]
+pub fn generate_swift_bindings(decl_src: &str) -> String {
+    try_generate_swift_bindings(decl_src).expect("the types are supported in Swift glue")
+}
+
+/// Like [generate_swift_bindings], but for types Swift glue might not support
+#[allow(dead_code)]
+pub fn try_generate_swift_bindings(decl_src: &str) -> Result<String, Vec<Unsupported>> {
+    swift_glue::emit(host_types(&load_decls(decl_src)), "Glue.h")
+}
+
+/// The round trip test for the architecture the tests are running on
+#[allow(dead_code)]
+pub fn generate_round_trip(decl_src: &str) -> Harness {
+    round_trip::emit(host_types(&load_decls(decl_src)))
+}
+
+fn host_types(pairs: &[(Types, TargetInfo)]) -> &Types {
+    let architecture = TargetInfo::from(&target_lexicon::Triple::host()).architecture;
+
+    pairs
+        .iter()
+        .find(|(_, target_info)| target_info.architecture == architecture)
+        .map(|(types, _)| types)
+        .expect("no types for the host architecture")
+}
+
+fn load_decls(decl_src: &str) -> Vec<(Types, TargetInfo)> {
     use tempfile::tempdir;
 
     let mut src = indoc!(
@@ -25,7 +102,7 @@ pub fn generate_bindings(decl_src: &str) -> String {
 
     src.push_str(decl_src);
 
-    let pairs = {
+    {
         let dir = tempdir().expect("Unable to create tempdir");
         let filename = PathBuf::from("platform.roc");
         let file_path = dir.path().join(filename);
@@ -43,9 +120,7 @@ pub fn generate_bindings(decl_src: &str) -> String {
         dir.close().expect("Unable to close tempdir");
 
         result.expect("had problems loading")
-    };
-
-    rust_glue::emit(&pairs)
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,40 @@
+app "entry-points"
+    packages { pf: "../../platform/RocType.roc" }
+    imports []
+    provides [makeGlue] to pf
+
+# Lists the entry points of the platform, and their types, for every target architecture.
+makeGlue = \typesByArch ->
+    content =
+        typesByArch
+        |> List.map \types ->
+            entryPoints =
+                types.entryPoints
+                |> List.map \{ name, id } ->
+                    type = typeName types id
+
+                    "\(name) : \(type)"
+                |> Str.joinWith ", "
+
+            arch = archName types.target.architecture
+
+            "\(arch): \(entryPoints)"
+        |> Str.joinWith "\n"
+
+    Ok [{ name: "entry-points.txt", content }]
+
+typeName = \types, id ->
+    when List.get types.types id is
+        Ok (Struct { name }) -> name
+        Ok RocStr -> "Str"
+        Ok (TagUnion (Enumeration { name })) -> name
+        Ok _ -> "(unnamed)"
+        Err OutOfBounds -> "(missing)"
+
+archName = \arch ->
+    when arch is
+        Aarch32 -> "aarch32"
+        Aarch64 -> "aarch64"
+        Wasm32 -> "wasm32"
+        X86x32 -> "x86_32"
+        X86x64 -> "x86_64"
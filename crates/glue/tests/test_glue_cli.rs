@@ -129,6 +129,95 @@ mod glue_cli_run {
         "#),
     }
 
+    #[test]
+    fn glue_spec() {
+        let platform_dir = fixtures_dir("basic-record");
+        let spec_path = platform_dir
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("specs")
+            .join("entry-points.roc");
+        let out_dir = tempfile::tempdir().unwrap();
+
+        let glue_out = run_glue([
+            "glue".as_ref(),
+            "--spec".as_ref(),
+            spec_path.as_os_str(),
+            platform_dir.join("platform.roc").as_os_str(),
+            out_dir.path().as_os_str(),
+        ]);
+
+        assert!(glue_out.status.success(), "bad status {:?}", glue_out);
+
+        let generated = fs::read_to_string(out_dir.path().join("entry-points.txt")).unwrap();
+
+        assert_eq!(
+            generated,
+            indoc!(
+                r#"
+                aarch32: mainForHost : MyRcd
+                aarch64: mainForHost : MyRcd
+                wasm32: mainForHost : MyRcd
+                x86_32: mainForHost : MyRcd
+                x86_64: mainForHost : MyRcd"#
+            )
+        );
+    }
+
+    /// Generates a round trip test for each of these fixtures' platforms with
+    /// `roc glue --round-trip` and runs it, checking that it round-tripped these types.
+    macro_rules! round_trips {
+        ($($test_name:ident:$fixture_dir:expr => [$($type_name:expr),+],)+) => {
+            $(
+                #[test]
+                fn $test_name() {
+                    let platform_dir = fixtures_dir($fixture_dir);
+                    let out_dir = tempfile::tempdir().unwrap();
+
+                    let glue_out = run_glue([
+                        "glue".as_ref(),
+                        "--round-trip".as_ref(),
+                        platform_dir.join("platform.roc").as_os_str(),
+                        out_dir.path().as_os_str(),
+                    ]);
+
+                    assert!(glue_out.status.success(), "bad status {:?}", glue_out);
+
+                    let out = run_app(&out_dir.path().join("app.roc"), std::iter::empty());
+                    let expected: String = [$($type_name),+]
+                        .iter()
+                        .map(|type_name| format!("{} round-tripped\n", type_name))
+                        .collect();
+
+                    assert!(out.status.success());
+                    assert!(
+                        out.stdout.ends_with(&expected),
+                        "Unexpected stdout ending\n\nexpected:\n\n{}\n\nbut stdout was:\n\n{}",
+                        expected,
+                        out.stdout
+                    );
+                }
+            )*
+        }
+    }
+
+    round_trips! {
+        round_trip_basic_record:"basic-record" => ["MyRcd"],
+        round_trip_enumeration:"enumeration" => ["MyEnum"],
+        round_trip_union_with_padding:"union-with-padding" => ["NonRecursive"],
+        round_trip_union_without_padding:"union-without-padding" => ["NonRecursive"],
+        round_trip_single_tag_union:"single-tag-union" => ["SingleTagUnion"],
+        round_trip_basic_recursive_union:"basic-recursive-union" => ["Expr"],
+        round_trip_nullable_wrapped:"nullable-wrapped" => ["StrFingerTree"],
+        round_trip_nullable_unwrapped:"nullable-unwrapped" => ["StrConsList"],
+        round_trip_nonnullable_unwrapped:"nonnullable-unwrapped" => ["StrRoseTree"],
+        round_trip_advanced_recursive_union:"advanced-recursive-union" => ["R3", "R4", "Tool", "R2", "Command", "R1", "Job", "Rbt"],
+        round_trip_list_recursive_union:"list-recursive-union" => ["R3", "Tool", "R2", "Command", "R1", "Job", "Rbt"],
+        round_trip_multiple_modules:"multiple-modules" => ["DepStr1", "DepStr2", "Combined"],
+    }
+
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
         use roc_collections::VecSet;
 
@@ -0,0 +1,325 @@
+//! Type-checking a document and answering questions about it.
+
+use bumpalo::Bump;
+use roc_can::traverse::{find_closest_type_at, find_declaration_region, find_symbol_at};
+use roc_fmt::def::fmt_defs;
+use roc_fmt::module::fmt_module;
+use roc_fmt::{Ast, Buf};
+use roc_load::{LoadedModule, LoadingProblem};
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
+use roc_reporting::report::{
+    can_problem, type_problem, RenderTarget, Report, RocDocAllocator, Severity,
+};
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use std::path::{Path, PathBuf};
+
+/// How the client counts the columns of positions. The compiler's regions count bytes, so every
+/// column is converted on its way in and out unless the client agreed to count bytes, too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionEncoding {
+    Utf8,
+    /// The protocol's default, which every client supports
+    Utf16,
+}
+
+impl PositionEncoding {
+    pub fn name(self) -> &'static str {
+        match self {
+            PositionEncoding::Utf8 => "utf-8",
+            PositionEncoding::Utf16 => "utf-16",
+        }
+    }
+
+    /// The byte offset of `column` into `line`, moved back to the start of the character it
+    /// is in, and clamped to the end of the line.
+    fn byte_column(self, line: &str, column: u32) -> usize {
+        let column = column as usize;
+
+        match self {
+            PositionEncoding::Utf8 => {
+                let mut byte_column = column.min(line.len());
+
+                while !line.is_char_boundary(byte_column) {
+                    byte_column -= 1;
+                }
+
+                byte_column
+            }
+            PositionEncoding::Utf16 => {
+                let mut units = 0;
+
+                for (offset, ch) in line.char_indices() {
+                    units += ch.len_utf16();
+
+                    if units > column {
+                        return offset;
+                    }
+                }
+
+                line.len()
+            }
+        }
+    }
+
+    /// The column of the character at `byte_column` in `line`.
+    fn column(self, line: &str, byte_column: u32) -> u32 {
+        match self {
+            PositionEncoding::Utf8 => byte_column,
+            PositionEncoding::Utf16 => line
+                .char_indices()
+                .take_while(|(offset, _)| *offset < byte_column as usize)
+                .map(|(_, ch)| ch.len_utf16() as u32)
+                .sum(),
+        }
+    }
+
+    /// Converts a region of `text`, whose columns count bytes, to the columns of the client.
+    fn convert_region(self, text: &str, region: LineColumnRegion) -> LineColumnRegion {
+        let convert = |line_column: LineColumn| {
+            let column = match text.split('\n').nth(line_column.line as usize) {
+                Some(line) => self.column(line, line_column.column),
+                None => line_column.column,
+            };
+
+            LineColumn {
+                line: line_column.line,
+                column,
+            }
+        };
+
+        LineColumnRegion::new(convert(region.start), convert(region.end))
+    }
+}
+
+pub struct Diagnostic {
+    pub region: LineColumnRegion,
+    pub severity: Severity,
+    pub title: String,
+    pub message: String,
+}
+
+/// An open document, along with the results of type-checking it as the root module.
+///
+/// Positions and regions going in and out of it use the columns of the client's encoding.
+pub struct AnalyzedDocument {
+    text: String,
+    lines: LineInfo,
+    encoding: PositionEncoding,
+    module: Option<LoadedModule>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AnalyzedDocument {
+    pub fn new(path: PathBuf, text: String, encoding: PositionEncoding) -> Self {
+        let arena = Bump::new();
+        let src_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let loaded = roc_load::load_and_typecheck_str(
+            &arena,
+            path,
+            &text,
+            src_dir,
+            Default::default(),
+            TargetInfo::default_x86_64(),
+            RenderTarget::Generic,
+        );
+
+        let (module, mut diagnostics) = match loaded {
+            Ok(module) => {
+                let diagnostics = module_diagnostics(&module);
+
+                (Some(module), diagnostics)
+            }
+            Err(problem) => (None, vec![loading_problem_diagnostic(problem)]),
+        };
+
+        for diagnostic in diagnostics.iter_mut() {
+            diagnostic.region = encoding.convert_region(&text, diagnostic.region);
+        }
+
+        Self {
+            lines: LineInfo::new(&text),
+            text,
+            encoding,
+            module,
+            diagnostics,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The solved type of the innermost expression or pattern at `position`.
+    pub fn hover(&mut self, position: LineColumn) -> Option<(LineColumnRegion, String)> {
+        let position = self.position(position)?;
+        let module = self.module.as_mut()?;
+        let decls = module.declarations_by_id.get(&module.module_id)?;
+        let (region, var) = find_closest_type_at(position, decls)?;
+
+        let type_str = name_and_print_var(
+            var,
+            module.solved.inner_mut(),
+            module.module_id,
+            &module.interns,
+            DebugPrint::NOTHING,
+        );
+
+        let region = self.lines.convert_region(region);
+
+        Some((self.encoding.convert_region(&self.text, region), type_str))
+    }
+
+    /// Where the symbol at `position` is defined, which may be in another module.
+    pub fn definition(&self, position: LineColumn) -> Option<(PathBuf, LineColumnRegion)> {
+        let position = self.position(position)?;
+        let module = self.module.as_ref()?;
+        let decls = module.declarations_by_id.get(&module.module_id)?;
+        let symbol = find_symbol_at(position, decls)?.value;
+
+        let home = symbol.module_id();
+        let region = find_declaration_region(symbol, module.declarations_by_id.get(&home)?)?;
+        let (path, src) = module.sources.get(&home)?;
+
+        let region = LineInfo::new(src).convert_region(region);
+
+        Some((path.clone(), self.encoding.convert_region(src, region)))
+    }
+
+    /// The formatted text of this document, or `None` if it does not parse.
+    pub fn format(&self) -> Option<String> {
+        let arena = Bump::new();
+
+        let (module, state) =
+            module::parse_header(&arena, State::new(self.text.as_bytes())).ok()?;
+        let (_, defs, _) = module_defs().parse(&arena, state, 0).ok()?;
+        let ast = arena.alloc(Ast { module, defs });
+
+        let mut buf = Buf::new_in(&arena);
+
+        fmt_module(&mut buf, &ast.module);
+        fmt_defs(&mut buf, &ast.defs, 0);
+        buf.fmt_end_of_file();
+
+        Some(buf.as_str().to_string())
+    }
+
+    /// The position just past the last character of the document.
+    pub fn end(&self) -> LineColumn {
+        let line = self.text.matches('\n').count();
+        let last_line = self.text.rsplit('\n').next().unwrap_or_default();
+
+        LineColumn {
+            line: line as u32,
+            column: self.encoding.column(last_line, last_line.len() as u32),
+        }
+    }
+
+    fn position(&self, line_column: LineColumn) -> Option<Position> {
+        let line = self.text.split('\n').nth(line_column.line as usize)?;
+        let column = self.encoding.byte_column(line, line_column.column);
+
+        Some(self.lines.convert_line_column(LineColumn {
+            line: line_column.line,
+            column: column as u32,
+        }))
+    }
+}
+
+fn module_diagnostics(module: &LoadedModule) -> Vec<Diagnostic> {
+    let home = module.module_id;
+    let (path, src) = match module.sources.get(&home) {
+        Some(source) => source,
+        None => return Vec::new(),
+    };
+
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let lines = LineInfo::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, home, &module.interns);
+    let mut diagnostics = Vec::new();
+
+    for problem in module.can_problems.get(&home).into_iter().flatten() {
+        let report = can_problem(&alloc, &lines, path.clone(), problem.clone());
+
+        diagnostics.push(diagnostic_from_report(
+            report,
+            problem.region(),
+            &lines,
+            &alloc,
+        ));
+    }
+
+    for problem in module.type_problems.get(&home).into_iter().flatten() {
+        let report = match type_problem(&alloc, &lines, path.clone(), problem.clone()) {
+            Some(report) => report,
+            None => continue,
+        };
+
+        diagnostics.push(diagnostic_from_report(
+            report,
+            problem.region(),
+            &lines,
+            &alloc,
+        ));
+    }
+
+    diagnostics
+}
+
+fn diagnostic_from_report<'b>(
+    report: Report<'b>,
+    region: Option<Region>,
+    lines: &LineInfo,
+    alloc: &'b RocDocAllocator<'b>,
+) -> Diagnostic {
+    let Report {
+        title,
+        filename,
+        doc,
+        severity,
+        fixes,
+    } = report;
+
+    // Leave the title out of the message; editors show it separately.
+    let untitled = Report {
+        title: String::new(),
+        filename,
+        doc,
+        severity,
+        fixes,
+    };
+
+    let mut message = String::new();
+    untitled.render_ci(&mut message, alloc);
+
+    Diagnostic {
+        region: region
+            .map(|region| lines.convert_region(region))
+            .unwrap_or_else(LineColumnRegion::zero),
+        severity,
+        title,
+        message: message.trim().to_string(),
+    }
+}
+
+fn loading_problem_diagnostic(problem: LoadingProblem) -> Diagnostic {
+    let message = match problem {
+        LoadingProblem::FormattedReport(report) => report,
+        other => format!("{:?}", other),
+    };
+
+    Diagnostic {
+        region: LineColumnRegion::zero(),
+        severity: Severity::RuntimeError,
+        title: String::new(),
+        message: message.trim().to_string(),
+    }
+}
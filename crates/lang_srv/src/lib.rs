@@ -0,0 +1,569 @@
+//! A language server for Roc, speaking the Language Server Protocol over stdio.
+//!
+//! Every open document is type-checked as the root module with [roc_load], and the
+//! resulting reports from [roc_reporting] are published as diagnostics. On top of that
+//! the server answers hover (the solved type under the cursor), go-to-definition and
+//! whole-document formatting requests.
+
+use analysis::{AnalyzedDocument, PositionEncoding};
+use roc_collections::MutMap;
+use roc_region::all::{LineColumn, LineColumnRegion};
+use roc_reporting::report::Severity;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+mod analysis;
+mod rpc;
+
+/// Serve requests on stdin and stdout until the client sends `exit` or closes stdin.
+///
+/// Returns the exit code the process should use, as specified by the protocol:
+/// 0 if the client asked us to shut down first, and 1 otherwise.
+pub fn run_stdio() -> io::Result<i32> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    Server::new(stdout.lock()).run(&mut stdin.lock())
+}
+
+struct Server<W> {
+    output: W,
+    documents: MutMap<String, AnalyzedDocument>,
+    position_encoding: PositionEncoding,
+    shutdown_requested: bool,
+}
+
+impl<W: Write> Server<W> {
+    fn new(output: W) -> Self {
+        Self {
+            output,
+            documents: MutMap::default(),
+            position_encoding: PositionEncoding::Utf16,
+            shutdown_requested: false,
+        }
+    }
+
+    fn run<R: BufRead>(&mut self, input: &mut R) -> io::Result<i32> {
+        while let Some(message) = rpc::read_message(input)? {
+            let message = match message {
+                Ok(message) => message,
+                Err(error) => {
+                    // We can't tell which request this was, so the response has no id.
+                    let response = rpc::error_response(
+                        Value::Null,
+                        rpc::PARSE_ERROR,
+                        &format!("invalid JSON: {}", error),
+                    );
+
+                    rpc::write_message(&mut self.output, &response)?;
+
+                    continue;
+                }
+            };
+
+            let method = message.get("method").and_then(Value::as_str);
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+            match (method, message.get("id")) {
+                (Some("exit"), _) => break,
+                (Some(method), Some(id)) => {
+                    let response = match self.handle_request(method, &params) {
+                        Ok(result) => rpc::response(id.clone(), result),
+                        Err((code, error)) => rpc::error_response(id.clone(), code, &error),
+                    };
+
+                    rpc::write_message(&mut self.output, &response)?;
+                }
+                (Some(method), None) => self.handle_notification(method, &params)?,
+                (None, _) => {
+                    // A response to a request; we never send any, so there is nothing to do.
+                }
+            }
+        }
+
+        Ok(if self.shutdown_requested { 0 } else { 1 })
+    }
+
+    fn handle_request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        if self.shutdown_requested {
+            return Err((
+                rpc::INVALID_REQUEST,
+                "the server is shutting down".to_string(),
+            ));
+        }
+
+        match method {
+            "initialize" => {
+                // Regions count bytes, so positions need no converting if the client counts bytes.
+                let offers_utf8 = params["capabilities"]["general"]["positionEncodings"]
+                    .as_array()
+                    .map_or(false, |encodings| {
+                        encodings.iter().any(|enc| enc == "utf-8")
+                    });
+
+                if offers_utf8 {
+                    self.position_encoding = PositionEncoding::Utf8;
+                }
+
+                Ok(json!({
+                    "capabilities": {
+                        "positionEncoding": self.position_encoding.name(),
+                        // Full text synchronization: every change sends the whole document.
+                        "textDocumentSync": 1,
+                        "hoverProvider": true,
+                        "definitionProvider": true,
+                        "documentFormattingProvider": true,
+                    },
+                    "serverInfo": {
+                        "name": "roc",
+                        "version": include_str!("../../../version.txt").trim(),
+                    },
+                }))
+            }
+            "shutdown" => {
+                self.shutdown_requested = true;
+
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let (uri, position) = text_document_position(params)?;
+
+                let hover = self
+                    .documents
+                    .get_mut(uri)
+                    .and_then(|document| document.hover(position));
+
+                Ok(match hover {
+                    Some((region, type_str)) => json!({
+                        "contents": {
+                            "kind": "markdown",
+                            "value": format!("```roc\n{}\n```", type_str),
+                        },
+                        "range": range_to_json(region),
+                    }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/definition" => {
+                let (uri, position) = text_document_position(params)?;
+
+                let definition = self
+                    .documents
+                    .get(uri)
+                    .and_then(|document| document.definition(position));
+
+                Ok(match definition {
+                    Some((path, region)) => json!({
+                        "uri": path_to_uri(&path),
+                        "range": range_to_json(region),
+                    }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/formatting" => {
+                let uri = text_document_uri(params)?;
+
+                let edits = self.documents.get(uri).and_then(|document| {
+                    let formatted = document.format()?;
+
+                    if formatted == document.text() {
+                        return Some(json!([]));
+                    }
+
+                    let whole_document = LineColumnRegion::new(LineColumn::zero(), document.end());
+
+                    Some(json!([{
+                        "range": range_to_json(whole_document),
+                        "newText": formatted,
+                    }]))
+                });
+
+                Ok(edits.unwrap_or(Value::Null))
+            }
+            _ => Err((
+                rpc::METHOD_NOT_FOUND,
+                format!("unsupported method: {}", method),
+            )),
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> io::Result<()> {
+        match method {
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+
+                if let (Some(uri), Some(text)) =
+                    (document["uri"].as_str(), document["text"].as_str())
+                {
+                    self.analyze(uri, text.to_string())?;
+                }
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str();
+
+                // With full text synchronization, the last change holds the entire document.
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+
+                if let (Some(uri), Some(text)) = (uri, text) {
+                    self.analyze(uri, text.to_string())?;
+                }
+            }
+            "textDocument/didSave" => {
+                // Other modules on disk may have changed too, so check the document again.
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    let text = match params["text"].as_str() {
+                        Some(text) => Some(text.to_string()),
+                        None => self.documents.get(uri).map(|doc| doc.text().to_string()),
+                    };
+
+                    if let Some(text) = text {
+                        self.analyze(uri, text)?;
+                    }
+                }
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                    self.publish_diagnostics(uri, Vec::new())?;
+                }
+            }
+            _ => {
+                // Notifications we don't support (e.g. `initialized`) can safely be ignored.
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analyze(&mut self, uri: &str, text: String) -> io::Result<()> {
+        let path = match uri_to_path(uri) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let document = AnalyzedDocument::new(path, text, self.position_encoding);
+
+        let diagnostics = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                json!({
+                    "range": range_to_json(diagnostic.region),
+                    "severity": match diagnostic.severity {
+                        Severity::RuntimeError => 1,
+                        Severity::Warning => 2,
+                    },
+                    "source": "roc",
+                    "code": diagnostic.title,
+                    "message": diagnostic.message,
+                })
+            })
+            .collect();
+
+        self.documents.insert(uri.to_string(), document);
+
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> io::Result<()> {
+        let notification = rpc::notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        );
+
+        rpc::write_message(&mut self.output, &notification)
+    }
+}
+
+fn text_document_uri(params: &Value) -> Result<&str, (i64, String)> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .ok_or_else(|| (rpc::INVALID_PARAMS, "missing textDocument.uri".to_string()))
+}
+
+fn text_document_position(params: &Value) -> Result<(&str, LineColumn), (i64, String)> {
+    let uri = text_document_uri(params)?;
+    let position = &params["position"];
+
+    match (position["line"].as_u64(), position["character"].as_u64()) {
+        (Some(line), Some(column)) => Ok((
+            uri,
+            LineColumn {
+                line: line as u32,
+                column: column as u32,
+            },
+        )),
+        _ => Err((rpc::INVALID_PARAMS, "missing position".to_string())),
+    }
+}
+
+fn range_to_json(region: LineColumnRegion) -> Value {
+    json!({
+        "start": { "line": region.start.line, "character": region.start.column },
+        "end": { "line": region.end.line, "character": region.end.column },
+    })
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = percent_decode(uri.strip_prefix("file://")?);
+
+    // Windows paths come through as e.g. file:///C:/Users/...
+    let path = match path.as_bytes() {
+        [b'/', _, b':', ..] => path[1..].to_string(),
+        _ => path,
+    };
+
+    Some(PathBuf::from(path))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    uri
+}
+
+fn percent_decode(string: &str) -> String {
+    let bytes = string.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = match bytes.get(index + 1..index + 3) {
+            Some(hex) if bytes[index] == b'%' => std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const URI: &str = "file:///roc-lang-srv-test/Test.roc";
+
+    fn frame(body: &str) -> String {
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    fn open(text: &str) -> String {
+        frame(
+            &json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": URI, "text": text } },
+            })
+            .to_string(),
+        )
+    }
+
+    fn request(id: u64, method: &str, params: Value) -> String {
+        frame(
+            &json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string(),
+        )
+    }
+
+    fn at(line: u32, character: u32) -> Value {
+        json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+    }
+
+    /// Runs the server on the given input, and returns its exit code and every message it sent.
+    fn run(input: &[String]) -> (i32, Vec<Value>) {
+        let mut output = Vec::new();
+        let exit_code = Server::new(&mut output)
+            .run(&mut Cursor::new(input.concat()))
+            .unwrap();
+
+        let mut reader = Cursor::new(output);
+        let mut messages = Vec::new();
+
+        while let Some(message) = rpc::read_message(&mut reader).unwrap() {
+            messages.push(message.unwrap());
+        }
+
+        (exit_code, messages)
+    }
+
+    fn response(messages: &[Value], id: u64) -> &Value {
+        messages
+            .iter()
+            .find(|message| message["id"] == id)
+            .unwrap_or_else(|| panic!("no response to request {}", id))
+    }
+
+    fn diagnostics(messages: &[Value]) -> Vec<&Vec<Value>> {
+        messages
+            .iter()
+            .filter(|message| message["method"] == "textDocument/publishDiagnostics")
+            .map(|message| message["params"]["diagnostics"].as_array().unwrap())
+            .collect()
+    }
+
+    const SRC: &str =
+        "interface Test\n    exposes [foo, bar]\n    imports []\n\nfoo = \"hello\"\n\nbar = foo\n";
+
+    #[test]
+    fn hover() {
+        let (_, messages) = run(&[open(SRC), request(1, "textDocument/hover", at(6, 6))]);
+
+        let hover = &response(&messages, 1)["result"];
+
+        assert_eq!(hover["contents"]["value"], "```roc\nStr\n```");
+        assert_eq!(
+            hover["range"]["start"],
+            json!({ "line": 6, "character": 6 })
+        );
+    }
+
+    #[test]
+    fn hover_after_non_ascii_text() {
+        // "é" is one UTF-16 code unit but two bytes, and "😀" is two code units but four bytes.
+        let src = SRC.replace("bar = foo", "bar = [\"é😀\", foo]");
+        let (_, messages) = run(&[open(&src), request(1, "textDocument/hover", at(6, 15))]);
+
+        let hover = &response(&messages, 1)["result"];
+
+        assert_eq!(hover["contents"]["value"], "```roc\nStr\n```");
+        assert_eq!(
+            hover["range"],
+            json!({
+                "start": { "line": 6, "character": 14 },
+                "end": { "line": 6, "character": 17 },
+            })
+        );
+    }
+
+    #[test]
+    fn utf8_position_encoding() {
+        let src = SRC.replace("bar = foo", "bar = [\"é😀\", foo]");
+        let initialize = request(
+            1,
+            "initialize",
+            json!({ "capabilities": { "general": { "positionEncodings": ["utf-16", "utf-8"] } } }),
+        );
+        let (_, messages) = run(&[
+            initialize,
+            open(&src),
+            request(2, "textDocument/hover", at(6, 18)),
+        ]);
+
+        let capabilities = &response(&messages, 1)["result"]["capabilities"];
+        let hover = &response(&messages, 2)["result"];
+
+        assert_eq!(capabilities["positionEncoding"], "utf-8");
+        assert_eq!(hover["contents"]["value"], "```roc\nStr\n```");
+        assert_eq!(
+            hover["range"]["start"],
+            json!({ "line": 6, "character": 17 })
+        );
+    }
+
+    #[test]
+    fn definition() {
+        let (_, messages) = run(&[open(SRC), request(1, "textDocument/definition", at(6, 7))]);
+
+        let definition = &response(&messages, 1)["result"];
+
+        assert!(definition["uri"].as_str().unwrap().ends_with("/Test.roc"));
+        assert_eq!(definition["range"]["start"]["line"], 4);
+    }
+
+    #[test]
+    fn formatting() {
+        let unformatted = SRC.replace("bar = foo", "bar=foo");
+        let (_, messages) = run(&[
+            open(&unformatted),
+            request(
+                1,
+                "textDocument/formatting",
+                json!({ "textDocument": { "uri": URI } }),
+            ),
+        ]);
+
+        let edits = response(&messages, 1)["result"].as_array().unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0]["newText"], SRC);
+        assert_eq!(
+            edits[0]["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+    }
+
+    #[test]
+    fn did_change() {
+        let change = frame(
+            &json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": { "uri": URI, "version": 2 },
+                    "contentChanges": [{ "text": SRC.replace("bar = foo", "bar = baz") }],
+                },
+            })
+            .to_string(),
+        );
+
+        let (_, messages) = run(&[
+            open(SRC),
+            change,
+            request(1, "textDocument/hover", at(6, 6)),
+        ]);
+
+        let published = diagnostics(&messages);
+
+        assert_eq!(published.len(), 2);
+        assert!(published[0].is_empty());
+        assert!(published[1]
+            .iter()
+            .any(|diagnostic| diagnostic["message"].as_str().unwrap().contains("baz")));
+    }
+
+    #[test]
+    fn malformed_message() {
+        let (exit_code, messages) = run(&[
+            frame("{ not json"),
+            request(1, "shutdown", Value::Null),
+            frame(&json!({ "jsonrpc": "2.0", "method": "exit" }).to_string()),
+        ]);
+
+        assert_eq!(messages[0]["id"], Value::Null);
+        assert_eq!(messages[0]["error"]["code"], rpc::PARSE_ERROR);
+        assert_eq!(response(&messages, 1)["result"], Value::Null);
+        assert_eq!(exit_code, 0);
+    }
+}
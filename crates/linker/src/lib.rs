@@ -114,6 +114,8 @@ pub fn generate_stub_lib(input_path: &Path, triple: &Triple) -> std::io::Result<
             render: RenderTarget::Generic,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Executable,
+            cache_dir: None,
+            coverage: false,
         },
     )
     .unwrap_or_else(|problem| todo!("{:?}", problem));
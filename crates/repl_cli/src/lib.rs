@@ -4,25 +4,33 @@ use inkwell::context::Context;
 use libloading::Library;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
 use roc_intern::SingleThreadedInterner;
-use roc_mono::layout::Layout;
-use roc_types::subs::Subs;
+use roc_mono::layout::{Builtin, Layout};
+use roc_types::subs::{Subs, Variable};
+use rustyline::completion::Completer;
 use rustyline::highlight::{Highlighter, PromptInfo};
 use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
-use rustyline_derive::{Completer, Helper, Hinter};
+use rustyline_derive::{Helper, Hinter};
 use std::borrow::Cow;
 use std::io;
-use target_lexicon::Triple;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use target_lexicon::{Architecture, Triple};
 
-use roc_build::link::llvm_module_to_dylib;
+use roc_build::link::{dev_object_to_dylib, llvm_module_to_dylib};
 use roc_collections::all::MutSet;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
 use roc_load::{EntryPoint, MonomorphizedModule};
-use roc_mono::ir::OptLevel;
+use roc_mono::ir::{OptLevel, ProcLayout};
+use roc_mono::layout::LayoutIds;
 use roc_parse::ast::Expr;
 use roc_parse::parser::{EClosure, EExpr, SyntaxError};
+use roc_repl_eval::completion::complete;
+use roc_repl_eval::docs::doc;
 use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{compile_to_mono, format_answer, ReplOutput};
+use roc_repl_eval::gen::{compile_session_to_mono, format_answer, session_expr_type, ReplOutput};
+use roc_repl_eval::modules::ReplModule;
+use roc_repl_eval::session::{session_defs, ReplSession};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_std::RocStr;
@@ -61,24 +69,52 @@ pub const TIPS: &str = concatcp!(
     BLUE,
     "  - ",
     END_COL,
+    "To keep defs around for later entries, enter just the defs followed by two empty lines. ",
+    "Use :defs to list them and :reset to clear them.\n\n",
+    BLUE,
+    "  - ",
+    END_COL,
+    ":load path/to/Module.roc brings the values a module exposes into scope. ",
+    ":import Module loads a module from the same directory, :import pf.Module loads one from a package of a loaded app, and :reload loads everything again.\n\n",
+    BLUE,
+    "  - ",
+    END_COL,
+    ":type <expr> shows an expression's type without evaluating it, and :doc <name> shows the docs for ",
+    "a module or one of its values, e.g. :doc List.map. Press Tab to complete names.\n\n",
+    BLUE,
+    "  - ",
+    END_COL,
+    "To paste several lines at once, put them between a line with just :{ and a line with just :}\n\n",
+    BLUE,
+    "  - ",
+    END_COL,
     "You can use `Ctrl+V`+`Ctrl+J` to make a newline. The repl will also insert a newline if you press enter",
     " when the current expression can not be evaluated, e.g. when you type `foo =<ENTER>`.\n\n",
     BLUE,
     "  - ",
     END_COL,
+    "Start the repl with `roc repl --dev` to evaluate with the dev backend, which answers sooner ",
+    "than LLVM. Whatever it can't evaluate yet goes through LLVM as usual.\n\n",
+    BLUE,
+    "  - ",
+    END_COL,
     ":q to quit\n\n",
     BLUE,
     "  - ",
     END_COL,
     ":help\n"
 );
+
+const ROC_SKIP_REPL_HISTORY: &str = "ROC_SKIP_REPL_HISTORY";
+
 pub const PROMPT: &str = concatcp!("\n", BLUE, "»", END_COL, " ");
 pub const CONT_PROMPT: &str = concatcp!(BLUE, "…", END_COL, " ");
 
-#[derive(Completer, Helper, Hinter)]
+#[derive(Helper, Hinter)]
 struct ReplHelper {
     validator: InputValidator,
     pending_src: String,
+    session: ReplSession,
 }
 
 impl ReplHelper {
@@ -86,10 +122,24 @@ impl ReplHelper {
         ReplHelper {
             validator: InputValidator::new(),
             pending_src: String::new(),
+            session: ReplSession::default(),
         }
     }
 }
 
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&self.session, line, pos))
+    }
+}
+
 impl Highlighter for ReplHelper {
     fn has_continuation_prompt(&self) -> bool {
         true
@@ -133,6 +183,19 @@ impl Validator for InputValidator {
     fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
         if ctx.input().is_empty() {
             Ok(ValidationResult::Incomplete)
+        } else if ctx.input().trim_start().starts_with(":{") {
+            // Everything up to the closing :} is one entry, however it parses.
+            if block_contents(ctx.input()).is_some() {
+                Ok(ValidationResult::Valid(None))
+            } else {
+                Ok(ValidationResult::Incomplete)
+            }
+        } else if ctx.input().ends_with("\n\n") && session_defs(ctx.input()).is_some() {
+            // Defs on their own can't be evaluated, so they only get entered (and kept
+            // in the session) once they're followed by two empty lines. Just one empty
+            // line could be part of a multi-line input, e.g. between two type aliases
+            // which refer to each other.
+            Ok(ValidationResult::Valid(None))
         } else {
             let arena = bumpalo::Bump::new();
             let state = roc_parse::state::State::new(ctx.input().trim().as_bytes());
@@ -150,6 +213,36 @@ impl Validator for InputValidator {
     }
 }
 
+/// The source between `:{` and `:}`, if the input is a complete block.
+fn block_contents(input: &str) -> Option<&str> {
+    let contents = input.trim().strip_prefix(":{")?.strip_suffix(":}")?;
+
+    Some(contents.trim_matches('\n'))
+}
+
+/// Where the repl keeps its history between sessions: `~/.local/share/roc/repl_history`
+/// (respecting `XDG_DATA_HOME`), or `%APPDATA%\roc\repl_history` on Windows.
+///
+/// Returns `None` when history is disabled with `ROC_SKIP_REPL_HISTORY=1`.
+fn history_path() -> Option<PathBuf> {
+    if matches!(std::env::var(ROC_SKIP_REPL_HISTORY).as_deref(), Ok("1")) {
+        return None;
+    }
+
+    let data_home = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+    } else {
+        match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var_os("HOME")?)
+                .join(".local")
+                .join("share"),
+        }
+    };
+
+    Some(data_home.join("roc").join("repl_history"))
+}
+
 struct CliApp {
     lib: Library,
 }
@@ -186,6 +279,102 @@ impl<'a> ReplApp<'a> for CliApp {
     }
 }
 
+/// Runs what the dev backend generated. Unlike LLVM's output, that returns its answer
+/// directly instead of wrapping it in a `RocCallResult`.
+struct DevApp {
+    lib: Library,
+    /// The size of what the main function returns. This decides how it gets returned, and
+    /// for structs, it's less than the `ret_bytes` which `jit_to_ast` asks for.
+    ret_size: usize,
+}
+
+/// A value the dev backend returns in rax and rdx.
+#[repr(C)]
+struct TwoRegisters(u64, u64);
+
+impl<'a> ReplApp<'a> for DevApp {
+    type Memory = CliMemory;
+
+    fn call_function<Return, F>(&mut self, main_fn_name: &str, mut transform: F) -> Expr<'a>
+    where
+        F: FnMut(&'a Self::Memory, Return) -> Expr<'a>,
+        Self::Memory: 'a,
+    {
+        unsafe {
+            let main: libloading::Symbol<unsafe extern "C" fn() -> Return> = self
+                .lib
+                .get(main_fn_name.as_bytes())
+                .unwrap_or_else(|_| panic!("Unable to find `{}` in the dylib", main_fn_name));
+
+            transform(&CliMemory, main())
+        }
+    }
+
+    fn call_function_returns_roc_list<F>(
+        &mut self,
+        main_fn_name: &str,
+        mut transform: F,
+    ) -> Expr<'a>
+    where
+        F: FnMut(&'a Self::Memory, (usize, usize, usize)) -> Expr<'a>,
+        Self::Memory: 'a,
+    {
+        let word = std::mem::size_of::<usize>();
+
+        self.call_function_dynamic_size(main_fn_name, 3 * word, |memory: &'a CliMemory, addr| {
+            let list = (
+                memory.deref_usize(addr),
+                memory.deref_usize(addr + word),
+                memory.deref_usize(addr + 2 * word),
+            );
+
+            transform(memory, list)
+        })
+    }
+
+    fn call_function_dynamic_size<T, F>(
+        &mut self,
+        main_fn_name: &str,
+        ret_bytes: usize,
+        mut transform: F,
+    ) -> T
+    where
+        F: FnMut(&'a Self::Memory, usize) -> T,
+        Self::Memory: 'a,
+    {
+        // u128s, so the answer is aligned for any value it could contain
+        let mut buffer = vec![0u128; ret_bytes.max(self.ret_size) / 16 + 1];
+        let addr = buffer.as_mut_ptr() as usize;
+
+        // This follows the dev backend's x86_64 convention (see `return_complex_symbol`), which
+        // `gen_and_eval_dev` checks the target for. Only a lone F32 or F64 comes back in xmm0,
+        // and those go through `call_function`. Anything else up to 16 bytes comes back in rax
+        // and rdx, even if it's a struct of floats, where the C calling convention would use
+        // xmm0 and xmm1. Anything bigger gets written to a pointer passed as the first argument.
+        unsafe {
+            if self.ret_size > 16 {
+                let main: libloading::Symbol<unsafe extern "C" fn(*mut u8)> = self
+                    .lib
+                    .get(main_fn_name.as_bytes())
+                    .unwrap_or_else(|_| panic!("Unable to find `{}` in the dylib", main_fn_name));
+
+                main(addr as *mut u8);
+            } else {
+                let main: libloading::Symbol<unsafe extern "C" fn() -> TwoRegisters> = self
+                    .lib
+                    .get(main_fn_name.as_bytes())
+                    .unwrap_or_else(|_| panic!("Unable to find `{}` in the dylib", main_fn_name));
+
+                let TwoRegisters(first, second) = main();
+
+                buffer[0] = (first as u128) | ((second as u128) << 64);
+            }
+        }
+
+        transform(&CliMemory, addr)
+    }
+}
+
 macro_rules! deref_number {
     ($name: ident, $t: ty) => {
         fn $name(&self, addr: usize) -> $t {
@@ -281,6 +470,7 @@ pub fn mono_module_to_dylib<'a>(
         mode: LlvmBackendMode::GenTest, // so roc_panic is generated
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        coverage: None,
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
@@ -334,20 +524,24 @@ pub fn mono_module_to_dylib<'a>(
         .map(|lib| (lib, main_fn_name, subs, layout_interner))
 }
 
-fn gen_and_eval_llvm<'a>(
-    src: &str,
-    target: Triple,
-    opt_level: OptLevel,
-) -> Result<ReplOutput, SyntaxError<'a>> {
-    let arena = Bump::new();
-    let target_info = TargetInfo::from(&target);
+/// An entry which has been through the frontend, and is ready to be evaluated.
+struct CompiledEntry<'a> {
+    loaded: MonomorphizedModule<'a>,
+    main_fn_layout: ProcLayout<'a>,
+    main_fn_var: Variable,
+    expr_type_str: String,
+}
 
-    let mut loaded = match compile_to_mono(&arena, src, target_info, DEFAULT_PALETTE) {
-        Ok(x) => x,
-        Err(prob_strings) => {
-            return Ok(ReplOutput::Problems(prob_strings));
-        }
-    };
+/// Everything before code gen. If there's nothing to evaluate, e.g. because there were
+/// problems, returns the output for the entry instead.
+fn compile_entry<'a>(
+    arena: &'a Bump,
+    src: &str,
+    session: &ReplSession,
+    target_info: TargetInfo,
+) -> Result<CompiledEntry<'a>, ReplOutput> {
+    let mut loaded = compile_session_to_mono(arena, session, src, target_info, DEFAULT_PALETTE)
+        .map_err(ReplOutput::Problems)?;
 
     debug_assert_eq!(loaded.exposed_to_host.values.len(), 1);
     let (main_fn_symbol, main_fn_var) = loaded.exposed_to_host.values.iter().next().unwrap();
@@ -366,13 +560,40 @@ fn gen_and_eval_llvm<'a>(
     let (_, main_fn_layout) = match loaded.procedures.keys().find(|(s, _)| *s == main_fn_symbol) {
         Some(layout) => *layout,
         None => {
-            return Ok(ReplOutput::NoProblems {
+            return Err(ReplOutput::NoProblems {
                 expr: "<function>".to_string(),
                 expr_type: expr_type_str,
             });
         }
     };
 
+    Ok(CompiledEntry {
+        loaded,
+        main_fn_layout,
+        main_fn_var,
+        expr_type_str,
+    })
+}
+
+fn gen_and_eval_llvm<'a>(
+    src: &str,
+    session: &ReplSession,
+    target: Triple,
+    opt_level: OptLevel,
+) -> Result<ReplOutput, SyntaxError<'a>> {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&target);
+
+    let CompiledEntry {
+        loaded,
+        main_fn_layout,
+        main_fn_var,
+        expr_type_str,
+    } = match compile_entry(&arena, src, session, target_info) {
+        Ok(entry) => entry,
+        Err(output) => return Ok(output),
+    };
+
     let interns = loaded.interns.clone();
 
     let (lib, main_fn_name, subs, layout_interner) =
@@ -396,7 +617,166 @@ fn gen_and_eval_llvm<'a>(
     Ok(formatted)
 }
 
-fn eval_and_format<'a>(src: &str) -> Result<String, SyntaxError<'a>> {
+/// Evaluates the entry with the dev backend, which gets to the answer much sooner than LLVM
+/// does. It doesn't support everything yet, though. If it runs into something it can't
+/// compile, or what it compiled doesn't run to the end, this returns `None`.
+fn gen_and_eval_dev(src: &str, session: &ReplSession, target: &Triple) -> Option<ReplOutput> {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(target);
+
+    let CompiledEntry {
+        loaded,
+        main_fn_layout,
+        main_fn_var,
+        expr_type_str,
+    } = match compile_entry(&arena, src, session, target_info) {
+        Ok(entry) => entry,
+        Err(output) => return Some(output),
+    };
+
+    let MonomorphizedModule {
+        module_id,
+        procedures,
+        entry_point,
+        mut interns,
+        exposed_to_host,
+        subs,
+        layout_interner,
+        ..
+    } = loaded;
+
+    let main_fn_symbol = match entry_point {
+        EntryPoint::Executable { symbol, .. } => symbol,
+        EntryPoint::Test => {
+            unreachable!()
+        }
+    };
+
+    let main_fn_name = LayoutIds::default()
+        .get_toplevel(main_fn_symbol, &main_fn_layout)
+        .to_exposed_symbol_string(main_fn_symbol, &interns);
+
+    let ret_size = main_fn_layout
+        .result
+        .stack_size(&layout_interner, target_info) as usize;
+
+    // Other than numbers and Bools, `DevApp` reads answers the way the dev backend returns
+    // them on x86_64. On AArch64, for one, big answers get written to the address in x8.
+    let returned_like_c = matches!(
+        main_fn_layout.result,
+        Layout::Builtin(Builtin::Bool | Builtin::Int(_) | Builtin::Float(_))
+    );
+
+    if !returned_like_c && target.architecture != Architecture::X86_64 {
+        return None;
+    }
+
+    let env = roc_gen_dev::Env {
+        arena: &arena,
+        layout_interner: &layout_interner,
+        module_id,
+        exposed_to_host: exposed_to_host.values.keys().copied().collect(),
+        lazy_literals: false,
+        generate_allocators: true, // the repl has no platform to provide them
+        abort_on_panic: true,      // so a Roc panic ends the child process running the entry
+    };
+
+    // The dev backend panics when it gets to something it doesn't support. Those panics
+    // aren't the user's problem, since LLVM will evaluate the entry instead.
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let object_bytes = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        roc_gen_dev::build_module(&env, &mut interns, target, procedures).write()
+    }));
+
+    std::panic::set_hook(default_hook);
+
+    let object_bytes = object_bytes.ok()?.ok()?;
+    let lib = dev_object_to_dylib(&object_bytes, target).expect("we produce a valid Dylib");
+
+    let mut app = DevApp { lib, ret_size };
+
+    let expr = run_in_child(|| {
+        let res_answer = jit_to_ast(
+            &arena,
+            &mut app,
+            &main_fn_name,
+            main_fn_layout,
+            main_fn_var,
+            &subs,
+            &interns,
+            layout_interner.into_global().fork(),
+            target_info,
+        );
+
+        match format_answer(&arena, res_answer, String::new()) {
+            ReplOutput::NoProblems { expr, .. } => expr,
+            ReplOutput::Problems(_) => unreachable!("answers don't have problems"),
+        }
+    })?;
+
+    Some(ReplOutput::NoProblems {
+        expr,
+        expr_type: expr_type_str,
+    })
+}
+
+/// Runs `eval` in a child process, and returns the answer it produced. If the child crashes
+/// or panics, or `eval` panics, this returns `None`.
+#[cfg(unix)]
+fn run_in_child<F: FnOnce() -> String>(eval: F) -> Option<String> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let (mut parent_end, mut child_end) = UnixStream::pair().ok()?;
+
+    match unsafe { libc::fork() } {
+        0 => {
+            // we are the child
+            drop(parent_end);
+
+            // Whatever goes wrong here, the parent reports by evaluating the entry with LLVM
+            std::panic::set_hook(Box::new(|_| {}));
+
+            let exit_code = match std::panic::catch_unwind(AssertUnwindSafe(eval)) {
+                Ok(answer) if child_end.write_all(answer.as_bytes()).is_ok() => 0,
+                _ => 1,
+            };
+
+            // Exit without running destructors or atexit handlers: those belong to the parent.
+            unsafe { libc::_exit(exit_code) }
+        }
+        -1 => None,
+        pid => {
+            // The child's end has to be closed here too, or reading never gets to the end
+            drop(child_end);
+
+            let mut answer = String::new();
+            let read = parent_end.read_to_string(&mut answer);
+
+            let mut status = 0;
+            unsafe { libc::waitpid(pid, &mut status, 0) };
+
+            let succeeded = libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0;
+
+            (read.is_ok() && succeeded).then(|| answer)
+        }
+    }
+}
+
+/// Without a child process to run it in, a crash in the code the dev backend generated would
+/// take the repl down too, so everything gets evaluated with LLVM.
+#[cfg(not(unix))]
+fn run_in_child<F: FnOnce() -> String>(_eval: F) -> Option<String> {
+    None
+}
+
+fn eval_and_format<'a>(
+    src: &str,
+    session: &mut ReplSession,
+    backend: ReplBackend,
+) -> Result<String, SyntaxError<'a>> {
     let format_output = |output| match output {
         ReplOutput::NoProblems { expr, expr_type } => {
             format!("\n{} {}:{} {}", expr, PINK, END_COL, expr_type)
@@ -404,14 +784,86 @@ fn eval_and_format<'a>(src: &str) -> Result<String, SyntaxError<'a>> {
         ReplOutput::Problems(lines) => format!("\n{}\n", lines.join("\n\n")),
     };
 
-    gen_and_eval_llvm(src, Triple::host(), OptLevel::Normal).map(format_output)
+    let target = Triple::host();
+
+    match session_defs(src) {
+        Some(defs) => {
+            let target_info = TargetInfo::from(&target);
+
+            match session.add_defs(defs, target_info, DEFAULT_PALETTE) {
+                Ok(()) => Ok(String::new()),
+                Err(lines) => Ok(format_output(ReplOutput::Problems(lines))),
+            }
+        }
+        None if backend == ReplBackend::Dev => match gen_and_eval_dev(src, session, &target) {
+            Some(output) => Ok(format_output(output)),
+            // LLVM supports everything the dev backend doesn't yet.
+            None => gen_and_eval_llvm(src, session, target, OptLevel::Normal).map(format_output),
+        },
+        None => gen_and_eval_llvm(src, session, target, OptLevel::Normal).map(format_output),
+    }
+}
+
+fn format_session_defs(session: &ReplSession) -> String {
+    if session.defs().is_empty() {
+        return "No defs have been entered yet.".to_string();
+    }
+
+    let defs: Vec<&str> = session.defs().iter().map(|def| def.src.as_str()).collect();
+
+    format!("\n{}", defs.join("\n\n"))
+}
+
+fn format_loaded_modules(result: Result<Vec<ReplModule>, Vec<String>>) -> String {
+    match result {
+        Ok(modules) if modules.is_empty() => "\nNo modules were loaded.".to_string(),
+        Ok(modules) => {
+            let lines: Vec<String> = modules
+                .iter()
+                .map(|module| {
+                    if module.exposed.is_empty() {
+                        format!("Loaded {}, which exposes nothing.", module.qualified_name())
+                    } else {
+                        format!(
+                            "Loaded {}, which exposes {}.",
+                            module.qualified_name(),
+                            module.exposed.join(", ")
+                        )
+                    }
+                })
+                .collect();
+
+            format!("\n{}", lines.join("\n"))
+        }
+        Err(problems) => format_problems(&problems),
+    }
+}
+
+fn format_problems(problems: &[String]) -> String {
+    format!("\n{}\n", problems.join("\n\n"))
+}
+
+/// Entering defs prints nothing unless there's a problem with them.
+fn print_output(output: &str) {
+    if !output.is_empty() {
+        println!("{}", output);
+    }
 }
 
 fn report_parse_error(fail: SyntaxError) {
     println!("TODO Gracefully report parse error in repl: {:?}", fail);
 }
 
-pub fn main() -> io::Result<()> {
+/// Which backend the repl evaluates entries with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplBackend {
+    Llvm,
+    /// The dev backend, which gets to an answer sooner. Entries it can't evaluate yet are
+    /// evaluated with LLVM instead.
+    Dev,
+}
+
+pub fn main(backend: ReplBackend) -> io::Result<()> {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
@@ -425,6 +877,17 @@ pub fn main() -> io::Result<()> {
     let repl_helper = ReplHelper::new();
     editor.set_helper(Some(repl_helper));
 
+    let history_path = history_path();
+
+    if let Some(path) = &history_path {
+        // There's no history file yet the first time the repl runs.
+        let _ = editor.load_history(path);
+
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+    }
+
     loop {
         let readline = editor.readline(PROMPT);
 
@@ -433,10 +896,17 @@ pub fn main() -> io::Result<()> {
                 let trim_line = line.trim();
                 editor.add_history_entry(trim_line);
 
-                let pending_src = &mut editor
-                    .helper_mut()
-                    .expect("Editor helper was not set")
-                    .pending_src;
+                // Save after every entry, so the history survives the repl crashing.
+                // Losing it isn't worth interrupting the session over, though.
+                if let Some(path) = &history_path {
+                    let _ = editor.save_history(path);
+                }
+
+                let ReplHelper {
+                    pending_src,
+                    session,
+                    ..
+                } = editor.helper_mut().expect("Editor helper was not set");
 
                 match trim_line.to_lowercase().as_str() {
                     "" => {
@@ -445,9 +915,9 @@ pub fn main() -> io::Result<()> {
                         } else if prev_line_blank {
                             // After two blank lines in a row, give up and try parsing it
                             // even though it's going to fail. This way you don't get stuck.
-                            match eval_and_format(pending_src.as_str()) {
+                            match eval_and_format(pending_src.as_str(), session, backend) {
                                 Ok(output) => {
-                                    println!("{}", output);
+                                    print_output(&output);
                                 }
                                 Err(fail) => {
                                     report_parse_error(fail);
@@ -464,24 +934,90 @@ pub fn main() -> io::Result<()> {
                     }
                     ":help" => {
                         // TODO add link to repl tutorial(does not yet exist).
-                        println!("Use :q to exit.");
+                        println!("Use :load <path> or :import <Module> to bring a module's exposed values into scope, and :reload to load them again.");
+                        println!("Use :type <expr> to see an expression's type without evaluating it, and :doc <name> to see the docs for a module or something it exposes.");
+                        println!("Use :defs to list the defs entered so far, :reset to clear them along with any modules, and :q to exit.");
+                    }
+                    ":defs" => {
+                        println!("{}", format_session_defs(session));
+                    }
+                    ":reset" => {
+                        session.reset();
+                        println!("Cleared all defs and modules.");
+                    }
+                    ":reload" => {
+                        let target_info = TargetInfo::from(&Triple::host());
+                        let result = session
+                            .reload(target_info, DEFAULT_PALETTE)
+                            .map(|()| session.modules().to_vec());
+
+                        println!("{}", format_loaded_modules(result));
+                    }
+                    command if command.starts_with(":load ") => {
+                        // Use the line as it was entered, since paths are case-sensitive
+                        let path = trim_line[":load ".len()..].trim();
+                        let target_info = TargetInfo::from(&Triple::host());
+                        let result = session.load(Path::new(path), target_info, DEFAULT_PALETTE);
+
+                        println!("{}", format_loaded_modules(result));
+                    }
+                    command if command.starts_with(":type ") => {
+                        let expr = trim_line[":type ".len()..].trim();
+                        let target_info = TargetInfo::from(&Triple::host());
+
+                        match session_expr_type(session, expr, target_info, DEFAULT_PALETTE) {
+                            Ok(expr_type) => {
+                                println!("\n{} {}:{} {}", expr, PINK, END_COL, expr_type)
+                            }
+                            Err(problems) => println!("{}", format_problems(&problems)),
+                        }
+                    }
+                    command if command.starts_with(":doc ") => {
+                        let name = trim_line[":doc ".len()..].trim();
+                        let target_info = TargetInfo::from(&Triple::host());
+
+                        match doc(session, name, target_info, DEFAULT_PALETTE) {
+                            Ok(docs) => println!("\n{}", docs),
+                            Err(problems) => println!("{}", format_problems(&problems)),
+                        }
+                    }
+                    command if command.starts_with(":import ") => {
+                        let module_name = trim_line[":import ".len()..].trim();
+                        let target_info = TargetInfo::from(&Triple::host());
+                        let result = session.import(module_name, target_info, DEFAULT_PALETTE);
+
+                        println!("{}", format_loaded_modules(result));
+                    }
+                    block if block_contents(block).is_some() => {
+                        let src = block_contents(trim_line).unwrap_or_default();
+
+                        match eval_and_format(src, session, backend) {
+                            Ok(output) => {
+                                print_output(&output);
+                            }
+                            Err(fail) => {
+                                report_parse_error(fail);
+                            }
+                        }
+
+                        pending_src.clear();
                     }
                     ":exit" | ":quit" | ":q" => {
                         break;
                     }
                     _ => {
                         let result = if pending_src.is_empty() {
-                            eval_and_format(trim_line)
+                            eval_and_format(trim_line, session, backend)
                         } else {
                             pending_src.push('\n');
                             pending_src.push_str(trim_line);
 
-                            eval_and_format(pending_src.as_str())
+                            eval_and_format(pending_src.as_str(), session, backend)
                         };
 
                         match result {
                             Ok(output) => {
-                                println!("{}", output);
+                                print_output(&output);
                                 pending_src.clear();
                             }
                             //                            Err(Fail {
@@ -0,0 +1,51 @@
+//! Tab completion in the repl, for the names in scope and for what modules expose.
+
+use crate::docs::exposed;
+use crate::modules::BUILTIN_MODULES;
+use crate::session::ReplSession;
+
+/// Completes the name which ends at `pos` in `line`, e.g. `List.ma` to `List.map` and
+/// `List.mapWithIndex`. Returns where the name starts, and what it could be completed to.
+pub fn complete(session: &ReplSession, line: &str, pos: usize) -> (usize, Vec<String>) {
+    let start = line[..pos]
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let word = &line[start..pos];
+
+    if word.is_empty() {
+        return (start, Vec::new());
+    }
+
+    let mut candidates: Vec<String> = match word.rsplit_once('.') {
+        Some((module_name, _)) => exposed(session, module_name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| format!("{}.{}", module_name, name))
+            .collect(),
+        None => {
+            let def_names = session
+                .defs()
+                .iter()
+                .map(|def| def.name.clone())
+                // Destructures don't have a name to complete
+                .filter(|name| name.chars().all(|c| c.is_alphanumeric() || c == '_'));
+            let module_names = BUILTIN_MODULES
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .chain(session.modules().iter().map(|module| module.name.clone()));
+            let exposed_names = session
+                .modules()
+                .iter()
+                .flat_map(|module| module.exposed.iter().cloned());
+
+            def_names.chain(module_names).chain(exposed_names).collect()
+        }
+    };
+
+    candidates.retain(|candidate| candidate.starts_with(word));
+    candidates.sort();
+    candidates.dedup();
+
+    (start, candidates)
+}
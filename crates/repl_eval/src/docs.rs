@@ -0,0 +1,152 @@
+//! What `:doc` shows: the doc comments of builtins, and of the modules loaded into the repl.
+
+use bumpalo::Bump;
+use roc_load::docs::{DocEntry, ModuleDocumentation};
+use roc_module::symbol::ModuleId;
+use roc_reporting::report::{Palette, RenderTarget};
+use roc_target::TargetInfo;
+use std::path::PathBuf;
+
+use crate::gen::session_expr_type;
+use crate::modules::{builtin_exposed, builtin_module_id, typecheck};
+use crate::session::ReplSession;
+
+/// Renders the docs for a module (e.g. `List`), or for something a module exposes, either
+/// qualified (e.g. `List.map`) or, for loaded modules, unqualified (e.g. `greet`).
+pub fn doc(
+    session: &ReplSession,
+    name: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<String, Vec<String>> {
+    let not_found = || vec![format!("I couldn't find any docs for {}.", name)];
+
+    let (module_name, def_name) = match name.rsplit_once('.') {
+        _ if is_module(session, name) => (name, None),
+        Some((module_name, def_name)) => (module_name, Some(def_name)),
+        None => match session
+            .modules()
+            .iter()
+            .find(|module| module.exposed.iter().any(|exposed| exposed == name))
+        {
+            Some(module) => (module.name.as_str(), Some(name)),
+            None => return Err(not_found()),
+        },
+    };
+
+    let module_docs = match module_docs(session, module_name, target_info)? {
+        Some(module_docs) => module_docs,
+        None => return Err(not_found()),
+    };
+
+    match def_name {
+        None => Ok(render_module(
+            &module_docs,
+            &exposed(session, module_name).unwrap_or_default(),
+        )),
+        Some(def_name) => {
+            let doc_def = module_docs.entries.iter().find_map(|entry| match entry {
+                DocEntry::DocDef(doc_def) if doc_def.name == def_name => Some(doc_def),
+                _ => None,
+            });
+
+            let doc_def = match doc_def {
+                Some(doc_def) => doc_def,
+                None => return Err(not_found()),
+            };
+
+            let mut buf = name.to_string();
+
+            // Types don't have a type, but for values it's the best summary there is.
+            if def_name.starts_with(char::is_lowercase) {
+                buf.push_str(" : ");
+                buf.push_str(&session_expr_type(session, name, target_info, palette)?);
+            }
+
+            if let Some(docs) = &doc_def.docs {
+                buf.push_str("\n\n");
+                buf.push_str(docs.trim_end());
+            }
+
+            Ok(buf)
+        }
+    }
+}
+
+fn is_module(session: &ReplSession, name: &str) -> bool {
+    builtin_module_id(name).is_some() || session.modules().iter().any(|m| m.name == name)
+}
+
+/// The names the given builtin or loaded module exposes.
+pub(crate) fn exposed(session: &ReplSession, module_name: &str) -> Option<Vec<String>> {
+    match builtin_module_id(module_name) {
+        Some(module_id) => Some(builtin_exposed(module_id)),
+        None => session
+            .modules()
+            .iter()
+            .find(|module| module.name == module_name)
+            .map(|module| module.exposed.clone()),
+    }
+}
+
+fn module_docs(
+    session: &ReplSession,
+    module_name: &str,
+    target_info: TargetInfo,
+) -> Result<Option<ModuleDocumentation>, Vec<String>> {
+    let mut loaded = match builtin_module_id(module_name) {
+        Some(module_id) => typecheck_builtin(module_name, module_id, target_info),
+        None => match session.modules().iter().find(|m| m.name == module_name) {
+            Some(module) => typecheck(&module.path, target_info)?,
+            None => return Ok(None),
+        },
+    };
+
+    Ok(loaded.documentation.remove(&loaded.module_id))
+}
+
+/// Builtins are loaded from the compiler's cache without their docs, so get those from
+/// the builtin's source instead.
+fn typecheck_builtin(
+    module_name: &str,
+    module_id: ModuleId,
+    target_info: TargetInfo,
+) -> roc_load::LoadedModule {
+    let arena = Bump::new();
+    let src = roc_builtins::roc::module_source(module_id);
+
+    let loaded = roc_load::load_and_typecheck_str(
+        &arena,
+        PathBuf::from(format!("{}.roc", module_name)),
+        src,
+        PathBuf::from("fake/test/path"),
+        Default::default(),
+        target_info,
+        RenderTarget::ColorTerminal,
+    );
+
+    match loaded {
+        Ok(loaded) => loaded,
+        Err(problem) => panic!(
+            "error while loading the docs for builtin module {}: {:?}",
+            module_name, problem
+        ),
+    }
+}
+
+fn render_module(module_docs: &ModuleDocumentation, exposed: &[String]) -> String {
+    let mut buf = module_docs.name.clone();
+
+    // Docs which come before any of the module's defs describe the module itself
+    if let Some(DocEntry::DetachedDoc(docs)) = module_docs.entries.first() {
+        buf.push_str("\n\n");
+        buf.push_str(docs.trim_end());
+    }
+
+    if !exposed.is_empty() {
+        buf.push_str("\n\nExposes: ");
+        buf.push_str(&exposed.join(", "));
+    }
+
+    buf
+}
@@ -3,15 +3,21 @@ use roc_load::{ExecutionMode, LoadConfig, Threading};
 use roc_reporting::report::Palette;
 use std::path::PathBuf;
 
+use roc_collections::all::MutMap;
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
-use roc_load::{LoadingProblem, MonomorphizedModule};
+use roc_load::{LoadedModule, LoadingProblem, MonomorphizedModule};
+use roc_module::symbol::{Interns, ModuleId};
 use roc_parse::ast::Expr;
+use roc_problem::can::Problem;
 use roc_region::all::LineInfo;
-use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
+use roc_reporting::report::{can_problem, type_problem, Report, RocDocAllocator, Severity};
+use roc_solve_problem::TypeError;
 use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 
 use crate::eval::ToAstProblem;
+use crate::session::ReplSession;
 
 pub enum ReplOutput {
     Problems(Vec<String>),
@@ -48,10 +54,20 @@ pub fn compile_to_mono<'a>(
     target_info: TargetInfo,
     palette: Palette,
 ) -> Result<MonomorphizedModule<'a>, Vec<String>> {
-    let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
+    compile_session_to_mono(arena, &ReplSession::default(), src, target_info, palette)
+}
 
-    let module_src = arena.alloc(promote_expr_to_module(src));
+/// Like `compile_to_mono`, but with the session's defs in scope.
+pub fn compile_session_to_mono<'a>(
+    arena: &'a Bump,
+    session: &ReplSession,
+    src: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<MonomorphizedModule<'a>, Vec<String>> {
+    let filename = PathBuf::from("");
+    let src_dir = session_src_dir(session);
+    let module_src = arena.alloc(session.module_src(src));
 
     let exposed_types = Default::default();
     let loaded = roc_load::load_and_monomorphize_from_str(
@@ -65,6 +81,8 @@ pub fn compile_to_mono<'a>(
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Executable,
+            cache_dir: None,
+            coverage: false,
         },
     );
 
@@ -79,6 +97,7 @@ pub fn compile_to_mono<'a>(
     };
 
     let MonomorphizedModule {
+        module_id,
         interns,
         sources,
         can_problems,
@@ -86,6 +105,147 @@ pub fn compile_to_mono<'a>(
         ..
     } = &mut loaded;
 
+    let lines = render_session_problems(
+        session,
+        src,
+        *module_id,
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        palette,
+    );
+
+    if !lines.is_empty() {
+        Err(lines)
+    } else {
+        Ok(loaded)
+    }
+}
+
+/// The type of the given expression, with the session's defs in scope. Unlike
+/// `compile_session_to_mono`, this only typechecks, so nothing gets evaluated.
+pub fn session_expr_type(
+    session: &ReplSession,
+    src: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<String, Vec<String>> {
+    let arena = Bump::new();
+    let filename = PathBuf::from("");
+    let src_dir = session_src_dir(session);
+    let module_src = arena.alloc(session.module_src(src));
+
+    let exposed_types = Default::default();
+    let loaded = roc_load::load_and_typecheck_str(
+        &arena,
+        filename,
+        module_src,
+        src_dir,
+        exposed_types,
+        target_info,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+    );
+
+    let mut loaded = match loaded {
+        Ok(v) => v,
+        Err(LoadingProblem::FormattedReport(report)) => {
+            return Err(vec![report]);
+        }
+        Err(e) => {
+            panic!("error while loading module: {:?}", e)
+        }
+    };
+
+    let LoadedModule {
+        module_id,
+        interns,
+        sources,
+        can_problems,
+        type_problems,
+        exposed_to_host,
+        solved,
+        ..
+    } = &mut loaded;
+
+    let lines = render_session_problems(
+        session,
+        src,
+        *module_id,
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        palette,
+    );
+
+    if !lines.is_empty() {
+        return Err(lines);
+    }
+
+    debug_assert_eq!(exposed_to_host.len(), 1);
+    let expr_var = *exposed_to_host.values().next().unwrap();
+
+    Ok(name_and_print_var(
+        expr_var,
+        solved.inner_mut(),
+        *module_id,
+        interns,
+        DebugPrint::NOTHING,
+    ))
+}
+
+/// Loaded modules are found relative to the directory the session's modules are in.
+fn session_src_dir(session: &ReplSession) -> PathBuf {
+    session
+        .src_dir()
+        .unwrap_or_else(|| PathBuf::from("fake/test/path"))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_session_problems(
+    session: &ReplSession,
+    src: &str,
+    module_id: ModuleId,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    palette: Palette,
+) -> Vec<String> {
+    // The session's defs come after the expression, and the loaded modules are imported
+    // in the header. Most of them won't be used by it, so don't warn about that.
+    let session_defs_start = session.module_src_without_defs(src).len() as u32;
+
+    if let Some(problems) = can_problems.get_mut(&module_id) {
+        problems.retain(|problem| match problem {
+            Problem::UnusedDef(_, region) => region.start().offset < session_defs_start,
+            Problem::UnusedImport(..) | Problem::UnusedModuleImport(..) => false,
+            _ => true,
+        });
+    }
+
+    render_problems(
+        Some(module_id),
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        palette,
+    )
+}
+
+/// Renders the problems in each module. Warnings are only rendered for the repl's own
+/// module, if there is one; warnings in modules loaded into the repl aren't the repl's
+/// business, but their errors are.
+pub(crate) fn render_problems(
+    repl_module: Option<ModuleId>,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    palette: Palette,
+) -> Vec<String> {
     let mut lines = Vec::new();
 
     for (home, (module_path, src)) in sources.iter() {
@@ -98,42 +258,57 @@ pub fn compile_to_mono<'a>(
             continue;
         }
 
-        let line_info = LineInfo::new(module_src);
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
 
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
+        let is_shown = |report: &Report| {
+            Some(*home) == repl_module || report.severity == Severity::RuntimeError
+        };
 
         for problem in can_probs.into_iter() {
             let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
-            let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            if is_shown(&report) {
+                let mut buf = String::new();
+
+                report.render_color_terminal(&mut buf, &alloc, &palette);
 
-            lines.push(buf);
+                lines.push(buf);
+            }
         }
 
         for problem in type_probs {
             if let Some(report) = type_problem(&alloc, &line_info, module_path.clone(), problem) {
-                let mut buf = String::new();
+                if is_shown(&report) {
+                    let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                    report.render_color_terminal(&mut buf, &alloc, &palette);
 
-                lines.push(buf);
+                    lines.push(buf);
+                }
             }
         }
     }
 
-    if !lines.is_empty() {
-        Err(lines)
-    } else {
-        Ok(loaded)
-    }
+    lines
 }
 
-fn promote_expr_to_module(src: &str) -> String {
-    let mut buffer =
-        String::from("app \"app\" provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
+/// The header of the repl's module, with the given `packages` and `imports` entries, and
+/// the start of the def whose value is the given expression.
+pub(crate) fn promote_expr_to_module(packages: &[String], imports: &[String], src: &str) -> String {
+    let mut buffer = String::from("app \"app\" ");
+
+    if !packages.is_empty() {
+        buffer.push_str(&format!("packages {{ {} }} ", packages.join(", ")));
+    }
+
+    if !imports.is_empty() {
+        buffer.push_str(&format!("imports [{}] ", imports.join(", ")));
+    }
+
+    buffer.push_str("provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
 
     for line in src.lines() {
         // indent the body!
@@ -2,8 +2,13 @@ use roc_parse::ast::Expr;
 use roc_std::RocDec;
 use roc_target::TargetInfo;
 
+pub mod completion;
+pub mod docs;
 pub mod eval;
 pub mod gen;
+pub mod modules;
+mod rename;
+pub mod session;
 
 pub trait ReplApp<'a> {
     type Memory: 'a + ReplAppMemory;
@@ -0,0 +1,397 @@
+use bumpalo::Bump;
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_module::symbol::ModuleId;
+use roc_parse::ast::{ExtractSpaces, Module};
+use roc_parse::header::ImportsEntry;
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
+use roc_reporting::report::{Palette, RenderTarget};
+use roc_target::TargetInfo;
+use std::path::{Path, PathBuf};
+
+use crate::gen::render_problems;
+use crate::session::{session_defs, SessionDef};
+
+/// An interface module loaded into the repl with `:load` or `:import`. Its exposed values
+/// and types are in scope for every entry, both unqualified and qualified with its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplModule {
+    /// e.g. `Json.Decode`
+    pub name: String,
+    /// The shorthand of the package the module is from, e.g. `pf` for `pf.Stdout`
+    pub package: Option<String>,
+    pub path: PathBuf,
+    pub exposed: Vec<String>,
+}
+
+impl ReplModule {
+    /// The directory the module's name is relative to, which is where the modules it
+    /// imports are found. For `Json.Decode` at `src/Json/Decode.roc`, this is `src`.
+    pub fn src_dir(&self) -> PathBuf {
+        let mut src_dir = self.path.clone();
+
+        for _ in self.name.split('.') {
+            src_dir.pop();
+        }
+
+        src_dir
+    }
+
+    /// The name it's imported by, e.g. `Json.Decode` or `pf.Stdout`
+    pub fn qualified_name(&self) -> String {
+        match &self.package {
+            Some(shorthand) => format!("{}.{}", shorthand, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// e.g. `Json.Decode.{ decode, Decoder }`, with only the exposed names that `unqualified`
+    /// accepts in the braces
+    pub(crate) fn imports_entry(&self, unqualified: impl Fn(&str) -> bool) -> String {
+        let names: Vec<&str> = self
+            .exposed
+            .iter()
+            .map(String::as_str)
+            .filter(|name| unqualified(name))
+            .collect();
+
+        format!("{}.{{ {} }}", self.qualified_name(), names.join(", "))
+    }
+}
+
+/// A package in the header of an app loaded into the repl, whose modules can be imported
+/// with e.g. `:import pf.Stdout`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplPackage {
+    /// e.g. `pf`
+    pub shorthand: String,
+    /// The package's main module, e.g. `platform/main.roc`. Its modules are next to it.
+    pub path: PathBuf,
+}
+
+impl ReplPackage {
+    /// e.g. `pf: "platform/main.roc"`
+    pub(crate) fn packages_entry(&self) -> String {
+        format!("{}: \"{}\"", self.shorthand, self.path.display())
+    }
+}
+
+/// The builtin modules which every repl entry can use without importing them.
+pub const BUILTIN_MODULES: &[(&str, ModuleId)] = &[
+    ("Bool", ModuleId::BOOL),
+    ("Box", ModuleId::BOX),
+    ("Decode", ModuleId::DECODE),
+    ("Dict", ModuleId::DICT),
+    ("Encode", ModuleId::ENCODE),
+    ("Hash", ModuleId::HASH),
+    ("List", ModuleId::LIST),
+    ("Num", ModuleId::NUM),
+    ("Result", ModuleId::RESULT),
+    ("Set", ModuleId::SET),
+    ("Str", ModuleId::STR),
+];
+
+pub fn builtin_module_id(module_name: &str) -> Option<ModuleId> {
+    BUILTIN_MODULES
+        .iter()
+        .find(|(name, _)| *name == module_name)
+        .map(|(_, module_id)| *module_id)
+}
+
+/// The names a builtin module exposes, e.g. `map` and `len` for `List`, in the order its
+/// header lists them.
+pub fn builtin_exposed(module_id: ModuleId) -> Vec<String> {
+    let src = roc_builtins::roc::module_source(module_id);
+
+    match header_exposes(src) {
+        Some(exposed) => exposed,
+        None => panic!("Builtin module {:?} has an invalid header", module_id),
+    }
+}
+
+/// The names in the `exposes` list of an interface module's header.
+fn header_exposes(src: &str) -> Option<Vec<String>> {
+    let arena = Bump::new();
+
+    match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((Module::Interface { header }, _)) => Some(
+            header
+                .exposes
+                .items
+                .iter()
+                .map(|name| {
+                    let name: &str = name.value.extract_spaces().item.into();
+
+                    name.to_string()
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// What `:load` found in a module.
+pub(crate) enum LoadedSrc {
+    Interface(ReplModule),
+    /// An app can't be imported, so its defs (other than `main`) go in the session instead,
+    /// along with the modules it imports from its own directory.
+    App {
+        modules: Vec<ReplModule>,
+        defs: Vec<SessionDef>,
+        packages: Vec<ReplPackage>,
+    },
+}
+
+/// Where `:import` looks for the given module: next to the modules which are already loaded,
+/// or else in the current directory.
+pub fn module_path(src_dir: Option<&Path>, module_name: &str) -> PathBuf {
+    let mut path = src_dir.map(Path::to_path_buf).unwrap_or_default();
+
+    for part in module_name.split('.') {
+        path.push(part);
+    }
+
+    path.set_extension("roc");
+
+    path
+}
+
+pub(crate) fn load_src(
+    path: &Path,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<LoadedSrc, Vec<String>> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|err| vec![format!("I couldn't read {}: {}", path.display(), err)])?;
+
+    let arena = Bump::new();
+
+    match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((Module::Interface { .. }, _)) => {
+            load_interface(path, target_info, palette).map(LoadedSrc::Interface)
+        }
+        Ok((Module::App { header }, state)) => {
+            let app_dir = path.parent().unwrap_or_else(|| Path::new(""));
+            let packages: Vec<ReplPackage> = header
+                .packages
+                .items
+                .iter()
+                .map(|entry| {
+                    let entry = entry.value.extract_spaces().item;
+
+                    ReplPackage {
+                        shorthand: entry.shorthand.to_string(),
+                        path: app_dir.join(entry.package_name.value.as_str()),
+                    }
+                })
+                .collect();
+            let mut modules = Vec::new();
+
+            for entry in header.imports.items.iter() {
+                let module = match entry.value.extract_spaces().item {
+                    ImportsEntry::Module(module_name, _) => {
+                        let module_path = module_path(Some(app_dir), module_name.as_str());
+
+                        load_interface(&module_path, target_info, palette)?
+                    }
+                    ImportsEntry::Package(shorthand, module_name, _) => {
+                        match packages
+                            .iter()
+                            .find(|package| package.shorthand == shorthand)
+                        {
+                            Some(package) => load_package_module(
+                                package,
+                                module_name.as_str(),
+                                target_info,
+                                palette,
+                            )?,
+                            None => {
+                                return Err(vec![format!(
+                                    "{} imports {}.{}, but there's no package named {} in its header.",
+                                    path.display(),
+                                    shorthand,
+                                    module_name.as_str(),
+                                    shorthand
+                                )]);
+                            }
+                        }
+                    }
+                };
+
+                modules.push(module);
+            }
+
+            let body = &src[state.pos().offset as usize..];
+            let defs = session_defs(body)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|def| def.name != "main")
+                .collect();
+
+            Ok(LoadedSrc::App {
+                modules,
+                defs,
+                packages,
+            })
+        }
+        Ok(_) => Err(vec![format!(
+            "{} is not an interface or app module, so it can't be loaded into the repl.",
+            path.display()
+        )]),
+        Err(_) => {
+            // Typechecking the module renders a proper report about the syntax error.
+            load_interface(path, target_info, palette).map(LoadedSrc::Interface)
+        }
+    }
+}
+
+/// Typechecks the interface module at the given path, along with everything it imports.
+fn load_interface(
+    path: &Path,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<ReplModule, Vec<String>> {
+    let mut loaded = typecheck(path, target_info)?;
+
+    let problems = render_problems(
+        None,
+        &loaded.sources,
+        &loaded.interns,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+        palette,
+    );
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let home = loaded.module_id;
+    let interns = &loaded.interns;
+    let name = interns.module_name(home).as_str().to_string();
+    let mut exposed: Vec<String> = loaded
+        .exposed_values
+        .iter()
+        .map(|symbol| symbol.as_str(interns).to_string())
+        .collect();
+
+    let mut exposed_types: Vec<String> = loaded
+        .exposed_aliases
+        .keys()
+        .filter(|symbol| symbol.module_id() == home)
+        .map(|symbol| symbol.as_str(interns).to_string())
+        .collect();
+
+    exposed_types.sort();
+    exposed.extend(exposed_types);
+
+    Ok(ReplModule {
+        name,
+        package: None,
+        path: path.to_path_buf(),
+        exposed,
+    })
+}
+
+/// Typechecks the module with the given name from the given package, e.g. `Stdout` from `pf`.
+/// A package's shorthand only means something in an app's header, so this typechecks an app
+/// which imports the module.
+pub(crate) fn load_package_module(
+    package: &ReplPackage,
+    module_name: &str,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<ReplModule, Vec<String>> {
+    let arena = Bump::new();
+    let src = arena.alloc(format!(
+        "app \"app\" packages {{ {} }} imports [{}.{}] provides [replOutput] to \"./platform\"\n\nreplOutput = {{}}\n",
+        package.packages_entry(),
+        package.shorthand,
+        module_name
+    ));
+    let loaded = roc_load::load_and_typecheck_str(
+        &arena,
+        PathBuf::from(""),
+        src,
+        PathBuf::from("."),
+        Default::default(),
+        target_info,
+        RenderTarget::ColorTerminal,
+    );
+
+    let mut loaded = match loaded {
+        Ok(loaded) => loaded,
+        Err(problem) => return Err(loading_problem(&package.path, problem)),
+    };
+
+    let problems = render_problems(
+        None,
+        &loaded.sources,
+        &loaded.interns,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+        palette,
+    );
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let interns = &loaded.interns;
+    let (path, src) = loaded
+        .sources
+        .iter()
+        .find(|(module_id, _)| {
+            **module_id != loaded.module_id
+                && interns.module_name(**module_id).as_str() == module_name
+        })
+        .map(|(_, source)| source)
+        .ok_or_else(|| {
+            vec![format!(
+                "The {} package doesn't have a module named {}.",
+                package.shorthand, module_name
+            )]
+        })?;
+
+    Ok(ReplModule {
+        name: module_name.to_string(),
+        package: Some(package.shorthand.clone()),
+        path: path.clone(),
+        exposed: header_exposes(src).unwrap_or_default(),
+    })
+}
+
+pub(crate) fn typecheck(path: &Path, target_info: TargetInfo) -> Result<LoadedModule, Vec<String>> {
+    let arena = Bump::new();
+    let loaded = roc_load::load_and_typecheck(
+        &arena,
+        path.to_path_buf(),
+        Default::default(),
+        LoadConfig {
+            target_info,
+            render: RenderTarget::ColorTerminal,
+            threading: Threading::Single,
+            exec_mode: ExecutionMode::Check,
+            cache_dir: None,
+            coverage: false,
+        },
+    );
+
+    loaded.map_err(|problem| loading_problem(path, problem))
+}
+
+fn loading_problem(path: &Path, problem: LoadingProblem) -> Vec<String> {
+    match problem {
+        LoadingProblem::FormattedReport(report) => vec![report],
+        LoadingProblem::FileProblem { filename, error } => vec![format!(
+            "I couldn't read {}: {}",
+            filename.display(),
+            std::io::Error::from(error)
+        )],
+        LoadingProblem::TriedToImportAppModule => vec![format!(
+            "{} imports an app module, which isn't allowed.",
+            path.display()
+        )],
+        problem => panic!("error while loading module: {:?}", problem),
+    }
+}
@@ -0,0 +1,209 @@
+//! Renaming a top-level value in the source of the session's defs, so an expression can
+//! define a value with the same name while the defs which use the session's value keep
+//! using it.
+
+use bumpalo::Bump;
+use roc_parse::ast::{AssignedField, Expr, Pattern, StrLiteral, StrSegment, ValueDef};
+use roc_parse::module::module_defs;
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_region::all::{Loc, Position, Region};
+
+/// Renames the top-level value `from` to `to` in the given top-level defs, both where it's
+/// defined and where it's used. Roc doesn't allow shadowing, so every unqualified `from` in
+/// them is the top-level one. Source that doesn't parse is returned as it was.
+pub(crate) fn rename_value(src: &str, from: &str, to: &str) -> String {
+    let arena = Bump::new();
+    let defs = match module_defs().parse(&arena, State::new(src.as_bytes()), 0) {
+        Ok((_, defs, _)) => defs,
+        Err(_) => return src.to_string(),
+    };
+
+    let mut renamer = Renamer {
+        src,
+        from,
+        to,
+        edits: Vec::new(),
+    };
+
+    for def in defs.value_defs.iter() {
+        renamer.value_def(def);
+    }
+
+    // Apply the last edit first, so the regions of the others stay where they were
+    renamer
+        .edits
+        .sort_by_key(|(region, _)| region.start().offset);
+
+    let mut buf = src.to_string();
+
+    for (region, replacement) in renamer.edits.into_iter().rev() {
+        let range = region.start().offset as usize..region.end().offset as usize;
+
+        buf.replace_range(range, &replacement);
+    }
+
+    buf
+}
+
+struct Renamer<'s> {
+    src: &'s str,
+    from: &'s str,
+    to: &'s str,
+    edits: Vec<(Region, String)>,
+}
+
+impl Renamer<'_> {
+    fn value_def(&mut self, def: &ValueDef) {
+        match def {
+            ValueDef::Annotation(pattern, _) => self.pattern(pattern.region, &pattern.value),
+            ValueDef::Body(pattern, body) => {
+                self.pattern(pattern.region, &pattern.value);
+                self.expr(body.region, &body.value);
+            }
+            ValueDef::AnnotatedBody {
+                ann_pattern,
+                body_pattern,
+                body_expr,
+                ..
+            } => {
+                self.pattern(ann_pattern.region, &ann_pattern.value);
+                self.pattern(body_pattern.region, &body_pattern.value);
+                self.expr(body_expr.region, &body_expr.value);
+            }
+            ValueDef::Expect { condition, .. } | ValueDef::ExpectFx { condition, .. } => {
+                self.expr(condition.region, &condition.value)
+            }
+        }
+    }
+
+    /// Only the name of a def can be `from`; anything else that defined it would shadow it.
+    fn pattern(&mut self, region: Region, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(ident) if *ident == self.from => self.rename_in(region),
+            Pattern::SpaceBefore(pattern, _) | Pattern::SpaceAfter(pattern, _) => {
+                self.pattern(region, pattern)
+            }
+            _ => {}
+        }
+    }
+
+    /// Spaces and parens don't have regions of their own, so this is given the region of
+    /// the closest expression around `expr` which has one.
+    fn expr(&mut self, region: Region, expr: &Expr) {
+        match expr {
+            Expr::Var { module_name, ident } if module_name.is_empty() && *ident == self.from => {
+                self.rename_in(region)
+            }
+            Expr::Access(record, _) => self.expr(region, record),
+            Expr::List(items) => items.iter().for_each(|item| self.loc_expr(item)),
+            Expr::RecordUpdate { update, fields } => {
+                self.loc_expr(update);
+                fields.iter().for_each(|field| self.field(&field.value));
+            }
+            Expr::Record(fields) => fields.iter().for_each(|field| self.field(&field.value)),
+            Expr::Closure(_, body) => self.loc_expr(body),
+            Expr::Defs(defs, body) => {
+                defs.value_defs.iter().for_each(|def| self.value_def(def));
+                self.loc_expr(body);
+            }
+            Expr::Backpassing(_, first, second) | Expr::Expect(first, second) => {
+                self.loc_expr(first);
+                self.loc_expr(second);
+            }
+            Expr::Apply(function, args, _) => {
+                self.loc_expr(function);
+                args.iter().for_each(|arg| self.loc_expr(arg));
+            }
+            Expr::BinOps(operands, last) => {
+                operands
+                    .iter()
+                    .for_each(|(operand, _)| self.loc_expr(operand));
+                self.loc_expr(last);
+            }
+            Expr::UnaryOp(operand, _) => self.loc_expr(operand),
+            Expr::If(branches, final_else) => {
+                for (condition, then) in branches.iter() {
+                    self.loc_expr(condition);
+                    self.loc_expr(then);
+                }
+
+                self.loc_expr(final_else);
+            }
+            Expr::When(condition, branches) => {
+                self.loc_expr(condition);
+
+                for branch in branches.iter() {
+                    if let Some(guard) = &branch.guard {
+                        self.loc_expr(guard);
+                    }
+
+                    self.loc_expr(&branch.value);
+                }
+            }
+            Expr::Str(StrLiteral::Line(segments)) => self.segments(segments),
+            Expr::Str(StrLiteral::Block(lines)) => {
+                lines.iter().for_each(|segments| self.segments(segments))
+            }
+            Expr::SpaceBefore(expr, _) | Expr::SpaceAfter(expr, _) | Expr::ParensAround(expr) => {
+                self.expr(region, expr)
+            }
+            Expr::PrecedenceConflict(conflict) => self.loc_expr(conflict.expr),
+            _ => {}
+        }
+    }
+
+    fn loc_expr(&mut self, loc_expr: &Loc<Expr>) {
+        self.expr(loc_expr.region, &loc_expr.value)
+    }
+
+    fn field(&mut self, field: &AssignedField<Expr>) {
+        match field {
+            AssignedField::RequiredValue(_, _, value)
+            | AssignedField::OptionalValue(_, _, value) => self.expr(value.region, &value.value),
+            // `{ foo }` is short for `{ foo: foo }`, and only the value gets renamed
+            AssignedField::LabelOnly(label) if label.value == self.from => {
+                let replacement = format!("{}: {}", self.from, self.to);
+
+                self.edits.push((label.region, replacement));
+            }
+            AssignedField::SpaceBefore(field, _) | AssignedField::SpaceAfter(field, _) => {
+                self.field(field)
+            }
+            _ => {}
+        }
+    }
+
+    fn segments(&mut self, segments: &[StrSegment]) {
+        for segment in segments {
+            if let StrSegment::Interpolated(interpolated) = segment {
+                self.expr(interpolated.region, interpolated.value);
+            }
+        }
+    }
+
+    /// Renames the `from` in the given region, which may also contain parens or comments.
+    fn rename_in(&mut self, region: Region) {
+        let start = region.start().offset as usize;
+        let text = &self.src[start..region.end().offset as usize];
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+
+        let found = text.match_indices(self.from).find(|(index, _)| {
+            let before = text[..*index].chars().next_back();
+            let after = text[index + self.from.len()..].chars().next();
+
+            !before.map_or(false, |c| is_ident_char(c) || c == '.')
+                && !after.map_or(false, is_ident_char)
+        });
+
+        if let Some((index, _)) = found {
+            let start = (start + index) as u32;
+            let region = Region::new(
+                Position::new(start),
+                Position::new(start + self.from.len() as u32),
+            );
+
+            self.edits.push((region, self.to.to_string()));
+        }
+    }
+}
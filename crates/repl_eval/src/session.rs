@@ -0,0 +1,468 @@
+use bumpalo::Bump;
+use roc_parse::ast::{Expr, ExtractSpaces, Pattern, TypeDef, ValueDef};
+use roc_parse::module::module_defs;
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_region::all::{Loc, Region};
+use roc_reporting::report::Palette;
+use roc_target::TargetInfo;
+use std::path::{Path, PathBuf};
+
+use crate::gen::{compile_session_to_mono, promote_expr_to_module};
+use crate::modules::{
+    load_package_module, load_src, module_path, LoadedSrc, ReplModule, ReplPackage,
+};
+use crate::rename::rename_value;
+
+/// A top-level def entered in the repl, e.g. `foo = 1`, `Point : { x : F64, y : F64 }`,
+/// or an opaque type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionDef {
+    /// The name the def introduces. For a destructure, this is the whole pattern.
+    pub name: String,
+    /// The def as it was entered, including its annotation if it has one.
+    pub src: String,
+}
+
+/// The defs entered so far in a repl session, and the modules loaded into it, which each
+/// new expression is compiled against.
+#[derive(Debug, Clone, Default)]
+pub struct ReplSession {
+    defs: Vec<SessionDef>,
+    modules: Vec<ReplModule>,
+    /// The packages in the headers of the apps given to `:load`
+    packages: Vec<ReplPackage>,
+    /// The files given to `:load` or found by `:import`, in order, for `:reload`
+    loaded_paths: Vec<PathBuf>,
+}
+
+impl ReplSession {
+    pub fn defs(&self) -> &[SessionDef] {
+        &self.defs
+    }
+
+    pub fn modules(&self) -> &[ReplModule] {
+        &self.modules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty() && self.modules.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.defs.clear();
+        self.modules.clear();
+        self.packages.clear();
+        self.loaded_paths.clear();
+    }
+
+    /// Where the loaded modules are, if any have been loaded. Modules they import, and
+    /// modules given to `:import`, are found relative to this. Modules from packages are
+    /// found relative to their package instead.
+    pub fn src_dir(&self) -> Option<PathBuf> {
+        self.modules
+            .iter()
+            .find(|module| module.package.is_none())
+            .map(ReplModule::src_dir)
+    }
+
+    /// Loads the interface or app module at the given path into the session. Returns the
+    /// modules which are now in scope.
+    pub fn load(
+        &mut self,
+        path: &Path,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<Vec<ReplModule>, Vec<String>> {
+        let mut candidate = self.clone();
+        let loaded = candidate.load_without_checking(path, target_info, palette)?;
+
+        candidate.check(target_info, palette)?;
+
+        *self = candidate;
+
+        Ok(loaded)
+    }
+
+    /// Loads the module with the given name, e.g. `Json.Decode`, from the directory of the
+    /// modules which are already loaded, or else from the current directory. A module from a
+    /// package, e.g. `pf.Stdout`, is loaded from the package with that shorthand in the
+    /// header of an app which was loaded.
+    pub fn import(
+        &mut self,
+        module_name: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<Vec<ReplModule>, Vec<String>> {
+        match module_name.split_once('.') {
+            // e.g. `pf.Stdout`
+            Some((shorthand, package_module)) if shorthand.starts_with(char::is_lowercase) => {
+                let mut candidate = self.clone();
+                let module = candidate.import_without_checking(
+                    shorthand,
+                    package_module,
+                    target_info,
+                    palette,
+                )?;
+
+                candidate.check(target_info, palette)?;
+
+                *self = candidate;
+
+                Ok(vec![module])
+            }
+            _ => {
+                let path = module_path(self.src_dir().as_deref(), module_name);
+
+                self.load(&path, target_info, palette)
+            }
+        }
+    }
+
+    /// Loads everything that was loaded into the session again, e.g. after the files changed.
+    pub fn reload(&mut self, target_info: TargetInfo, palette: Palette) -> Result<(), Vec<String>> {
+        let mut candidate = self.clone();
+
+        candidate.modules.clear();
+        candidate.packages.clear();
+        candidate.loaded_paths.clear();
+
+        for path in self.loaded_paths.iter() {
+            candidate.load_without_checking(path, target_info, palette)?;
+        }
+
+        // Modules from packages which were given to `:import`, rather than imported by an app
+        for module in self.modules.iter() {
+            if let Some(shorthand) = &module.package {
+                if !candidate.modules.contains(module) {
+                    candidate.import_without_checking(
+                        shorthand,
+                        &module.name,
+                        target_info,
+                        palette,
+                    )?;
+                }
+            }
+        }
+
+        candidate.check(target_info, palette)?;
+
+        *self = candidate;
+
+        Ok(())
+    }
+
+    fn load_without_checking(
+        &mut self,
+        path: &Path,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<Vec<ReplModule>, Vec<String>> {
+        let (modules, defs, packages) = match load_src(path, target_info, palette)? {
+            LoadedSrc::Interface(module) => (vec![module], Vec::new(), Vec::new()),
+            LoadedSrc::App {
+                modules,
+                defs,
+                packages,
+            } => (modules, defs, packages),
+        };
+
+        for package in packages {
+            let existing = self
+                .packages
+                .iter()
+                .find(|existing| existing.shorthand == package.shorthand);
+
+            match existing {
+                Some(existing) if existing.path != package.path => {
+                    return Err(vec![format!(
+                        "{} has {} as its {} package, but {} already is. Use :reset to start over with another package.",
+                        path.display(),
+                        package.path.display(),
+                        package.shorthand,
+                        existing.path.display()
+                    )]);
+                }
+                Some(_) => {}
+                None => self.packages.push(package),
+            }
+        }
+
+        for module in modules.iter() {
+            // Modules from packages are found relative to their package
+            if module.package.is_none() {
+                let src_dir = module.src_dir();
+                let existing_src_dir = self
+                    .modules
+                    .iter()
+                    .find(|existing| existing.package.is_none() && existing.name != module.name)
+                    .map(ReplModule::src_dir);
+
+                // Everything else the repl imports has to be in the one directory it looks
+                // for modules in.
+                if let Some(existing) = existing_src_dir {
+                    if existing != src_dir {
+                        return Err(vec![format!(
+                            "{} is in {}, but the modules which are already loaded are in {}. Use :reset to start over with modules from another directory.",
+                            module.path.display(),
+                            src_dir.display(),
+                            existing.display()
+                        )]);
+                    }
+                }
+            }
+
+            self.add_module(module.clone());
+        }
+
+        for def in defs {
+            self.defs.retain(|existing| existing.name != def.name);
+            self.defs.push(def);
+        }
+
+        let path = path.to_path_buf();
+
+        if !self.loaded_paths.contains(&path) {
+            self.loaded_paths.push(path);
+        }
+
+        Ok(modules)
+    }
+
+    fn import_without_checking(
+        &mut self,
+        shorthand: &str,
+        module_name: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<ReplModule, Vec<String>> {
+        let package = match self
+            .packages
+            .iter()
+            .find(|package| package.shorthand == shorthand)
+        {
+            Some(package) => package,
+            None => {
+                return Err(vec![format!(
+                    "There's no package named {} in the session. Packages come from the headers of the apps given to :load.",
+                    shorthand
+                )]);
+            }
+        };
+
+        let module = load_package_module(package, module_name, target_info, palette)?;
+
+        self.add_module(module.clone());
+
+        Ok(module)
+    }
+
+    fn add_module(&mut self, module: ReplModule) {
+        self.modules
+            .retain(|existing| existing.qualified_name() != module.qualified_name());
+        self.modules.push(module);
+    }
+
+    /// Does everything in the session still compile?
+    fn check(&self, target_info: TargetInfo, palette: Palette) -> Result<(), Vec<String>> {
+        let arena = Bump::new();
+
+        // The output doesn't matter here; we only want to know if the session compiles.
+        compile_session_to_mono(&arena, self, "{}", target_info, palette).map(|_| ())
+    }
+
+    /// Adds the given defs to the session, replacing any earlier defs with the same names
+    /// (or giving a body to an earlier annotation), as long as everything still compiles. Otherwise, the session is left as it was and
+    /// the problems are returned.
+    pub fn add_defs(
+        &mut self,
+        defs: Vec<SessionDef>,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<(), Vec<String>> {
+        let mut candidate = self.clone();
+
+        for mut def in defs {
+            let existing = candidate
+                .defs
+                .iter()
+                .position(|existing| existing.name == def.name)
+                .map(|index| candidate.defs.remove(index));
+
+            // `foo = 1` after `foo : I64` gives the annotated def a body
+            if let Some(existing) = existing {
+                if def_kind(&existing.src) == Some(DefKind::Annotation)
+                    && def_kind(&def.src) == Some(DefKind::Body)
+                {
+                    def.src = format!("{}\n{}", existing.src, def.src);
+                }
+            }
+
+            candidate.defs.push(def);
+        }
+
+        candidate.check(target_info, palette)?;
+
+        *self = candidate;
+
+        Ok(())
+    }
+
+    /// The source of a module whose output is the given expression, with the session's
+    /// defs at the top level. Roc doesn't allow shadowing, so session defs which the
+    /// expression defines itself are renamed, along with their uses in the other session defs.
+    pub fn module_src(&self, expr: &str) -> String {
+        // The defs go after the expression, so line numbers in reports about the
+        // expression don't depend on what has been defined so far.
+        let mut buffer = self.module_src_without_defs(expr);
+        let renames: Vec<(String, String)> = names_defined_by(expr)
+            .into_iter()
+            .filter(|name| self.defs.iter().any(|def| def.name == *name))
+            .map(|name| {
+                let renamed = self.unused_name(&name, expr);
+
+                (name, renamed)
+            })
+            .collect();
+
+        for def in self.defs.iter() {
+            let src = renames.iter().fold(def.src.clone(), |src, (from, to)| {
+                rename_value(&src, from, to)
+            });
+
+            buffer.push('\n');
+            buffer.push_str(&src);
+            buffer.push('\n');
+        }
+
+        buffer
+    }
+
+    /// A name based on the given one which neither the session nor the expression uses.
+    fn unused_name(&self, name: &str, expr: &str) -> String {
+        (1..)
+            .map(|n| format!("{}Shadowed{}", name, n))
+            .find(|renamed| {
+                !expr.contains(renamed.as_str())
+                    && !self
+                        .defs
+                        .iter()
+                        .any(|def| def.src.contains(renamed.as_str()))
+            })
+            .unwrap_or_default()
+    }
+
+    /// The module's header, which imports the loaded modules, and the expression.
+    pub(crate) fn module_src_without_defs(&self, expr: &str) -> String {
+        let packages: Vec<String> = self
+            .packages
+            .iter()
+            .map(ReplPackage::packages_entry)
+            .collect();
+        // A name which more than one module exposes, like `line` in both `pf.Stdout` and
+        // `pf.Stderr`, can only be used qualified.
+        let exposed_once = |name: &str| {
+            self.modules
+                .iter()
+                .filter(|module| module.exposed.iter().any(|exposed| exposed == name))
+                .count()
+                == 1
+        };
+        let imports: Vec<String> = self
+            .modules
+            .iter()
+            .map(|module| module.imports_entry(exposed_once))
+            .collect();
+
+        promote_expr_to_module(&packages, &imports, expr)
+    }
+}
+
+/// If the input consists only of top-level defs (and no `expect`s), returns them.
+/// Anything else, e.g. defs followed by an expression, returns `None`.
+pub fn session_defs(src: &str) -> Option<Vec<SessionDef>> {
+    let arena = Bump::new();
+    let src = src.trim();
+    let (_, defs, _) = module_defs()
+        .parse(&arena, State::new(src.as_bytes()), 0)
+        .ok()?;
+
+    if defs.is_empty() {
+        return None;
+    }
+
+    defs.defs()
+        .zip(defs.regions.iter())
+        .map(|(def, region)| {
+            Some(SessionDef {
+                name: def_name(src, def)?,
+                src: slice(src, *region).to_string(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefKind {
+    Annotation,
+    Body,
+    Other,
+}
+
+fn def_kind(src: &str) -> Option<DefKind> {
+    let arena = Bump::new();
+    let (_, defs, _) = module_defs()
+        .parse(&arena, State::new(src.as_bytes()), 0)
+        .ok()?;
+
+    let kind = defs.defs().next().map(|def| match def {
+        Err(ValueDef::Annotation(..)) => DefKind::Annotation,
+        Err(ValueDef::Body(..)) => DefKind::Body,
+        _ => DefKind::Other,
+    });
+
+    kind
+}
+
+/// The names an expression defines before its final expression, e.g. `x` in `x = 1\nx + 1`.
+/// These shadow any session defs with the same names.
+fn names_defined_by(expr_src: &str) -> Vec<String> {
+    let arena = Bump::new();
+    let expr_src = expr_src.trim();
+    let state = State::new(expr_src.as_bytes());
+
+    match roc_parse::expr::parse_loc_expr(&arena, state, 0) {
+        Ok((_, loc_expr, _)) => match loc_expr.value.extract_spaces().item {
+            Expr::Defs(defs, _) => defs
+                .defs()
+                .filter_map(|def| def_name(expr_src, def))
+                .collect(),
+            _ => Vec::new(),
+        },
+        Err(_) => Vec::new(),
+    }
+}
+
+fn def_name(src: &str, def: Result<&TypeDef, &ValueDef>) -> Option<String> {
+    let pattern_name = |pattern: &Loc<Pattern>| match pattern.value {
+        Pattern::Identifier(ident) => ident.to_string(),
+        _ => slice(src, pattern.region).trim().to_string(),
+    };
+
+    match def {
+        Ok(TypeDef::Alias { header, .. })
+        | Ok(TypeDef::Opaque { header, .. })
+        | Ok(TypeDef::Ability { header, .. }) => Some(header.name.value.to_string()),
+        Err(ValueDef::Annotation(pattern, _)) => Some(pattern_name(pattern)),
+        Err(ValueDef::Body(pattern, _))
+        | Err(ValueDef::AnnotatedBody {
+            body_pattern: pattern,
+            ..
+        }) => Some(pattern_name(pattern)),
+        Err(ValueDef::Expect { .. }) | Err(ValueDef::ExpectFx { .. }) => None,
+    }
+}
+
+fn slice(src: &str, region: Region) -> &str {
+    &src[region.start().offset as usize..region.end().offset as usize]
+}
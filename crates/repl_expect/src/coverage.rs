@@ -0,0 +1,369 @@
+//! `roc test --coverage`: counting which top-level defs and branches the tests reached, and
+//! writing that down as an lcov file and a per-module summary.
+
+use std::io;
+use std::path::PathBuf;
+
+use roc_collections::{MutMap, VecMap};
+use roc_gen_llvm::llvm::coverage::{CoveragePoint, COUNTERS_GLOBAL};
+use roc_load::Expectations;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::{LineInfo, Region};
+
+/// The counters of an instrumented dylib. They live in memory that is shared with the worker
+/// processes, so they count the hits of every test.
+pub struct CoverageCounters {
+    ptr: *mut u64,
+    length: usize,
+}
+
+impl CoverageCounters {
+    /// Makes the counters for these points, and points the dylib at them. This must happen
+    /// before any worker processes are started.
+    pub fn new(lib: &libloading::Library, points: &[CoveragePoint]) -> Self {
+        if points.is_empty() {
+            // nothing was instrumented, so the dylib doesn't have the global either
+            return Self {
+                ptr: std::ptr::null_mut(),
+                length: 0,
+            };
+        }
+
+        let size = points.len() * std::mem::size_of::<u64>();
+
+        let ptr = unsafe {
+            // anonymous memory is zeroed
+            let ptr = libc::mmap(
+                std::ptr::null_mut(),
+                size,
+                libc::PROT_WRITE | libc::PROT_READ,
+                libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+
+            if ptr == libc::MAP_FAILED {
+                panic!(
+                    "could not allocate {} bytes for the coverage counters",
+                    size
+                );
+            }
+
+            let global: libloading::Symbol<*mut *mut u64> = lib
+                .get(COUNTERS_GLOBAL.as_bytes())
+                .unwrap_or_else(|error| panic!("{} is missing: {}", COUNTERS_GLOBAL, error));
+
+            **global = ptr.cast();
+
+            ptr
+        };
+
+        Self {
+            ptr: ptr.cast(),
+            length: points.len(),
+        }
+    }
+
+    /// How often each point was reached, in the same order as the points.
+    pub fn counts(&self) -> Vec<u64> {
+        (0..self.length)
+            .map(|index| unsafe { std::ptr::read_volatile(self.ptr.add(index)) })
+            .collect()
+    }
+}
+
+impl Drop for CoverageCounters {
+    fn drop(&mut self) {
+        if self.length > 0 {
+            unsafe {
+                libc::munmap(self.ptr.cast(), self.length * std::mem::size_of::<u64>());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefCoverage {
+    pub name: String,
+    /// 1-based, like in lcov
+    pub line: u32,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// 1-based, like in lcov
+    pub line: u32,
+    /// The index of the `when` or `if` in the module
+    pub block: u32,
+    /// The index of the branch in its `when` or `if`
+    pub branch: u32,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleCoverage {
+    pub path: PathBuf,
+    pub defs: Vec<DefCoverage>,
+    /// Sorted by block, then by branch
+    pub branches: Vec<BranchCoverage>,
+}
+
+impl ModuleCoverage {
+    pub fn defs_hit(&self) -> usize {
+        self.defs.iter().filter(|def| def.hits > 0).count()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches
+            .iter()
+            .filter(|branch| branch.hits > 0)
+            .count()
+    }
+
+    /// The hits of each line that has a def or a branch on it, sorted by line
+    fn line_hits(&self) -> Vec<(u32, u64)> {
+        let mut lines: VecMap<u32, u64> = VecMap::default();
+
+        let hits = self.defs.iter().map(|def| (def.line, def.hits)).chain(
+            self.branches
+                .iter()
+                .map(|branch| (branch.line, branch.hits)),
+        );
+
+        for (line, hits) in hits {
+            let line_hits = lines.get_or_insert(line, || 0);
+            *line_hits = (*line_hits).max(hits);
+        }
+
+        let mut lines: Vec<_> = lines.into_iter().collect();
+        lines.sort_unstable();
+
+        lines
+    }
+}
+
+/// Matches the counts up with the top-level defs and branches of the tested modules, sorted by
+/// path. A def or branch that no test reached is in there with 0 hits, even if it was never
+/// compiled.
+pub fn coverage_by_module(
+    points: &[CoveragePoint],
+    counts: &[u64],
+    expectations: &VecMap<ModuleId, Expectations>,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+) -> Vec<ModuleCoverage> {
+    // a def has a point per specialization
+    let mut def_hits: MutMap<_, u64> = MutMap::default();
+    let mut branch_hits: MutMap<(ModuleId, Region), u64> = MutMap::default();
+
+    for (point, count) in points.iter().zip(counts) {
+        match point {
+            CoveragePoint::Def(symbol) => *def_hits.entry(*symbol).or_default() += count,
+            CoveragePoint::Branch { module_id, region } => {
+                *branch_hits.entry((*module_id, *region)).or_default() += count
+            }
+        }
+    }
+
+    let mut modules: Vec<ModuleCoverage> = expectations
+        .iter()
+        .filter_map(|(module_id, module_expectations)| {
+            let (path, source) = sources.get(module_id)?;
+            let line_info = LineInfo::new(source);
+            let line_of = |region: &Region| line_info.convert_pos(region.start()).line + 1;
+
+            let mut defs: Vec<DefCoverage> = module_expectations
+                .toplevel_defs
+                .iter()
+                .map(|(symbol, region)| DefCoverage {
+                    name: symbol.as_str(interns).to_string(),
+                    line: line_of(region),
+                    hits: def_hits.get(symbol).copied().unwrap_or(0),
+                })
+                .collect();
+
+            defs.sort_by_key(|def| def.line);
+
+            let branches = module_expectations
+                .branches
+                .iter()
+                .enumerate()
+                .flat_map(|(block, regions)| {
+                    regions
+                        .iter()
+                        .enumerate()
+                        .map(move |(branch, region)| (block, branch, region))
+                })
+                .map(|(block, branch, region)| BranchCoverage {
+                    line: line_of(region),
+                    block: block as u32,
+                    branch: branch as u32,
+                    hits: branch_hits
+                        .get(&(*module_id, *region))
+                        .copied()
+                        .unwrap_or(0),
+                })
+                .collect();
+
+            Some(ModuleCoverage {
+                path: path.clone(),
+                defs,
+                branches,
+            })
+        })
+        .collect();
+
+    modules.sort_by(|a, b| a.path.cmp(&b.path));
+
+    modules
+}
+
+/// Writes the coverage in the lcov tracefile format, which e.g. `genhtml` and most CI services
+/// understand.
+pub fn write_lcov<W: io::Write>(writer: &mut W, modules: &[ModuleCoverage]) -> io::Result<()> {
+    for module in modules {
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", module.path.display())?;
+
+        for def in &module.defs {
+            writeln!(writer, "FN:{},{}", def.line, def.name)?;
+        }
+
+        for def in &module.defs {
+            writeln!(writer, "FNDA:{},{}", def.hits, def.name)?;
+        }
+
+        writeln!(writer, "FNF:{}", module.defs.len())?;
+        writeln!(writer, "FNH:{}", module.defs_hit())?;
+
+        for branch in &module.branches {
+            writeln!(
+                writer,
+                "BRDA:{},{},{},{}",
+                branch.line, branch.block, branch.branch, branch.hits
+            )?;
+        }
+
+        writeln!(writer, "BRF:{}", module.branches.len())?;
+        writeln!(writer, "BRH:{}", module.branches_hit())?;
+
+        let lines = module.line_hits();
+
+        for (line, hits) in &lines {
+            writeln!(writer, "DA:{},{}", line, hits)?;
+        }
+
+        writeln!(writer, "LF:{}", lines.len())?;
+        writeln!(
+            writer,
+            "LH:{}",
+            lines.iter().filter(|(_, hits)| *hits > 0).count()
+        )?;
+        writeln!(writer, "end_of_record")?;
+    }
+
+    Ok(())
+}
+
+/// Writes how many of each module's defs and branches the tests reached, one module per line.
+pub fn write_summary<W: io::Write>(writer: &mut W, modules: &[ModuleCoverage]) -> io::Result<()> {
+    let percentage = |hit: usize, total: usize| {
+        if total == 0 {
+            100.0
+        } else {
+            hit as f64 * 100.0 / total as f64
+        }
+    };
+
+    let width = modules
+        .iter()
+        .map(|module| module.path.display().to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    for module in modules {
+        let defs_hit = module.defs_hit();
+        let branches_hit = module.branches_hit();
+
+        writeln!(
+            writer,
+            "{:width$}  defs {}/{} ({:.1}%)  branches {}/{} ({:.1}%)",
+            module.path.display().to_string(),
+            defs_hit,
+            module.defs.len(),
+            percentage(defs_hit, module.defs.len()),
+            branches_hit,
+            module.branches.len(),
+            percentage(branches_hit, module.branches.len()),
+            width = width,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn module() -> ModuleCoverage {
+        ModuleCoverage {
+            path: PathBuf::from("Main.roc"),
+            defs: vec![
+                DefCoverage {
+                    name: "double".to_string(),
+                    line: 3,
+                    hits: 2,
+                },
+                DefCoverage {
+                    name: "unused".to_string(),
+                    line: 6,
+                    hits: 0,
+                },
+            ],
+            branches: vec![
+                BranchCoverage {
+                    line: 4,
+                    block: 0,
+                    branch: 0,
+                    hits: 2,
+                },
+                BranchCoverage {
+                    line: 4,
+                    block: 0,
+                    branch: 1,
+                    hits: 0,
+                },
+                BranchCoverage {
+                    line: 7,
+                    block: 1,
+                    branch: 0,
+                    hits: 0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn lcov() {
+        let mut buf = Vec::new();
+        write_lcov(&mut buf, &[module()]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "TN:\nSF:Main.roc\nFN:3,double\nFN:6,unused\nFNDA:2,double\nFNDA:0,unused\nFNF:2\nFNH:1\nBRDA:4,0,0,2\nBRDA:4,0,1,0\nBRDA:7,1,0,0\nBRF:3\nBRH:1\nDA:3,2\nDA:4,2\nDA:6,0\nDA:7,0\nLF:4\nLH:2\nend_of_record\n"
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let mut buf = Vec::new();
+        write_summary(&mut buf, &[module()]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "Main.roc  defs 1/2 (50.0%)  branches 1/3 (33.3%)\n"
+        );
+    }
+}
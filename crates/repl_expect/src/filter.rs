@@ -0,0 +1,197 @@
+//! Choosing which top-level `expect`s and properties `roc test` runs, and describing them for
+//! `roc test --list` and the per-expect results.
+
+use std::path::PathBuf;
+
+use roc_load::MonomorphizedModule;
+use roc_module::symbol::Symbol;
+use roc_region::all::{LineInfo, Region};
+
+/// A top-level `expect` or property, and where to find it.
+#[derive(Debug, Clone)]
+pub struct ExpectInfo {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub module_name: String,
+    pub path: PathBuf,
+    /// 1-based, like in reports.
+    pub line: u32,
+    /// 1-based, like in reports.
+    pub column: u32,
+    /// The comment right above the `expect`, or if there is none, its first line of code.
+    pub name: String,
+    /// The source code of the whole `expect`.
+    pub source: String,
+}
+
+impl ExpectInfo {
+    /// e.g. `src/Main.roc:12:1`
+    pub fn location(&self) -> String {
+        format!("{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Which expects to run. An expect runs when it passes every kind of filter that was given;
+/// the filters of one kind (e.g. several `--module`s) are alternatives.
+#[derive(Debug, Clone, Default)]
+pub struct ExpectFilter {
+    /// Module names, e.g. `Dict` for `Dict.roc`
+    pub modules: Vec<String>,
+    /// Exact expect names
+    pub names: Vec<String>,
+    /// Text to look for in the expect's name or source code
+    pub substrings: Vec<String>,
+}
+
+impl ExpectFilter {
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.names.is_empty() && self.substrings.is_empty()
+    }
+
+    pub fn matches(&self, expect: &ExpectInfo) -> bool {
+        let module_matches =
+            self.modules.is_empty() || self.modules.iter().any(|m| *m == expect.module_name);
+
+        let name_matches = self.names.is_empty() || self.names.iter().any(|n| *n == expect.name);
+
+        let substring_matches = self.substrings.is_empty()
+            || self.substrings.iter().any(|substring| {
+                expect.name.contains(substring.as_str())
+                    || expect.source.contains(substring.as_str())
+            });
+
+        module_matches && name_matches && substring_matches
+    }
+}
+
+/// All the top-level expects and properties of the loaded modules, sorted by file and position.
+pub fn describe_expects(loaded: &MonomorphizedModule) -> Vec<ExpectInfo> {
+    let toplevel = &loaded.toplevel_expects;
+
+    let expects = toplevel.pure.iter().chain(toplevel.fx.iter());
+
+    let properties = toplevel
+        .properties
+        .iter()
+        .map(|(symbol, property)| (symbol, &property.region));
+
+    let mut expects: Vec<ExpectInfo> = expects
+        .chain(properties)
+        .filter_map(|(symbol, region)| {
+            let module_id = symbol.module_id();
+            let (path, src) = loaded.sources.get(&module_id)?;
+
+            let module_name = loaded.interns.module_name(module_id);
+            let module_name = if module_name.is_empty() {
+                // the app module
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            } else {
+                module_name.to_string()
+            };
+
+            // The region starts with the whitespace and comments before the `expect`.
+            let start = region.start().offset as usize;
+            let end = region.end().offset as usize;
+            let region_src = src.get(start..end).unwrap_or_default();
+            let (comment, code_offset) = split_preceding_comment(region_src);
+            let source = region_src[code_offset..].to_string();
+
+            let position = LineInfo::new(src).convert_offset((start + code_offset) as u32);
+
+            let name = if comment.is_empty() {
+                source.lines().next().unwrap_or_default().trim().to_string()
+            } else {
+                comment
+            };
+
+            Some(ExpectInfo {
+                symbol: *symbol,
+                region: *region,
+                module_name,
+                path: path.clone(),
+                line: position.line + 1,
+                column: position.column + 1,
+                name,
+                source,
+            })
+        })
+        .collect();
+
+    expects.sort_by(|a, b| (&a.path, a.region.start()).cmp(&(&b.path, b.region.start())));
+
+    expects
+}
+
+/// Drops the top-level expects and properties that don't match the filter from the loaded module,
+/// so they don't get compiled or run, and returns the ones that are left.
+pub fn retain_expects(loaded: &mut MonomorphizedModule, filter: &ExpectFilter) -> Vec<ExpectInfo> {
+    retain_expects_with(loaded, |expect| {
+        if filter.matches(&expect) {
+            Some(expect)
+        } else {
+            None
+        }
+    })
+}
+
+/// Like [retain_expects], but `select` decides which expects to keep, and can change how the
+/// ones it keeps are described (e.g. to point at where their code came from). It sees the
+/// expects sorted by file and position.
+pub fn retain_expects_with(
+    loaded: &mut MonomorphizedModule,
+    select: impl FnMut(ExpectInfo) -> Option<ExpectInfo>,
+) -> Vec<ExpectInfo> {
+    let expects: Vec<ExpectInfo> = describe_expects(loaded)
+        .into_iter()
+        .filter_map(select)
+        .collect();
+
+    let keep = |symbol: &Symbol| expects.iter().any(|expect| expect.symbol == *symbol);
+
+    let toplevel = &mut loaded.toplevel_expects;
+
+    toplevel.pure = std::mem::take(&mut toplevel.pure)
+        .into_iter()
+        .filter(|(symbol, _)| keep(symbol))
+        .collect();
+
+    toplevel.fx = std::mem::take(&mut toplevel.fx)
+        .into_iter()
+        .filter(|(symbol, _)| keep(symbol))
+        .collect();
+
+    toplevel.properties = std::mem::take(&mut toplevel.properties)
+        .into_iter()
+        .filter(|(symbol, _)| keep(symbol))
+        .collect();
+
+    expects
+}
+
+/// The comment right above the code in an expect's region (joined into one line), and where
+/// the code starts.
+fn split_preceding_comment(region_src: &str) -> (String, usize) {
+    let mut comment_lines = Vec::new();
+    let mut offset = 0;
+
+    for line in region_src.split_inclusive('\n') {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            // a blank line separates the comment from the expect
+            comment_lines.clear();
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            comment_lines.push(comment.trim_start_matches('#').trim());
+        } else {
+            let indent = line.len() - line.trim_start().len();
+
+            return (comment_lines.join(" ").trim().to_string(), offset + indent);
+        }
+
+        offset += line.len();
+    }
+
+    (String::new(), 0)
+}
@@ -19,6 +19,11 @@ use {
 #[cfg(not(windows))]
 mod app;
 #[cfg(not(windows))]
+pub mod coverage;
+pub mod filter;
+#[cfg(not(windows))]
+mod property;
+#[cfg(not(windows))]
 pub mod run;
 
 #[cfg(not(windows))]
@@ -118,6 +123,8 @@ mod test {
             render: RenderTarget::ColorTerminal,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Test,
+            cache_dir: None,
+            coverage: false,
         };
         let loaded = roc_load::load_and_monomorphize_from_str(
             arena,
@@ -141,6 +148,7 @@ mod test {
             loaded,
             opt_level,
             LlvmBackendMode::CliTest,
+            false,
         )
         .unwrap();
 
@@ -158,7 +166,7 @@ mod test {
         unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
 
         let mut writer = Vec::with_capacity(1024);
-        let (_failed, _passed) = crate::run::run_expects_with_memory(
+        let _results = crate::run::run_expects_with_memory(
             &mut writer,
             RenderTarget::ColorTerminal,
             arena,
@@ -168,6 +176,10 @@ mod test {
             &mut expectations,
             expects,
             &mut memory,
+            crate::run::RunOptions {
+                seed: 42,
+                ..Default::default()
+            },
         )
         .unwrap();
 
@@ -953,4 +965,65 @@ mod test {
             ),
         );
     }
+
+    #[test]
+    fn property_shrinks_counterexample() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect \n -> n < 10
+                "#
+            ),
+            indoc!(
+                r#"
+                This property failed:
+
+                5│  expect \n -> n < 10
+                    ^^^^^^^^^^^^^^^^^^^^
+
+                It returned Bool.false for these arguments:
+
+                n : I64
+                n = 10
+
+                Run roc test --seed 42 to generate the same arguments again.
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn property_of_opaque_type() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                NonEmpty := List U8
+
+                len = \@NonEmpty list -> List.len list
+
+                expect \nonEmpty -> len nonEmpty > 0
+                "#
+            ),
+            indoc!(
+                r#"
+                There was a problem with this property:
+
+                9│  expect \nonEmpty -> len nonEmpty > 0
+                    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+                I can't generate values for nonEmpty because its type contains the
+                opaque type NonEmpty. Only the module of an opaque type knows which
+                values are valid ones, so I don't make up values of opaque types.
+                "#
+            ),
+        );
+    }
 }
@@ -0,0 +1,1260 @@
+//! Property-based testing for `roc test`.
+//!
+//! A property is a top-level `expect` of a function, like
+//! `expect \list -> List.reverse (List.reverse list) == list`. `roc test` generates arguments
+//! for it based on their layouts and calls it with many of them. When the property returns
+//! `Bool.false` (or crashes) for some arguments, they get shrunk to a small example that still
+//! fails, which is then reported. Arguments of opaque types are not generated, since values made
+//! up from their representation may not be valid ones.
+
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_intern::Interner;
+use roc_mono::layout::{round_up_to_alignment, Builtin, Layout, TagIdIntType, UnionLayout};
+use roc_target::TargetInfo;
+
+/// `Dec` stores its numbers multiplied by this much.
+const DEC_ONE: i128 = 1_000_000_000_000_000_000;
+
+/// Give up on finding a smaller failing example after this many calls.
+const MAX_SHRINK_ATTEMPTS: usize = 1000;
+
+/// A layout that `roc test` can generate values of, and how those values are laid out in memory.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Shape {
+    kind: ShapeKind,
+    stack_size: u32,
+    alignment: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ShapeKind {
+    Bool,
+    Int(IntWidth),
+    Float(FloatWidth),
+    Dec,
+    Str,
+    List(Box<Shape>),
+    /// The fields in the order they are stored in memory
+    Struct(Vec<Shape>),
+    TagUnion(Box<TagUnion>),
+    /// A value of the closest recursive tag union that this shape is a part of
+    RecursivePointer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TagUnion {
+    /// Sorted by tag id
+    tags: Vec<Tag>,
+    storage: TagUnionStorage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Tag {
+    id: TagIdIntType,
+    /// The payload in the order it is stored in memory
+    payload: Vec<Shape>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TagUnionStorage {
+    /// The payload is stored in place, followed by the tag id
+    Stack { tag_id: TagIdSlot },
+    /// The payload is stored on the heap, and the tag union is a pointer to it
+    Heap {
+        tag_id: HeapTagId,
+        /// The tag that is stored as a null pointer, if any
+        nullable_id: Option<TagIdIntType>,
+        size: u32,
+        alignment: u32,
+    },
+}
+
+/// Where the tag id is stored, relative to the start of the payload
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TagIdSlot {
+    offset: u32,
+    size: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HeapTagId {
+    /// There is only one tag that is not stored as a null pointer
+    Implicit,
+    /// In the low bits of the pointer
+    InPointer,
+    InData(TagIdSlot),
+}
+
+impl Shape {
+    /// The shape of values of this layout, unless it's a layout we can't generate values of yet
+    /// (e.g. functions).
+    pub(crate) fn from_layout<'a, I>(
+        interner: &I,
+        layout: &Layout<'a>,
+        target_info: TargetInfo,
+    ) -> Option<Self>
+    where
+        I: Interner<'a, Layout<'a>>,
+    {
+        let kind = match layout {
+            Layout::Builtin(builtin) => match builtin {
+                Builtin::Bool => ShapeKind::Bool,
+                Builtin::Int(width) => ShapeKind::Int(*width),
+                Builtin::Float(width) => ShapeKind::Float(*width),
+                Builtin::Decimal => ShapeKind::Dec,
+                Builtin::Str => ShapeKind::Str,
+                Builtin::List(element) => {
+                    let element = Self::from_layout(interner, element, target_info)?;
+
+                    ShapeKind::List(Box::new(element))
+                }
+            },
+            Layout::Struct { field_layouts, .. } => {
+                ShapeKind::Struct(Self::from_layouts(interner, field_layouts, target_info)?)
+            }
+            Layout::Union(union_layout) => {
+                let union = TagUnion::from_layout(interner, union_layout, target_info)?;
+
+                ShapeKind::TagUnion(Box::new(union))
+            }
+            Layout::RecursivePointer => ShapeKind::RecursivePointer,
+            Layout::Boxed(_) | Layout::LambdaSet(_) => return None,
+        };
+
+        let (stack_size, alignment) = layout.stack_size_and_alignment(interner, target_info);
+
+        Some(Self {
+            kind,
+            stack_size,
+            alignment,
+        })
+    }
+
+    fn from_layouts<'a, I>(
+        interner: &I,
+        layouts: &[Layout<'a>],
+        target_info: TargetInfo,
+    ) -> Option<Vec<Self>>
+    where
+        I: Interner<'a, Layout<'a>>,
+    {
+        layouts
+            .iter()
+            .map(|layout| Self::from_layout(interner, layout, target_info))
+            .collect()
+    }
+
+    pub(crate) fn stack_size(&self) -> u32 {
+        self.stack_size
+    }
+
+    pub(crate) fn alignment(&self) -> u32 {
+        self.alignment
+    }
+
+    /// Whether values of this shape contain a value of the recursive tag union that this shape
+    /// is a part of. Lists don't count, because they can be empty.
+    fn refers_back(&self) -> bool {
+        match &self.kind {
+            ShapeKind::RecursivePointer => true,
+            ShapeKind::Struct(fields) => fields.iter().any(Shape::refers_back),
+            ShapeKind::TagUnion(union) => {
+                !union.is_heap() && union.tags.iter().any(Tag::refers_back)
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn generate(&self, rng: &mut Rng, size: u32) -> Value {
+        self.generate_in(None, rng, size)
+    }
+
+    /// `recursive` is the closest recursive tag union that this shape is a part of.
+    fn generate_in(&self, recursive: Option<&Shape>, rng: &mut Rng, size: u32) -> Value {
+        match &self.kind {
+            ShapeKind::Bool => Value::Bool(rng.below(2) == 1),
+            ShapeKind::Int(width) => {
+                let (min, max) = int_range(*width);
+
+                let value = if rng.one_in(10) {
+                    let edge_cases = [min, max, 0, 1, -1];
+
+                    edge_cases[rng.below(edge_cases.len() as u64) as usize]
+                } else {
+                    let size = size as i128;
+
+                    rng.below(2 * size as u64 + 1) as i128 - size
+                };
+
+                Value::Int(value.clamp(min, max))
+            }
+            ShapeKind::Float(width) => {
+                let value = if rng.one_in(10) {
+                    let edge_cases = match width {
+                        FloatWidth::F32 => [0.0, 1.0, -1.0, f32::MAX as f64, f32::MIN as f64],
+                        _ => [0.0, 1.0, -1.0, f64::MAX, f64::MIN],
+                    };
+
+                    edge_cases[rng.below(edge_cases.len() as u64) as usize]
+                } else {
+                    (rng.unit() * 2.0 - 1.0) * size as f64
+                };
+
+                match width {
+                    FloatWidth::F32 => Value::Float(value as f32 as f64),
+                    _ => Value::Float(value),
+                }
+            }
+            ShapeKind::Dec => {
+                // whole numbers, or numbers with up to three digits after the point
+                let thousandths =
+                    rng.below(2 * 1000 * size as u64 + 1) as i128 - 1000 * size as i128;
+
+                Value::Int(thousandths * (DEC_ONE / 1000))
+            }
+            ShapeKind::Str => {
+                let length = rng.below(size as u64 + 1);
+
+                Value::Str((0..length).map(|_| generate_char(rng)).collect())
+            }
+            ShapeKind::List(element) => {
+                let length = rng.below(size as u64 + 1);
+
+                // the elements share the size, so recursive values don't grow exponentially
+                let element_size = if element.refers_back() {
+                    size / (length as u32).max(1)
+                } else {
+                    size
+                };
+
+                Value::List(
+                    (0..length)
+                        .map(|_| element.generate_in(recursive, rng, element_size))
+                        .collect(),
+                )
+            }
+            ShapeKind::Struct(fields) => Value::Struct(
+                fields
+                    .iter()
+                    .map(|field| field.generate_in(recursive, rng, size))
+                    .collect(),
+            ),
+            ShapeKind::TagUnion(union) => {
+                let recursive = if union.is_heap() {
+                    Some(self)
+                } else {
+                    recursive
+                };
+
+                // at size 0, pick tags that don't recurse, so generating a value always ends
+                let mut candidates: Vec<usize> = (0..union.tags.len())
+                    .filter(|index| size > 0 || !union.tags[*index].refers_back())
+                    .collect();
+
+                if candidates.is_empty() {
+                    candidates = (0..union.tags.len()).collect();
+                }
+
+                let index = candidates[rng.below(candidates.len() as u64) as usize];
+
+                let payload = union.tags[index]
+                    .payload
+                    .iter()
+                    .map(|shape| shape.generate_in(recursive, rng, size))
+                    .collect();
+
+                Value::Tag(index, payload)
+            }
+            ShapeKind::RecursivePointer => {
+                let recursive = recursive.expect("a recursive pointer outside a recursive union");
+
+                recursive.generate_in(Some(recursive), rng, size / 2)
+            }
+        }
+    }
+
+    /// Values that are "smaller" than the given one, simplest first.
+    pub(crate) fn shrink(&self, value: &Value) -> Vec<Value> {
+        self.shrink_in(None, value)
+    }
+
+    fn shrink_in(&self, recursive: Option<&Shape>, value: &Value) -> Vec<Value> {
+        match (&self.kind, value) {
+            (ShapeKind::Bool, Value::Bool(true)) => vec![Value::Bool(false)],
+            (ShapeKind::Int(width), Value::Int(n)) => {
+                let (min, max) = int_range(*width);
+
+                shrink_int(*n)
+                    .into_iter()
+                    .filter(|n| (min..=max).contains(n))
+                    .map(Value::Int)
+                    .collect()
+            }
+            (ShapeKind::Dec, Value::Int(n)) => {
+                let fraction = n % DEC_ONE;
+
+                if fraction != 0 {
+                    // first get rid of the digits after the point
+                    vec![Value::Int(0), Value::Int(n - fraction)]
+                } else {
+                    shrink_int(n / DEC_ONE)
+                        .into_iter()
+                        .map(|whole| Value::Int(whole * DEC_ONE))
+                        .collect()
+                }
+            }
+            (ShapeKind::Float(_), Value::Float(x)) => {
+                if *x == 0.0 || !x.is_finite() {
+                    Vec::new()
+                } else if x.fract() != 0.0 {
+                    vec![Value::Float(0.0), Value::Float(x.trunc())]
+                } else if x.abs() < 2f64.powi(53) {
+                    shrink_int(*x as i128)
+                        .into_iter()
+                        .map(|n| Value::Float(n as f64))
+                        .collect()
+                } else {
+                    vec![Value::Float(0.0), Value::Float(x / 2.0)]
+                }
+            }
+            (ShapeKind::Str, Value::Str(string)) => {
+                let chars: Vec<char> = string.chars().collect();
+
+                let mut candidates: Vec<Value> =
+                    shrink_sequence(&chars, |c| if *c == 'a' { Vec::new() } else { vec!['a'] })
+                        .into_iter()
+                        .map(|chars| Value::Str(chars.into_iter().collect()))
+                        .collect();
+
+                candidates.dedup();
+
+                candidates
+            }
+            (ShapeKind::List(element), Value::List(items)) => {
+                shrink_sequence(items, |item| element.shrink_in(recursive, item))
+                    .into_iter()
+                    .map(Value::List)
+                    .collect()
+            }
+            (ShapeKind::Struct(fields), Value::Struct(values)) => {
+                shrink_fields(fields, values, recursive)
+                    .into_iter()
+                    .map(Value::Struct)
+                    .collect()
+            }
+            (ShapeKind::TagUnion(union), Value::Tag(index, payload)) => {
+                let mut candidates = Vec::new();
+
+                // tags without a payload are the simplest
+                for (other, tag) in union.tags.iter().enumerate() {
+                    if tag.payload.is_empty()
+                        && other != *index
+                        && (other < *index || !payload.is_empty())
+                    {
+                        candidates.push(Value::Tag(other, Vec::new()));
+                    }
+                }
+
+                let recursive = if union.is_heap() {
+                    // then the values of the same type inside this one, e.g. the tail of a list
+                    for (shape, value) in union.tags[*index].payload.iter().zip(payload) {
+                        shape.collect_recursive(value, &mut candidates);
+                    }
+
+                    Some(self)
+                } else {
+                    recursive
+                };
+
+                let payloads = shrink_fields(&union.tags[*index].payload, payload, recursive);
+
+                candidates.extend(
+                    payloads
+                        .into_iter()
+                        .map(|payload| Value::Tag(*index, payload)),
+                );
+
+                candidates
+            }
+            (ShapeKind::RecursivePointer, _) => {
+                let recursive = recursive.expect("a recursive pointer outside a recursive union");
+
+                recursive.shrink_in(Some(recursive), value)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The values of the recursive tag union that this shape is a part of, in a value of this
+    /// shape.
+    fn collect_recursive(&self, value: &Value, found: &mut Vec<Value>) {
+        match (&self.kind, value) {
+            (ShapeKind::RecursivePointer, _) => found.push(value.clone()),
+            (ShapeKind::List(element), Value::List(items)) => {
+                for item in items {
+                    element.collect_recursive(item, found);
+                }
+            }
+            (ShapeKind::Struct(fields), Value::Struct(values)) => {
+                for (field, value) in fields.iter().zip(values) {
+                    field.collect_recursive(value, found);
+                }
+            }
+            (ShapeKind::TagUnion(union), Value::Tag(index, payload)) if !union.is_heap() => {
+                for (shape, value) in union.tags[*index].payload.iter().zip(payload) {
+                    shape.collect_recursive(value, found);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl TagUnion {
+    fn from_layout<'a, I>(
+        interner: &I,
+        union_layout: &UnionLayout<'a>,
+        target_info: TargetInfo,
+    ) -> Option<Self>
+    where
+        I: Interner<'a, Layout<'a>>,
+    {
+        let tag = |id, payload: &[Layout<'a>]| {
+            Some(Tag {
+                id,
+                payload: Shape::from_layouts(interner, payload, target_info)?,
+            })
+        };
+
+        let tag_id_slot = |offset| TagIdSlot {
+            offset,
+            size: union_layout.discriminant().stack_size(),
+        };
+
+        let heap = |nullable_id| {
+            let tag_id = if union_layout.stores_tag_id_in_pointer(target_info) {
+                HeapTagId::InPointer
+            } else {
+                match union_layout.tag_id_offset(interner, target_info) {
+                    Some(offset) => HeapTagId::InData(tag_id_slot(offset)),
+                    None => HeapTagId::Implicit,
+                }
+            };
+
+            let (size, _) = union_layout.data_size_and_alignment(interner, target_info);
+
+            TagUnionStorage::Heap {
+                tag_id,
+                nullable_id,
+                size,
+                alignment: union_layout.allocation_alignment_bytes(interner, target_info),
+            }
+        };
+
+        let (mut tags, storage) = match union_layout {
+            UnionLayout::NonRecursive(payloads) => {
+                let tags = payloads
+                    .iter()
+                    .zip(0..)
+                    .map(|(payload, id)| tag(id, payload))
+                    .collect::<Option<Vec<_>>>()?;
+
+                let offset = union_layout
+                    .tag_id_offset(interner, target_info)
+                    .unwrap_or(0);
+
+                let storage = TagUnionStorage::Stack {
+                    tag_id: tag_id_slot(offset),
+                };
+
+                (tags, storage)
+            }
+            UnionLayout::Recursive(payloads) => {
+                let tags = payloads
+                    .iter()
+                    .zip(0..)
+                    .map(|(payload, id)| tag(id, payload))
+                    .collect::<Option<Vec<_>>>()?;
+
+                (tags, heap(None))
+            }
+            UnionLayout::NonNullableUnwrapped(payload) => (vec![tag(0, payload)?], heap(None)),
+            UnionLayout::NullableWrapped {
+                nullable_id,
+                other_tags,
+            } => {
+                // the ids of the other tags skip the nullable id
+                let mut tags = other_tags
+                    .iter()
+                    .zip(0..)
+                    .map(|(payload, index)| {
+                        let id = if index < *nullable_id {
+                            index
+                        } else {
+                            index + 1
+                        };
+
+                        tag(id, payload)
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                tags.push(tag(*nullable_id, &[])?);
+
+                (tags, heap(Some(*nullable_id)))
+            }
+            UnionLayout::NullableUnwrapped {
+                nullable_id,
+                other_fields,
+            } => {
+                let nullable_id = *nullable_id as TagIdIntType;
+
+                let tags = vec![tag(1 - nullable_id, other_fields)?, tag(nullable_id, &[])?];
+
+                (tags, heap(Some(nullable_id)))
+            }
+        };
+
+        tags.sort_by_key(|tag| tag.id);
+
+        let union = Self { tags, storage };
+
+        // generating a value of a recursive union has to end with a tag that doesn't recurse
+        let can_end = union.tags.iter().any(|tag| !tag.refers_back());
+
+        if union.tags.is_empty() || (union.is_heap() && !can_end) {
+            None
+        } else {
+            Some(union)
+        }
+    }
+
+    fn is_heap(&self) -> bool {
+        matches!(self.storage, TagUnionStorage::Heap { .. })
+    }
+}
+
+impl Tag {
+    fn refers_back(&self) -> bool {
+        self.payload.iter().any(Shape::refers_back)
+    }
+}
+
+/// Smaller versions of the fields of a record or the payload of a tag, one field at a time.
+fn shrink_fields(fields: &[Shape], values: &[Value], recursive: Option<&Shape>) -> Vec<Vec<Value>> {
+    let mut candidates = Vec::new();
+
+    for (index, (field, value)) in fields.iter().zip(values).enumerate() {
+        for smaller in field.shrink_in(recursive, value) {
+            let mut values = values.to_vec();
+            values[index] = smaller;
+
+            candidates.push(values);
+        }
+    }
+
+    candidates
+}
+
+fn int_range(width: IntWidth) -> (i128, i128) {
+    use IntWidth::*;
+
+    match width {
+        U8 => (0, u8::MAX as i128),
+        U16 => (0, u16::MAX as i128),
+        U32 => (0, u32::MAX as i128),
+        U64 => (0, u64::MAX as i128),
+        // we only generate the U128s that fit in an I128
+        U128 => (0, i128::MAX),
+        I8 => (i8::MIN as i128, i8::MAX as i128),
+        I16 => (i16::MIN as i128, i16::MAX as i128),
+        I32 => (i32::MIN as i128, i32::MAX as i128),
+        I64 => (i64::MIN as i128, i64::MAX as i128),
+        I128 => (i128::MIN, i128::MAX),
+    }
+}
+
+fn generate_char(rng: &mut Rng) -> char {
+    const UNUSUAL: [char; 8] = ['\n', '\t', '"', '\\', 'é', 'ß', '中', '🦀'];
+
+    if rng.one_in(10) {
+        UNUSUAL[rng.below(UNUSUAL.len() as u64) as usize]
+    } else {
+        // printable ASCII
+        (b' ' + rng.below(95) as u8) as char
+    }
+}
+
+/// `0`, then numbers ever closer to `n`.
+fn shrink_int(n: i128) -> Vec<i128> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut candidates = vec![0];
+
+    if let Some(positive) = n.checked_neg().filter(|positive| *positive > 0) {
+        candidates.push(positive);
+    }
+
+    let mut distance = n / 2;
+
+    while distance != 0 {
+        candidates.push(n - distance);
+        distance /= 2;
+    }
+
+    if n > 0 {
+        candidates.push(n - 1);
+    } else {
+        candidates.push(n + 1);
+    }
+
+    candidates.dedup();
+    candidates.retain(|candidate| *candidate != n);
+
+    candidates
+}
+
+/// Shorter sequences (with chunks of elements removed), then ones with a smaller element.
+fn shrink_sequence<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    if items.is_empty() {
+        return candidates;
+    }
+
+    candidates.push(Vec::new());
+
+    let mut chunk = items.len() / 2;
+
+    while chunk > 0 {
+        let mut start = 0;
+
+        while start + chunk <= items.len() {
+            let mut shorter = items[..start].to_vec();
+            shorter.extend_from_slice(&items[start + chunk..]);
+
+            candidates.push(shorter);
+
+            start += chunk;
+        }
+
+        chunk /= 2;
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut items = items.to_vec();
+            items[index] = smaller;
+
+            candidates.push(items);
+        }
+    }
+
+    candidates
+}
+
+/// A generated value, for a [Shape].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Bool(bool),
+    /// Any integer, or a `Dec` (multiplied by 10^18, like in memory)
+    Int(i128),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    /// The fields in the order they are stored in memory, like in the [Shape]
+    Struct(Vec<Value>),
+    /// The index of the tag in the [Shape] (which is not always its tag id), and its payload
+    Tag(usize, Vec<Value>),
+}
+
+/// SplitMix64: tiny, and plenty random for generating test inputs.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next_u64() % n
+        }
+    }
+
+    fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    /// A number in `0.0..1.0`
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// What happened when a property was called with some arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CallOutcome {
+    Passed,
+    Failed,
+    Crashed(String),
+}
+
+/// Arguments that make a property fail.
+#[derive(Debug, Clone)]
+pub(crate) struct Counterexample {
+    pub arguments: Vec<Value>,
+    /// The message, if the property crashed rather than returning `Bool.false`
+    pub crash_message: Option<String>,
+}
+
+/// Calls the property with `runs` sets of generated arguments, which start out small and get
+/// bigger. Returns the (shrunk) arguments of the first call that fails, if any.
+pub(crate) fn find_counterexample<F>(
+    shapes: &[Shape],
+    runs: usize,
+    seed: u64,
+    mut call: F,
+) -> Option<Counterexample>
+where
+    F: FnMut(&[Value]) -> CallOutcome,
+{
+    let mut rng = Rng::new(seed);
+
+    for run in 0..runs {
+        // grow the size from 0 to 100 over the runs
+        let size = (run * 100 / runs.max(1)) as u32;
+
+        let arguments: Vec<Value> = shapes
+            .iter()
+            .map(|shape| shape.generate(&mut rng, size))
+            .collect();
+
+        let outcome = call(&arguments);
+
+        if outcome != CallOutcome::Passed {
+            let (arguments, outcome) = shrink_counterexample(shapes, arguments, outcome, &mut call);
+
+            return Some(Counterexample {
+                arguments,
+                crash_message: match outcome {
+                    CallOutcome::Crashed(message) => Some(message),
+                    _ => None,
+                },
+            });
+        }
+    }
+
+    None
+}
+
+/// Keeps replacing an argument by a smaller one for which the property still fails.
+fn shrink_counterexample<F>(
+    shapes: &[Shape],
+    mut arguments: Vec<Value>,
+    mut outcome: CallOutcome,
+    call: &mut F,
+) -> (Vec<Value>, CallOutcome)
+where
+    F: FnMut(&[Value]) -> CallOutcome,
+{
+    let mut attempts = 0;
+
+    'shrink: loop {
+        for (index, shape) in shapes.iter().enumerate() {
+            for smaller in shape.shrink(&arguments[index]) {
+                if attempts == MAX_SHRINK_ATTEMPTS {
+                    break 'shrink;
+                }
+
+                attempts += 1;
+
+                let mut candidate = arguments.clone();
+                candidate[index] = smaller;
+
+                let candidate_outcome = call(&candidate);
+
+                if candidate_outcome != CallOutcome::Passed {
+                    arguments = candidate;
+                    outcome = candidate_outcome;
+
+                    continue 'shrink;
+                }
+            }
+        }
+
+        break;
+    }
+
+    (arguments, outcome)
+}
+
+/// Writes values into a buffer the way Roc lays them out in memory. The bytes of strings and the
+/// elements of lists go after the values themselves, and are pointed to as `base + index`, where
+/// `index` is their position in the buffer.
+pub(crate) struct MemoryWriter<'b> {
+    bytes: &'b mut [u8],
+    base: usize,
+    next_free: usize,
+    /// Whether the values are written in the format `crate::get_values` reads
+    for_lookups: bool,
+    target_info: TargetInfo,
+}
+
+impl<'b> MemoryWriter<'b> {
+    /// For pointers that work in this process, `base` should be the address of `bytes`, which
+    /// needs to be 16-byte aligned.
+    pub(crate) fn new(bytes: &'b mut [u8], base: usize, target_info: TargetInfo) -> Self {
+        Self {
+            bytes,
+            base,
+            next_free: 0,
+            for_lookups: false,
+            target_info,
+        }
+    }
+
+    /// Lays out arguments the way a property's exposed wrapper expects them: one after the other,
+    /// each aligned to its own alignment, starting at `start`. Returns `None` if they don't fit.
+    pub(crate) fn write_arguments(
+        &mut self,
+        start: usize,
+        shapes: &[Shape],
+        values: &[Value],
+    ) -> Option<()> {
+        let offsets = self.argument_offsets(start, shapes);
+
+        self.write_at(&offsets, shapes, values)
+    }
+
+    /// Writes the values in the format `crate::get_values` reads: a table with the index of each
+    /// value at `table_start`, followed by the values. Returns `None` if they don't fit.
+    pub(crate) fn write_lookups(
+        &mut self,
+        table_start: usize,
+        shapes: &[Shape],
+        values: &[Value],
+    ) -> Option<()> {
+        self.for_lookups = true;
+
+        let offsets = self.argument_offsets(table_start + 8 * shapes.len(), shapes);
+
+        for (index, offset) in offsets.iter().enumerate() {
+            let table_entry = table_start + 8 * index;
+
+            self.bytes
+                .get_mut(table_entry..table_entry + 8)?
+                .copy_from_slice(&(*offset as u64).to_le_bytes());
+        }
+
+        self.write_at(&offsets, shapes, values)
+    }
+
+    fn argument_offsets(&self, start: usize, shapes: &[Shape]) -> Vec<usize> {
+        let mut offset = start;
+
+        shapes
+            .iter()
+            .map(|shape| {
+                offset = round_up_to_alignment(offset as u32, shape.alignment()) as usize;
+
+                let argument_offset = offset;
+                offset += shape.stack_size() as usize;
+
+                argument_offset
+            })
+            .collect()
+    }
+
+    fn write_at(&mut self, offsets: &[usize], shapes: &[Shape], values: &[Value]) -> Option<()> {
+        let end = offsets
+            .iter()
+            .zip(shapes)
+            .map(|(offset, shape)| offset + shape.stack_size() as usize)
+            .max()
+            .unwrap_or(0);
+
+        self.next_free = self.next_free.max(end);
+
+        for ((offset, shape), value) in offsets.iter().zip(shapes).zip(values) {
+            self.write_value(*offset, shape, value, None)?;
+        }
+
+        Some(())
+    }
+
+    /// `recursive` is the closest recursive tag union that the shape is a part of.
+    fn write_value(
+        &mut self,
+        at: usize,
+        shape: &Shape,
+        value: &Value,
+        recursive: Option<&Shape>,
+    ) -> Option<()> {
+        match (&shape.kind, value) {
+            (ShapeKind::Bool, Value::Bool(b)) => self.write_bytes(at, &[*b as u8]),
+            (ShapeKind::Int(width), Value::Int(n)) => {
+                let size = width.stack_size() as usize;
+
+                self.write_bytes(at, &n.to_le_bytes()[..size])
+            }
+            (ShapeKind::Dec, Value::Int(n)) => self.write_bytes(at, &n.to_le_bytes()),
+            (ShapeKind::Float(FloatWidth::F32), Value::Float(x)) => {
+                self.write_bytes(at, &(*x as f32).to_le_bytes())
+            }
+            (ShapeKind::Float(_), Value::Float(x)) => self.write_bytes(at, &x.to_le_bytes()),
+            (ShapeKind::Str, Value::Str(string)) => {
+                let data = self.allocate(string.len(), 1)?;
+                self.write_bytes(data, string.as_bytes())?;
+
+                self.write_header(at, Some(data), string.len())
+            }
+            (ShapeKind::List(element), Value::List(items)) => {
+                if items.is_empty() {
+                    return self.write_header(at, None, 0);
+                }
+
+                let element_size = element.stack_size() as usize;
+                let element_alignment = element.alignment() as usize;
+
+                let data = self.allocate(items.len() * element_size, element_alignment)?;
+
+                for (index, item) in items.iter().enumerate() {
+                    self.write_value(data + index * element_size, element, item, recursive)?;
+                }
+
+                self.write_header(at, Some(data), items.len())
+            }
+            (ShapeKind::Struct(fields), Value::Struct(values)) => {
+                self.write_fields(at, fields, values, recursive)
+            }
+            (ShapeKind::TagUnion(union), Value::Tag(index, payload)) => {
+                let tag = union.tags.get(*index)?;
+
+                match union.storage {
+                    TagUnionStorage::Stack { tag_id } => {
+                        self.write_fields(at, &tag.payload, payload, recursive)?;
+                        self.write_tag_id(at, tag_id, tag.id)
+                    }
+                    TagUnionStorage::Heap {
+                        tag_id,
+                        nullable_id,
+                        size,
+                        alignment,
+                    } => {
+                        if nullable_id == Some(tag.id) {
+                            return self.write_usize(at, 0);
+                        }
+
+                        let data = self.allocate(size as usize, alignment as usize)?;
+                        self.write_fields(data, &tag.payload, payload, Some(shape))?;
+
+                        match tag_id {
+                            HeapTagId::Implicit => self.write_usize(at, self.base + data),
+                            HeapTagId::InData(slot) => {
+                                self.write_tag_id(data, slot, tag.id)?;
+                                self.write_usize(at, self.base + data)
+                            }
+                            HeapTagId::InPointer if self.for_lookups => {
+                                // the format `ExpectMemory::deref_pointer_with_tag_id` reads
+                                self.write_bytes(at, &(tag.id as u32).to_le_bytes())?;
+                                self.write_bytes(at + 4, &((self.base + data) as u32).to_le_bytes())
+                            }
+                            HeapTagId::InPointer => {
+                                self.write_usize(at, (self.base + data) | tag.id as usize)
+                            }
+                        }
+                    }
+                }
+            }
+            (ShapeKind::RecursivePointer, _) => {
+                let recursive = recursive?;
+
+                self.write_value(at, recursive, value, Some(recursive))
+            }
+            _ => None,
+        }
+    }
+
+    /// The fields of a record or the payload of a tag, one after the other
+    fn write_fields(
+        &mut self,
+        at: usize,
+        fields: &[Shape],
+        values: &[Value],
+        recursive: Option<&Shape>,
+    ) -> Option<()> {
+        let mut offset = at;
+
+        for (field, value) in fields.iter().zip(values) {
+            self.write_value(offset, field, value, recursive)?;
+
+            offset += field.stack_size() as usize;
+        }
+
+        Some(())
+    }
+
+    fn write_tag_id(&mut self, at: usize, slot: TagIdSlot, id: TagIdIntType) -> Option<()> {
+        let at = at + slot.offset as usize;
+
+        self.write_bytes(at, &id.to_le_bytes()[..slot.size as usize])
+    }
+
+    /// The pointer, length and capacity of a `Str` or `List`
+    fn write_header(&mut self, at: usize, data: Option<usize>, length: usize) -> Option<()> {
+        let pointer = data.map_or(0, |index| self.base + index);
+
+        self.write_usize(at, pointer)?;
+        self.write_usize(at + self.target_info.ptr_size(), length)?;
+        self.write_usize(at + 2 * self.target_info.ptr_size(), length)
+    }
+
+    /// Room for heap data, preceded by a refcount. The refcount says the data lives for the whole
+    /// program, so Roc never frees it, and makes a copy before changing it.
+    fn allocate(&mut self, size: usize, alignment: usize) -> Option<usize> {
+        let ptr_size = self.target_info.ptr_size();
+        let alignment = alignment.max(ptr_size);
+
+        let data =
+            round_up_to_alignment((self.next_free + ptr_size) as u32, alignment as u32) as usize;
+
+        self.write_usize(data - ptr_size, 0)?;
+
+        self.next_free = data + size;
+
+        if self.next_free <= self.bytes.len() {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
+    fn write_usize(&mut self, at: usize, value: usize) -> Option<()> {
+        let size = self.target_info.ptr_size();
+
+        self.write_bytes(at, &(value as u64).to_le_bytes()[..size])
+    }
+
+    fn write_bytes(&mut self, at: usize, bytes: &[u8]) -> Option<()> {
+        self.bytes
+            .get_mut(at..at + bytes.len())?
+            .copy_from_slice(bytes);
+
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use roc_mono::layout::{FieldOrderHash, STLayoutInterner};
+
+    const TARGET_INFO: TargetInfo = TargetInfo::default_x86_64();
+
+    fn shape(layout: Layout) -> Shape {
+        let interner = STLayoutInterner::with_capacity(4);
+
+        Shape::from_layout(&interner, &layout, TARGET_INFO).unwrap()
+    }
+
+    fn struct_layout<'a>(field_layouts: &'a [Layout<'a>]) -> Layout<'a> {
+        Layout::Struct {
+            field_order_hash: FieldOrderHash::from_ordered_fields(&[]),
+            field_layouts,
+        }
+    }
+
+    /// `ConsList : [Cons I64 ConsList, Nil]`
+    fn cons_list() -> Shape {
+        shape(Layout::Union(UnionLayout::NullableUnwrapped {
+            nullable_id: true,
+            other_fields: &[
+                Layout::Builtin(Builtin::Int(IntWidth::I64)),
+                Layout::RecursivePointer,
+            ],
+        }))
+    }
+
+    fn cons(head: i128, tail: Value) -> Value {
+        Value::Tag(0, vec![Value::Int(head), tail])
+    }
+
+    fn nil() -> Value {
+        Value::Tag(1, Vec::new())
+    }
+
+    fn counterexample(shapes: &[Shape], property: impl Fn(&[Value]) -> bool) -> Vec<Value> {
+        let call = |arguments: &[Value]| {
+            if property(arguments) {
+                CallOutcome::Passed
+            } else {
+                CallOutcome::Failed
+            }
+        };
+
+        find_counterexample(shapes, 100, 42, call)
+            .expect("the property should fail")
+            .arguments
+    }
+
+    #[test]
+    fn shrinks_integers_to_the_boundary() {
+        let arguments = counterexample(
+            &[shape(Layout::i64())],
+            |arguments| matches!(arguments[0], Value::Int(n) if n < 10),
+        );
+
+        assert_eq!(arguments, [Value::Int(10)]);
+    }
+
+    #[test]
+    fn shrinks_lists_to_the_shortest() {
+        let element = Layout::u8();
+        let shape = shape(Layout::Builtin(Builtin::List(&element)));
+
+        let arguments = counterexample(
+            &[shape],
+            |arguments| matches!(&arguments[0], Value::List(items) if items.len() < 3),
+        );
+
+        assert_eq!(
+            arguments,
+            [Value::List(vec![
+                Value::Int(0),
+                Value::Int(0),
+                Value::Int(0)
+            ])]
+        );
+    }
+
+    #[test]
+    fn generates_integers_in_range() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            match shape(Layout::u8()).generate(&mut rng, 100) {
+                Value::Int(n) => assert!((0..=255).contains(&n)),
+                other => panic!("generated {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_arguments() {
+        let dec = Layout::Builtin(Builtin::Decimal);
+        let fields = [
+            Layout::Builtin(Builtin::Str),
+            Layout::Builtin(Builtin::List(&dec)),
+        ];
+        let shape = shape(struct_layout(&fields));
+
+        let generate = |seed| {
+            let mut rng = Rng::new(seed);
+
+            (0..10)
+                .map(|size| shape.generate(&mut rng, size))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+    }
+
+    #[test]
+    fn struct_fields_one_after_the_other() {
+        // { a : U8, b : I64, c : Str } is stored as b, c, a
+        let fields = [Layout::i64(), Layout::Builtin(Builtin::Str), Layout::u8()];
+        let shape = shape(struct_layout(&fields));
+
+        let value = Value::Struct(vec![
+            Value::Int(2),
+            Value::Str(String::new()),
+            Value::Int(1),
+        ]);
+
+        let mut bytes = [0xAAu8; 128];
+        let mut writer = MemoryWriter::new(&mut bytes, 0, TARGET_INFO);
+
+        writer
+            .write_arguments(0, std::slice::from_ref(&shape), &[value])
+            .unwrap();
+
+        assert_eq!(shape.stack_size(), 40);
+        assert_eq!(bytes[0..8], 2u64.to_le_bytes());
+        // the empty string's length and capacity
+        assert_eq!(bytes[16..32], [0; 16]);
+        assert_eq!(bytes[32], 1);
+    }
+
+    #[test]
+    fn tag_id_after_payload() {
+        // [A U8, B I64]
+        let shape = shape(Layout::Union(UnionLayout::NonRecursive(&[
+            &[Layout::Builtin(Builtin::Int(IntWidth::U8))],
+            &[Layout::Builtin(Builtin::Int(IntWidth::I64))],
+        ])));
+
+        let mut bytes = [0xAAu8; 64];
+        let mut writer = MemoryWriter::new(&mut bytes, 0, TARGET_INFO);
+
+        writer
+            .write_arguments(
+                0,
+                std::slice::from_ref(&shape),
+                &[Value::Tag(1, vec![Value::Int(5)])],
+            )
+            .unwrap();
+
+        assert_eq!(shape.stack_size(), 16);
+        assert_eq!(bytes[0..8], 5u64.to_le_bytes());
+        assert_eq!(bytes[8], 1);
+    }
+
+    #[test]
+    fn shrinks_recursive_tag_unions() {
+        fn length(value: &Value) -> usize {
+            match value {
+                Value::Tag(0, payload) => 1 + length(&payload[1]),
+                _ => 0,
+            }
+        }
+
+        let arguments = counterexample(&[cons_list()], |arguments| length(&arguments[0]) < 3);
+
+        assert_eq!(arguments, [cons(0, cons(0, cons(0, nil())))]);
+    }
+
+    #[test]
+    fn recursive_tag_unions_on_the_heap() {
+        let shape = cons_list();
+
+        let mut bytes = [0xAAu8; 128];
+        let mut writer = MemoryWriter::new(&mut bytes, 0, TARGET_INFO);
+
+        writer
+            .write_arguments(0, std::slice::from_ref(&shape), &[cons(1, nil())])
+            .unwrap();
+
+        let data = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+
+        // the refcount, the head, and Nil as a null pointer
+        assert_eq!(bytes[data - 8..data], 0u64.to_le_bytes());
+        assert_eq!(bytes[data..data + 8], 1u64.to_le_bytes());
+        assert_eq!(bytes[data + 8..data + 16], 0u64.to_le_bytes());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{os::unix::process::parent_id, sync::Arc};
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
@@ -7,20 +11,31 @@ use roc_build::link::llvm_module_to_dylib;
 use roc_can::expr::ExpectLookup;
 use roc_collections::{MutSet, VecMap};
 use roc_gen_llvm::{
-    llvm::{build::LlvmBackendMode, externs::add_default_roc_externs},
+    llvm::{
+        build::LlvmBackendMode,
+        coverage::{Coverage, CoveragePoint},
+        externs::add_default_roc_externs,
+    },
     run_roc::RocCallResult,
     run_roc_dylib,
 };
 use roc_intern::{GlobalInterner, SingleThreadedInterner};
 use roc_load::{EntryPoint, Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_mono::{ir::OptLevel, layout::Layout};
+use roc_mono::{
+    ir::{OptLevel, ProcLayout},
+    layout::Layout,
+};
 use roc_region::all::Region;
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::TargetInfo;
 use roc_types::subs::{Subs, Variable};
 use target_lexicon::Triple;
 
+use crate::property::{
+    find_counterexample, CallOutcome, Counterexample, MemoryWriter, Shape, Value,
+};
+
 pub(crate) struct ExpectMemory<'a> {
     ptr: *mut u8,
     length: usize,
@@ -46,25 +61,25 @@ impl<'a> ExpectMemory<'a> {
         Self::mmap_help(cstring, libc::O_RDWR | libc::O_CREAT)
     }
 
-    fn reuse_mmap(&mut self) -> Option<Self> {
-        let shm_name = self.shm_name.as_ref()?.clone();
-        Some(Self::mmap_help(shm_name, libc::O_RDWR))
-    }
-
     fn mmap_help(cstring: std::ffi::CString, shm_flags: i32) -> Self {
         let ptr = unsafe {
             let shared_fd = libc::shm_open(cstring.as_ptr().cast(), shm_flags, 0o666);
 
             libc::ftruncate(shared_fd, Self::SHM_SIZE as _);
 
-            libc::mmap(
+            let ptr = libc::mmap(
                 std::ptr::null_mut(),
                 Self::SHM_SIZE,
                 libc::PROT_WRITE | libc::PROT_READ,
                 libc::MAP_SHARED,
                 shared_fd,
                 0,
-            )
+            );
+
+            // the mapping stays valid after the file descriptor is closed
+            libc::close(shared_fd);
+
+            ptr
         };
 
         Self {
@@ -80,8 +95,206 @@ impl<'a> ExpectMemory<'a> {
         let mut result = RocCallResult::default();
         unsafe { set_shared_buffer((self.ptr, self.length), &mut result) };
     }
+
+    /// A worker that caught a `roc_panic` leaves the message where the failures would otherwise
+    /// go: its length as a u64, followed by the (possibly truncated) UTF-8 bytes.
+    fn write_panic_message(&mut self, message: &str) {
+        let capacity = self.length - ExpectSequence::START_OFFSET - 8;
+        let bytes = &message.as_bytes()[..message.len().min(capacity)];
+
+        unsafe {
+            let start = self.ptr.add(ExpectSequence::START_OFFSET);
+            std::ptr::write_unaligned(start.cast::<u64>(), bytes.len() as u64);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), start.add(8), bytes.len());
+        }
+    }
+
+    fn read_panic_message(&self) -> String {
+        let capacity = self.length - ExpectSequence::START_OFFSET - 8;
+
+        let bytes = unsafe {
+            let start = self.ptr.add(ExpectSequence::START_OFFSET);
+            let length = std::ptr::read_unaligned(start.cast::<u64>()) as usize;
+
+            std::slice::from_raw_parts(start.add(8), length.min(capacity))
+        };
+
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+
+    /// Where a property's failing arguments go, in the format `crate::get_values` reads.
+    const COUNTEREXAMPLE_TABLE_OFFSET: usize =
+        ExpectSequence::START_OFFSET + 16 + Self::COUNTEREXAMPLE_MESSAGE_CAPACITY;
+
+    const COUNTEREXAMPLE_MESSAGE_CAPACITY: usize = 128;
+
+    /// A worker whose property failed leaves the arguments it failed for where the failures
+    /// would otherwise go: a u64 that says whether the arguments fit, the length of the crash
+    /// message (if any) as a u64, the (possibly truncated) message, and then the arguments.
+    fn write_counterexample(
+        &mut self,
+        counterexample: &Counterexample,
+        shapes: &[Shape],
+        target_info: TargetInfo,
+    ) {
+        let bytes = unsafe { std::slice::from_raw_parts_mut(self.ptr, self.length) };
+
+        let mut writer = MemoryWriter::new(bytes, 0, target_info);
+        let fits = writer
+            .write_lookups(
+                Self::COUNTEREXAMPLE_TABLE_OFFSET,
+                shapes,
+                &counterexample.arguments,
+            )
+            .is_some();
+
+        let message = counterexample.crash_message.as_deref().unwrap_or_default();
+        let message =
+            &message.as_bytes()[..message.len().min(Self::COUNTEREXAMPLE_MESSAGE_CAPACITY)];
+
+        unsafe {
+            let start = self.ptr.add(ExpectSequence::START_OFFSET);
+            std::ptr::write_unaligned(start.cast::<u64>(), fits as u64);
+            std::ptr::write_unaligned(start.add(8).cast::<u64>(), message.len() as u64);
+            std::ptr::copy_nonoverlapping(message.as_ptr(), start.add(16), message.len());
+        }
+    }
+}
+
+/// The arguments a property failed for, as a worker left them in the shared memory.
+struct PropertyFailure {
+    /// Whether the arguments fit in the shared memory
+    fits: bool,
+    crash_message: Option<String>,
+}
+
+impl PropertyFailure {
+    fn read(shared_memory_ptr: *const u8) -> Self {
+        unsafe {
+            let start = shared_memory_ptr.add(ExpectSequence::START_OFFSET);
+            let fits = std::ptr::read_unaligned(start.cast::<u64>()) != 0;
+            let length = std::ptr::read_unaligned(start.add(8).cast::<u64>()) as usize;
+            let length = length.min(ExpectMemory::COUNTEREXAMPLE_MESSAGE_CAPACITY);
+
+            let crash_message = if length == 0 {
+                None
+            } else {
+                let bytes = std::slice::from_raw_parts(start.add(16), length);
+
+                Some(String::from_utf8_lossy(bytes).into_owned())
+            };
+
+            Self {
+                fits,
+                crash_message,
+            }
+        }
+    }
+}
+
+impl<'a> Drop for ExpectMemory<'a> {
+    fn drop(&mut self) {
+        if let Some(shm_name) = &self.shm_name {
+            unsafe {
+                libc::munmap(self.ptr.cast(), self.length);
+                libc::shm_unlink(shm_name.as_ptr());
+            }
+        }
+    }
+}
+
+/// How running one top-level expect went.
+#[derive(Debug, Clone)]
+pub struct ExpectResult {
+    pub symbol: Symbol,
+    pub passed: bool,
+    pub duration: Duration,
+    /// The failure and crash reports for this expect, as they were written to the writer.
+    pub output: String,
+}
+
+/// How `run_expects` runs the top-level expects.
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    /// How many expects run at the same time, each in a worker process of its own.
+    pub jobs: usize,
+    /// Expects that run for longer than this are stopped and reported as failures.
+    pub timeout: Option<Duration>,
+    /// How many sets of generated arguments each property is called with.
+    pub property_runs: usize,
+    /// Where generating property arguments starts from. The same seed generates the same
+    /// arguments.
+    pub seed: u64,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            timeout: None,
+            property_runs: 100,
+            seed: 0,
+        }
+    }
+}
+
+/// The exit code of a worker whose expect called `roc_panic`.
+const PANIC_EXIT_CODE: i32 = 2;
+
+/// The exit code of a worker whose property failed for some arguments.
+const PROPERTY_FAILED_EXIT_CODE: i32 = 3;
+
+/// A top-level expect, or a property together with the shapes of its arguments (or the reason
+/// it can't be run).
+enum Test<'a> {
+    Expect(ToplevelExpect<'a>),
+    Property(ToplevelProperty<'a>, Result<Vec<Shape>, String>),
+}
+
+impl<'a> Test<'a> {
+    fn symbol(&self) -> Symbol {
+        self.as_expect().symbol
+    }
+
+    /// Crashes and inline expect failures are reported the same way for properties and expects.
+    fn as_expect(&self) -> ToplevelExpect<'a> {
+        match self {
+            Test::Expect(expect) => *expect,
+            Test::Property(property, _) => ToplevelExpect {
+                name: property.name,
+                symbol: property.symbol,
+                region: property.region,
+            },
+        }
+    }
+}
+
+/// How a worker process ended.
+#[derive(Debug, Clone, Copy)]
+enum WorkerOutcome {
+    Exited(i32),
+    /// e.g. a segfault or a stack overflow
+    Signaled(i32),
+    TimedOut(Duration),
+}
+
+/// An expect that is running in a worker process.
+struct RunningExpect {
+    index: usize,
+    pid: libc::pid_t,
+    start: Instant,
 }
 
+struct WorkerSlot<'a> {
+    memory: ExpectMemory<'a>,
+    running: Option<RunningExpect>,
+}
+
+/// Runs every top-level expect in a forked worker process, so that an expect that crashes or
+/// never finishes is reported as a failure rather than taking `roc test` down with it.
+///
+/// The reports are written in the same order as the expects, regardless of which ones finish
+/// first.
 #[allow(clippy::too_many_arguments)]
 pub fn run_expects<'a, W: std::io::Write>(
     writer: &mut W,
@@ -91,24 +304,324 @@ pub fn run_expects<'a, W: std::io::Write>(
     layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
-    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
-    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+    expects: ExpectFunctions<'a>,
+    options: RunOptions,
+) -> std::io::Result<Vec<ExpectResult>> {
+    let tests = collect_tests(layout_interner, interns, &expects);
+
+    let jobs = options.jobs.clamp(1, tests.len().max(1));
+    let mut slots: Vec<WorkerSlot> = (0..jobs)
+        .map(|slot| {
+            let shm_name = format!("/roc_expect_buffer_{}_{}", std::process::id(), slot);
+
+            WorkerSlot {
+                memory: ExpectMemory::create_or_reuse_mmap(&shm_name),
+                running: None,
+            }
+        })
+        .collect();
+
+    let mut results: Vec<Option<ExpectResult>> = vec![None; tests.len()];
+    let mut next_to_start = 0;
+    let mut next_to_write = 0;
+
+    while next_to_write < tests.len() {
+        for slot in slots.iter_mut() {
+            while slot.running.is_none() && next_to_start < tests.len() {
+                let test = &tests[next_to_start];
+
+                if let Test::Property(property, Err(problem)) = test {
+                    // there is nothing to run
+                    let mut output = Vec::new();
+                    report_property_problem(
+                        &mut output,
+                        render_target,
+                        arena,
+                        interns,
+                        expectations,
+                        *property,
+                        problem,
+                    )?;
+
+                    results[next_to_start] = Some(ExpectResult {
+                        symbol: property.symbol,
+                        passed: false,
+                        duration: Duration::ZERO,
+                        output: String::from_utf8_lossy(&output).into_owned(),
+                    });
+                } else {
+                    let pid = spawn_worker(lib, &mut slot.memory, test, options)?;
 
-    run_expects_with_memory(
-        writer,
-        render_target,
-        arena,
-        interns,
-        layout_interner,
-        lib,
-        expectations,
-        expects,
-        &mut memory,
-    )
+                    slot.running = Some(RunningExpect {
+                        index: next_to_start,
+                        pid,
+                        start: Instant::now(),
+                    });
+                }
+
+                next_to_start += 1;
+            }
+        }
+
+        let mut any_finished = false;
+
+        for slot in slots.iter_mut() {
+            let outcome = match &slot.running {
+                Some(running) => poll_worker(running, options.timeout)?,
+                None => None,
+            };
+
+            if let (Some(outcome), Some(running)) = (outcome, slot.running.take()) {
+                let test = &tests[running.index];
+                let duration = running.start.elapsed();
+
+                let what = match test {
+                    Test::Expect(_) => "expectation",
+                    Test::Property(..) => "property",
+                };
+
+                let crash_message = match outcome {
+                    WorkerOutcome::Exited(0) | WorkerOutcome::Exited(PROPERTY_FAILED_EXIT_CODE) => {
+                        None
+                    }
+                    WorkerOutcome::Exited(PANIC_EXIT_CODE) => {
+                        Some(slot.memory.read_panic_message())
+                    }
+                    WorkerOutcome::Exited(code) => Some(format!(
+                        "The process running this {} exited with code {}.",
+                        what, code
+                    )),
+                    WorkerOutcome::Signaled(signal) => Some(format!(
+                        "The process running this {} was killed by signal {} ({}).",
+                        what,
+                        signal,
+                        signal_name(signal)
+                    )),
+                    WorkerOutcome::TimedOut(timeout) => Some(format!(
+                        "This {} did not finish within {} ms, so it was stopped.",
+                        what,
+                        timeout.as_millis()
+                    )),
+                };
+
+                let mut output = Vec::new();
+                let passed = match (test, outcome) {
+                    (
+                        Test::Property(property, Ok(_)),
+                        WorkerOutcome::Exited(PROPERTY_FAILED_EXIT_CODE),
+                    ) => {
+                        report_property_failure(
+                            &mut output,
+                            render_target,
+                            arena,
+                            interns,
+                            layout_interner,
+                            expectations,
+                            slot.memory.ptr,
+                            *property,
+                            options.seed,
+                        )?;
+
+                        false
+                    }
+                    _ => report_expect(
+                        &mut output,
+                        render_target,
+                        arena,
+                        interns,
+                        layout_interner,
+                        expectations,
+                        slot.memory.ptr,
+                        test.as_expect(),
+                        crash_message,
+                    )?,
+                };
+
+                results[running.index] = Some(ExpectResult {
+                    symbol: test.symbol(),
+                    passed,
+                    duration,
+                    output: String::from_utf8_lossy(&output).into_owned(),
+                });
+
+                any_finished = true;
+            }
+        }
+
+        // Write the reports in order, as soon as all the expects before them are done.
+        while let Some(Some(result)) = results.get(next_to_write) {
+            writer.write_all(result.output.as_bytes())?;
+            next_to_write += 1;
+        }
+
+        if !any_finished && next_to_write < tests.len() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Collects the expects and the properties in the order they run, and works out how to generate
+/// arguments for each property.
+fn collect_tests<'a>(
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    interns: &Interns,
+    expects: &ExpectFunctions<'a>,
+) -> Vec<Test<'a>> {
+    let expect_tests = expects
+        .fx
+        .iter()
+        .chain(expects.pure.iter())
+        .map(|expect| Test::Expect(*expect));
+
+    let property_tests = expects.properties.iter().map(|property| {
+        let shapes = property_shapes(layout_interner, interns, property);
+
+        Test::Property(*property, shapes)
+    });
+
+    expect_tests.chain(property_tests).collect()
+}
+
+/// The shapes of the property's arguments, or why we can't generate values for them.
+fn property_shapes<'a>(
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    interns: &Interns,
+    property: &ToplevelProperty<'a>,
+) -> Result<Vec<Shape>, String> {
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
+
+    let interner = layout_interner.fork();
+
+    property
+        .argument_names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            // Values made up from an opaque type's representation could be ones its module never
+            // builds, like an empty list for a type of non-empty lists.
+            if let Some(Some(opaque)) = property.argument_opaques.get(index) {
+                return Err(format!(
+                    "I can't generate values for {} because its type contains the opaque type \
+                    {}. Only the module of an opaque type knows which values are valid ones, so \
+                    I don't make up values of opaque types.",
+                    name,
+                    opaque.as_str(interns)
+                ));
+            }
+
+            // the arguments have no layouts when the property could not be specialized
+            property
+                .arguments
+                .get(index)
+                .and_then(|layout| Shape::from_layout(&interner, layout, target_info))
+                .ok_or_else(|| {
+                    format!(
+                        "I don't know how to generate values for {} yet. I can generate Bools, \
+                        numbers, Strs, and Lists, records and tag unions of those.",
+                        name
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Forks a worker process that runs the expect or property and then exits. The failures it finds
+/// end up in the shared memory, where the parent can read them once the worker has exited.
+fn spawn_worker(
+    lib: &libloading::Library,
+    memory: &mut ExpectMemory,
+    test: &Test<'_>,
+    options: RunOptions,
+) -> std::io::Result<libc::pid_t> {
+    match unsafe { libc::fork() } {
+        0 => unsafe {
+            // we are the child
+
+            use roc_gen_llvm::try_run_jit_function;
+
+            memory.set_shared_buffer(lib);
+
+            let exit_code = match test {
+                Test::Expect(expect) => {
+                    let _sequence = ExpectSequence::new(memory.ptr);
+
+                    let result: Result<(), String> =
+                        try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+
+                    match result {
+                        Ok(()) => 0,
+                        Err(roc_panic_message) => {
+                            memory.write_panic_message(&roc_panic_message);
+
+                            PANIC_EXIT_CODE
+                        }
+                    }
+                }
+                Test::Property(property, Ok(shapes)) => {
+                    if run_property(lib, memory, *property, shapes, options) {
+                        0
+                    } else {
+                        PROPERTY_FAILED_EXIT_CODE
+                    }
+                }
+                Test::Property(_, Err(_)) => unreachable!("unsupported properties are not run"),
+            };
+
+            // Exit without running destructors or atexit handlers: those belong to the parent,
+            // and e.g. dropping the shared memory would unlink it.
+            libc::_exit(exit_code)
+        },
+        -1 => Err(io::Error::last_os_error()),
+        pid => Ok(pid),
+    }
+}
+
+/// Checks on a worker without blocking, and stops it if it has run for longer than the timeout.
+fn poll_worker(
+    running: &RunningExpect,
+    timeout: Option<Duration>,
+) -> std::io::Result<Option<WorkerOutcome>> {
+    let mut status = 0;
+
+    match unsafe { libc::waitpid(running.pid, &mut status, libc::WNOHANG) } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => match timeout {
+            Some(timeout) if running.start.elapsed() > timeout => {
+                unsafe {
+                    libc::kill(running.pid, libc::SIGKILL);
+                    libc::waitpid(running.pid, &mut status, 0);
+                }
+
+                Ok(Some(WorkerOutcome::TimedOut(timeout)))
+            }
+            _ => Ok(None),
+        },
+        _ => {
+            if libc::WIFSIGNALED(status) {
+                Ok(Some(WorkerOutcome::Signaled(libc::WTERMSIG(status))))
+            } else {
+                Ok(Some(WorkerOutcome::Exited(libc::WEXITSTATUS(status))))
+            }
+        }
+    }
 }
 
+fn signal_name(signal: i32) -> String {
+    let description = unsafe { libc::strsignal(signal) };
+
+    if description.is_null() {
+        String::from("unknown signal")
+    } else {
+        unsafe { std::ffi::CStr::from_ptr(description) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Runs the expects one after the other in this process, which means a crash ends the run.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     writer: &mut W,
@@ -118,53 +631,87 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    expects: ExpectFunctions<'_>,
+    expects: ExpectFunctions<'a>,
     memory: &mut ExpectMemory,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+    options: RunOptions,
+) -> std::io::Result<Vec<ExpectResult>> {
+    let tests = collect_tests(layout_interner, interns, &expects);
+    let mut results = Vec::with_capacity(tests.len());
 
-    for expect in expects.fx {
-        let result = run_expect_fx(
-            writer,
-            render_target,
-            arena,
-            interns,
-            layout_interner,
-            lib,
-            expectations,
-            memory,
-            expect,
-        )?;
+    memory.set_shared_buffer(lib);
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
-    }
+    for test in tests.iter() {
+        let mut output = Vec::new();
+        let start = Instant::now();
+        let passed = match test {
+            Test::Expect(expect) => run_expect_pure(
+                &mut output,
+                render_target,
+                arena,
+                interns,
+                layout_interner,
+                lib,
+                expectations,
+                memory,
+                *expect,
+            )?,
+            Test::Property(property, Ok(shapes)) => {
+                if run_property(lib, memory, *property, shapes, options) {
+                    report_expect(
+                        &mut output,
+                        render_target,
+                        arena,
+                        interns,
+                        layout_interner,
+                        expectations,
+                        memory.ptr,
+                        test.as_expect(),
+                        None,
+                    )?
+                } else {
+                    report_property_failure(
+                        &mut output,
+                        render_target,
+                        arena,
+                        interns,
+                        layout_interner,
+                        expectations,
+                        memory.ptr,
+                        *property,
+                        options.seed,
+                    )?;
+
+                    false
+                }
+            }
+            Test::Property(property, Err(problem)) => {
+                report_property_problem(
+                    &mut output,
+                    render_target,
+                    arena,
+                    interns,
+                    expectations,
+                    *property,
+                    problem,
+                )?;
 
-    memory.set_shared_buffer(lib);
+                false
+            }
+        };
 
-    for expect in expects.pure {
-        let result = run_expect_pure(
-            writer,
-            render_target,
-            arena,
-            interns,
-            layout_interner,
-            lib,
-            expectations,
-            memory,
-            expect,
-        )?;
+        let duration = start.elapsed();
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        writer.write_all(&output)?;
+
+        results.push(ExpectResult {
+            symbol: test.symbol(),
+            passed,
+            duration,
+            output: String::from_utf8_lossy(&output).into_owned(),
+        });
     }
 
-    Ok((failed, passed))
+    Ok(results)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -181,148 +728,232 @@ fn run_expect_pure<'a, W: std::io::Write>(
 ) -> std::io::Result<bool> {
     use roc_gen_llvm::try_run_jit_function;
 
-    let sequence = ExpectSequence::new(shared_memory.ptr.cast());
+    let _sequence = ExpectSequence::new(shared_memory.ptr.cast());
 
     let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
 
-    let shared_memory_ptr: *const u8 = shared_memory.ptr.cast();
+    report_expect(
+        writer,
+        render_target,
+        arena,
+        interns,
+        layout_interner,
+        expectations,
+        shared_memory.ptr,
+        expect,
+        result.err(),
+    )
+}
+
+/// Renders the failures of an expect that ran to the end, or else the reason it crashed.
+/// Returns whether the expect passed.
+#[allow(clippy::too_many_arguments)]
+fn report_expect<'a, W: std::io::Write>(
+    writer: &mut W,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    shared_memory_ptr: *const u8,
+    expect: ToplevelExpect<'_>,
+    crash_message: Option<String>,
+) -> std::io::Result<bool> {
+    let sequence = ExpectSequence {
+        ptr: shared_memory_ptr,
+    };
 
-    if result.is_err() || sequence.count_failures() > 0 {
-        let module_id = expect.symbol.module_id();
-        let data = expectations.get_mut(&module_id).unwrap();
+    if crash_message.is_none() && sequence.count_failures() == 0 {
+        return Ok(true);
+    }
 
-        let path = &data.path;
-        let filename = data.path.to_owned();
-        let source = std::fs::read_to_string(path).unwrap();
+    let module_id = expect.symbol.module_id();
+    let data = expectations.get_mut(&module_id).unwrap();
 
-        let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
+    let path = &data.path;
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(path).unwrap();
 
-        if let Err(roc_panic_message) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
-        } else {
-            let mut offset = ExpectSequence::START_OFFSET;
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
-            for _ in 0..sequence.count_failures() {
-                offset += render_expect_failure(
-                    writer,
-                    &renderer,
-                    arena,
-                    Some(expect),
-                    expectations,
-                    interns,
-                    layout_interner,
-                    shared_memory_ptr,
-                    offset,
-                )?;
-            }
+    if let Some(crash_message) = crash_message {
+        renderer.render_panic(writer, &crash_message, expect.region)?;
+    } else {
+        let mut offset = ExpectSequence::START_OFFSET;
+
+        for _ in 0..sequence.count_failures() {
+            offset += render_expect_failure(
+                writer,
+                &renderer,
+                arena,
+                Some(expect),
+                expectations,
+                interns,
+                layout_interner,
+                shared_memory_ptr,
+                offset,
+            )?;
         }
+    }
 
+    // Keep JSON output to exactly one report per line.
+    if !matches!(render_target, RenderTarget::Json) {
         writeln!(writer)?;
+    }
 
-        Ok(false)
-    } else {
-        Ok(true)
+    Ok(false)
+}
+
+/// Calls the property with generated arguments, and returns whether it returned `Bool.true` for
+/// all of them. Otherwise, the (shrunk) arguments it failed for are left in the shared memory.
+fn run_property(
+    lib: &libloading::Library,
+    memory: &mut ExpectMemory,
+    property: ToplevelProperty<'_>,
+    shapes: &[Shape],
+    options: RunOptions,
+) -> bool {
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
+
+    let call_property = run_roc_dylib!(lib, property.name, *const u8, bool);
+
+    // u128s, so the arguments are aligned for any type
+    let mut buffer: Vec<u128> = vec![0; 64];
+
+    let call = |arguments: &[Value]| {
+        loop {
+            let bytes = unsafe {
+                std::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), buffer.len() * 16)
+            };
+            let base = bytes.as_ptr() as usize;
+
+            if MemoryWriter::new(bytes, base, target_info)
+                .write_arguments(0, shapes, arguments)
+                .is_some()
+            {
+                break;
+            }
+
+            buffer = vec![0; 2 * buffer.len()];
+        }
+
+        // inline expects in the property report the failures of the latest call
+        let _sequence = ExpectSequence::new(memory.ptr);
+
+        let mut result = RocCallResult::default();
+        unsafe { call_property(buffer.as_ptr().cast(), &mut result) };
+
+        match Result::from(result) {
+            Ok(true) => CallOutcome::Passed,
+            Ok(false) => CallOutcome::Failed,
+            Err(roc_panic_message) => CallOutcome::Crashed(roc_panic_message),
+        }
+    };
+
+    match find_counterexample(shapes, options.property_runs, options.seed, call) {
+        None => true,
+        Some(counterexample) => {
+            memory.write_counterexample(&counterexample, shapes, target_info);
+
+            false
+        }
     }
 }
 
+/// Renders the arguments a property failed for.
 #[allow(clippy::too_many_arguments)]
-fn run_expect_fx<'a, W: std::io::Write>(
+fn report_property_failure<'a, W: std::io::Write>(
     writer: &mut W,
     render_target: RenderTarget,
     arena: &'a Bump,
     interns: &'a Interns,
     layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
-    lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    parent_memory: &mut ExpectMemory,
-    expect: ToplevelExpect<'_>,
-) -> std::io::Result<bool> {
-    use signal_hook::{consts::signal::SIGCHLD, consts::signal::SIGUSR1, iterator::Signals};
+    shared_memory_ptr: *const u8,
+    property: ToplevelProperty<'_>,
+    seed: u64,
+) -> std::io::Result<()> {
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
 
-    let mut signals = Signals::new(&[SIGCHLD, SIGUSR1]).unwrap();
+    let failure = PropertyFailure::read(shared_memory_ptr);
 
-    match unsafe { libc::fork() } {
-        0 => unsafe {
-            // we are the child
-
-            use roc_gen_llvm::try_run_jit_function;
+    let module_id = property.symbol.module_id();
+    let data = expectations.get_mut(&module_id).unwrap();
 
-            let mut child_memory = parent_memory.reuse_mmap().unwrap();
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(&data.path).unwrap();
 
-            let sequence = ExpectSequence::new(child_memory.ptr);
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
-            child_memory.set_shared_buffer(lib);
+    if failure.fits {
+        let subs = arena.alloc(&mut data.subs);
 
-            let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+        let (_, expressions) = crate::get_values(
+            target_info,
+            arena,
+            subs,
+            interns,
+            layout_interner,
+            shared_memory_ptr,
+            ExpectMemory::COUNTEREXAMPLE_TABLE_OFFSET,
+            property.argument_vars,
+        )
+        .unwrap();
 
-            if let Err(msg) = result {
-                panic!("roc panic {}", msg);
-            }
+        renderer.render_property_failure(
+            writer,
+            subs,
+            property.argument_names,
+            property.argument_vars,
+            &expressions,
+            property.region,
+            failure.crash_message.as_deref(),
+            seed,
+        )?;
+    } else {
+        let message = format!(
+            "It failed, but the arguments it failed for are too big to show here. \
+            Run `roc test --seed {}` to generate the same arguments again.",
+            seed
+        );
 
-            if sequence.count_failures() > 0 {
-                libc::kill(parent_id() as _, SIGUSR1);
-            }
+        renderer.render_property_problem(writer, &message, property.region)?;
+    }
 
-            std::process::exit(0)
-        },
-        -1 => {
-            // something failed
+    if !matches!(render_target, RenderTarget::Json) {
+        writeln!(writer)?;
+    }
 
-            // Display a human-friendly error message
-            println!("Error {:?}", std::io::Error::last_os_error());
+    Ok(())
+}
 
-            std::process::exit(1)
-        }
-        1.. => {
-            let mut has_succeeded = true;
-
-            for sig in &mut signals {
-                match sig {
-                    SIGCHLD => {
-                        // done!
-                        return Ok(has_succeeded);
-                    }
-                    SIGUSR1 => {
-                        // this is the signal we use for an expect failure. Let's see what the child told us
-                        has_succeeded = false;
+/// Renders why a property could not be run.
+fn report_property_problem<'a, W: std::io::Write>(
+    writer: &mut W,
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    expectations: &VecMap<ModuleId, Expectations>,
+    property: ToplevelProperty<'_>,
+    problem: &str,
+) -> std::io::Result<()> {
+    let module_id = property.symbol.module_id();
+    let data = expectations.get(&module_id).unwrap();
 
-                        let frame =
-                            ExpectFrame::at_offset(parent_memory.ptr, ExpectSequence::START_OFFSET);
-                        let module_id = frame.module_id;
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(&data.path).unwrap();
 
-                        let data = expectations.get_mut(&module_id).unwrap();
-                        let filename = data.path.to_owned();
-                        let source = std::fs::read_to_string(&data.path).unwrap();
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
-                        let renderer = Renderer::new(
-                            arena,
-                            interns,
-                            render_target,
-                            module_id,
-                            filename,
-                            &source,
-                        );
-
-                        render_expect_failure(
-                            writer,
-                            &renderer,
-                            arena,
-                            None,
-                            expectations,
-                            interns,
-                            layout_interner,
-                            parent_memory.ptr,
-                            ExpectSequence::START_OFFSET,
-                        )?;
-                    }
-                    _ => println!("received signal {}", sig),
-                }
-            }
+    renderer.render_property_problem(writer, problem, property.region)?;
 
-            Ok(true)
-        }
-        _ => unreachable!(),
+    if !matches!(render_target, RenderTarget::Json) {
+        writeln!(writer)?;
     }
+
+    Ok(())
 }
 
 pub fn roc_dev_expect<'a>(
@@ -498,10 +1129,29 @@ pub struct ToplevelExpect<'a> {
     pub region: Region,
 }
 
+/// A property, i.e. a top-level `expect` of a function that should return `Bool.true` for any
+/// arguments.
+#[derive(Debug, Clone, Copy)]
+pub struct ToplevelProperty<'a> {
+    /// The name of the exposed wrapper, which takes the arguments as a pointer. Empty (and
+    /// without `arguments`) if the property could not be specialized.
+    pub name: &'a str,
+    pub symbol: Symbol,
+    pub region: Region,
+    pub arguments: &'a [Layout<'a>],
+    pub argument_names: &'a [&'a str],
+    pub argument_vars: &'a [Variable],
+    /// An opaque type in the type of each argument, if there is one
+    pub argument_opaques: &'a [Option<Symbol>],
+}
+
 #[derive(Debug)]
 pub struct ExpectFunctions<'a> {
     pub pure: BumpVec<'a, ToplevelExpect<'a>>,
     pub fx: BumpVec<'a, ToplevelExpect<'a>>,
+    pub properties: BumpVec<'a, ToplevelProperty<'a>>,
+    /// What the coverage counters count, if the dylib was instrumented for coverage
+    pub coverage: Vec<CoveragePoint>,
 }
 
 pub fn expect_mono_module_to_dylib<'a>(
@@ -510,6 +1160,7 @@ pub fn expect_mono_module_to_dylib<'a>(
     loaded: MonomorphizedModule<'a>,
     opt_level: OptLevel,
     mode: LlvmBackendMode,
+    coverage: bool,
 ) -> Result<
     (
         libloading::Library,
@@ -540,6 +1191,7 @@ pub fn expect_mono_module_to_dylib<'a>(
         roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
     let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
+    let coverage_points = Coverage::default();
 
     // Compile and add all the Procs before adding main
     let env = roc_gen_llvm::llvm::build::Env {
@@ -555,6 +1207,11 @@ pub fn expect_mono_module_to_dylib<'a>(
         mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        coverage: if coverage {
+            Some(&coverage_points)
+        } else {
+            None
+        },
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
@@ -574,14 +1231,58 @@ pub fn expect_mono_module_to_dylib<'a>(
     expect_symbols.extend(toplevel_expects.pure.keys().copied());
     expect_symbols.extend(toplevel_expects.fx.keys().copied());
 
-    let expect_names = roc_gen_llvm::llvm::build::build_procedures_expose_expects(
+    // properties are exposed like any other host-exposed function, so there is at most one
+    // specialization; there is none when the arguments have no layout
+    let property_layouts: Vec<(Symbol, ProcLayout)> = toplevel_expects
+        .properties
+        .keys()
+        .filter_map(|symbol| procedures.keys().find(|(s, _)| s == symbol).copied())
+        .collect();
+
+    let (expect_names, property_names) = roc_gen_llvm::llvm::build::build_procedures_expose_expects(
         &env,
         opt_level,
         &expect_symbols,
+        &property_layouts,
         procedures,
         opt_entry_point,
     );
 
+    let mut specialized = property_layouts
+        .iter()
+        .zip(property_names.iter())
+        .peekable();
+
+    let properties = BumpVec::from_iter_in(
+        toplevel_expects
+            .properties
+            .iter()
+            .map(|(symbol, property)| {
+                let argument_names = property
+                    .argument_names
+                    .iter()
+                    .map(|argument_name| &*arena.alloc_str(argument_name));
+
+                // an unspecialized property is not called, but reported as one we can't
+                // generate arguments for
+                let (name, arguments) = match specialized.next_if(|((s, _), _)| s == symbol) {
+                    Some(((_, proc_layout), name)) => (*name, proc_layout.arguments),
+                    None => ("", &[][..]),
+                };
+
+                ToplevelProperty {
+                    name,
+                    symbol: *symbol,
+                    region: property.region,
+                    arguments,
+                    argument_names: arena.alloc_slice_fill_iter(argument_names),
+                    argument_vars: arena.alloc_slice_copy(&property.argument_vars),
+                    argument_opaques: arena.alloc_slice_copy(&property.argument_opaques),
+                }
+            }),
+        env.arena,
+    );
+
     let expects_fx = bumpalo::collections::Vec::from_iter_in(
         toplevel_expects
             .fx
@@ -611,6 +1312,8 @@ pub fn expect_mono_module_to_dylib<'a>(
     let expects = ExpectFunctions {
         pure: expects_pure,
         fx: expects_fx,
+        properties,
+        coverage: coverage_points.points(),
     };
 
     env.dibuilder.finalize();
@@ -0,0 +1,10 @@
+interface Greeting
+    exposes [greet, Name]
+    imports []
+
+## A person's name, e.g. "Roc"
+Name : Str
+
+## Says hello to the person with the given name.
+greet : Name -> Str
+greet = \name -> "Hello, \(name)!"
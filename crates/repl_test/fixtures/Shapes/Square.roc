@@ -0,0 +1,6 @@
+interface Shapes.Square
+    exposes [area]
+    imports []
+
+area : I64 -> I64
+area = \side -> side * side
@@ -0,0 +1,10 @@
+app "greeter"
+    packages { pf: "../../../examples/cli/cli-platform/main.roc" }
+    imports [pf.Stdout, Greeting]
+    provides [main] to pf
+
+me = "Roc"
+
+greetMe = Stdout.line (Greeting.greet me)
+
+main = greetMe
@@ -39,10 +39,14 @@ fn path_to_roc_binary() -> PathBuf {
     path
 }
 
-fn repl_eval(input: &str) -> Out {
+fn repl_eval(input: &str, args: &[&str]) -> Out {
     let mut cmd = Command::new(path_to_roc_binary());
 
     cmd.arg("repl");
+    cmd.args(args);
+
+    // Don't mix the tests' entries into the history of the person running them.
+    cmd.env("ROC_SKIP_REPL_HISTORY", "1");
 
     let mut child = cmd
         .stdin(Stdio::piped())
@@ -124,7 +128,16 @@ fn repl_eval(input: &str) -> Out {
 }
 
 pub fn expect_success(input: &str, expected: &str) {
-    let out = repl_eval(input);
+    expect_success_with_args(input, &[], expected)
+}
+
+/// Like `expect_success`, but evaluates with `roc repl --dev`
+pub fn expect_dev_success(input: &str, expected: &str) {
+    expect_success_with_args(input, &["--dev"], expected)
+}
+
+fn expect_success_with_args(input: &str, args: &[&str], expected: &str) {
+    let out = repl_eval(input, args);
 
     assert_multiline_str_eq!("", out.stderr.as_str());
     assert_multiline_str_eq!(expected, out.stdout.as_str());
@@ -132,7 +145,7 @@ pub fn expect_success(input: &str, expected: &str) {
 }
 
 pub fn expect_failure(input: &str, expected: &str) {
-    let out = repl_eval(input);
+    let out = repl_eval(input, &[]);
 
     // there may be some other stuff printed (e.g. unification errors)
     // so skip till the header of the first error
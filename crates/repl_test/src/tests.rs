@@ -2,7 +2,7 @@
 use indoc::indoc;
 
 #[cfg(not(feature = "wasm"))]
-use crate::cli::{expect_failure, expect_success};
+use crate::cli::{expect_dev_success, expect_failure, expect_success};
 
 #[cfg(feature = "wasm")]
 #[allow(unused_imports)]
@@ -1281,3 +1281,378 @@ fn newtype_by_void_is_wrapped() {
         r#"Ok 43 : Result (Num *) err"#,
     );
 }
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn defs_are_kept_for_later_entries() {
+    expect_success(
+        indoc!(
+            r#"
+            Point : { x : I64, y : I64 }
+            origin : Point
+            origin = { x: 0, y: 1 }
+
+
+            origin.y + 1
+            "#
+        ),
+        r#"2 : I64"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn defs_can_be_redefined() {
+    expect_success(
+        indoc!(
+            r#"
+            foo = 1
+
+
+            foo = "one"
+
+
+            foo
+            "#
+        ),
+        r#""one" : Str"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn expression_can_define_a_name_other_defs_use() {
+    expect_success(
+        indoc!(
+            r#"
+            foo = 1
+
+
+            bar = { foo, next: foo + 1 }
+
+
+            foo = 5
+            foo + bar.next
+            "#
+        ),
+        r#"7 : Num *"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn body_entered_after_its_annotation() {
+    expect_success(
+        indoc!(
+            r#"
+            foo : U8
+
+
+            foo = 1
+
+
+            foo
+            "#
+        ),
+        r#"1 : U8"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn list_defs() {
+    expect_success(
+        indoc!(
+            r#"
+            Age := U8
+
+
+            foo = 1
+
+
+            :defs
+            "#
+        ),
+        indoc!(
+            r#"
+            Age := U8
+
+            foo = 1"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))] // TODO: mismatch is due to terminal control codes!
+#[test]
+fn reset_defs() {
+    expect_failure(
+        indoc!(
+            r#"
+            foo = 1
+
+
+            :reset
+            foo
+            "#
+        ),
+        indoc!(
+            r#"
+                ── UNRECOGNIZED NAME ───────────────────────────────────────────────────────────
+
+                Nothing is named `foo` in this scope.
+
+                4│      foo
+                        ^^^
+
+                Did you mean one of these?
+
+                    Box
+                    Bool
+                    U8
+                    F64
+                "#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+fn fixture(file_name: &str) -> String {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(file_name)
+        .display()
+        .to_string()
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn load_module() {
+    expect_success(
+        &format!(":load {}\ngreet \"Roc\"", fixture("Greeting.roc")),
+        indoc!(
+            r#"
+            Loaded Greeting, which exposes greet, Name.
+
+            "Hello, Roc!" : Str"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn import_module_next_to_loaded_module() {
+    expect_success(
+        &format!(
+            ":load {}\n:import Shapes.Square\nShapes.Square.area 3 + area 2",
+            fixture("Greeting.roc")
+        ),
+        indoc!(
+            r#"
+            Loaded Greeting, which exposes greet, Name.
+
+            Loaded Shapes.Square, which exposes area.
+
+            13 : I64"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn load_app_module() {
+    expect_success(
+        &format!(":load {}\ngreet me", fixture("main.roc")),
+        indoc!(
+            r#"
+            Loaded pf.Stdout, which exposes line, write.
+            Loaded Greeting, which exposes greet, Name.
+
+            "Hello, Roc!" : Str"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn app_defs_can_use_package_modules() {
+    expect_success(
+        &format!(":load {}\n:type greetMe", fixture("main.roc")),
+        indoc!(
+            r#"
+            Loaded pf.Stdout, which exposes line, write.
+            Loaded Greeting, which exposes greet, Name.
+
+            greetMe : Task.Task {} * [Write [Stdout]]"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn import_module_from_package() {
+    expect_success(
+        &format!(
+            ":load {}\n:import pf.Stderr\n:type Stderr.line",
+            fixture("main.roc")
+        ),
+        indoc!(
+            r#"
+            Loaded pf.Stdout, which exposes line, write.
+            Loaded Greeting, which exposes greet, Name.
+
+            Loaded pf.Stderr, which exposes line, write.
+
+            Stderr.line : Str -> Task.Task {} * [Write [Stderr]]"#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn import_module_from_unknown_package() {
+    expect_success(
+        ":import pf.Stdout",
+        "There's no package named pf in the session. Packages come from the headers of the apps given to :load.\n",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn type_of_expr() {
+    expect_success(":type List.map", "List.map : List a, (a -> b) -> List b");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn type_of_session_def() {
+    expect_success(
+        indoc!(
+            r#"
+            foo = \x -> Num.toStr x
+
+
+            :type foo 1
+            "#
+        ),
+        r#"foo 1 : Str"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn doc_of_loaded_module_def() {
+    expect_success(
+        &format!(":load {}\n:doc greet", fixture("Greeting.roc")),
+        indoc!(
+            r#"
+            Loaded Greeting, which exposes greet, Name.
+
+            greet : Greeting.Name -> Str
+
+            Says hello to the person with the given name."#
+        ),
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn doc_of_unknown_name() {
+    expect_success(
+        ":doc List.nope",
+        "I couldn't find any docs for List.nope.\n",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn block_of_defs() {
+    expect_success(
+        indoc!(
+            r#"
+            :{
+            Point : { x : I64, y : I64 }
+
+            origin : Point
+            origin = { x: 0, y: 1 }
+            :}
+            origin.y + 1
+            "#
+        ),
+        r#"2 : I64"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn block_with_expr() {
+    expect_success(
+        indoc!(
+            r#"
+            :{
+            x = 1
+
+            x + 1
+            :}
+            "#
+        ),
+        r#"2 : Num *"#,
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_number() {
+    expect_dev_success("1 + 2", "3 : Num *");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_float() {
+    expect_dev_success("3.25f64", "3.25 : F64");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_record_of_floats() {
+    expect_dev_success(
+        "{ a: 1.5, b: 2.5 }",
+        "{ a: 1.5, b: 2.5 } : { a : Float *, b : Float * }",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_record_returned_through_pointer() {
+    expect_dev_success(
+        "{ a: 1, b: 2, c: 3 }",
+        "{ a: 1, b: 2, c: 3 } : { a : Num *, b : Num *, c : Num * }",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_str() {
+    expect_dev_success(r#""hello""#, r#""hello" : Str"#);
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_list() {
+    expect_dev_success("[1, 2, 3]", "[1, 2, 3] : List (Num *)");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_session_defs() {
+    expect_dev_success("x = 5\nx + 1", "6 : Num *");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn dev_backend_falls_back_to_llvm() {
+    // The dev backend can't generate code for this record yet
+    expect_dev_success(
+        "{ a: 1u8, b: 2u32 }",
+        "{ a: 1, b: 2 } : { a : U8, b : U32 }",
+    );
+}
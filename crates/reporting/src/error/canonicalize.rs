@@ -6,11 +6,12 @@ use roc_problem::can::{
     BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind, Problem, RuntimeError,
     ShadowKind,
 };
-use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Position, Region};
 use roc_types::types::AliasKind;
 use std::path::PathBuf;
 
 use crate::error::r#type::suggest;
+use crate::fix::{remove_collection_item, remove_def, Fix};
 use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder, Severity};
 use ven_pretty::DocAllocator;
 
@@ -69,6 +70,8 @@ pub fn can_problem<'b>(
     let title;
     let severity;
 
+    let fixes = suggested_fixes(alloc, &problem);
+
     match problem {
         Problem::UnusedDef(symbol, region) => {
             let line =
@@ -1044,9 +1047,95 @@ pub fn can_problem<'b>(
         filename,
         doc,
         severity,
+        fixes,
     }
 }
 
+/// Fixes for the problems that have an obvious solution. Each one is only offered when the
+/// source looks exactly like we expect, because a wrong edit is worse than no edit at all.
+fn suggested_fixes<'b>(alloc: &'b RocDocAllocator<'b>, problem: &Problem) -> Vec<Fix> {
+    let src = alloc.src();
+    let text_at = |region: Region| {
+        src.get(region.start().offset as usize..region.end().offset as usize)
+            .unwrap_or_default()
+    };
+
+    let fix = match problem {
+        Problem::UnusedImport(symbol, region)
+            if text_at(*region) == symbol.as_str(alloc.interns) =>
+        {
+            Fix::new(
+                "Remove the unused import",
+                remove_collection_item(src, *region),
+                "",
+            )
+        }
+        Problem::UnusedModuleImport(_, region) if !text_at(*region).is_empty() => Fix::new(
+            "Remove the unused import",
+            remove_collection_item(src, *region),
+            "",
+        ),
+        Problem::UnusedDef(symbol, region) => {
+            let name = symbol.as_str(alloc.interns);
+
+            match remove_def(src, *region, name) {
+                Some(def_region) => Fix::new(format!("Remove `{}`", name), def_region, ""),
+                None => return Vec::new(),
+            }
+        }
+        Problem::RuntimeError(RuntimeError::LookupNotInScope(loc_name, options)) => {
+            let typo = loc_name.value.as_str();
+            let options: Vec<&str> = options.iter().map(|option| option.as_ref()).collect();
+
+            match suggest::best(typo, options) {
+                Some(name) => {
+                    match replace_name(text_at(loc_name.region), loc_name.region, typo, name) {
+                        Some(fix) => fix,
+                        None => return Vec::new(),
+                    }
+                }
+                None => return Vec::new(),
+            }
+        }
+        Problem::RuntimeError(RuntimeError::ValueNotExposed {
+            ident,
+            region,
+            exposed_values,
+            ..
+        }) => {
+            let typo = ident.as_str();
+
+            match suggest::best(typo, exposed_values.iter().collect()) {
+                Some(name) => match replace_name(text_at(*region), *region, typo, name.as_str()) {
+                    Some(fix) => fix,
+                    None => return Vec::new(),
+                },
+                None => return Vec::new(),
+            }
+        }
+        _ => return Vec::new(),
+    };
+
+    vec![fix]
+}
+
+/// Replaces a misspelled name at the end of `region` (which may also contain e.g. the module
+/// the name was qualified with) with the name that was probably meant.
+fn replace_name(text: &str, region: Region, typo: &str, name: &str) -> Option<Fix> {
+    if !text.ends_with(typo) {
+        return None;
+    }
+
+    let end = region.end();
+    let start = Position::new(end.offset - typo.len() as u32);
+
+    Some(Fix::new(
+        format!("Change `{}` to `{}`", typo, name),
+        Region::new(start, end),
+        name,
+    ))
+}
+
 fn list_builtin_abilities<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'a> {
     alloc.intersperse(
         DERIVABLE_ABILITIES
@@ -1072,12 +1161,7 @@ fn to_invalid_optional_value_report<'b>(
         record_region,
     );
 
-    Report {
-        title: "BAD OPTIONAL VALUE".to_string(),
-        filename,
-        doc,
-        severity: Severity::RuntimeError,
-    }
+    Report::new("BAD OPTIONAL VALUE", filename, doc, Severity::RuntimeError)
 }
 
 fn to_invalid_optional_value_report_help<'b>(
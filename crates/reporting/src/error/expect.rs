@@ -47,6 +47,15 @@ impl<'a> Renderer<'a> {
         symbol: Symbol,
         expr: &Expr<'_>,
         error_type: ErrorType,
+    ) -> RocDocBuilder<'a> {
+        self.render_value(self.alloc.symbol_unqualified(symbol), expr, error_type)
+    }
+
+    fn render_value(
+        &'a self,
+        name: RocDocBuilder<'a>,
+        expr: &Expr<'_>,
+        error_type: ErrorType,
     ) -> RocDocBuilder<'a> {
         use crate::error::r#type::error_type_to_doc;
         use roc_fmt::annotation::Formattable;
@@ -55,14 +64,10 @@ impl<'a> Renderer<'a> {
         expr.format(&mut buf, 0);
 
         self.alloc.vcat([
-            self.alloc
-                .symbol_unqualified(symbol)
+            name.clone()
                 .append(" : ")
                 .append(error_type_to_doc(&self.alloc, error_type)),
-            self.alloc
-                .symbol_unqualified(symbol)
-                .append(" = ")
-                .append(buf.into_bump_str()),
+            name.append(" = ").append(buf.into_bump_str()),
         ])
     }
 
@@ -143,17 +148,18 @@ impl<'a> Renderer<'a> {
         let line_col_region = self.to_line_col_region(expect_region, failure_region);
         let doc = self.render_lookups(subs, line_col_region, symbols, variables, expressions);
 
-        let report = Report {
-            title: "EXPECT FAILED".into(),
+        let report = Report::new(
+            "EXPECT FAILED",
+            self.filename.clone(),
             doc,
-            filename: self.filename.clone(),
-            severity: crate::report::Severity::RuntimeError,
-        };
+            crate::report::Severity::RuntimeError,
+        );
 
         let mut buf = String::new();
 
-        report.render(
+        report.render_with_region(
             self.render_target,
+            Some(line_col_region),
             &mut buf,
             &self.alloc,
             &crate::report::DEFAULT_PALETTE,
@@ -183,17 +189,137 @@ impl<'a> Renderer<'a> {
             self.alloc.text(message),
         ]);
 
-        let report = Report {
-            title: "EXPECT PANICKED".into(),
+        let report = Report::new(
+            "EXPECT PANICKED",
+            self.filename.clone(),
             doc,
-            filename: self.filename.clone(),
-            severity: crate::report::Severity::RuntimeError,
+            crate::report::Severity::RuntimeError,
+        );
+
+        let mut buf = String::new();
+
+        report.render_with_region(
+            self.render_target,
+            Some(line_col_region),
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{}", buf)
+    }
+
+    /// Reports the (shrunk) arguments for which a property returned `Bool.false`, or crashed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_property_failure<W>(
+        &self,
+        writer: &mut W,
+        subs: &mut Subs,
+        argument_names: &[&str],
+        variables: &[Variable],
+        expressions: &[Expr<'_>],
+        property_region: Region,
+        crash_message: Option<&str>,
+        seed: u64,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(property_region);
+
+        let arguments = argument_names.iter().zip(variables).zip(expressions).map(
+            |((name, variable), expr)| {
+                let (error_type, _) = subs.var_to_error_type(*variable, Polarity::OF_VALUE);
+                self.render_value(self.alloc.text(*name), expr, error_type)
+            },
+        );
+
+        let explanation = match crash_message {
+            None => self
+                .alloc
+                .text("It returned ")
+                .append(self.alloc.tag("Bool.false".into()))
+                .append(" for these arguments:"),
+            Some(_) => self.alloc.text("It crashed for these arguments:"),
         };
 
+        let mut parts = vec![
+            self.alloc.text("This property failed:"),
+            self.alloc.region(line_col_region),
+            explanation,
+            self.alloc.stack(arguments),
+        ];
+
+        if let Some(message) = crash_message {
+            parts.push(self.alloc.text("The crash reported this message:"));
+            parts.push(self.alloc.text(message.to_string()));
+        }
+
+        let command = self.arena.alloc_str(&format!("roc test --seed {}", seed));
+
+        parts.push(self.alloc.concat([
+            self.alloc.reflow("Run "),
+            self.alloc.parser_suggestion(command),
+            self.alloc.reflow(" to generate the same arguments again."),
+        ]));
+
+        let report = Report::new(
+            "PROPERTY FAILED",
+            self.filename.clone(),
+            self.alloc.stack(parts),
+            crate::report::Severity::RuntimeError,
+        );
+
+        let mut buf = String::new();
+
+        report.render_with_region(
+            self.render_target,
+            Some(line_col_region),
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{}", buf)
+    }
+
+    /// Reports a property that failed without its arguments being known, e.g. because `roc test`
+    /// can't generate values of one of the argument types.
+    pub fn render_property_problem<W>(
+        &self,
+        writer: &mut W,
+        message: &str,
+        property_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(property_region);
+
+        let doc = self.alloc.stack([
+            self.alloc.text("There was a problem with this property:"),
+            self.alloc.region(line_col_region),
+            self.alloc.reflow(message),
+        ]);
+
+        let report = Report::new(
+            "PROPERTY FAILED",
+            self.filename.clone(),
+            doc,
+            crate::report::Severity::RuntimeError,
+        );
+
         let mut buf = String::new();
 
-        report.render(
+        report.render_with_region(
             self.render_target,
+            Some(line_col_region),
             &mut buf,
             &self.alloc,
             &crate::report::DEFAULT_PALETTE,
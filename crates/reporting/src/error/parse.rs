@@ -67,11 +67,13 @@ fn to_syntax_report<'a>(
 ) -> Report<'a> {
     use SyntaxError::*;
 
-    let report = |doc| Report {
-        filename: filename.clone(),
-        doc,
-        title: "PARSE PROBLEM".to_string(),
-        severity: Severity::RuntimeError,
+    let report = |doc| {
+        Report::new(
+            "PARSE PROBLEM",
+            filename.clone(),
+            doc,
+            Severity::RuntimeError,
+        )
     };
 
     match parse_problem {
@@ -81,12 +83,7 @@ fn to_syntax_report<'a>(
                 alloc.region(lines.convert_region(*region)),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "PARSE PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("PARSE PROBLEM", filename, doc, Severity::RuntimeError)
         }
         Unexpected(region) => {
             let mut region = lines.convert_region(*region);
@@ -113,12 +110,7 @@ fn to_syntax_report<'a>(
                 alloc.region(region),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "NOT END OF FILE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("NOT END OF FILE", filename, doc, Severity::RuntimeError)
         }
         SyntaxError::Eof(region) => {
             let doc = alloc.stack([
@@ -126,22 +118,12 @@ fn to_syntax_report<'a>(
                 alloc.region(lines.convert_region(*region)),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "PARSE PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("PARSE PROBLEM", filename, doc, Severity::RuntimeError)
         }
         SyntaxError::OutdentedTooFar => {
             let doc = alloc.stack([alloc.reflow("OutdentedTooFar")]);
 
-            Report {
-                filename,
-                doc,
-                title: "PARSE PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("PARSE PROBLEM", filename, doc, Severity::RuntimeError)
         }
         Type(typ) => to_type_report(alloc, lines, filename, typ, Position::default()),
         Pattern(pat) => to_pattern_report(alloc, lines, filename, pat, Position::default()),
@@ -215,12 +197,12 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "ARGUMENTS BEFORE EQUALS",
                 filename,
                 doc,
-                title: "ARGUMENTS BEFORE EQUALS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EExpr::BadOperator(op, pos) => {
@@ -313,12 +295,7 @@ fn to_expr_report<'a>(
                 alloc.concat(suggestion),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNKNOWN OPERATOR".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNKNOWN OPERATOR", filename, doc, Severity::RuntimeError)
         }
 
         EExpr::Ident(_pos) => unreachable!("another branch would be taken"),
@@ -339,12 +316,7 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD IDENTIFIER".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD IDENTIFIER", filename, doc, Severity::RuntimeError)
         }
 
         EExpr::Start(pos) | EExpr::IndentStart(pos) => {
@@ -419,12 +391,7 @@ fn to_expr_report<'a>(
                 expecting,
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: title.to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new(title.to_string(), filename, doc, Severity::RuntimeError)
         }
 
         EExpr::DefMissingFinalExpr(pos) => {
@@ -447,12 +414,12 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "MISSING FINAL EXPRESSION",
                 filename,
                 doc,
-                title: "MISSING FINAL EXPRESSION".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EExpr::DefMissingFinalExpr2(expr, pos) => to_expr_report(
@@ -478,12 +445,7 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "SYNTAX PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("SYNTAX PROBLEM", filename, doc, Severity::RuntimeError)
         }
 
         EExpr::Colon(pos) => {
@@ -501,12 +463,12 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "ARGUMENTS BEFORE EQUALS",
                 filename,
                 doc,
-                title: "ARGUMENTS BEFORE EQUALS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EExpr::BackpassArrow(pos) => {
@@ -519,12 +481,12 @@ fn to_expr_report<'a>(
                 alloc.concat([alloc.reflow("Looks like you are trying to define a function. ")]),
             ]);
 
-            Report {
+            Report::new(
+                "BAD BACKPASSING ARROW",
                 filename,
                 doc,
-                title: "BAD BACKPASSING ARROW".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EExpr::Record(_erecord, pos) => {
@@ -537,12 +499,12 @@ fn to_expr_report<'a>(
                 alloc.concat([alloc.reflow("TODO provide more context.")]),
             ]);
 
-            Report {
+            Report::new(
+                "RECORD PARSE PROBLEM",
                 filename,
                 doc,
-                title: "RECORD PARSE PROBLEM".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EExpr::Space(error, pos) => to_space_report(alloc, lines, filename, error, *pos),
@@ -565,12 +527,12 @@ fn to_expr_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "INDENT ENDS AFTER EXPRESSION",
                 filename,
                 doc,
-                title: "INDENT ENDS AFTER EXPRESSION".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         _ => todo!("unhandled parse error: {:?}", parse_problem),
     }
@@ -603,12 +565,7 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "WEIRD ARROW".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("WEIRD ARROW", filename, doc, Severity::RuntimeError)
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -625,12 +582,7 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "MISSING ARROW".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("MISSING ARROW", filename, doc, Severity::RuntimeError)
             }
         },
 
@@ -650,12 +602,7 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "WEIRD ARROW".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("WEIRD ARROW", filename, doc, Severity::RuntimeError)
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -672,12 +619,7 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "MISSING ARROW".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("MISSING ARROW", filename, doc, Severity::RuntimeError)
             }
         },
 
@@ -696,12 +638,12 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED ARGUMENT LIST",
                     filename,
                     doc,
-                    title: "UNFINISHED ARGUMENT LIST".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -717,12 +659,7 @@ fn to_lambda_report<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "MISSING ARROW".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("MISSING ARROW", filename, doc, Severity::RuntimeError)
             }
         },
 
@@ -798,12 +735,7 @@ fn to_unfinished_lambda_report<'a>(
         message,
     ]);
 
-    Report {
-        filename,
-        doc,
-        title: "UNFINISHED FUNCTION".to_string(),
-        severity: Severity::RuntimeError,
-    }
+    Report::new("UNFINISHED FUNCTION", filename, doc, Severity::RuntimeError)
 }
 
 fn to_str_report<'a>(
@@ -864,12 +796,7 @@ fn to_str_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD ESCAPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD ESCAPE", filename, doc, Severity::RuntimeError)
         }
         EString::CodePtOpen(pos) | EString::CodePtEnd(pos) => {
             let surroundings = Region::new(start, pos);
@@ -890,12 +817,7 @@ fn to_str_report<'a>(
                 alloc.reflow(r"Learn more about working with unicode in roc at TODO"),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD CODE POINT".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD CODE POINT", filename, doc, Severity::RuntimeError)
         }
         EString::FormatEnd(pos) => {
             let surroundings = Region::new(start, pos);
@@ -911,12 +833,7 @@ fn to_str_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "ENDLESS FORMAT".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("ENDLESS FORMAT", filename, doc, Severity::RuntimeError)
         }
         EString::EndlessSingle(pos) => {
             let surroundings = Region::new(start, pos);
@@ -934,12 +851,7 @@ fn to_str_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "ENDLESS STRING".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("ENDLESS STRING", filename, doc, Severity::RuntimeError)
         }
         EString::EndlessMulti(pos) => {
             let surroundings = Region::new(start, pos);
@@ -957,12 +869,7 @@ fn to_str_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "ENDLESS STRING".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("ENDLESS STRING", filename, doc, Severity::RuntimeError)
         }
         EString::MultilineInsufficientIndent(pos) => {
             let surroundings = Region::new(start, pos);
@@ -978,12 +885,12 @@ fn to_str_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "INSUFFICIENT INDENT IN MULTI-LINE STRING",
                 filename,
                 doc,
-                title: "INSUFFICIENT INDENT IN MULTI-LINE STRING".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
     }
 }
@@ -1024,12 +931,12 @@ fn to_expr_in_parens_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         EInParens::Open(pos) | EInParens::IndentOpen(pos) => {
             let surroundings = Region::new(start, pos);
@@ -1049,12 +956,12 @@ fn to_expr_in_parens_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
     }
 }
@@ -1099,12 +1006,7 @@ fn to_list_report<'a>(
                             alloc.reflow(r" and see if that helps?"),
                         ]),
                     ]);
-                    Report {
-                        filename,
-                        doc,
-                        title: "UNFINISHED LIST".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("UNFINISHED LIST", filename, doc, Severity::RuntimeError)
                 }
                 _ => {
                     let surroundings = Region::new(start, pos);
@@ -1132,12 +1034,7 @@ fn to_list_report<'a>(
                         ]),
                     ]);
 
-                    Report {
-                        filename,
-                        doc,
-                        title: "UNFINISHED LIST".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("UNFINISHED LIST", filename, doc, Severity::RuntimeError)
                 }
             }
         }
@@ -1160,12 +1057,7 @@ fn to_list_report<'a>(
                 note_for_tag_union_type_indent(alloc),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED LIST".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED LIST", filename, doc, Severity::RuntimeError)
         }
     }
 }
@@ -1275,12 +1167,7 @@ fn to_unfinished_if_report<'a>(
         message,
     ]);
 
-    Report {
-        filename,
-        doc,
-        title: "UNFINISHED IF".to_string(),
-        severity: Severity::RuntimeError,
-    }
+    Report::new("UNFINISHED IF", filename, doc, Severity::RuntimeError)
 }
 
 fn to_when_report<'a>(
@@ -1308,12 +1195,12 @@ fn to_when_report<'a>(
                         alloc.concat([alloc.reflow("Try adding an expression before the arrow!")]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "IF GUARD NO CONDITION",
                         filename,
                         doc,
-                        title: "IF GUARD NO CONDITION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 _ => to_expr_report(
                     alloc,
@@ -1340,12 +1227,7 @@ fn to_when_report<'a>(
                 note_for_when_indent_error(alloc),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "MISSING ARROW".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("MISSING ARROW", filename, doc, Severity::RuntimeError)
         }
 
         EWhen::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -1495,12 +1377,7 @@ fn to_unfinished_when_report<'a>(
                 note_for_when_error(alloc),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED WHEN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED WHEN", filename, doc, Severity::RuntimeError)
         }
     }
 }
@@ -1535,12 +1412,7 @@ fn to_unexpected_arrow_report<'a>(
         note_for_when_error(alloc),
     ]);
 
-    Report {
-        filename,
-        doc,
-        title: "UNEXPECTED ARROW".to_string(),
-        severity: Severity::RuntimeError,
-    }
+    Report::new("UNEXPECTED ARROW", filename, doc, Severity::RuntimeError)
 }
 
 fn note_for_when_error<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'a> {
@@ -1611,12 +1483,7 @@ fn to_pattern_report<'a>(
                 alloc.note("I may be confused by indentation"),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED PATTERN", filename, doc, Severity::RuntimeError)
         }
         EPattern::Record(record, pos) => to_precord_report(alloc, lines, filename, record, *pos),
         EPattern::List(list, pos) => to_plist_report(alloc, lines, filename, list, *pos),
@@ -1655,12 +1522,12 @@ fn to_precord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD PATTERN",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD PATTERN".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -1672,12 +1539,12 @@ fn to_precord_report<'a>(
                     record_patterns_look_like(alloc),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD PATTERN",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD PATTERN".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         },
 
@@ -1697,12 +1564,12 @@ fn to_precord_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD PATTERN",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD PATTERN".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 _ => {
                     let doc = alloc.stack([
@@ -1717,12 +1584,12 @@ fn to_precord_report<'a>(
                 ]),
             ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD PATTERN",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD PATTERN".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -1742,12 +1609,12 @@ fn to_precord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD PATTERN",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD PATTERN".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             Next::Other(Some(',')) => todo!(),
             Next::Other(Some('}')) => unreachable!("or is it?"),
@@ -1767,12 +1634,12 @@ fn to_precord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "PROBLEM IN RECORD PATTERN",
                     filename,
                     doc,
-                    title: "PROBLEM IN RECORD PATTERN".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         },
 
@@ -1809,12 +1676,12 @@ fn to_precord_report<'a>(
                 note_for_record_pattern_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED RECORD PATTERN",
                 filename,
                 doc,
-                title: "UNFINISHED RECORD PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PRecord::IndentEnd(pos) => {
@@ -1833,12 +1700,12 @@ fn to_precord_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -1858,12 +1725,12 @@ fn to_precord_report<'a>(
                         note_for_record_pattern_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD PATTERN",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD PATTERN".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -1898,12 +1765,12 @@ fn to_plist_report<'a>(
                 list_patterns_look_like(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED LIST PATTERN",
                 filename,
                 doc,
-                title: "UNFINISHED LIST PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PList::End(pos) => {
@@ -1920,12 +1787,12 @@ fn to_plist_report<'a>(
                     alloc.reflow(" and see if that helps?"),
                 ])]);
 
-            Report {
+            Report::new(
+                "UNFINISHED LIST PATTERN",
                 filename,
                 doc,
-                title: "UNFINISHED LIST PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PList::Rest(pos) => {
@@ -1942,12 +1809,12 @@ fn to_plist_report<'a>(
                     alloc.reflow(" - is that what you meant?"),
                 ])]);
 
-            Report {
+            Report::new(
+                "INCORRECT REST PATTERN",
                 filename,
                 doc,
-                title: "INCORRECT REST PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PList::Pattern(pattern, pos) => to_pattern_report(alloc, lines, filename, pattern, pos),
@@ -1963,12 +1830,12 @@ fn to_plist_report<'a>(
                 note_for_list_pattern_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED LIST PATTERN",
                 filename,
                 doc,
-                title: "UNFINISHED LIST PATTERN".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PList::IndentEnd(pos) => {
@@ -1990,12 +1857,12 @@ fn to_plist_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -2015,12 +1882,12 @@ fn to_plist_report<'a>(
                         note_for_list_pattern_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED LIST PATTERN",
                         filename,
                         doc,
-                        title: "UNFINISHED LIST PATTERN".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2058,12 +1925,12 @@ fn to_pattern_in_parens_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PInParens::End(pos) => {
@@ -2082,12 +1949,12 @@ fn to_pattern_in_parens_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PInParens::Pattern(pattern, pos) => to_pattern_report(alloc, lines, filename, pattern, pos),
@@ -2105,12 +1972,12 @@ fn to_pattern_in_parens_report<'a>(
                 note_for_record_pattern_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         PInParens::IndentEnd(pos) => {
@@ -2129,12 +1996,12 @@ fn to_pattern_in_parens_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -2154,12 +2021,12 @@ fn to_pattern_in_parens_report<'a>(
                         note_for_record_pattern_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED PARENTHESES",
                         filename,
                         doc,
-                        title: "UNFINISHED PARENTHESES".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2182,12 +2049,12 @@ fn to_malformed_number_literal_report<'a>(
         alloc.region_with_subregion(lines.convert_region(surroundings), region),
     ]);
 
-    Report {
+    Report::new(
+        "INVALID NUMBER LITERAL",
         filename,
         doc,
-        title: "INVALID NUMBER LITERAL".to_string(),
-        severity: Severity::RuntimeError,
-    }
+        Severity::RuntimeError,
+    )
 }
 
 fn to_type_report<'a>(
@@ -2222,12 +2089,7 @@ fn to_type_report<'a>(
                     alloc.concat([alloc.reflow("Try removing one of them.")]),
                 ]);
 
-                    Report {
-                        filename,
-                        doc,
-                        title: "DOUBLE COMMA".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("DOUBLE COMMA", filename, doc, Severity::RuntimeError)
                 }
                 _ => todo!(),
             }
@@ -2249,12 +2111,7 @@ fn to_type_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED TYPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED TYPE", filename, doc, Severity::RuntimeError)
         }
 
         EType::TIndentStart(pos) => {
@@ -2267,12 +2124,7 @@ fn to_type_report<'a>(
                 alloc.note("I may be confused by indentation"),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED TYPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED TYPE", filename, doc, Severity::RuntimeError)
         }
 
         EType::TIndentEnd(pos) => {
@@ -2285,12 +2137,7 @@ fn to_type_report<'a>(
                 alloc.note("I may be confused by indentation"),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "UNFINISHED TYPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("UNFINISHED TYPE", filename, doc, Severity::RuntimeError)
         }
 
         EType::TAsIndentStart(pos) => {
@@ -2303,12 +2150,12 @@ fn to_type_report<'a>(
                 alloc.note("I may be confused by indentation"),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED INLINE ALIAS",
                 filename,
                 doc,
-                title: "UNFINISHED INLINE ALIAS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         EType::TBadTypeVariable(pos) => {
@@ -2320,12 +2167,7 @@ fn to_type_report<'a>(
                 alloc.region_with_subregion(lines.convert_region(surroundings), region),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "BAD TYPE VARIABLE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("BAD TYPE VARIABLE", filename, doc, Severity::RuntimeError)
         }
 
         _ => todo!("unhandled type parse error: {:?}", &parse_problem),
@@ -2357,12 +2199,12 @@ fn to_trecord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD TYPE",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -2378,12 +2220,12 @@ fn to_trecord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD TYPE",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         },
 
@@ -2403,12 +2245,12 @@ fn to_trecord_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD TYPE",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD TYPE".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 _ => {
                     let doc = alloc.stack([
@@ -2423,12 +2265,12 @@ fn to_trecord_report<'a>(
                 ]),
             ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD TYPE",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD TYPE".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2448,12 +2290,12 @@ fn to_trecord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED RECORD TYPE",
                     filename,
                     doc,
-                    title: "UNFINISHED RECORD TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             Next::Other(Some(',')) => todo!(),
             Next::Other(Some('}')) => unreachable!("or is it?"),
@@ -2473,12 +2315,12 @@ fn to_trecord_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "PROBLEM IN RECORD TYPE",
                     filename,
                     doc,
-                    title: "PROBLEM IN RECORD TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         },
 
@@ -2506,12 +2348,12 @@ fn to_trecord_report<'a>(
                 note_for_record_type_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED RECORD TYPE",
                 filename,
                 doc,
-                title: "UNFINISHED RECORD TYPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         ETypeRecord::IndentEnd(pos) => {
@@ -2530,12 +2372,12 @@ fn to_trecord_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -2555,12 +2397,12 @@ fn to_trecord_report<'a>(
                         note_for_record_type_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED RECORD TYPE",
                         filename,
                         doc,
-                        title: "UNFINISHED RECORD TYPE".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2602,12 +2444,12 @@ fn to_ttag_union_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED TAG UNION TYPE",
                     filename,
                     doc,
-                    title: "UNFINISHED TAG UNION TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
             Next::Other(Some(c)) if c.is_alphabetic() => {
                 debug_assert!(c.is_lowercase());
@@ -2624,12 +2466,7 @@ fn to_ttag_union_report<'a>(
                     hint_for_tag_name(alloc),
                 ]);
 
-                Report {
-                    filename,
-                    doc,
-                    title: "WEIRD TAG NAME".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("WEIRD TAG NAME", filename, doc, Severity::RuntimeError)
             }
             _ => {
                 let surroundings = Region::new(start, pos);
@@ -2645,12 +2482,12 @@ fn to_ttag_union_report<'a>(
                     ]),
                 ]);
 
-                Report {
+                Report::new(
+                    "UNFINISHED TAG UNION TYPE",
                     filename,
                     doc,
-                    title: "UNFINISHED TAG UNION TYPE".to_string(),
-                    severity: Severity::RuntimeError,
-                }
+                    Severity::RuntimeError,
+                )
             }
         },
 
@@ -2671,12 +2508,7 @@ fn to_ttag_union_report<'a>(
                         hint_for_tag_name(alloc),
                     ]);
 
-                    Report {
-                        filename,
-                        doc,
-                        title: "WEIRD TAG NAME".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("WEIRD TAG NAME", filename, doc, Severity::RuntimeError)
                 }
                 _ => {
                     let doc = alloc.stack([
@@ -2691,12 +2523,12 @@ fn to_ttag_union_report<'a>(
                             ]),
                         ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED TAG UNION TYPE",
                         filename,
                         doc,
-                        title: "UNFINISHED TAG UNION TYPE".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2718,12 +2550,12 @@ fn to_ttag_union_report<'a>(
                 note_for_tag_union_type_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED TAG UNION TYPE",
                 filename,
                 doc,
-                title: "UNFINISHED TAG UNION TYPE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         ETypeTagUnion::IndentEnd(pos) => {
@@ -2745,12 +2577,12 @@ fn to_ttag_union_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -2770,12 +2602,12 @@ fn to_ttag_union_report<'a>(
                         note_for_tag_union_type_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED TAG UNION TYPE",
                         filename,
                         doc,
-                        title: "UNFINISHED TAG UNION TYPE".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2811,12 +2643,12 @@ fn to_tinparens_report<'a>(
                     ]),
                 ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED PARENTHESES",
                         filename,
                         doc,
-                        title: "UNFINISHED PARENTHESES".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 Next::Other(Some(c)) if c.is_alphabetic() => {
                     debug_assert!(c.is_lowercase());
@@ -2833,12 +2665,7 @@ fn to_tinparens_report<'a>(
                     hint_for_tag_name(alloc),
                 ]);
 
-                    Report {
-                        filename,
-                        doc,
-                        title: "WEIRD TAG NAME".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("WEIRD TAG NAME", filename, doc, Severity::RuntimeError)
                 }
                 _ => {
                     let surroundings = Region::new(start, pos);
@@ -2856,12 +2683,12 @@ fn to_tinparens_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED PARENTHESES",
                         filename,
                         doc,
-                        title: "UNFINISHED PARENTHESES".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2884,12 +2711,7 @@ fn to_tinparens_report<'a>(
                         hint_for_tag_name(alloc),
                     ]);
 
-                    Report {
-                        filename,
-                        doc,
-                        title: "WEIRD TAG NAME".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                    Report::new("WEIRD TAG NAME", filename, doc, Severity::RuntimeError)
                 }
                 _ => {
                     let doc = alloc.stack([
@@ -2904,12 +2726,12 @@ fn to_tinparens_report<'a>(
                             ]),
                         ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED PARENTHESES",
                         filename,
                         doc,
-                        title: "UNFINISHED PARENTHESES".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -2932,12 +2754,12 @@ fn to_tinparens_report<'a>(
                 note_for_tag_union_type_indent(alloc),
             ]);
 
-            Report {
+            Report::new(
+                "UNFINISHED PARENTHESES",
                 filename,
                 doc,
-                title: "UNFINISHED PARENTHESES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         ETypeInParens::IndentEnd(pos) => {
@@ -2956,12 +2778,12 @@ fn to_tinparens_report<'a>(
                         ]),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "NEED MORE INDENTATION",
                         filename,
                         doc,
-                        title: "NEED MORE INDENTATION".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
                 None => {
                     let surroundings = Region::new(start, pos);
@@ -2981,12 +2803,12 @@ fn to_tinparens_report<'a>(
                         note_for_tag_union_type_indent(alloc),
                     ]);
 
-                    Report {
+                    Report::new(
+                        "UNFINISHED PARENTHESES",
                         filename,
                         doc,
-                        title: "UNFINISHED PARENTHESES".to_string(),
-                        severity: Severity::RuntimeError,
-                    }
+                        Severity::RuntimeError,
+                    )
                 }
             }
         }
@@ -3014,12 +2836,7 @@ fn to_tapply_report<'a>(
                 alloc.concat([alloc.reflow("Try removing one of them.")]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "DOUBLE DOT".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("DOUBLE DOT", filename, doc, Severity::RuntimeError)
         }
         ETypeApply::TrailingDot(pos) => {
             let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
@@ -3036,12 +2853,7 @@ fn to_tapply_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "TRAILING DOT".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("TRAILING DOT", filename, doc, Severity::RuntimeError)
         }
         ETypeApply::StartIsNumber(pos) => {
             let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
@@ -3058,12 +2870,12 @@ fn to_tapply_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "WEIRD QUALIFIED NAME",
                 filename,
                 doc,
-                title: "WEIRD QUALIFIED NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         ETypeApply::StartNotUppercase(pos) => {
             let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
@@ -3080,12 +2892,12 @@ fn to_tapply_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "WEIRD QUALIFIED NAME",
                 filename,
                 doc,
-                title: "WEIRD QUALIFIED NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
 
         ETypeApply::End(pos) => {
@@ -3098,12 +2910,7 @@ fn to_tapply_report<'a>(
                 alloc.region(region),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "END OF FILE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("END OF FILE", filename, doc, Severity::RuntimeError)
         }
 
         ETypeApply::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3138,12 +2945,7 @@ fn to_talias_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "NOT AN INLINE ALIAS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("NOT AN INLINE ALIAS", filename, doc, Severity::RuntimeError)
         }
         ETypeInlineAlias::Qualified(pos) => {
             let region = Region::from_pos(pos);
@@ -3154,12 +2956,12 @@ fn to_talias_report<'a>(
                 alloc.reflow("An alias introduces a new name to the current scope, so it must be unqualified."),
             ]);
 
-            Report {
+            Report::new(
+                "QUALIFIED ALIAS NAME",
                 filename,
                 doc,
-                title: "QUALIFIED ALIAS NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         ETypeInlineAlias::ArgumentNotLowercase(pos) => {
             let region = Region::from_pos(pos);
@@ -3170,12 +2972,12 @@ fn to_talias_report<'a>(
                 alloc.reflow("All type arguments must be lowercase."),
             ]);
 
-            Report {
+            Report::new(
+                "TYPE ARGUMENT NOT LOWERCASE",
                 filename,
                 doc,
-                title: "TYPE ARGUMENT NOT LOWERCASE".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
     }
 }
@@ -3216,12 +3018,7 @@ fn to_header_report<'a>(
                 alloc.concat([alloc.reflow("I may be confused by indentation.")]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "INCOMPLETE HEADER".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("INCOMPLETE HEADER", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::Start(pos) => {
@@ -3242,12 +3039,7 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "MISSING HEADER".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("MISSING HEADER", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::ModuleName(pos) => {
@@ -3266,12 +3058,7 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD MODULE NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD MODULE NAME", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::InconsistentModuleName(region) => {
@@ -3293,12 +3080,7 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD MODULE NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD MODULE NAME", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::AppName(_, pos) => {
@@ -3317,12 +3099,7 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD APP NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD APP NAME", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::PlatformName(_, pos) => {
@@ -3339,12 +3116,7 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD MODULE NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD MODULE NAME", filename, doc, Severity::RuntimeError)
         }
 
         EHeader::Space(error, pos) => to_space_report(alloc, lines, filename, error, *pos),
@@ -3362,12 +3134,12 @@ fn to_header_report<'a>(
                 ]),
             ]);
 
-            Report {
+            Report::new(
+                "WEIRD GENERATED TYPE NAME",
                 filename,
                 doc,
-                title: "WEIRD GENERATED TYPE NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         EHeader::GeneratesWith(generates_with, pos) => {
             to_generates_with_report(alloc, lines, filename, generates_with, *pos)
@@ -3402,12 +3174,7 @@ fn to_generates_with_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD GENERATES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD GENERATES", filename, doc, Severity::RuntimeError)
         }
 
         EGeneratesWith::With(pos) => {
@@ -3427,12 +3194,7 @@ fn to_generates_with_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD GENERATES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD GENERATES", filename, doc, Severity::RuntimeError)
         }
 
         EGeneratesWith::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3468,12 +3230,7 @@ fn to_provides_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD PROVIDES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD PROVIDES", filename, doc, Severity::RuntimeError)
         }
 
         EProvides::Provides(pos) => {
@@ -3493,12 +3250,7 @@ fn to_provides_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD PROVIDES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD PROVIDES", filename, doc, Severity::RuntimeError)
         }
 
         EProvides::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3533,12 +3285,7 @@ fn to_exposes_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD EXPOSES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD EXPOSES", filename, doc, Severity::RuntimeError)
         }
 
         EExposes::Exposes(pos) => {
@@ -3558,12 +3305,7 @@ fn to_exposes_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD EXPOSES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD EXPOSES", filename, doc, Severity::RuntimeError)
         }
 
         EExposes::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3597,12 +3339,7 @@ fn to_imports_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD IMPORTS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD IMPORTS", filename, doc, Severity::RuntimeError)
         }
 
         EImports::Imports(pos) | EImports::IndentImports(pos) => {
@@ -3622,12 +3359,7 @@ fn to_imports_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD IMPORTS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD IMPORTS", filename, doc, Severity::RuntimeError)
         }
 
         EImports::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3648,12 +3380,7 @@ fn to_imports_report<'a>(
                 ]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD MODULE NAME".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD MODULE NAME", filename, doc, Severity::RuntimeError)
         }
 
         EImports::ListEnd(pos) => {
@@ -3667,12 +3394,7 @@ fn to_imports_report<'a>(
                 alloc.parser_suggestion("imports [Shape, Vector]").indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "WEIRD IMPORTS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("WEIRD IMPORTS", filename, doc, Severity::RuntimeError)
         }
 
         _ => todo!("unhandled parse error {:?}", parse_problem),
@@ -3706,12 +3428,7 @@ fn to_requires_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "MISSING REQUIRES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("MISSING REQUIRES", filename, doc, Severity::RuntimeError)
         }
 
         ERequires::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3733,12 +3450,7 @@ fn to_requires_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "MISSING REQUIRES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("MISSING REQUIRES", filename, doc, Severity::RuntimeError)
         }
 
         ERequires::Rigid(pos) => {
@@ -3762,12 +3474,7 @@ fn to_requires_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "BAD REQUIRES RIGIDS".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("BAD REQUIRES RIGIDS", filename, doc, Severity::RuntimeError)
         }
 
         ERequires::Open(pos) => {
@@ -3791,12 +3498,7 @@ fn to_requires_report<'a>(
                     .indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "BAD REQUIRES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("BAD REQUIRES", filename, doc, Severity::RuntimeError)
         }
 
         _ => todo!("unhandled parse error {:?}", parse_problem),
@@ -3828,12 +3530,7 @@ fn to_packages_report<'a>(
                 alloc.parser_suggestion("packages {}").indent(4),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "MISSING PACKAGES".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("MISSING PACKAGES", filename, doc, Severity::RuntimeError)
         }
 
         EPackages::Space(error, pos) => to_space_report(alloc, lines, filename, &error, pos),
@@ -3861,12 +3558,7 @@ fn to_space_report<'a>(
                 alloc.concat([alloc.reflow("Tab characters are not allowed.")]),
             ]);
 
-            Report {
-                filename,
-                doc,
-                title: "TAB CHARACTER".to_string(),
-                severity: Severity::RuntimeError,
-            }
+            Report::new("TAB CHARACTER", filename, doc, Severity::RuntimeError)
         }
 
         _ => todo!("unhandled type parse error: {:?}", &parse_problem),
@@ -3942,12 +3634,7 @@ fn to_unfinished_ability_report<'a>(
         message,
     ]);
 
-    Report {
-        filename,
-        doc,
-        title: "UNFINISHED ABILITY".to_string(),
-        severity: Severity::RuntimeError,
-    }
+    Report::new("UNFINISHED ABILITY", filename, doc, Severity::RuntimeError)
 }
 
 #[derive(Debug)]
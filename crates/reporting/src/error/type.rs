@@ -1,5 +1,6 @@
 use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
-use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder, Severity};
+use crate::fix::{when_branch_insertion, Fix};
+use crate::report::{Annotation, CiWrite, Report, RocDocAllocator, RocDocBuilder, Severity};
 use roc_can::expected::{Expected, PExpected};
 use roc_collections::all::{HumanIndex, MutSet, SendMap};
 use roc_collections::VecMap;
@@ -8,7 +9,7 @@ use roc_exhaustive::{CtorName, ListArity};
 use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::{Ident, IdentStr, Lowercase, TagName};
 use roc_module::symbol::Symbol;
-use roc_region::all::{LineInfo, Loc, Region};
+use roc_region::all::{LineInfo, Loc, Position, Region};
 use roc_solve_problem::{
     NotDerivableContext, NotDerivableDecode, NotDerivableEq, TypeError, UnderivableReason,
     Unfulfilled,
@@ -40,12 +41,7 @@ pub fn type_problem<'b>(
     use TypeError::*;
 
     fn report(title: String, doc: RocDocBuilder<'_>, filename: PathBuf) -> Option<Report<'_>> {
-        Some(Report {
-            title,
-            filename,
-            doc,
-            severity: Severity::RuntimeError,
-        })
+        Some(Report::new(title, filename, doc, Severity::RuntimeError))
     }
 
     match problem {
@@ -160,12 +156,12 @@ pub fn type_problem<'b>(
                 note
             ];
 
-            let report = Report {
-                title: "TYPE MISMATCH".to_string(),
+            let report = Report::new(
+                "TYPE MISMATCH",
                 filename,
-                doc: alloc.stack(stack),
-                severity: Severity::RuntimeError,
-            };
+                alloc.stack(stack),
+                Severity::RuntimeError,
+            );
             Some(report)
         }
         BadPatternMissingAbility(region, _category, _found, incomplete) => {
@@ -182,12 +178,12 @@ pub fn type_problem<'b>(
                 note,
             ];
 
-            let report = Report {
-                title: "TYPE MISMATCH".to_string(),
+            let report = Report::new(
+                "TYPE MISMATCH",
                 filename,
-                doc: alloc.stack(stack),
-                severity: Severity::RuntimeError,
-            };
+                alloc.stack(stack),
+                Severity::RuntimeError,
+            );
             Some(report)
         }
         Exhaustive(problem) => Some(exhaustive_problem(alloc, lines, filename, problem)),
@@ -196,12 +192,7 @@ pub fn type_problem<'b>(
             let title = CIRCULAR_DEF.to_string();
             let severity = Severity::RuntimeError;
 
-            Some(Report {
-                title,
-                filename,
-                doc,
-                severity,
-            })
+            Some(Report::new(title, filename, doc, severity))
         }
         StructuralSpecialization {
             region,
@@ -226,12 +217,12 @@ pub fn type_problem<'b>(
                 ])),
             ];
 
-            Some(Report {
-                title: "ILLEGAL SPECIALIZATION".to_string(),
+            Some(Report::new(
+                "ILLEGAL SPECIALIZATION",
                 filename,
-                doc: alloc.stack(stack),
-                severity: Severity::RuntimeError,
-            })
+                alloc.stack(stack),
+                Severity::RuntimeError,
+            ))
         }
         WrongSpecialization {
             region,
@@ -255,12 +246,12 @@ pub fn type_problem<'b>(
                 ]),
             ];
 
-            Some(Report {
-                title: "WRONG SPECIALIZATION TYPE".to_string(),
+            Some(Report::new(
+                "WRONG SPECIALIZATION TYPE",
                 filename,
-                doc: alloc.stack(stack),
-                severity: Severity::RuntimeError,
-            })
+                alloc.stack(stack),
+                Severity::RuntimeError,
+            ))
         }
     }
 }
@@ -569,12 +560,12 @@ fn report_mismatch<'b>(
         ),
     ];
 
-    Report {
-        title: "TYPE MISMATCH".to_string(),
+    Report::new(
+        "TYPE MISMATCH",
         filename,
-        doc: alloc.stack(lines),
-        severity: Severity::RuntimeError,
-    }
+        alloc.stack(lines),
+        Severity::RuntimeError,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -612,12 +603,12 @@ fn report_bad_type<'b>(
         ),
     ];
 
-    Report {
-        title: "TYPE MISMATCH".to_string(),
+    Report::new(
+        "TYPE MISMATCH",
         filename,
-        doc: alloc.stack(lines),
-        severity: Severity::RuntimeError,
-    }
+        alloc.stack(lines),
+        Severity::RuntimeError,
+    )
 }
 
 fn pattern_to_doc<'b>(
@@ -693,16 +684,16 @@ fn to_expr_report<'b>(
                 None,
             );
 
-            Report {
+            Report::new(
+                "TYPE MISMATCH",
                 filename,
-                title: "TYPE MISMATCH".to_string(),
-                doc: alloc.stack([
+                alloc.stack([
                     alloc.text("This expression is used in an unexpected way:"),
                     alloc.region(lines.convert_region(expr_region)),
                     comparison,
                 ]),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         Expected::FromAnnotation(name, _arity, annotation_source, expected_type) => {
             use roc_types::types::AnnotationSource::*;
@@ -808,10 +799,10 @@ fn to_expr_report<'b>(
                 )
             };
 
-            Report {
-                title: "TYPE MISMATCH".to_string(),
+            Report::new(
+                "TYPE MISMATCH",
                 filename,
-                doc: alloc.stack([
+                alloc.stack([
                     alloc.text("Something is off with the ").append(thing),
                     {
                         // for typed bodies, include the line(s) with the signature
@@ -824,8 +815,8 @@ fn to_expr_report<'b>(
                     },
                     comparison,
                 ]),
-                severity: Severity::RuntimeError,
-            }
+                Severity::RuntimeError,
+            )
         }
         Expected::ForReason(reason, expected_type, region) => match reason {
             Reason::ExpectCondition => {
@@ -1164,12 +1155,12 @@ fn to_expr_report<'b>(
                         alloc.reflow("Are there any missing commas? Or missing parentheses?"),
                     ];
 
-                    Report {
+                    Report::new(
+                        "TOO MANY ARGS",
                         filename,
-                        title: "TOO MANY ARGS".to_string(),
-                        doc: alloc.stack(lines),
-                        severity: Severity::RuntimeError,
-                    }
+                        alloc.stack(lines),
+                        Severity::RuntimeError,
+                    )
                 }
                 n => {
                     let this_function = match name {
@@ -1199,12 +1190,12 @@ fn to_expr_report<'b>(
                             alloc.reflow("Are there any missing commas? Or missing parentheses?"),
                         ];
 
-                        Report {
+                        Report::new(
+                            "TOO MANY ARGS",
                             filename,
-                            title: "TOO MANY ARGS".to_string(),
-                            doc: alloc.stack(lines),
-                            severity: Severity::RuntimeError,
-                        }
+                            alloc.stack(lines),
+                            Severity::RuntimeError,
+                        )
                     } else {
                         let lines = vec![
                             alloc.concat([
@@ -1226,12 +1217,12 @@ fn to_expr_report<'b>(
                             ),
                         ];
 
-                        Report {
+                        Report::new(
+                            "TOO FEW ARGS",
                             filename,
-                            title: "TOO FEW ARGS".to_string(),
-                            doc: alloc.stack(lines),
-                            severity: Severity::RuntimeError,
-                        }
+                            alloc.stack(lines),
+                            Severity::RuntimeError,
+                        )
                     }
                 }
             },
@@ -1411,12 +1402,12 @@ fn to_expr_report<'b>(
                     ),
                 ];
 
-                Report {
-                    title: "TYPE MISMATCH".to_string(),
+                Report::new(
+                    "TYPE MISMATCH",
                     filename,
-                    doc: alloc.stack(lines),
-                    severity: Severity::RuntimeError,
-                }
+                    alloc.stack(lines),
+                    Severity::RuntimeError,
+                )
             }
 
             Reason::TypedArg { name, arg_index } => {
@@ -1448,12 +1439,7 @@ fn to_expr_report<'b>(
                     ),
                 ]);
 
-                Report {
-                    filename,
-                    title: "TYPE MISMATCH".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
             }
 
             Reason::LowLevelOpArg { op, arg_index } => {
@@ -1815,12 +1801,7 @@ fn to_pattern_report<'b>(
                 ),
             ]);
 
-            Report {
-                filename,
-                title: "TYPE MISMATCH".to_string(),
-                doc,
-                severity: Severity::RuntimeError,
-            }
+            Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
         }
 
         PExpected::ForReason(reason, expected_type, region) => match reason {
@@ -1858,12 +1839,7 @@ fn to_pattern_report<'b>(
                     ),
                 ]);
 
-                Report {
-                    filename,
-                    title: "TYPE MISMATCH".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
             }
             PReason::WhenMatch { index, sub_pattern } => {
                 let doc = match (index, sub_pattern) {
@@ -1938,12 +1914,7 @@ fn to_pattern_report<'b>(
                         ])
                     }
                 };
-                Report {
-                    filename,
-                    title: "TYPE MISMATCH".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
             }
             PReason::ListElem => {
                 let doc = alloc.stack([
@@ -1965,12 +1936,7 @@ fn to_pattern_report<'b>(
                     ),
                 ]);
 
-                Report {
-                    filename,
-                    title: "TYPE MISMATCH".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
             }
             PReason::TagArg { .. } | PReason::PatternGuard => {
                 internal_error!("We didn't think this could trigger. Please tell us about it on Zulip if it does!")
@@ -2048,10 +2014,10 @@ fn to_circular_report<'b>(
     symbol: Symbol,
     overall_type: ErrorType,
 ) -> Report<'b> {
-    Report {
-        title: "CIRCULAR TYPE".to_string(),
+    Report::new(
+        "CIRCULAR TYPE",
         filename,
-        doc: {
+        {
             alloc.stack([
                 alloc
                     .reflow("I'm inferring a weird self-referential type for ")
@@ -2068,8 +2034,8 @@ fn to_circular_report<'b>(
                 ]),
             ])
         },
-        severity: Severity::RuntimeError,
-    }
+        Severity::RuntimeError,
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -2152,6 +2118,32 @@ pub mod suggest {
 
         options
     }
+
+    /// The one option that is clearly what was meant by `typo`, if any: it must be close to
+    /// the typo, and strictly closer than every other option.
+    pub fn best<T>(typo: &str, options: Vec<T>) -> Option<T>
+    where
+        T: ToStr,
+    {
+        let max_distance = (typo.chars().count() / 3).max(1);
+
+        let mut ranked: Vec<(usize, T)> = options
+            .into_iter()
+            .map(|option| (distance::damerau_levenshtein(typo, option.to_str()), option))
+            .collect();
+
+        ranked.sort_by_key(|(distance, _)| *distance);
+
+        let mut ranked = ranked.into_iter();
+
+        match (ranked.next(), ranked.next()) {
+            (Some((best, option)), next) if best <= max_distance => match next {
+                Some((second, _)) if second == best => None,
+                _ => Some(option),
+            },
+            _ => None,
+        }
+    }
 }
 
 pub struct Comparison<'b> {
@@ -4185,12 +4177,7 @@ fn report_record_field_typo<'b>(
         },
     ]);
 
-    Report {
-        filename,
-        title: "TYPE MISMATCH".to_string(),
-        doc,
-        severity: Severity::RuntimeError,
-    }
+    Report::new("TYPE MISMATCH", filename, doc, Severity::RuntimeError)
 }
 
 fn exhaustive_problem<'a>(
@@ -4220,12 +4207,7 @@ fn exhaustive_problem<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    title: "UNSAFE PATTERN".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("UNSAFE PATTERN", filename, doc, Severity::RuntimeError)
             }
             BadDestruct => {
                 let doc = alloc.stack([
@@ -4244,14 +4226,13 @@ fn exhaustive_problem<'a>(
                     ]),
                 ]);
 
-                Report {
-                    filename,
-                    title: "UNSAFE PATTERN".to_string(),
-                    doc,
-                    severity: Severity::RuntimeError,
-                }
+                Report::new("UNSAFE PATTERN", filename, doc, Severity::RuntimeError)
             }
             BadCase => {
+                let fixes = missing_branches_fix(alloc, region, &missing)
+                    .into_iter()
+                    .collect();
+
                 let doc = alloc.stack([
                     alloc.concat([
                         alloc.reflow("This "),
@@ -4273,6 +4254,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    fixes,
                 }
             }
         },
@@ -4297,12 +4279,7 @@ fn exhaustive_problem<'a>(
                 ),
             ]);
 
-            Report {
-                filename,
-                title: "REDUNDANT PATTERN".to_string(),
-                doc,
-                severity: Severity::Warning,
-            }
+            Report::new("REDUNDANT PATTERN", filename, doc, Severity::Warning)
         }
         Unmatchable {
             overall_region,
@@ -4325,12 +4302,67 @@ fn exhaustive_problem<'a>(
                 ),
             ]);
 
-            Report {
-                filename,
-                title: "UNMATCHABLE PATTERN".to_string(),
-                doc,
-                severity: Severity::Warning,
-            }
+            Report::new("UNMATCHABLE PATTERN", filename, doc, Severity::Warning)
+        }
+    }
+}
+
+/// Adds a branch for each missing pattern to the end of a `when`. The branches use the
+/// undefined name `todo` as a placeholder for the programmer to replace, so this is only
+/// a suggestion.
+///
+/// Not offered when a missing pattern can't be written down as-is, e.g. because it is
+/// only missing due to an `if` guard.
+fn missing_branches_fix<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    when_region: Region,
+    missing: &[roc_exhaustive::Pattern],
+) -> Option<Fix> {
+    if missing.is_empty() || !missing.iter().all(can_write_pattern) {
+        return None;
+    }
+
+    let (offset, indent) = when_branch_insertion(alloc.src(), when_region)?;
+
+    let mut branches = String::new();
+
+    for pattern in missing {
+        let mut text = String::new();
+
+        exhaustive_pattern_to_doc(alloc, pattern.clone())
+            .annotate(Annotation::TypeBlock)
+            .1
+            .render_raw(usize::MAX, &mut CiWrite::new(&mut text))
+            .ok()?;
+
+        branches.push('\n');
+        branches.push_str(&indent);
+        branches.push_str(&text);
+        branches.push_str(" -> todo");
+    }
+
+    let insert_at = Region::new(Position::new(offset as u32), Position::new(offset as u32));
+
+    Some(Fix::suggestion(
+        "Add the missing branches",
+        insert_at,
+        branches,
+    ))
+}
+
+fn can_write_pattern(pattern: &roc_exhaustive::Pattern) -> bool {
+    use roc_exhaustive::Literal;
+    use roc_exhaustive::Pattern::*;
+    use roc_exhaustive::RenderAs;
+
+    match pattern {
+        Anything => true,
+        // strings are rendered without their quotes
+        Literal(Literal::Str(_)) => false,
+        Literal(_) => true,
+        List(_, patterns) => patterns.iter().all(can_write_pattern),
+        Ctor(union, _, args) => {
+            !matches!(union.render_as, RenderAs::Guard) && args.iter().all(can_write_pattern)
         }
     }
 }
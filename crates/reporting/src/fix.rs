@@ -0,0 +1,308 @@
+//! Fixes that can be attached to a [Report](crate::report::Report), e.g. removing an unused
+//! import, or replacing a misspelled name with the one that was meant.
+
+use roc_region::all::{Position, Region};
+
+use crate::report::{Report, Severity};
+
+/// A change to the file a report is about, which resolves the problem it reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What the fix does, e.g. "Remove the unused import".
+    pub title: String,
+    pub edits: Vec<Edit>,
+    /// Whether the fix can be applied without looking at it, i.e. by `roc check --fix`.
+    /// Otherwise, it leaves something for the programmer to fill in.
+    pub machine_applicable: bool,
+}
+
+/// Replaces the source in `region` with `replacement`.
+/// An empty region inserts text; an empty replacement deletes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub region: Region,
+    pub replacement: String,
+}
+
+impl Fix {
+    pub fn new(title: impl Into<String>, region: Region, replacement: impl Into<String>) -> Self {
+        Fix {
+            title: title.into(),
+            edits: vec![Edit {
+                region,
+                replacement: replacement.into(),
+            }],
+            machine_applicable: true,
+        }
+    }
+
+    /// A fix that leaves placeholders for the programmer to fill in. Editors can offer it,
+    /// but `roc check --fix` doesn't apply it.
+    pub fn suggestion(
+        title: impl Into<String>,
+        region: Region,
+        replacement: impl Into<String>,
+    ) -> Self {
+        Fix {
+            machine_applicable: false,
+            ..Fix::new(title, region, replacement)
+        }
+    }
+}
+
+/// The machine-applicable fixes to apply for the reports about a module. Warnings are often a
+/// consequence of errors (e.g. a definition looks unused because of a typo in a name that refers
+/// to it), so when there are any errors, only their fixes are used.
+pub fn fixes_to_apply<'b, I>(reports: I) -> Vec<Fix>
+where
+    I: IntoIterator<Item = Report<'b>>,
+{
+    let mut error_fixes = Vec::new();
+    let mut warning_fixes = Vec::new();
+    let mut has_errors = false;
+
+    for mut report in reports {
+        report.fixes.retain(|fix| fix.machine_applicable);
+
+        match report.severity {
+            Severity::RuntimeError => {
+                has_errors = true;
+                error_fixes.extend(report.fixes);
+            }
+            Severity::Warning => {
+                warning_fixes.extend(report.fixes);
+            }
+        }
+    }
+
+    if has_errors {
+        error_fixes
+    } else {
+        warning_fixes
+    }
+}
+
+/// Applies the fixes to a module's source. A fix that overlaps one applied before it is skipped
+/// (applying the rest, and then checking again, will usually report it again in a form that
+/// does apply), as is one that does not fit the source at all.
+///
+/// Returns the new source, and how many fixes were applied.
+pub fn apply_fixes<'a, I>(src: &str, fixes: I) -> (String, usize)
+where
+    I: IntoIterator<Item = &'a Fix>,
+{
+    let mut accepted: Vec<&Edit> = Vec::new();
+    let mut applied = 0;
+
+    for fix in fixes {
+        let fits = fix.edits.iter().all(|edit| {
+            let start = edit.region.start().offset as usize;
+            let end = edit.region.end().offset as usize;
+
+            start <= end
+                && end <= src.len()
+                && src.is_char_boundary(start)
+                && src.is_char_boundary(end)
+        });
+
+        let overlaps = fix.edits.iter().any(|edit| {
+            accepted
+                .iter()
+                .any(|other| edits_overlap(edit.region, other.region))
+        });
+
+        if fits && !overlaps {
+            accepted.extend(fix.edits.iter());
+            applied += 1;
+        }
+    }
+
+    accepted.sort_by_key(|edit| edit.region.start().offset);
+
+    let mut fixed = String::with_capacity(src.len());
+    let mut copied_up_to = 0;
+
+    for edit in accepted {
+        let start = edit.region.start().offset as usize;
+
+        fixed.push_str(&src[copied_up_to..start]);
+        fixed.push_str(&edit.replacement);
+
+        copied_up_to = edit.region.end().offset as usize;
+    }
+
+    fixed.push_str(&src[copied_up_to..]);
+
+    (fixed, applied)
+}
+
+fn edits_overlap(a: Region, b: Region) -> bool {
+    let (a_start, a_end) = (a.start().offset, a.end().offset);
+    let (b_start, b_end) = (b.start().offset, b.end().offset);
+
+    // Two insertions at the same place conflict too, since their order would be arbitrary.
+    a_start < b_end && b_start < a_end || a_start == b_start
+}
+
+/// The region to delete to remove an item from a comma-separated collection
+/// (like `imports [A, B]` or `exposing { a, b }`), including a separating comma.
+/// When the item is on a line of its own, the whole line is removed.
+pub(crate) fn remove_collection_item(src: &str, item: Region) -> Region {
+    let start = item.start().offset as usize;
+    let end = item.end().offset as usize;
+    let bytes = src.as_bytes();
+
+    let is_space = |b: u8| b == b' ' || b == b'\t';
+
+    let mut after = end;
+    while after < bytes.len() && is_space(bytes[after]) {
+        after += 1;
+    }
+    let followed_by_comma = after < bytes.len() && bytes[after] == b',';
+
+    let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+    let alone_on_line = src[line_start..start].bytes().all(is_space);
+
+    if followed_by_comma {
+        let mut after_comma = after + 1;
+        while after_comma < bytes.len() && is_space(bytes[after_comma]) {
+            after_comma += 1;
+        }
+
+        if alone_on_line && src[after_comma..].starts_with('\n') {
+            return byte_region(line_start, after_comma + 1);
+        }
+
+        return byte_region(start, after_comma);
+    }
+
+    let mut before = start;
+    while before > 0 && bytes[before - 1].is_ascii_whitespace() {
+        before -= 1;
+    }
+
+    if before > 0 && bytes[before - 1] == b',' {
+        byte_region(before - 1, end)
+    } else {
+        item
+    }
+}
+
+/// The region to delete to remove a definition `name = ...` (along with its type annotation,
+/// if it has one), given the region of `name` in it. Returns `None` unless the definition
+/// starts its own line with just the name, as opposed to e.g. destructuring a record.
+pub(crate) fn remove_def(src: &str, name_region: Region, name: &str) -> Option<Region> {
+    let start = name_region.start().offset as usize;
+    let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &src[line_start..start];
+
+    if !indent.bytes().all(|b| b == b' ') {
+        return None;
+    }
+
+    let is_def_of = |line: &str, separator: char| {
+        line.strip_prefix(indent)
+            .and_then(|rest| rest.strip_prefix(name))
+            .map(|rest| rest.trim_start().starts_with(separator))
+            .unwrap_or(false)
+    };
+
+    let first_line = line_at(src, line_start);
+
+    let (def_start, def_end) = if is_def_of(first_line, ':') {
+        // This is the annotation, so the body should follow it.
+        let body_start = block_end(src, line_start, indent.len());
+
+        if body_start < src.len() && is_def_of(line_at(src, body_start), '=') {
+            (line_start, block_end(src, body_start, indent.len()))
+        } else {
+            (line_start, body_start)
+        }
+    } else if is_def_of(first_line, '=') {
+        // This is the body, so an annotation may precede it.
+        let def_start = match previous_block_start(src, line_start, indent.len()) {
+            Some(start) if is_def_of(line_at(src, start), ':') => start,
+            _ => line_start,
+        };
+
+        (def_start, block_end(src, line_start, indent.len()))
+    } else {
+        return None;
+    };
+
+    Some(byte_region(def_start, def_end))
+}
+
+/// Where to insert more branches into a `when` (given the region from its condition to the end
+/// of its last branch), and the indentation of its existing branches.
+pub(crate) fn when_branch_insertion(src: &str, when_region: Region) -> Option<(usize, String)> {
+    let start = when_region.start().offset as usize;
+    let end = when_region.end().offset as usize;
+
+    let first_line_end = start + src.get(start..end)?.find('\n')?;
+
+    let indent = src[first_line_end + 1..end]
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()?;
+
+    Some((end, " ".repeat(indent)))
+}
+
+fn byte_region(start: usize, end: usize) -> Region {
+    Region::new(Position::new(start as u32), Position::new(end as u32))
+}
+
+fn line_at(src: &str, line_start: usize) -> &str {
+    let rest = &src[line_start..];
+
+    match rest.find('\n') {
+        Some(len) => &rest[..len],
+        None => rest,
+    }
+}
+
+/// The start of the first line after `line_start` that is not blank and is indented at most
+/// `indent`, i.e. where the definition or expression starting at `line_start` ends.
+fn block_end(src: &str, line_start: usize, indent: usize) -> usize {
+    let mut offset = line_start + line_at(src, line_start).len();
+
+    while offset < src.len() {
+        // skip the newline
+        offset += 1;
+
+        let line = line_at(src, offset);
+        let line_indent = line.len() - line.trim_start().len();
+
+        if !line.trim().is_empty() && line_indent <= indent {
+            return offset;
+        }
+
+        offset += line.len();
+    }
+
+    src.len()
+}
+
+/// The start of the closest line before `line_start` that is indented exactly `indent`,
+/// skipping blank lines and more deeply indented ones.
+fn previous_block_start(src: &str, line_start: usize, indent: usize) -> Option<usize> {
+    let mut end = line_start.checked_sub(1)?;
+
+    loop {
+        let start = src[..end].rfind('\n').map_or(0, |i| i + 1);
+        let line = &src[start..end];
+        let line_indent = line.len() - line.trim_start().len();
+
+        if !line.trim().is_empty() {
+            match line_indent.cmp(&indent) {
+                std::cmp::Ordering::Equal => return Some(start),
+                std::cmp::Ordering::Less => return None,
+                std::cmp::Ordering::Greater => {}
+            }
+        }
+
+        end = start.checked_sub(1)?;
+    }
+}
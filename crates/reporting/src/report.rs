@@ -1,7 +1,7 @@
 use roc_module::ident::Ident;
 use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, PQModuleName, PackageQualified, Symbol};
-use roc_region::all::LineColumnRegion;
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use ven_pretty::{BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
@@ -9,6 +9,7 @@ use ven_pretty::{BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated
 pub use crate::error::canonicalize::can_problem;
 pub use crate::error::parse::parse_problem;
 pub use crate::error::r#type::type_problem;
+pub use crate::fix::Fix;
 
 #[cfg(windows)]
 const CYCLE_ELEMENTS: [&str; 4] = ["+-----+", "|     ", "|     |", "+-<---+"];
@@ -115,6 +116,8 @@ pub enum Severity {
 pub enum RenderTarget {
     ColorTerminal,
     Generic,
+    /// One JSON object per report, on a line of its own. See [Report::render_json].
+    Json,
 }
 
 /// A textual report.
@@ -123,24 +126,129 @@ pub struct Report<'b> {
     pub filename: PathBuf,
     pub doc: RocDocBuilder<'b>,
     pub severity: Severity,
+    /// Edits to the file that resolve the problem; see `roc check --fix`.
+    pub fixes: Vec<Fix>,
 }
 
 impl<'b> Report<'b> {
+    /// A report without fixes
+    pub fn new(
+        title: impl Into<String>,
+        filename: PathBuf,
+        doc: RocDocBuilder<'b>,
+        severity: Severity,
+    ) -> Self {
+        Report {
+            title: title.into(),
+            filename,
+            doc,
+            severity,
+            fixes: Vec::new(),
+        }
+    }
+
     pub fn render(
         self,
         target: RenderTarget,
         buf: &'b mut String,
         alloc: &'b RocDocAllocator<'b>,
         palette: &'b Palette,
+    ) {
+        self.render_with_region(target, None, buf, alloc, palette)
+    }
+
+    /// Like [Report::render], but includes the region the report is about
+    /// when rendering for [RenderTarget::Json].
+    pub fn render_with_region(
+        self,
+        target: RenderTarget,
+        region: Option<LineColumnRegion>,
+        buf: &'b mut String,
+        alloc: &'b RocDocAllocator<'b>,
+        palette: &'b Palette,
     ) {
         match target {
             RenderTarget::Generic => self.render_ci(buf, alloc),
             RenderTarget::ColorTerminal => self.render_color_terminal(buf, alloc, palette),
+            RenderTarget::Json => self.render_json(buf, alloc, region),
         }
     }
 
+    /// Render as a single line of JSON, for tools like CI annotations and editor plugins.
+    ///
+    /// The message is the plain text of the report without its header, and the
+    /// line and column numbers of the region are 1-based, like in the rendered report.
+    pub fn render_json(
+        self,
+        buf: &mut String,
+        alloc: &'b RocDocAllocator<'b>,
+        region: Option<LineColumnRegion>,
+    ) {
+        let err_msg = "<buffer is not a utf-8 encoded string>";
+
+        let mut message = String::new();
+        self.doc
+            .1
+            .render_raw(70, &mut CiWrite::new(&mut message))
+            .expect(err_msg);
+
+        let position = |line_column: LineColumn| {
+            serde_json::json!({
+                "line": line_column.line + 1,
+                "column": line_column.column + 1,
+            })
+        };
+
+        let fixes = if self.fixes.is_empty() {
+            Vec::new()
+        } else {
+            let lines = LineInfo::new(alloc.src());
+
+            self.fixes
+                .iter()
+                .map(|fix| {
+                    let edits = fix.edits.iter().map(|edit| {
+                        let region = lines.convert_region(edit.region);
+
+                        serde_json::json!({
+                            "region": {
+                                "start": position(region.start),
+                                "end": position(region.end),
+                            },
+                            "replacement": edit.replacement,
+                        })
+                    });
+
+                    serde_json::json!({
+                        "title": fix.title,
+                        "edits": edits.collect::<Vec<_>>(),
+                        "machineApplicable": fix.machine_applicable,
+                    })
+                })
+                .collect()
+        };
+
+        let json = serde_json::json!({
+            "title": self.title,
+            "filename": self.filename,
+            "severity": match self.severity {
+                Severity::RuntimeError => "error",
+                Severity::Warning => "warning",
+            },
+            "region": region.map(|region| serde_json::json!({
+                "start": position(region.start),
+                "end": position(region.end),
+            })),
+            "message": message.trim_end(),
+            "fixes": fixes,
+        });
+
+        buf.push_str(&json.to_string());
+        buf.push('\n');
+    }
+
     /// Render to CI console output, where no colors are available.
-    pub fn render_ci(self, buf: &'b mut String, alloc: &'b RocDocAllocator<'b>) {
+    pub fn render_ci(self, buf: &mut String, alloc: &'b RocDocAllocator<'b>) {
         let err_msg = "<buffer is not a utf-8 encoded string>";
 
         self.pretty(alloc)
@@ -188,6 +296,7 @@ impl<'b> Report<'b> {
 /// 2. A set of colors we decided to use
 /// 3. A mapping from UI elements to the styles we use for them
 /// Note: This should really be called Theme! Usually a "palette" is just (2).
+#[derive(Clone, Copy)]
 pub struct Palette {
     pub primary: &'static str,
     pub code_block: &'static str,
@@ -297,6 +406,8 @@ pub struct RocDocAllocator<'a> {
     pub src_lines: &'a [&'a str],
     pub home: ModuleId,
     pub interns: &'a Interns,
+    /// The lines joined back together, for fixes, which edit the source by byte offsets
+    src: String,
 }
 
 pub type RocDocBuilder<'b> = DocBuilder<'b, RocDocAllocator<'b>, Annotation>;
@@ -333,9 +444,15 @@ impl<'a> RocDocAllocator<'a> {
             home,
             src_lines,
             interns,
+            src: src_lines.join("\n"),
         }
     }
 
+    /// The source of the module the reports are about
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
     /// vertical concatenation. Adds a newline between elements
     pub fn vcat<A, I>(&'a self, docs: I) -> DocBuilder<'a, Self, A>
     where
@@ -33,12 +33,12 @@ mod test_reporting {
     }
 
     fn to_simple_report(doc: RocDocBuilder) -> Report {
-        Report {
-            title: "".to_string(),
+        Report::new(
+            "",
+            filename_from_string(r"/code/proj/Main.roc"),
             doc,
-            filename: filename_from_string(r"/code/proj/Main.roc"),
-            severity: Severity::RuntimeError,
-        }
+            Severity::RuntimeError,
+        )
     }
 
     fn promote_expr_to_module(src: &str) -> String {
@@ -88,6 +88,8 @@ mod test_reporting {
                 render: RenderTarget::Generic,
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
+                cache_dir: None,
+                coverage: false,
             };
             let result =
                 roc_load::load_and_typecheck(arena, full_file_path, exposed_types, load_config);
@@ -144,10 +146,10 @@ mod test_reporting {
             Ok((module_src, type_problems, can_problems, home, interns)) => {
                 let lines = LineInfo::new(&module_src);
                 let src_lines: Vec<&str> = module_src.split('\n').collect();
-                let mut reports = Vec::new();
-
                 let alloc = RocDocAllocator::new(&src_lines, home, &interns);
 
+                let mut reports = Vec::new();
+
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
                     reports.push(report);
@@ -266,10 +268,10 @@ mod test_reporting {
                 callback(doc.pretty(&alloc).append(alloc.line()), buf)
             }
             Ok((type_problems, can_problems, home, interns)) => {
-                let mut reports = Vec::new();
-
                 let alloc = RocDocAllocator::new(&src_lines, home, &interns);
 
+                let mut reports = Vec::new();
+
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
                     reports.push(report);
@@ -414,6 +416,59 @@ mod test_reporting {
         }
     }
 
+    /// Passes the source of the program and the reports for its problems to `f`.
+    fn with_reports<T>(subdir: &str, src: &str, f: impl FnOnce(&str, Vec<Report>) -> T) -> T {
+        let arena = Bump::new();
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+
+        match infer_expr_help_new(subdir, &arena, src) {
+            Ok((module_src, type_problems, can_problems, home, interns)) => {
+                let lines = LineInfo::new(&module_src);
+                let src_lines: Vec<&str> = module_src.split('\n').collect();
+                let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+                let mut reports = Vec::new();
+
+                for problem in can_problems {
+                    reports.push(can_problem(&alloc, &lines, filename.clone(), problem));
+                }
+
+                for problem in type_problems {
+                    if let Some(report) = type_problem(&alloc, &lines, filename.clone(), problem) {
+                        reports.push(report);
+                    }
+                }
+
+                f(&module_src, reports)
+            }
+            Err(other) => {
+                panic!("failed to load: {:?}", other);
+            }
+        }
+    }
+
+    /// Do not call this directly! Use the test_fix macro below!
+    fn __apply_suggested_fixes(subdir: &str, src: &str) -> String {
+        with_reports(subdir, src, |module_src, reports| {
+            let fixes = roc_reporting::fix::fixes_to_apply(reports);
+
+            roc_reporting::fix::apply_fixes(module_src, &fixes).0
+        })
+    }
+
+    /// Checks the program after applying the fixes suggested for its problems.
+    macro_rules! test_fix {
+        ($(#[$meta:meta])* $test_name: ident, $program:expr, $fixed:expr) => {
+            #[test]
+            $(#[$meta])*
+            fn $test_name() {
+                let fixed = __apply_suggested_fixes(std::stringify!($test_name), $program);
+
+                pretty_assertions::assert_eq!(fixed, $fixed)
+            }
+        }
+    }
+
     fn human_readable(str: &str) -> String {
         str.replace(ANSI_STYLE_CODES.red, "<red>")
             .replace(ANSI_STYLE_CODES.white, "<white>")
@@ -12397,4 +12452,139 @@ All branches in an `if` must have the same type!
             "#
         )
     );
+
+    test_fix!(
+        fix_unused_def,
+        indoc!(
+            r#"
+            x = 1
+            y : U8
+            y = 2
+
+            x
+            "#
+        ),
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main =
+                x = 1
+                x
+            "#
+        )
+    );
+
+    test_fix!(
+        fix_unused_import,
+        indoc!(
+            r#"
+            app "test" imports [List.{ concat, map }] provides [main] to "./platform"
+
+            main = List.map [] (\x -> x)
+            "#
+        ),
+        indoc!(
+            r#"
+            app "test" imports [List.{ map }] provides [main] to "./platform"
+
+            main = List.map [] (\x -> x)
+            "#
+        )
+    );
+
+    test_fix!(
+        fix_value_not_exposed,
+        indoc!(
+            r#"
+            List.isempty []
+            "#
+        ),
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main =
+                List.isEmpty []
+            "#
+        )
+    );
+
+    test_fix!(
+        fix_lookup_not_in_scope,
+        indoc!(
+            r#"
+            total = 1
+            totl + 1
+            "#
+        ),
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main =
+                total = 1
+                total + 1
+            "#
+        )
+    );
+
+    // The branches added for a non-exhaustive `when` have placeholders to fill in,
+    // so they are only suggested, and not applied by `roc check --fix`.
+    test_fix!(
+        fix_missing_when_branches,
+        indoc!(
+            r#"
+            x : [A, B, C]
+            x = A
+            when x is
+                A -> 1
+            "#
+        ),
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main =
+                x : [A, B, C]
+                x = A
+                when x is
+                    A -> 1
+            "#
+        )
+    );
+
+    #[test]
+    fn suggest_missing_when_branches() {
+        let src = indoc!(
+            r#"
+            x : [A, B, C]
+            x = A
+            when x is
+                A -> 1
+            "#
+        );
+
+        with_reports(
+            "suggest_missing_when_branches",
+            src,
+            |module_src, reports| {
+                let fixes: Vec<_> = reports
+                    .into_iter()
+                    .flat_map(|report| report.fixes)
+                    .collect();
+
+                assert_eq!(fixes.len(), 1);
+                assert!(!fixes[0].machine_applicable);
+
+                let (suggested, _) = roc_reporting::fix::apply_fixes(module_src, &fixes);
+
+                assert!(
+                    suggested.ends_with("        A -> 1\n        B -> todo\n        C -> todo\n"),
+                    "{}",
+                    suggested
+                );
+            },
+        );
+    }
 }
@@ -0,0 +1,112 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+extern void *roc_alloc(size_t size, unsigned int alignment);
+
+// Roc stores the reference count right before a heap-allocated value.
+// A count of 1 is represented by PTRDIFF_MIN.
+static inline void *roc_alloc_refcounted(size_t size, size_t alignment) {
+    size_t prefix = alignment > sizeof(ptrdiff_t) ? alignment : sizeof(ptrdiff_t);
+
+    char *allocation = (char *)roc_alloc(size + prefix, (unsigned int)prefix);
+    char *data = allocation + prefix;
+
+    ((ptrdiff_t *)data)[-1] = PTRDIFF_MIN;
+
+    return data;
+}
+
+struct RocStr {
+    char *bytes;
+    size_t length;
+    size_t capacity;
+};
+
+// Strings that fit in a RocStr (minus one byte for the length) are stored inside the RocStr itself.
+// That is signalled by the highest bit of the capacity being set.
+static inline bool roc_str_is_small(const struct RocStr *str) {
+    return ((ptrdiff_t)str->capacity) < 0;
+}
+
+static inline size_t roc_str_len(const struct RocStr *str) {
+    if (roc_str_is_small(str)) {
+        const unsigned char *bytes = (const unsigned char *)str;
+
+        return (size_t)(bytes[sizeof(struct RocStr) - 1] ^ 0x80);
+    } else {
+        return str->length;
+    }
+}
+
+static inline const char *roc_str_bytes(const struct RocStr *str) {
+    if (roc_str_is_small(str)) {
+        return (const char *)str;
+    } else {
+        return str->bytes;
+    }
+}
+
+// Copies the bytes, which must be UTF-8, into a new RocStr
+static inline struct RocStr roc_str_from_bytes(const void *bytes, size_t length) {
+    struct RocStr str = { NULL, 0, 0 };
+
+    if (length < sizeof(struct RocStr)) {
+        if (length > 0) {
+            memcpy(&str, bytes, length);
+        }
+
+        ((unsigned char *)&str)[sizeof(struct RocStr) - 1] = (unsigned char)length | 0x80;
+    } else {
+        str.bytes = (char *)roc_alloc_refcounted(length, 1);
+        str.length = length;
+        str.capacity = length;
+
+        memcpy(str.bytes, bytes, length);
+    }
+
+    return str;
+}
+
+// C has no generics, so the element type of each list is in a comment next to it.
+struct RocList {
+    void *elements;
+    size_t length;
+    size_t capacity;
+};
+
+// A list of the key-value pairs
+struct RocDict {
+    struct RocList items;
+};
+
+// A dictionary with empty values
+struct RocSet {
+    struct RocDict dict;
+};
+
+// 128-bit integers. C compilers only provide these on some architectures; on the others,
+// they can be passed to and from Roc, but not used for arithmetic.
+#ifdef __SIZEOF_INT128__
+typedef __int128_t roc_i128;
+typedef __uint128_t roc_u128;
+#else
+typedef struct {
+    _Alignas(16) uint64_t words[2];
+} roc_i128;
+typedef struct {
+    _Alignas(16) uint64_t words[2];
+} roc_u128;
+#endif
+
+// A fixed-point decimal number with 18 digits after the decimal point
+struct RocDec {
+    roc_i128 value;
+};
+
+extern void roc__mainForHost_1_exposed_generic(struct RocStr *ret);
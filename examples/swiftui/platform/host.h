@@ -1,9 +1,2 @@
-#include <stdlib.h>
-
-struct RocStr {
-    char* bytes;
-    size_t len;
-    size_t capacity;
-};
-
-extern void roc__mainForHost_1_exposed_generic(const struct RocStr *data);
+// Generated by `roc glue main.roc Glue.swift`, along with Glue.swift
+#include "Glue.h"
@@ -36,7 +36,7 @@ rustc --version
 export ROC_DOCS_URL_ROOT=/builtins
 
 cargo run --bin roc-docs crates/compiler/builtins/roc/*.roc
-mv generated-docs/*.* www/build # move all the .js, .css, etc. files to build/
+mv generated-docs/{search.js,styles.css,favicon.svg} www/build # move the assets shared by all docs to build/
 mv generated-docs/ www/build/builtins # move all the folders to build/builtins/
 
 # Manually add this tip to all the builtin docs.
@@ -52,7 +52,7 @@ export ROC_DOCS_URL_ROOT=/examples/cli
 ls examples/cli/cli-platform/*.roc | grep -v Internal | grep -v main.roc | grep -v Effect.roc | xargs cargo run --bin roc-docs
 
 mkdir www/build/examples
-rm generated-docs/*.* # we already copied over the *.js and *.css files earlier, so just drop these.
+rm generated-docs/{search.js,styles.css,favicon.svg} # we already copied over the shared assets earlier, so just drop these.
 mv generated-docs/ www/build/examples/cli # move all the folders to build/examples/cli
 
 popd